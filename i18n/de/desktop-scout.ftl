@@ -0,0 +1,557 @@
+# desktop-scout messages (German).
+#
+# Desktop entry keys (Exec, TryExec, Hidden, ...), reason codes and command-line options are
+# literal and must not be translated.
+
+## Text report
+
+report-warning = Warnung [{ $code }]: { $message }
+report-baseline-suppressed = { $count ->
+    [one] { $count } bekannter defekter Eintrag durch die Baseline unterdrückt.
+   *[other] { $count } bekannte defekte Einträge durch die Baseline unterdrückt.
+}
+report-no-broken = Keine defekten Desktop-Einträge gefunden.
+report-broken-header = Defekte .desktop-Einträge ({ $count }):
+report-warned-header = .desktop-Einträge mit Warnungen ({ $count }):
+report-id = ID: { $id }
+report-user = Benutzer: { $user }
+report-name = Name: { $name }
+report-exec = Exec: { $exec }
+report-tryexec = TryExec: { $tryexec }
+report-path = Path: { $path }
+report-suggested-exec = Vorgeschlagenes Exec: { $exec }
+report-flathub-available = Flathub: { $app_id } wird weiterhin angeboten, die lokale Installation ist beschädigt
+report-flathub-removed = Flathub: { $app_id } wurde von Flathub entfernt
+report-history = { $scans ->
+    [one] Defekt seit: { $first_seen } (erster Scan)
+   *[other] Defekt seit: { $first_seen } ({ $scans } Scans in Folge)
+}
+report-same-file = Gleiche Datei wie: { $paths }
+quiet-broken = { $file }: defekt [{ $code }]: { $reason }
+quiet-warning = { $file }: Warnung [{ $code }]: { $message }
+
+## Table
+
+table-status = Status
+table-code = Code
+table-id = ID
+table-name = Name
+table-file = Datei
+table-reason = Grund
+table-broken = defekt
+table-warning = Warnung
+table-info = Hinweis
+table-ok = ok
+table-skipped = übersprungen
+table-suppressed = unterdrückt
+table-timed-out = Zeitüberschreitung
+table-application = Anwendung
+table-origin = Herkunft
+table-directory = Verzeichnis
+table-files = Dateien
+table-count-ok = Ok
+table-count-broken = Defekt
+table-count-skipped = Übersprungen
+
+## Groups (--group-by dir|source)
+
+group-user-data = Benutzereinträge ({ $path })
+group-desktop = Schreibtisch-Symbole ({ $path })
+group-system-data = Systemeinträge (XDG_DATA_DIRS)
+group-flatpak = Flatpak-Exporte
+group-snap = Snap
+group-extra = { $path } (--dir)
+group-other = Andere Dateien
+
+## Notifications (--notify)
+
+notify-broken = { $count ->
+    [one] { $count } defekter Starter gefunden
+   *[other] { $count } defekte Starter gefunden
+}
+notify-warned = { $count ->
+    [one] { $count } Starter mit Warnungen gefunden
+   *[other] { $count } Starter mit Warnungen gefunden
+}
+notify-more = und { $count } weitere
+
+## Summary
+
+summary-scanned = { $files ->
+    [one] { $files } Datei geprüft: { $ok } ok, { $broken } defekt, { $skipped } übersprungen.
+   *[other] { $files } Dateien geprüft: { $ok } ok, { $broken } defekt, { $skipped } übersprungen.
+}
+summary-scanned-in = { $files ->
+    [one] { $files } Datei in { $seconds } s geprüft: { $ok } ok, { $broken } defekt, { $skipped } übersprungen.
+   *[other] { $files } Dateien in { $seconds } s geprüft: { $ok } ok, { $broken } defekt, { $skipped } übersprungen.
+}
+summary-suppressed = { $count ->
+    [one] { $count } defekter Eintrag durch --suppressions akzeptiert.
+   *[other] { $count } defekte Einträge durch --suppressions akzeptiert.
+}
+summary-timed-out = { $count ->
+    [one] { $count } Eintrag konnte wegen Zeitüberschreitung nicht geprüft werden.
+   *[other] { $count } Einträge konnten wegen Zeitüberschreitung nicht geprüft werden.
+}
+
+## Subcommands
+
+restore-done = { $path } wiederhergestellt
+fix-script-written = { $count ->
+    [one] { $path } mit Korrekturen für { $count } defekten Eintrag geschrieben.
+   *[other] { $path } mit Korrekturen für { $count } defekte Einträge geschrieben.
+}
+fix-patch-written = { $count ->
+    [one] { $path } mit Änderungen für { $count } Eintrag geschrieben (anwenden mit `cd / && patch -p0`).
+   *[other] { $path } mit Änderungen für { $count } Einträge geschrieben (anwenden mit `cd / && patch -p0`).
+}
+fix-aborted = Abgebrochen; es wurde nichts geändert.
+export-written = { $count ->
+    [one] { $path } mit { $count } Eintrag und dem Bericht geschrieben.
+   *[other] { $path } mit { $count } Einträgen und dem Bericht geschrieben.
+}
+
+## Fix
+
+fix-action = { $action ->
+    [delete] löschen
+    [hide] ausblenden
+    [override] überlagern
+    [quarantine] Quarantäne
+    [trash] Papierkorb
+    [repair] reparieren
+   *[skip] überspringen
+}
+fix-confirm = Diese Änderungen anwenden? [y/N]
+fix-nothing-to-fix = Keine defekten Desktop-Einträge zu korrigieren.
+fix-removed = Entfernt       { $path }
+fix-partially-removed = Entfernt       { $path } ({ $error })
+fix-trashed = Papierkorb     { $path }
+fix-hidden = Ausgeblendet   { $path }
+fix-repaired = Repariert      { $path } ({ $changes })
+fix-overridden = Ausgeblendet   { $path } (Überlagerung { $override })
+fix-quarantined = Verschoben     { $path } in die Quarantäne (ID { $id })
+fix-skipped = Übersprungen   { $path } ({ $reason })
+fix-failed = Fehlgeschlagen { $path } ({ $error })
+fix-working-copy = funktionierende Kopie    { $path }
+fix-removed-companion = entfernte Begleitdatei    { $path }
+fix-left-companion = verbliebene Begleitdatei  { $path }
+fix-companions-left = Erneut mit --with-companions ausführen, um auch die obigen Begleitdateien zu entfernen.
+fix-backups-written = Sicherungen in { $path } geschrieben
+
+## History
+
+journal-empty = Es wurden noch keine Änderungen protokolliert.
+journal-restore = { $path } (Quarantäne-ID { $id })
+journal-undo = { $path } (Korrektur aus Lauf { $run })
+journal-baseline = { $count ->
+    [one] { $path } ({ $count } Befund akzeptiert)
+   *[other] { $path } ({ $count } Befunde akzeptiert)
+}
+
+## Undo
+
+undo-nothing = Keine Korrekturen zum Rückgängigmachen.
+undo-reverted = Rückgängig gemacht { $path } (Lauf { $run })
+undo-failed = Fehlgeschlagen     { $path } ({ $error })
+
+## Formatting (fmt)
+
+fmt-reformatted = Neu formatiert  { $path }
+fmt-failed = Fehlgeschlagen  { $path }: { $error }
+fmt-all-formatted = { $files ->
+    [one] { $files } Datei ist kanonisch formatiert.
+   *[other] Alle { $files } Dateien sind kanonisch formatiert.
+}
+fmt-would-reformat = { $files ->
+    [one] Neu zu formatieren: { $changed } von { $files } Datei.
+   *[other] Neu zu formatieren: { $changed } von { $files } Dateien.
+}
+fmt-reformatted-summary = { $files ->
+    [one] { $changed } von { $files } Datei neu formatiert.
+   *[other] { $changed } von { $files } Dateien neu formatiert.
+}
+
+## Quarantine (restore)
+
+quarantine-empty = Die Quarantäne ist leer.
+quarantine-companions = { $count ->
+    [one] (+{ $count } Begleitdatei)
+   *[other] (+{ $count } Begleitdateien)
+}
+
+## Fleet (--hosts)
+
+fleet-summary = { $hosts ->
+    [one] Flottenübersicht ({ $hosts } Host):
+   *[other] Flottenübersicht ({ $hosts } Hosts):
+}
+fleet-host-error = Fehler: { $error }
+fleet-host-broken = { $count } defekt
+fleet-host-header = { $count ->
+    [one] { $host } ({ $count } defekter Eintrag):
+   *[other] { $host } ({ $count } defekte Einträge):
+}
+fleet-reason = Grund [{ $code }]: { $reason }
+
+## Timer (install-timer)
+
+timer-unit-written = { $path } geschrieben
+timer-report-path = Berichte werden nach { $path } geschrieben
+timer-enable = Den Timer aktivieren mit:
+
+## Scan warnings
+
+scan-source-dirs = { $count ->
+    [one] { $count } durchsuchten Verzeichnis
+   *[other] { $count } durchsuchten Verzeichnissen
+}
+scan-source-file-list = der Dateiliste { $path }
+warning-no-desktop-files = Keine .desktop-Dateien in { $source } gefunden; XDG_DATA_HOME/XDG_DATA_DIRS, --dir und --no-default prüfen
+
+## Reasons
+
+reason-unreadable-file = Datei konnte nicht gelesen/geparst werden: { $error }
+reason-dangling-symlink = Symlink auf { $target }, das nicht existiert
+reason-inspection-panicked = Prüfung unerwartet abgebrochen: { $error }
+reason-inspection-timed-out = Prüfung wurde nicht innerhalb von { $seconds } s abgeschlossen
+reason-max-runtime-exceeded = Nicht geprüft, bevor der Scan --max-runtime ({ $seconds } s) erreicht hat
+reason-missing-desktop-extension = Enthält eine [Desktop Entry]-Gruppe, aber nicht die Endung .desktop, daher ignorieren Starter die Datei (in { $name }.desktop umbenennen)
+reason-hidden-entry = Hidden=true oder NoDisplay=true (mit --include-hidden werden diese geprüft)
+reason-unsupported-type = Type={ $type } (nur Type=Application wird geprüft)
+reason-tryexec-unresolved = TryExec lässt sich nicht auflösen: { $tryexec }
+reason-exec-unresolved-despite-tryexec = Exec lässt sich nicht auflösen (obwohl TryExec es tut)
+reason-exec-missing = Kein Exec-Schlüssel vorhanden (und nicht DBusActivatable)
+reason-exec-not-found = Exec lässt sich nicht auflösen
+reason-exec-unquoted-spaces = Der Exec-Pfad enthält Leerzeichen und muss in Anführungszeichen stehen: { $path }
+reason-systemd-run-missing = Exec startet über { $program }, das nicht aufgelöst werden kann
+reason-user-manager-unavailable = Exec startet über systemd-run --user, aber in dieser Sitzung läuft kein systemd-Benutzerdienstmanager
+reason-exec-shell-builtin = `{ $command }` ist ein Shell-Builtin und im PATH gibt es kein Programm dieses Namens; Starter führen keine Shell aus
+reason-exec-shell-alias = `{ $command }` ist nur als Shell-{ $kind ->
+    [alias] Alias
+   *[function] Funktion
+} in { $source } definiert; Starter führen keine Shell aus, daher sind Aliase und Funktionen nicht verwendbar
+reason-check-timed-out = { $what }-Prüfung wurde nicht innerhalb von { $seconds } s abgeschlossen
+reason-exec-check-failed = Exec-Prüfung fehlgeschlagen: { $error }
+reason-exec-unparsable = Exec lässt sich nicht wie eine Shell-Befehlszeile zerlegen (Anführungszeichen prüfen)
+reason-exec-no-executable = Exec nennt kein Programm
+reason-script-missing = Der Interpreter { $interpreter } existiert, aber sein Skript-Argument fehlt: { $path }
+
+## Lint warnings
+
+lint-invalid-encoding = Die Datei ist kein gültiges UTF-8 (ungültige Bytes wurden für die übrigen Lints ersetzt)
+lint-not-key-value = Zeile { $line } ist kein key=value-Paar: { $text }
+lint-before-first-group = Zeile { $line } steht vor dem ersten Gruppenkopf: { $text }
+lint-wrong-first-group = Die erste Gruppe ist [{ $group }], nicht [Desktop Entry]
+lint-no-desktop-entry-group = Die Datei hat keine Gruppe [Desktop Entry]
+lint-invalid-boolean = { $key }={ $value } ist kein gültiger boolescher Wert (die Spezifikation erlaubt nur `true` oder `false`)
+lint-key-not-allowed-for-type = { $key } ist nur für Type=Application gültig (gefunden: Type={ $type })
+lint-invalid-dbus-name = DBusActivatable=true verlangt als Dateinamen einen bekannten D-Bus-Namen (z. B. org.example.App.desktop), gefunden: { $name }.desktop
+lint-template-name = Name={ $name } sieht nach einem Vorlagen-Platzhalter aus
+lint-template-path = { $key } enthält den Vorlagenpfad `{ $marker }`: { $value }
+lint-unfinished-comment = Die Datei enthält einen Kommentar zu unfertiger Arbeit: { $comment }
+lint-missing-icon = Der Anwendungseintrag hat kein Icon= und erscheint in Menüs mit einem generischen Symbol
+lint-deprecated-key = { $key } ist abgekündigt und wird von aktuellen Startern ignoriert
+lint-foreign-icon-dir = Icon={ $icon } zeigt in das Verzeichnis eines anderen Pakets ({ $owner }); es bricht, wenn sich dieses Paket ändert
+session-runtime-dir-unset = Exec= verweist auf $XDG_RUNTIME_DIR/{ $relative }, aber XDG_RUNTIME_DIR ist in dieser Sitzung nicht gesetzt
+session-runtime-path-missing = Exec= verweist auf $XDG_RUNTIME_DIR/{ $relative } ({ $path }), das in dieser Sitzung nicht existiert
+session-path-missing = Exec= verweist auf { $path }, das in dieser Sitzung nicht existiert
+gpu-nvidia-not-loaded = Exec= setzt { $name }={ $value }, aber der NVIDIA-Treiber ist nicht geladen
+gpu-manifest-missing = Exec= setzt { $name }={ $value }, aber { $count ->
+    [one] das Treibermanifest { $manifests } existiert nicht
+   *[other] die Treibermanifeste { $manifests } existieren nicht
+}
+gpu-too-few-gpus = Exec= setzt { $name }={ $value }, aber { $count ->
+    [one] es ist nur eine GPU vorhanden
+   *[other] es sind nur { $count } GPUs vorhanden
+}
+gpu-pci-device-missing = Exec= setzt { $name }={ $value }, aber es gibt kein PCI-Gerät { $device }
+path-entry-empty = PATH-Eintrag #{ $index } ist leer und steht für das aktuelle Arbeitsverzeichnis; Starter laufen in einem unvorhersehbaren Arbeitsverzeichnis
+path-entry-tilde = PATH-Eintrag #{ $index } ({ $entry }) enthält ein nicht expandiertes `~`; nur Shells expandieren es, Starter mit execvp nicht
+path-entry-relative = PATH-Eintrag #{ $index } ({ $entry }) ist relativ und hängt vom Arbeitsverzeichnis ab
+suppression-expired = Die Unterdrückung von { $path } ist am { $until } abgelaufen und gilt nicht mehr
+suppression-rule-expired = Die Unterdrückung von { $path } ({ $rule }) ist am { $until } abgelaufen und gilt nicht mehr
+
+## Explain
+
+explain-causes-header = Typische Ursachen:
+explain-remediation-header = Abhilfe:
+
+explain-exec-not-found = Das in Exec= genannte Programm wurde nicht gefunden oder ist nicht ausführbar.
+explain-exec-not-found-causes =
+    Die Anwendung wurde deinstalliert, ihr Starter ist aber zurückgeblieben.
+    Das Programm liegt in einem Verzeichnis, das nicht im PATH des Starters steht.
+    Ein absoluter Pfad in Exec= zeigt auf ein verschobenes oder umbenanntes Installationsverzeichnis.
+explain-exec-not-found-remediation =
+    Die Anwendung neu installieren oder die veraltete .desktop-Datei entfernen.
+    Einen absoluten Pfad in Exec= verwenden, wenn das Programm außerhalb des Standard-PATH liegt.
+    Exec= auf den neuen Ort des Programms anpassen.
+
+explain-exec-shell-alias = Exec= nennt einen Befehl, der nur als Shell-Alias oder -Funktion existiert.
+explain-exec-shell-alias-causes =
+    Der Befehl wurde für das Terminal in ~/.bashrc, ~/.zshrc oder einer fish-Konfiguration definiert.
+    Starter führen Exec= direkt ohne Shell aus, daher sind Aliase und Funktionen unbekannt.
+explain-exec-shell-alias-remediation =
+    Die vollständige Befehlszeile des Alias in Exec= eintragen.
+    Den Alias oder die Funktion in ein Skript in einem PATH-Verzeichnis umwandeln (z. B. ~/.local/bin).
+
+explain-exec-shell-builtin = Exec= nennt ein Shell-Builtin, und auf der Festplatte gibt es kein Programm dieses Namens.
+explain-exec-shell-builtin-causes =
+    Der Befehl (z. B. `cd`, `source`, `export`) existiert nur innerhalb einer Shell.
+    Ein Programm im Stil der coreutils (`test`, `[`, `time`) wird normalerweise mitgeliefert, ist aber nicht installiert.
+explain-exec-shell-builtin-remediation =
+    Die Befehlszeile in eine Shell einbetten: Exec=sh -c '...'.
+    Das Paket mit dem eigenständigen Programm installieren (z. B. coreutils oder time).
+
+explain-tryexec-unresolved = Das in TryExec= genannte Programm wurde nicht gefunden, daher blenden Starter den Eintrag aus.
+explain-tryexec-unresolved-causes =
+    Die Anwendung wurde deinstalliert, ihr Starter ist aber zurückgeblieben.
+    TryExec= nennt ein anderes Programm als das tatsächlich installierte.
+explain-tryexec-unresolved-remediation =
+    Die veraltete .desktop-Datei entfernen, wenn die Anwendung nicht mehr vorhanden ist.
+    TryExec= auf das installierte Programm zeigen lassen oder den Schlüssel entfernen.
+
+explain-exec-unresolved-despite-tryexec = TryExec= lässt sich auflösen, das Programm in Exec= aber nicht.
+explain-exec-unresolved-despite-tryexec-causes =
+    TryExec= und Exec= verweisen auf verschiedene Programme, und nur eines ist installiert.
+    Exec= verwendet ein Wrapper-Skript, das entfernt wurde.
+explain-exec-unresolved-despite-tryexec-remediation =
+    Exec= und TryExec= auf dasselbe, installierte Programm verweisen lassen.
+
+explain-exec-parse-error = Exec= lässt sich nicht in eine Befehlszeile zerlegen.
+explain-exec-parse-error-causes =
+    Unausgeglichene Anführungszeichen in Exec=.
+    Exec= besteht nur aus `env`-Zuweisungen oder Feldcodes.
+explain-exec-parse-error-remediation =
+    Die Anführungszeichen in Exec= gemäß der Desktop-Entry-Spezifikation korrigieren.
+
+explain-exec-unquoted-spaces = Exec= nennt ein Programm, dessen Pfad Leerzeichen enthält, ohne ihn in Anführungszeichen zu setzen.
+explain-exec-unquoted-spaces-causes =
+    Ein selbst geschriebener Starter für ein Programm in einem Verzeichnis wie `~/My Apps`.
+    Ein Generator, der den Installationspfad ohne Anführungszeichen in Exec= einfügt.
+explain-exec-unquoted-spaces-remediation =
+    Den Pfad in doppelte Anführungszeichen setzen: Exec="/home/user/My Apps/app" %U.
+    `desktop-scout fix --mode repair` ausführen, das die vorgeschlagene Exec=-Zeile übernimmt.
+
+explain-systemd-run-unavailable = Exec= startet das Programm über systemd-run, das es hier nicht ausführen kann.
+explain-systemd-run-unavailable-causes =
+    systemd-run ist nicht installiert (z. B. auf einem System ohne systemd).
+    Ein von GNOME übernommenes Präfix `systemd-run --user --scope`, aber in dieser Sitzung läuft kein systemd-Benutzerdienstmanager (geprüft mit --check-user-manager).
+explain-systemd-run-unavailable-remediation =
+    Das systemd-run-Präfix entfernen und das Programm direkt starten.
+    Den Benutzerdienstmanager (systemd --user) für die Sitzung aktivieren, z. B. über pam_systemd.
+
+explain-script-missing = Exec= startet einen Interpreter, aber das Skript, das er ausführen soll, fehlt.
+explain-script-missing-causes =
+    Ein selbst geschriebener Starter für ein Skript, das verschoben oder gelöscht wurde.
+    Ein virtualenv oder ein ausgechecktes Projekt wurde entfernt.
+explain-script-missing-remediation =
+    Das Skript wiederherstellen oder den Pfad in Exec= anpassen.
+    Den Starter entfernen, wenn das Skript nicht mehr gebraucht wird.
+
+explain-exec-missing = Der Eintrag hat keinen Exec=-Schlüssel und ist nicht DBusActivatable.
+explain-exec-missing-causes =
+    Eine abgeschnittene oder von Hand bearbeitete .desktop-Datei.
+explain-exec-missing-remediation =
+    Eine Exec=-Zeile ergänzen oder für D-Bus-Dienste DBusActivatable=true setzen.
+
+explain-unreadable-file = Die .desktop-Datei konnte nicht gelesen werden.
+explain-unreadable-file-causes =
+    Fehlende Berechtigungen.
+    Die Datei ist kein gültiges UTF-8.
+    Ein E/A-Fehler auf einem Netzwerk- oder Wechseldatenträger.
+explain-unreadable-file-remediation =
+    Berechtigungen und Kodierung der Datei prüfen.
+    Den Scan wiederholen, sobald das Dateisystem erreichbar ist.
+
+explain-dangling-symlink = Die .desktop-Datei ist ein Symlink, dessen Ziel nicht existiert.
+explain-dangling-symlink-causes =
+    Die Anwendung wurde deinstalliert, ein von Hand angelegter Link auf ihren Starter ist aber zurückgeblieben.
+    Ein Flatpak- oder Snap-Export wurde entfernt, ohne die Links darauf aufzuräumen.
+    Der Link ist relativ und wurde in ein anderes Verzeichnis verschoben oder kopiert.
+explain-dangling-symlink-remediation =
+    Den Symlink entfernen oder auf den neuen Ort des Starters zeigen lassen.
+
+explain-missing-desktop-extension = Die Datei ist ein Desktop-Eintrag, aber ohne `.desktop` ignorieren Starter sie.
+explain-missing-desktop-extension-causes =
+    Ein selbst geschriebener Starter, der ohne Endung (oder als .txt) gespeichert wurde.
+    Ein Download- oder Kopierprogramm hat die Endung entfernt.
+explain-missing-desktop-extension-remediation =
+    Die Datei so umbenennen, dass sie auf `.desktop` endet.
+
+explain-check-timed-out = Die Prüfung des Eintrags wurde nicht rechtzeitig abgeschlossen.
+explain-check-timed-out-causes =
+    Exec= oder TryExec= zeigt in ein hängendes Netzwerkdateisystem oder einen Automount.
+    PATH enthält ein Verzeichnis auf einem nicht erreichbaren Dateisystem.
+explain-check-timed-out-remediation =
+    Prüfen, ob die betroffenen Dateisysteme eingehängt und erreichbar sind.
+    Nicht erreichbare Verzeichnisse aus PATH entfernen.
+    --timeout für langsame, aber funktionierende Dateisysteme erhöhen.
+
+explain-max-runtime-exceeded = Der Scan hat --max-runtime erreicht, bevor der Eintrag geprüft wurde.
+explain-max-runtime-exceeded-causes =
+    Hängende Netzwerkdateisysteme oder Automounts haben den Scan verlangsamt.
+    Der Scan umfasst mehr Dateien, als in das Budget von --max-runtime passen.
+explain-max-runtime-exceeded-remediation =
+    Prüfen, ob die gescannten Dateisysteme eingehängt und erreichbar sind.
+    --max-runtime erhöhen oder den Scan mit --dir, --include oder --max-depth eingrenzen.
+
+explain-inspection-panicked = desktop-scout ist bei der Prüfung dieser Datei abgestürzt.
+explain-inspection-panicked-causes =
+    Ein Fehler in desktop-scout, ausgelöst durch ungewöhnlichen Dateiinhalt.
+explain-inspection-panicked-remediation =
+    Bitte den Dateiinhalt und die Log-Ausgabe als Fehler melden.
+
+explain-hidden-entry = Der Eintrag ist Hidden=true oder NoDisplay=true und wurde nicht geprüft.
+explain-hidden-entry-causes =
+    Versteckte Einträge werden standardmäßig absichtlich nicht gescannt.
+explain-hidden-entry-remediation =
+    Mit --include-hidden auch diese Einträge prüfen.
+
+explain-unsupported-type = Der Eintrag ist nicht Type=Application und wurde nicht geprüft.
+explain-unsupported-type-causes =
+    Link- und Directory-Einträge starten keine Programme.
+explain-unsupported-type-remediation =
+    Nichts zu tun; nur Anwendungen werden geprüft.
+    Mit --inventory diese Einträge mit ihrem Typ und den dateibezogenen Lints auflisten.
+
+explain-invalid-boolean = Ein boolescher Schlüssel hat einen anderen Wert als `true` oder `false`.
+explain-invalid-boolean-causes =
+    Werte wie `yes`, `1` oder `True` aus anderen Konfigurationsformaten übernommen.
+explain-invalid-boolean-remediation =
+    Genau `true` oder `false` verwenden.
+
+explain-key-not-allowed-for-type = Ein Schlüssel wird mit einem Type= verwendet, für den die Spezifikation ihn nicht definiert.
+explain-key-not-allowed-for-type-causes =
+    Schlüssel, die nur für Anwendungen gelten, in Link- oder Directory-Einträge übernommen.
+explain-key-not-allowed-for-type-remediation =
+    Den Schlüssel entfernen oder Type= korrigieren.
+
+explain-invalid-dbus-name = DBusActivatable=true, aber der Dateiname ist kein gültiger D-Bus-Name.
+explain-invalid-dbus-name-causes =
+    Der Starter wurde nicht nach der Reverse-DNS-ID der Anwendung benannt.
+explain-invalid-dbus-name-remediation =
+    Die Datei nach dem D-Bus-Namen umbenennen (z. B. org.example.App.desktop).
+    Andernfalls DBusActivatable=true entfernen.
+
+explain-template-placeholder = Der Eintrag enthält noch Platzhalter aus einer Starter-Vorlage.
+explain-template-placeholder-causes =
+    Ein selbst geschriebener Starter wurde aus einer Anleitung kopiert und nie fertiggestellt.
+explain-template-placeholder-remediation =
+    Den echten Namen und die echten Pfade eintragen oder den Starter löschen.
+
+explain-session-socket-missing = Exec= verweist auf einen Sitzungs-Socket oder Laufzeitpfad, der nicht existiert.
+explain-session-socket-missing-causes =
+    Ein Pfad /run/user/<uid>/ wurde für einen anderen Benutzer oder eine frühere Sitzung fest eingetragen.
+    WAYLAND_DISPLAY= oder ein Socket-Name (wayland-1, pipewire-0) wurde für einen Compositor oder Audioserver festgelegt, der nicht läuft.
+    Der Starter wird außerhalb einer grafischen Sitzung ohne XDG_RUNTIME_DIR gestartet.
+explain-session-socket-missing-remediation =
+    Den fest eingetragenen Socket entfernen, damit die Anwendung die Vorgaben der Sitzung verwendet.
+    Andernfalls $XDG_RUNTIME_DIR über ein Wrapper-Skript statt über einen festen Pfad ansprechen.
+
+explain-gpu-env-unavailable = Exec= wählt über die Umgebung eine GPU oder einen Grafiktreiber, die auf diesem Rechner nicht vorhanden sind.
+explain-gpu-env-unavailable-causes =
+    __NV_PRIME_RENDER_OFFLOAD=1 oder __GLX_VENDOR_LIBRARY_NAME=nvidia, aber der NVIDIA-Treiber wurde entfernt oder ist nicht geladen.
+    DRI_PRIME= nennt eine zweite GPU, die deaktiviert oder ausgebaut wurde.
+    VK_ICD_FILENAMES=/VK_DRIVER_FILES= zeigt auf ein Vulkan-Treibermanifest, das deinstalliert wurde.
+explain-gpu-env-unavailable-remediation =
+    Die GPU-Zuweisungen entfernen, damit die Anwendung die Standard-GPU verwendet.
+    Statt den Treiber fest einzutragen, die Aktion „Mit dedizierter Grafikkarte starten“ des Desktops verwenden (PrefersNonDefaultGPU=true).
+    Den Treiber neu installieren, wenn die GPU noch vorhanden ist.
+
+explain-icon-in-foreign-package-dir = Icon= zeigt auf eine Datei im privaten Verzeichnis eines anderen Pakets.
+explain-icon-in-foreign-package-dir-causes =
+    Ein Starter hat ein Symbol einer verwandten Anwendung übernommen (z. B. /usr/share/someotherapp/icon.png), statt ein eigenes mitzuliefern.
+explain-icon-in-foreign-package-dir-remediation =
+    Das Symbol mit dem eigenen Paket des Eintrags ausliefern, am besten im Symbolthema installiert (/usr/share/icons/hicolor) und über seinen Namen referenziert.
+    Andernfalls das Symbol über seinen Namen referenzieren und eine Abhängigkeit auf sein Paket angeben.
+
+explain-malformed-line = Eine Zeile ist weder Gruppenkopf noch Kommentar noch key=value-Paar.
+explain-malformed-line-causes =
+    Ein Kommentar ohne führendes `#`.
+    Ein Wert, der in der nächsten Zeile fortgesetzt wird, was das Format nicht unterstützt.
+    Schlüssel vor dem Kopf [Desktop Entry].
+explain-malformed-line-remediation =
+    Kommentaren ein `#` voranstellen und jeden Wert in einer einzigen Zeile halten.
+    Schlüssel unter ihren Gruppenkopf verschieben.
+
+explain-missing-desktop-entry-group = Die Datei beginnt nicht mit einer Gruppe [Desktop Entry].
+explain-missing-desktop-entry-group-causes =
+    Der Kopf ist falsch geschrieben (z. B. [Desktop entry]) oder fehlt.
+    Eine Gruppe [Desktop Action ...] steht vor der Hauptgruppe.
+explain-missing-desktop-entry-group-remediation =
+    Die Datei mit genau dem Kopf [Desktop Entry] beginnen.
+
+explain-invalid-encoding = Die Datei ist kein gültiges UTF-8.
+explain-invalid-encoding-causes =
+    Die Datei wurde in einer alten Kodierung wie Latin-1 geschrieben.
+explain-invalid-encoding-remediation =
+    Die Datei nach UTF-8 umwandeln (z. B. mit iconv -f latin1 -t utf-8).
+
+explain-missing-icon = Eine in Menüs angezeigte Anwendung hat kein Icon= (Hinweis).
+explain-missing-icon-causes =
+    Ein selbst geschriebener Starter, der nur Name= und Exec= setzt.
+explain-missing-icon-remediation =
+    Icon= mit einem Namen aus dem Symbolthema oder einem absoluten Pfad zu einem Bild ergänzen.
+    NoDisplay=true setzen, wenn der Eintrag nicht in Menüs erscheinen soll.
+
+explain-deprecated-key = Ein von der Desktop-Entry-Spezifikation abgekündigter Schlüssel ist noch gesetzt (Hinweis).
+explain-deprecated-key-causes =
+    Encoding=UTF-8, das die Spezifikation gestrichen hat, seit UTF-8 verpflichtend ist.
+    Schlüssel aus KDE 3 wie MiniIcon, TerminalOptions oder SwallowExec.
+explain-deprecated-key-remediation =
+    Den Schlüssel entfernen; aktuelle Starter ignorieren ihn.
+
+explain-unsafe-path-entry = Ein PATH-Eintrag ist leer, relativ oder beginnt mit einem nicht expandierten `~`.
+explain-unsafe-path-entry-causes =
+    Ein Shell-Profil hängt `:` oder `~/bin` an PATH an.
+    PATH wurde um ein relatives Verzeichnis wie `bin` erweitert.
+explain-unsafe-path-entry-remediation =
+    Absolute Verzeichnisse in PATH verwenden (z. B. `$HOME/bin` statt `~/bin`).
+    Leere Einträge entfernen (`::`, führendes oder abschließendes `:`).
+
+explain-no-desktop-files = Der Scan hat keine einzige .desktop-Datei gefunden.
+explain-no-desktop-files-causes =
+    XDG_DATA_HOME oder XDG_DATA_DIRS zeigen auf falsche Orte.
+    --no-default wurde ohne --dir angegeben.
+    Eine Quelle von --dir oder --files-from ist leer oder falsch geschrieben.
+explain-no-desktop-files-remediation =
+    Die Verzeichnisse mit RUST_LOG=debug prüfen und die XDG-Variablen oder Optionen korrigieren.
+
+explain-suppression-expired = Ein Eintrag der --suppressions-Datei hat sein `until`-Datum überschritten.
+explain-suppression-expired-causes =
+    Der akzeptierte Fehler sollte inzwischen behoben sein.
+    Die Unterdrückung wurde anderswo verlängert, dieser Eintrag aber nicht angepasst.
+explain-suppression-expired-remediation =
+    Den Eintrag reparieren und die Unterdrückung entfernen oder ihr `until`-Datum verschieben.
+
+## Plain output (--plain)
+
+plain-summary = { $broken ->
+    [one] 1 defekter Eintrag
+   *[other] { $broken } defekte Einträge
+}, { $warned ->
+    [one] 1 Eintrag mit Warnungen.
+   *[other] { $warned } Einträge mit Warnungen.
+}
+plain-broken-entry = Defekter Eintrag { $index } von { $total }.
+plain-warned-entry = Eintrag mit Warnungen { $index } von { $total }.
+plain-other-entry = Weiterer Eintrag { $index } von { $total }.
+plain-file = Datei: { $path }
+plain-status = Status: { $status }
+plain-hidden = Hidden: { $value }
+plain-no-display = NoDisplay: { $value }
+plain-reason-code = Grund-Code: { $code }
+plain-reason = Grund: { $reason }
+plain-first-seen = Zuerst defekt gesehen: { $first_seen }
+plain-broken-scans = Defekte Scans in Folge: { $scans }
+plain-same-file = Gleiche Datei wie: { $path }
+plain-warning-code = Warnung { $index } Code: { $code }
+plain-warning = Warnung { $index }: { $message }
+plain-scan-warning-code = Scan-Warnung { $index } Code: { $code }
+plain-scan-warning = Scan-Warnung { $index }: { $message }
+plain-fleet-summary = Flotten-Scan von { $hosts } Hosts.
+plain-host = Host { $index } von { $total }.
+plain-host-name = Hostname: { $host }
+plain-host-error = Fehler: { $error }
+plain-host-broken = Defekte Einträge: { $count }
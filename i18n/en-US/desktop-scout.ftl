@@ -0,0 +1,557 @@
+# desktop-scout messages (English, fallback for all other locales).
+#
+# Desktop entry keys (Exec, TryExec, Hidden, ...), reason codes and command-line options are
+# literal and must not be translated.
+
+## Text report
+
+report-warning = Warning [{ $code }]: { $message }
+report-baseline-suppressed = { $count ->
+    [one] { $count } known broken entry suppressed by the baseline.
+   *[other] { $count } known broken entries suppressed by the baseline.
+}
+report-no-broken = No broken desktop entries found.
+report-broken-header = Broken .desktop entries ({ $count }):
+report-warned-header = .desktop entries with warnings ({ $count }):
+report-id = ID: { $id }
+report-user = User: { $user }
+report-name = Name: { $name }
+report-exec = Exec: { $exec }
+report-tryexec = TryExec: { $tryexec }
+report-path = Path: { $path }
+report-suggested-exec = Suggested Exec: { $exec }
+report-flathub-available = Flathub: { $app_id } is still published, the local installation is damaged
+report-flathub-removed = Flathub: { $app_id } was removed from Flathub
+report-history = { $scans ->
+    [one] Broken since: { $first_seen } (first scan)
+   *[other] Broken since: { $first_seen } ({ $scans } consecutive scans)
+}
+report-same-file = Same file as: { $paths }
+quiet-broken = { $file }: broken [{ $code }]: { $reason }
+quiet-warning = { $file }: warning [{ $code }]: { $message }
+
+## Table
+
+table-status = Status
+table-code = Code
+table-id = ID
+table-name = Name
+table-file = File
+table-reason = Reason
+table-broken = broken
+table-warning = warning
+table-info = info
+table-ok = ok
+table-skipped = skipped
+table-suppressed = suppressed
+table-timed-out = timed out
+table-application = Application
+table-origin = Origin
+table-directory = Directory
+table-files = Files
+table-count-ok = Ok
+table-count-broken = Broken
+table-count-skipped = Skipped
+
+## Groups (--group-by dir|source)
+
+group-user-data = User entries ({ $path })
+group-desktop = Desktop icons ({ $path })
+group-system-data = System entries (XDG_DATA_DIRS)
+group-flatpak = Flatpak exports
+group-snap = Snap
+group-extra = { $path } (--dir)
+group-other = Other files
+
+## Notifications (--notify)
+
+notify-broken = { $count ->
+    [one] { $count } broken launcher found
+   *[other] { $count } broken launchers found
+}
+notify-warned = { $count ->
+    [one] { $count } launcher with warnings found
+   *[other] { $count } launchers with warnings found
+}
+notify-more = and { $count } more
+
+## Summary
+
+summary-scanned = { $files ->
+    [one] Scanned { $files } file: { $ok } ok, { $broken } broken, { $skipped } skipped.
+   *[other] Scanned { $files } files: { $ok } ok, { $broken } broken, { $skipped } skipped.
+}
+summary-scanned-in = { $files ->
+    [one] Scanned { $files } file in { $seconds }s: { $ok } ok, { $broken } broken, { $skipped } skipped.
+   *[other] Scanned { $files } files in { $seconds }s: { $ok } ok, { $broken } broken, { $skipped } skipped.
+}
+summary-suppressed = { $count ->
+    [one] { $count } broken entry accepted by --suppressions.
+   *[other] { $count } broken entries accepted by --suppressions.
+}
+summary-timed-out = { $count ->
+    [one] { $count } entry timed out and could not be checked.
+   *[other] { $count } entries timed out and could not be checked.
+}
+
+## Subcommands
+
+restore-done = Restored { $path }
+fix-script-written = { $count ->
+    [one] Wrote { $path } with fixes for { $count } broken entry.
+   *[other] Wrote { $path } with fixes for { $count } broken entries.
+}
+fix-patch-written = { $count ->
+    [one] Wrote { $path } with edits for { $count } entry (apply with `cd / && patch -p0`).
+   *[other] Wrote { $path } with edits for { $count } entries (apply with `cd / && patch -p0`).
+}
+fix-aborted = Aborted; nothing was changed.
+export-written = { $count ->
+    [one] Wrote { $path } with { $count } entry and the report.
+   *[other] Wrote { $path } with { $count } entries and the report.
+}
+
+## Fix
+
+fix-action = { $action ->
+    [delete] delete
+    [hide] hide
+    [override] override
+    [quarantine] quarantine
+    [trash] trash
+    [repair] repair
+   *[skip] skip
+}
+fix-confirm = Apply these changes? [y/N]
+fix-nothing-to-fix = No broken desktop entries to fix.
+fix-removed = Removed   { $path }
+fix-partially-removed = Removed   { $path } ({ $error })
+fix-trashed = Trashed   { $path }
+fix-hidden = Hidden    { $path }
+fix-repaired = Repaired  { $path } ({ $changes })
+fix-overridden = Hidden    { $path } (override { $override })
+fix-quarantined = Moved     { $path } to quarantine (id { $id })
+fix-skipped = Skipped   { $path } ({ $reason })
+fix-failed = Failed    { $path } ({ $error })
+fix-working-copy = working copy      { $path }
+fix-removed-companion = removed companion { $path }
+fix-left-companion = left companion    { $path }
+fix-companions-left = Re-run with --with-companions to also clean up the companion files above.
+fix-backups-written = Backups written to { $path }
+
+## History
+
+journal-empty = No changes have been journaled yet.
+journal-restore = { $path } (quarantine id { $id })
+journal-undo = { $path } (fix of run { $run })
+journal-baseline = { $count ->
+    [one] { $path } ({ $count } finding accepted)
+   *[other] { $path } ({ $count } findings accepted)
+}
+
+## Undo
+
+undo-nothing = No fixes to undo.
+undo-reverted = Reverted  { $path } (run { $run })
+undo-failed = Failed    { $path } ({ $error })
+
+## Formatting (fmt)
+
+fmt-reformatted = Reformatted { $path }
+fmt-failed = Failed      { $path }: { $error }
+fmt-all-formatted = { $files ->
+    [one] { $files } file is canonically formatted.
+   *[other] All { $files } files are canonically formatted.
+}
+fmt-would-reformat = { $files ->
+    [one] { $changed } of { $files } file would be reformatted.
+   *[other] { $changed } of { $files } files would be reformatted.
+}
+fmt-reformatted-summary = { $files ->
+    [one] { $changed } of { $files } file reformatted.
+   *[other] { $changed } of { $files } files reformatted.
+}
+
+## Quarantine (restore)
+
+quarantine-empty = The quarantine is empty.
+quarantine-companions = { $count ->
+    [one] (+{ $count } companion file)
+   *[other] (+{ $count } companion files)
+}
+
+## Fleet (--hosts)
+
+fleet-summary = { $hosts ->
+    [one] Fleet summary ({ $hosts } host):
+   *[other] Fleet summary ({ $hosts } hosts):
+}
+fleet-host-error = error: { $error }
+fleet-host-broken = { $count } broken
+fleet-host-header = { $count ->
+    [one] { $host } ({ $count } broken entry):
+   *[other] { $host } ({ $count } broken entries):
+}
+fleet-reason = Reason [{ $code }]: { $reason }
+
+## Timer (install-timer)
+
+timer-unit-written = Wrote { $path }
+timer-report-path = Reports will be written to { $path }
+timer-enable = Enable the timer with:
+
+## Scan warnings
+
+scan-source-dirs = { $count ->
+    [one] { $count } scanned directory
+   *[other] { $count } scanned directories
+}
+scan-source-file-list = the file list { $path }
+warning-no-desktop-files = No .desktop files found in { $source }; check XDG_DATA_HOME/XDG_DATA_DIRS, --dir and --no-default
+
+## Reasons
+
+reason-unreadable-file = Failed to read/parse file: { $error }
+reason-dangling-symlink = Symlink to { $target }, which does not exist
+reason-inspection-panicked = Inspection aborted unexpectedly: { $error }
+reason-inspection-timed-out = Inspection did not finish within { $seconds }s
+reason-max-runtime-exceeded = Not inspected before the scan reached --max-runtime ({ $seconds }s)
+reason-missing-desktop-extension = Contains a [Desktop Entry] group but lacks the .desktop extension, so launchers ignore it (rename to { $name }.desktop)
+reason-hidden-entry = Hidden=true or NoDisplay=true (use --include-hidden to scan these)
+reason-unsupported-type = Type={ $type } (only Type=Application is checked)
+reason-tryexec-unresolved = TryExec does not resolve: { $tryexec }
+reason-exec-unresolved-despite-tryexec = Exec does not resolve (even though TryExec does)
+reason-exec-missing = No Exec key found (and not DBusActivatable)
+reason-exec-not-found = Exec does not resolve
+reason-exec-unquoted-spaces = Exec path contains spaces and must be quoted: { $path }
+reason-systemd-run-missing = Exec launches through { $program }, which does not resolve
+reason-user-manager-unavailable = Exec launches through systemd-run --user, but no systemd user manager is running in this session
+reason-exec-shell-builtin = `{ $command }` is a shell builtin and no executable of that name is in PATH; launchers do not run a shell
+reason-exec-shell-alias = `{ $command }` is only defined as a shell { $kind ->
+    [alias] alias
+   *[function] function
+} in { $source }; launchers do not run a shell, so aliases and functions cannot be used
+reason-check-timed-out = { $what } check did not finish within { $seconds }s
+reason-exec-check-failed = Exec check failed: { $error }
+reason-exec-unparsable = Exec cannot be shell-split (check its quoting)
+reason-exec-no-executable = Exec names no executable
+reason-script-missing = Interpreter { $interpreter } exists, but its script argument is missing: { $path }
+
+## Lint warnings
+
+lint-invalid-encoding = File is not valid UTF-8 (invalid bytes were replaced for the other lints)
+lint-not-key-value = Line { $line } is not a key=value pair: { $text }
+lint-before-first-group = Line { $line } comes before the first group header: { $text }
+lint-wrong-first-group = The first group is [{ $group }], not [Desktop Entry]
+lint-no-desktop-entry-group = The file has no [Desktop Entry] group
+lint-invalid-boolean = { $key }={ $value } is not a valid boolean (the spec only allows `true` or `false`)
+lint-key-not-allowed-for-type = { $key } is only valid for Type=Application (found Type={ $type })
+lint-invalid-dbus-name = DBusActivatable=true requires the file name to be a D-Bus well-known name (e.g. org.example.App.desktop), found { $name }.desktop
+lint-template-name = Name={ $name } looks like a template placeholder
+lint-template-path = { $key } contains the template path `{ $marker }`: { $value }
+lint-unfinished-comment = File contains an unfinished-work comment: { $comment }
+lint-missing-icon = Application entry has no Icon= and shows a generic icon in menus
+lint-deprecated-key = { $key } is deprecated and ignored by current launchers
+lint-foreign-icon-dir = Icon={ $icon } points into the directory of another package ({ $owner }); it breaks when that package changes
+session-runtime-dir-unset = Exec= refers to $XDG_RUNTIME_DIR/{ $relative }, but XDG_RUNTIME_DIR is not set in this session
+session-runtime-path-missing = Exec= refers to $XDG_RUNTIME_DIR/{ $relative } ({ $path }), which does not exist in this session
+session-path-missing = Exec= refers to { $path }, which does not exist in this session
+gpu-nvidia-not-loaded = Exec= sets { $name }={ $value }, but the NVIDIA driver is not loaded
+gpu-manifest-missing = Exec= sets { $name }={ $value }, but { $count ->
+    [one] the driver manifest { $manifests } does not exist
+   *[other] the driver manifests { $manifests } do not exist
+}
+gpu-too-few-gpus = Exec= sets { $name }={ $value }, but { $count ->
+    [one] only one GPU is present
+   *[other] only { $count } GPUs are present
+}
+gpu-pci-device-missing = Exec= sets { $name }={ $value }, but no PCI device { $device } is present
+path-entry-empty = PATH entry #{ $index } is empty and means the current working directory; launchers start with an unpredictable working directory
+path-entry-tilde = PATH entry #{ $index } ({ $entry }) contains an unexpanded `~`; only shells expand it, launchers using execvp do not
+path-entry-relative = PATH entry #{ $index } ({ $entry }) is relative and depends on the working directory
+suppression-expired = The suppression of { $path } expired on { $until } and no longer applies
+suppression-rule-expired = The suppression of { $path } ({ $rule }) expired on { $until } and no longer applies
+
+## Explain
+
+explain-causes-header = Typical causes:
+explain-remediation-header = Remediation:
+
+explain-exec-not-found = The program named in Exec= cannot be found or is not executable.
+explain-exec-not-found-causes =
+    The application was uninstalled but its launcher was left behind.
+    The program lives in a directory that is not on the launcher's PATH.
+    An absolute path in Exec= points to a moved or renamed install prefix.
+explain-exec-not-found-remediation =
+    Reinstall the application, or remove the stale .desktop file.
+    Use an absolute path in Exec= if the program is outside the default PATH.
+    Update Exec= to the program's new location.
+
+explain-exec-shell-alias = Exec= names a command that only exists as a shell alias or function.
+explain-exec-shell-alias-causes =
+    The command was defined in ~/.bashrc, ~/.zshrc or a fish config for the terminal.
+    Launchers run Exec= directly without a shell, so aliases and functions are unknown.
+explain-exec-shell-alias-remediation =
+    Put the full command line from the alias into Exec=.
+    Turn the alias or function into a script in a PATH directory (e.g. ~/.local/bin).
+
+explain-exec-shell-builtin = Exec= names a shell builtin, and no program of that name exists on disk.
+explain-exec-shell-builtin-causes =
+    The command (e.g. `cd`, `source`, `export`) only exists inside a shell.
+    A coreutils-style binary (`test`, `[`, `time`) is normally provided but not installed.
+explain-exec-shell-builtin-remediation =
+    Wrap the command line in a shell: Exec=sh -c '...'.
+    Install the package providing the standalone binary (e.g. coreutils or time).
+
+explain-tryexec-unresolved = The program named in TryExec= cannot be found, so launchers hide the entry.
+explain-tryexec-unresolved-causes =
+    The application was uninstalled but its launcher was left behind.
+    TryExec= names a binary that differs from the one actually installed.
+explain-tryexec-unresolved-remediation =
+    Remove the stale .desktop file if the application is gone.
+    Point TryExec= at the installed binary, or drop the key.
+
+explain-exec-unresolved-despite-tryexec = TryExec= resolves, but the program in Exec= does not.
+explain-exec-unresolved-despite-tryexec-causes =
+    TryExec= and Exec= refer to different binaries and only one is installed.
+    Exec= uses a wrapper script that was removed.
+explain-exec-unresolved-despite-tryexec-remediation =
+    Make Exec= and TryExec= refer to the same, installed program.
+
+explain-exec-parse-error = Exec= cannot be parsed into a command line.
+explain-exec-parse-error-causes =
+    Unbalanced quotes in Exec=.
+    Exec= consists only of `env` assignments or field codes.
+explain-exec-parse-error-remediation =
+    Fix the quoting in Exec= according to the Desktop Entry spec.
+
+explain-exec-unquoted-spaces = Exec= names a program whose path contains spaces, but does not quote it.
+explain-exec-unquoted-spaces-causes =
+    A hand-written launcher for a program in a directory like `~/My Apps`.
+    A generator that pastes the install path into Exec= without quoting.
+explain-exec-unquoted-spaces-remediation =
+    Put the path in double quotes: Exec="/home/user/My Apps/app" %U.
+    Run `desktop-scout fix --mode repair`, which applies the suggested Exec= line.
+
+explain-systemd-run-unavailable = Exec= launches the program through systemd-run, which cannot run it here.
+explain-systemd-run-unavailable-causes =
+    systemd-run is not installed (e.g. on a system without systemd).
+    A `systemd-run --user --scope` prefix copied from GNOME, but no systemd user manager runs in this session (checked with --check-user-manager).
+explain-systemd-run-unavailable-remediation =
+    Drop the systemd-run prefix and run the program directly.
+    Enable the user manager (systemd --user) for the session, e.g. via pam_systemd.
+
+explain-script-missing = Exec= runs an interpreter, but the script it should run is missing.
+explain-script-missing-causes =
+    A hand-written launcher for a script that was moved or deleted.
+    A virtualenv or project checkout was removed.
+explain-script-missing-remediation =
+    Restore the script, or update the path in Exec=.
+    Remove the launcher if the script is no longer needed.
+
+explain-exec-missing = The entry has no Exec= key and is not DBusActivatable.
+explain-exec-missing-causes =
+    A truncated or hand-edited .desktop file.
+explain-exec-missing-remediation =
+    Add an Exec= line, or set DBusActivatable=true for D-Bus services.
+
+explain-unreadable-file = The .desktop file could not be read.
+explain-unreadable-file-causes =
+    Insufficient permissions.
+    The file is not valid UTF-8.
+    An I/O error on a network or removable filesystem.
+explain-unreadable-file-remediation =
+    Check file permissions and encoding.
+    Re-run the scan once the filesystem is reachable.
+
+explain-dangling-symlink = The .desktop file is a symlink whose target does not exist.
+explain-dangling-symlink-causes =
+    The application was uninstalled, but a manually created link to its launcher was left behind.
+    A Flatpak or Snap export was removed without cleaning up links to it.
+    The link is relative and was moved or copied to another directory.
+explain-dangling-symlink-remediation =
+    Remove the symlink, or point it at the launcher's new location.
+
+explain-missing-desktop-extension = The file is a desktop entry, but launchers ignore it without `.desktop`.
+explain-missing-desktop-extension-causes =
+    A hand-written launcher saved without an extension (or as .txt).
+    A download or copy tool stripped the extension.
+explain-missing-desktop-extension-remediation =
+    Rename the file so it ends in `.desktop`.
+
+explain-check-timed-out = Checking the entry did not finish in time.
+explain-check-timed-out-causes =
+    Exec= or TryExec= points into a hung network filesystem or automount.
+    PATH contains a directory on an unreachable filesystem.
+explain-check-timed-out-remediation =
+    Check that the referenced filesystems are mounted and reachable.
+    Remove unreachable directories from PATH.
+    Raise --timeout for filesystems that are slow but working.
+
+explain-max-runtime-exceeded = The scan reached --max-runtime before the entry was inspected.
+explain-max-runtime-exceeded-causes =
+    Hung network filesystems or automounts slowed the scan down.
+    The scan covers more files than fit into the --max-runtime budget.
+explain-max-runtime-exceeded-remediation =
+    Check that the scanned filesystems are mounted and reachable.
+    Raise --max-runtime, or narrow the scan with --dir, --include or --max-depth.
+
+explain-inspection-panicked = desktop-scout crashed while inspecting this file.
+explain-inspection-panicked-causes =
+    A bug in desktop-scout triggered by unusual file content.
+explain-inspection-panicked-remediation =
+    Please report the file content and the log output as a bug.
+
+explain-hidden-entry = The entry is Hidden=true or NoDisplay=true and was not checked.
+explain-hidden-entry-causes =
+    Hidden entries are deliberately excluded from scans by default.
+explain-hidden-entry-remediation =
+    Pass --include-hidden to check these entries as well.
+
+explain-unsupported-type = The entry is not Type=Application and was not checked.
+explain-unsupported-type-causes =
+    Link and Directory entries do not launch programs.
+explain-unsupported-type-remediation =
+    Nothing to do; only applications are validated.
+    Pass --inventory to list these entries with their type and file-level lints.
+
+explain-invalid-boolean = A boolean key has a value other than `true` or `false`.
+explain-invalid-boolean-causes =
+    Values like `yes`, `1` or `True` copied from other config formats.
+explain-invalid-boolean-remediation =
+    Use exactly `true` or `false`.
+
+explain-key-not-allowed-for-type = A key is used with a Type= the spec does not define it for.
+explain-key-not-allowed-for-type-causes =
+    Application-only keys copied into Link or Directory entries.
+explain-key-not-allowed-for-type-remediation =
+    Remove the key, or fix Type=.
+
+explain-invalid-dbus-name = DBusActivatable=true, but the file name is not a valid D-Bus name.
+explain-invalid-dbus-name-causes =
+    The launcher was not named after the application's reverse-DNS ID.
+explain-invalid-dbus-name-remediation =
+    Rename the file to the D-Bus name (e.g. org.example.App.desktop).
+    Otherwise drop DBusActivatable=true.
+
+explain-template-placeholder = The entry still contains placeholders from a launcher template.
+explain-template-placeholder-causes =
+    A hand-written launcher was copied from a tutorial and never finished.
+explain-template-placeholder-remediation =
+    Fill in the real name and paths, or delete the launcher.
+
+explain-session-socket-missing = Exec= refers to a session socket or runtime path that does not exist.
+explain-session-socket-missing-causes =
+    A /run/user/<uid>/ path was hard-coded for another user or an earlier session.
+    WAYLAND_DISPLAY= or a socket name (wayland-1, pipewire-0) was pinned for a compositor or audio server that is not running.
+    The launcher is started outside a graphical session, without XDG_RUNTIME_DIR.
+explain-session-socket-missing-remediation =
+    Drop the hard-coded socket so the application uses the session's defaults.
+    Otherwise refer to $XDG_RUNTIME_DIR via a wrapper script instead of a fixed path.
+
+explain-gpu-env-unavailable = Exec= selects a GPU or graphics driver through the environment that is not present on this machine.
+explain-gpu-env-unavailable-causes =
+    __NV_PRIME_RENDER_OFFLOAD=1 or __GLX_VENDOR_LIBRARY_NAME=nvidia, but the NVIDIA driver was removed or is not loaded.
+    DRI_PRIME= names a second GPU that was disabled or removed.
+    VK_ICD_FILENAMES=/VK_DRIVER_FILES= points at a Vulkan driver manifest that was uninstalled.
+explain-gpu-env-unavailable-remediation =
+    Drop the GPU assignments so the application uses the default GPU.
+    Use the desktop's "Launch using Discrete Graphics Card" action (PrefersNonDefaultGPU=true) instead of hard-coding the driver.
+    Reinstall the driver if the GPU is still present.
+
+explain-icon-in-foreign-package-dir = Icon= points at a file inside another package's private directory.
+explain-icon-in-foreign-package-dir-causes =
+    A launcher borrowed an icon from a related application (e.g. /usr/share/someotherapp/icon.png) instead of shipping its own.
+explain-icon-in-foreign-package-dir-remediation =
+    Ship the icon with the entry's own package, ideally installed into the icon theme (/usr/share/icons/hicolor) and referenced by name.
+    Otherwise refer to the icon by name and declare a dependency on its package.
+
+explain-malformed-line = A line is neither a group header, a comment nor a key=value pair.
+explain-malformed-line-causes =
+    A comment written without a leading `#`.
+    A value continued on the next line, which the format does not support.
+    Keys written before the [Desktop Entry] header.
+explain-malformed-line-remediation =
+    Prefix comments with `#` and keep every value on a single line.
+    Move keys below their group header.
+
+explain-missing-desktop-entry-group = The file does not start with a [Desktop Entry] group.
+explain-missing-desktop-entry-group-causes =
+    The header is misspelled (e.g. [Desktop entry]) or missing.
+    A [Desktop Action ...] group was placed before the main group.
+explain-missing-desktop-entry-group-remediation =
+    Start the file with the exact header [Desktop Entry].
+
+explain-invalid-encoding = The file is not valid UTF-8.
+explain-invalid-encoding-causes =
+    The file was written in a legacy encoding such as Latin-1.
+explain-invalid-encoding-remediation =
+    Convert the file to UTF-8 (e.g. with iconv -f latin1 -t utf-8).
+
+explain-missing-icon = An application shown in menus has no Icon= (info).
+explain-missing-icon-causes =
+    A hand-written launcher that only sets Name= and Exec=.
+explain-missing-icon-remediation =
+    Add Icon= with an icon theme name or an absolute path to an image.
+    Set NoDisplay=true if the entry is not meant to appear in menus.
+
+explain-deprecated-key = A key the Desktop Entry spec deprecated is still set (info).
+explain-deprecated-key-causes =
+    Encoding=UTF-8, which the spec dropped once UTF-8 became mandatory.
+    KDE 3 era keys such as MiniIcon, TerminalOptions or SwallowExec.
+explain-deprecated-key-remediation =
+    Remove the key; current launchers ignore it.
+
+explain-unsafe-path-entry = A PATH entry is empty, relative or starts with an unexpanded `~`.
+explain-unsafe-path-entry-causes =
+    A shell profile appends `:` or `~/bin` to PATH.
+    PATH was extended with a relative directory such as `bin`.
+explain-unsafe-path-entry-remediation =
+    Use absolute directories in PATH (e.g. `$HOME/bin` instead of `~/bin`).
+    Remove empty entries (`::`, leading or trailing `:`).
+
+explain-no-desktop-files = The scan did not find a single .desktop file.
+explain-no-desktop-files-causes =
+    XDG_DATA_HOME or XDG_DATA_DIRS point to the wrong locations.
+    --no-default was given without any --dir.
+    A --dir or --files-from source is empty or misspelled.
+explain-no-desktop-files-remediation =
+    Check the directories with RUST_LOG=debug and fix the XDG variables or flags.
+
+explain-suppression-expired = An entry of the --suppressions file is past its `until` date.
+explain-suppression-expired-causes =
+    The accepted breakage was meant to be fixed by now.
+    The suppression was renewed elsewhere but this entry was not updated.
+explain-suppression-expired-remediation =
+    Fix the entry and remove the suppression, or move its `until` date forward.
+
+## Plain output (--plain)
+
+plain-summary = { $broken ->
+    [one] 1 broken entry
+   *[other] { $broken } broken entries
+}, { $warned ->
+    [one] 1 entry with warnings.
+   *[other] { $warned } entries with warnings.
+}
+plain-broken-entry = Broken entry { $index } of { $total }.
+plain-warned-entry = Entry with warnings { $index } of { $total }.
+plain-other-entry = Other entry { $index } of { $total }.
+plain-file = File: { $path }
+plain-status = Status: { $status }
+plain-hidden = Hidden: { $value }
+plain-no-display = NoDisplay: { $value }
+plain-reason-code = Reason code: { $code }
+plain-reason = Reason: { $reason }
+plain-first-seen = First seen broken: { $first_seen }
+plain-broken-scans = Consecutive broken scans: { $scans }
+plain-same-file = Same file as: { $path }
+plain-warning-code = Warning { $index } code: { $code }
+plain-warning = Warning { $index }: { $message }
+plain-scan-warning-code = Scan warning { $index } code: { $code }
+plain-scan-warning = Scan warning { $index }: { $message }
+plain-fleet-summary = Fleet scan of { $hosts } hosts.
+plain-host = Host { $index } of { $total }.
+plain-host-name = Host name: { $host }
+plain-host-error = Error: { $error }
+plain-host-broken = Broken entries: { $count }
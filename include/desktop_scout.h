@@ -0,0 +1,121 @@
+#ifndef DESKTOP_SCOUT_H
+#define DESKTOP_SCOUT_H
+
+/* Generated by cbindgen from src/capi.rs. Do not edit. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+// Scan the default directories with default options.
+#define DS_SCAN_DEFAULT 0
+
+// Do not scan the default XDG directories (`--no-default`).
+#define DS_SCAN_NO_DEFAULT (1 << 0)
+
+// Include `Hidden`/`NoDisplay` entries (`--include-hidden`).
+#define DS_SCAN_INCLUDE_HIDDEN (1 << 1)
+
+// Enable the interpreter script heuristic (`--check-script-args`).
+#define DS_SCAN_CHECK_SCRIPT_ARGS (1 << 2)
+
+// A single finding with its fields pre-converted to C strings.
+typedef struct DsFinding DsFinding;
+
+// Result of [`ds_scan`]: the reported findings of one scan.
+typedef struct DsReport DsReport;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+// Run a scan and return its reported findings, or `NULL` on failure (see [`ds_last_error`]).
+//
+// `dirs` is an array of `n_dirs` extra directories (`--dir`) and may be `NULL` if `n_dirs` is
+// 0. `flags` is a bitwise OR of the `DS_SCAN_*` constants. The call blocks until the scan is
+// finished. The result must be released with [`ds_report_free`].
+//
+// # Safety
+// `dirs` must point to `n_dirs` valid, NUL-terminated strings (or be `NULL` with `n_dirs == 0`).
+DsReport *ds_scan(const char *const *dirs, size_t n_dirs, uint32_t flags);
+
+// Message of the last failed call on this thread, or `NULL` if there was none.
+//
+// The string is owned by the library and valid until the next failing call on this thread.
+const char *ds_last_error(void);
+
+// Number of findings in `report`.
+//
+// # Safety
+// `report` must be a valid pointer returned by [`ds_scan`].
+size_t ds_report_len(const DsReport *report);
+
+// Finding at `index`, or `NULL` if out of range. Borrowed from `report`.
+//
+// # Safety
+// `report` must be a valid pointer returned by [`ds_scan`].
+const DsFinding *ds_report_get(const DsReport *report, size_t index);
+
+// Serialize `report` as the JSON document `--output json` prints. Free with [`ds_string_free`].
+//
+// Returns `NULL` on failure (see [`ds_last_error`]).
+//
+// # Safety
+// `report` must be a valid pointer returned by [`ds_scan`].
+char *ds_report_to_json(const DsReport *report);
+
+// Free a report returned by [`ds_scan`]. `NULL` is ignored.
+//
+// # Safety
+// `report` must be `NULL` or a pointer returned by [`ds_scan`] that was not freed yet.
+void ds_report_free(DsReport *report);
+
+// Free a string returned by [`ds_report_to_json`]. `NULL` is ignored.
+//
+// # Safety
+// `s` must be `NULL` or a string returned by this library that was not freed yet.
+void ds_string_free(char *s);
+
+// Stable finding ID.
+//
+// # Safety
+// `finding` must be a valid pointer returned by [`ds_report_get`].
+const char *ds_finding_id(const DsFinding *finding);
+
+// Path of the `.desktop` file.
+//
+// # Safety
+// `finding` must be a valid pointer returned by [`ds_report_get`].
+const char *ds_finding_path(const DsFinding *finding);
+
+// `Name=` value, or `NULL` if absent.
+//
+// # Safety
+// `finding` must be a valid pointer returned by [`ds_report_get`].
+const char *ds_finding_name(const DsFinding *finding);
+
+// Status kind: `ok`, `broken`, `skipped`, `suppressed` or `timed_out`.
+//
+// # Safety
+// `finding` must be a valid pointer returned by [`ds_report_get`].
+const char *ds_finding_kind(const DsFinding *finding);
+
+// Reason code (e.g. `EXEC_NOT_FOUND`), or `NULL` for `ok` entries.
+//
+// # Safety
+// `finding` must be a valid pointer returned by [`ds_report_get`].
+const char *ds_finding_code(const DsFinding *finding);
+
+// Human-readable reason, or `NULL` for `ok` entries.
+//
+// # Safety
+// `finding` must be a valid pointer returned by [`ds_report_get`].
+const char *ds_finding_reason(const DsFinding *finding);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* DESKTOP_SCOUT_H */
@@ -3,10 +3,20 @@
 //! This module defines the CLI surfaced by `desktop-scout`.
 
 // -- std imports
-use std::path::PathBuf;
+use std::{path::PathBuf, sync::Arc};
 
 // -- crate imports
-use clap::Parser;
+use anyhow::{Result, bail};
+use clap::{Parser, Subcommand, ValueEnum};
+use globset::Glob;
+
+// -- module imports
+use crate::{
+    remap::RemapRule,
+    report::{Finding, ReasonCode, Report, Severity, Status},
+    scan::{NewerThan, OriginLimit},
+    simulate::Simulation,
+};
 
 /// Command-line arguments for `desktop-scout`.
 ///
@@ -17,14 +27,83 @@ use clap::Parser;
     about = "Detect broken/stale .desktop files by validating Exec/TryExec"
 )]
 pub struct Args {
-    /// Print JSON output (machine readable)
-    #[arg(long)]
+    /// Subcommand to run (scans when omitted)
+    #[command(subcommand)]
+    pub command: Option<Command>,
+
+    /// Read option defaults from FILE instead of ~/.config/desktop-scout/config.toml (options
+    /// on the command line take precedence)
+    #[arg(long, value_name = "FILE")]
+    pub config: Option<PathBuf>,
+
+    /// Apply the settings of the [profile.NAME] table of the configuration file, e.g. `ci`
+    #[arg(long, value_name = "NAME")]
+    pub profile: Option<String>,
+
+    /// Output format (csv and table need a list result, e.g. the scan report or `history`)
+    #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
+    pub output: OutputFormat,
+
+    /// Same as `--output json` (kept for existing scripts and older fleet hosts)
+    #[arg(long, hide = true, conflicts_with = "output")]
     pub json: bool,
 
+    /// Render the scan report with this Tera template file; implies `--output template`
+    #[arg(long, value_name = "FILE", conflicts_with_all = ["json", "plain"])]
+    pub template: Option<PathBuf>,
+
+    /// With `--output jsonl`, print each finding as soon as it is inspected instead of after the
+    /// scan (scan warnings and the summary follow at the end)
+    #[arg(long, conflicts_with_all = ["baseline", "check_flathub", "group_by"])]
+    pub stream: bool,
+
+    /// Print strictly linear, screen-reader-friendly text: one labeled paragraph per finding
+    #[arg(long, conflicts_with_all = ["json", "output"])]
+    pub plain: bool,
+
+    /// Print nothing for a clean scan and a single line per broken entry otherwise (per warning
+    /// as well with `--fail-on warning`), for cron jobs
+    #[arg(short, long, conflicts_with_all = ["json", "output", "plain", "template"])]
+    pub quiet: bool,
+
+    /// Print a line per listed entry from this format string instead of the report, e.g.
+    /// '{path}\t{status}\t{reason}' (fields: path, id, name, type, exec, try_exec, status, code,
+    /// reason, resolved, severity, warnings, suggested_exec)
+    #[arg(long, value_name = "FORMAT", conflicts_with_all = ["json", "output", "plain", "quiet", "template"])]
+    pub format: Option<String>,
+
+    /// Write the report to FILE instead of stdout (`-` for stdout); the file is replaced
+    /// atomically once the report is complete
+    #[arg(long, value_name = "FILE", conflicts_with = "stream")]
+    pub output_file: Option<PathBuf>,
+
+    /// Send a desktop notification summarizing the scan if it found broken entries or entries
+    /// with warnings (for scans run by timers in the background)
+    #[arg(long)]
+    pub notify: bool,
+
+    /// When to color text output (`auto` colors a terminal unless NO_COLOR is set)
+    #[arg(long, value_enum, default_value_t = ColorChoice::Auto)]
+    pub color: ColorChoice,
+
+    /// Order of the listed entries (the text report still lists broken entries first)
+    #[arg(long, value_enum, value_name = "KEY", default_value_t = SortKey::Path, conflicts_with = "stream")]
+    pub sort: SortKey,
+
+    /// Group the report: `app` nests every entry of an application (native, Flatpak, autostart,
+    /// overrides) under it, `dir` and `source` cluster entries by directory or source layer
+    #[arg(long, value_enum, value_name = "KEY")]
+    pub group_by: Option<GroupBy>,
+
     /// Do not use default scan directories
     #[arg(long)]
     pub no_default: bool,
 
+    /// Also scan the application, autostart and desktop folders of every regular user and
+    /// attribute findings to their user (requires root)
+    #[arg(long)]
+    pub all_users: bool,
+
     /// Suppress all logging output
     #[arg(long)]
     pub no_log: bool,
@@ -33,10 +112,73 @@ pub struct Args {
     #[arg(long)]
     pub include_hidden: bool,
 
+    /// List entries that are not Type=Application (links, directories, services) with their type
+    /// and file-level lint warnings, and lint files that are not valid UTF-8 instead of failing
+    /// them
+    #[arg(long)]
+    pub inventory: bool,
+
+    /// Report entries with these statuses (comma-separated) instead of only broken entries and
+    /// entries with warnings
+    #[arg(long, value_enum, value_delimiter = ',', value_name = "STATUS")]
+    pub status: Vec<StatusFilter>,
+
+    /// Report every scanned entry (same as `--status ok,broken,skipped,suppressed,timed-out`)
+    #[arg(long, conflicts_with = "status")]
+    pub all: bool,
+
     /// Additional directory to scan (can be passed multiple times)
     #[arg(long = "dir")]
     pub extra_dirs: Vec<PathBuf>,
 
+    /// Read additional scan directories from FILE, one per line (`-` for stdin)
+    #[arg(long, value_name = "FILE", conflicts_with = "files_from")]
+    pub dirs_from: Option<PathBuf>,
+
+    /// Read newline-separated `.desktop` paths from FILE (`-` for stdin) instead of scanning dirs
+    #[arg(long, value_name = "FILE")]
+    pub files_from: Option<PathBuf>,
+
+    /// Leave files matching GLOB out of the scan, e.g. 'steam_app_*.desktop' (patterns with a
+    /// `/` match the full path); repeatable
+    #[arg(long, value_name = "GLOB")]
+    pub ignore: Vec<Glob>,
+
+    /// Scan files matching GLOB even if an --ignore pattern matches them; repeatable
+    #[arg(long, value_name = "GLOB")]
+    pub allow: Vec<Glob>,
+
+    /// Only scan files whose name matches GLOB, e.g. 'org.kde.*'; repeatable
+    #[arg(long, value_name = "GLOB")]
+    pub include: Vec<Glob>,
+
+    /// Leave files whose name matches GLOB out of the scan, even if included, e.g. 'wine-*';
+    /// repeatable
+    #[arg(long, value_name = "GLOB")]
+    pub exclude: Vec<Glob>,
+
+    /// Do not descend into directories on a different filesystem than their scan root
+    #[arg(long)]
+    pub one_file_system: bool,
+
+    /// Descend at most N directory levels below each scanned directory (0 scans only the files
+    /// directly in it)
+    #[arg(long, value_name = "N")]
+    pub max_depth: Option<usize>,
+
+    /// Only scan files modified after WHEN: an age such as 30m, 12h, 7d or 2w, or a local date or
+    /// time such as 2026-10-01 or 2026-10-01T14:00 (RFC 3339 timestamps with an offset work too)
+    #[arg(long, value_name = "WHEN")]
+    pub newer_than: Option<NewerThan>,
+
+    /// Which symlinks the directory walk follows
+    #[arg(long, value_enum, default_value_t = SymlinkPolicy::Files)]
+    pub follow_symlinks: SymlinkPolicy,
+
+    /// Also report files containing a [Desktop Entry] group that lack the .desktop extension
+    #[arg(long)]
+    pub detect_misnamed: bool,
+
     /// Do not scan common extra dirs (Flatpak, Snap desktop exports)
     #[arg(long)]
     pub no_common_extras: bool,
@@ -45,7 +187,578 @@ pub struct Args {
     #[arg(long)]
     pub check_script_args: bool,
 
-    /// Max concurrent inspections (defaults to CPU count * 4)
+    /// Explain unresolved commands that are only defined as shell aliases/functions in rc files
+    #[arg(long)]
+    pub check_shell_aliases: bool,
+
+    /// Require a running systemd user manager for Exec lines wrapped in `systemd-run --user`
+    #[arg(long)]
+    pub check_user_manager: bool,
+
+    /// Evaluate only file-intrinsic rules (no PATH, package, session or GPU lookups), so results
+    /// are reproducible across machines, e.g. for generated files in packaging CI; implies
+    /// --no-history
+    #[arg(
+        long,
+        conflicts_with_all = [
+            "check_script_args",
+            "check_shell_aliases",
+            "check_user_manager",
+            "check_flathub",
+        ]
+    )]
+    pub frozen: bool,
+
+    /// Check an offline system image mounted at DIR: the default directories, PATH and absolute
+    /// Exec paths are resolved inside it, and host session checks are skipped
+    #[arg(
+        long,
+        value_name = "DIR",
+        conflicts_with_all = ["frozen", "check_shell_aliases", "check_user_manager"]
+    )]
+    pub root: Option<PathBuf>,
+
+    /// Scan every host listed in FILE (one per line) over SSH and aggregate the results (not
+    /// with subcommands)
+    #[arg(long, value_name = "FILE")]
+    pub hosts: Option<PathBuf>,
+
+    /// desktop-scout executable to run on remote hosts (with --hosts)
+    #[arg(
+        long,
+        value_name = "CMD",
+        default_value = "desktop-scout",
+        requires = "hosts"
+    )]
+    pub remote_command: String,
+
+    /// Max concurrent inspections and directory reads (defaults to CPU count * 4)
     #[arg(long)]
     pub jobs: Option<usize>,
+
+    /// Inspect at most N files below DIR at once (e.g. a slow SSHFS mount), leaving the other
+    /// jobs to the remaining directories; repeatable
+    #[arg(long, value_name = "DIR=N")]
+    pub dir_jobs: Vec<OriginLimit>,
+
+    /// Give up on a file after SECS seconds (e.g. a hung stat on a dead NFS mount) and report it
+    /// as timed out
+    #[arg(
+        long,
+        value_name = "SECS",
+        default_value_t = 30,
+        value_parser = clap::value_parser!(u64).range(1..)
+    )]
+    pub timeout: u64,
+
+    /// Stop the scan after SECS seconds; files not inspected by then are reported as timed out
+    #[arg(long, value_name = "SECS", value_parser = clap::value_parser!(u64).range(1..))]
+    pub max_runtime: Option<u64>,
+
+    /// Leave out warnings below this severity: `info` adds hints such as missing icons and
+    /// deprecated keys, `error` keeps broken entries only
+    #[arg(long, value_enum, value_name = "LEVEL", default_value_t = Severity::Warning)]
+    pub min_severity: Severity,
+
+    /// Enable rules (comma-separated reason codes): opt-in heuristics such as SCRIPT_MISSING
+    /// (same as --check-script-args) and EXEC_SHELL_ALIAS, or lints below --min-severity such as
+    /// MISSING_ICON
+    #[arg(long, value_delimiter = ',', value_name = "CODE", value_parser = parse_rule)]
+    pub enable_rule: Vec<ReasonCode>,
+
+    /// Disable rules (comma-separated reason codes): their warnings are left out and their
+    /// heuristics not run, even if enabled otherwise
+    #[arg(long, value_delimiter = ',', value_name = "CODE", value_parser = parse_rule)]
+    pub disable_rule: Vec<ReasonCode>,
+
+    /// Exit with status 4 if the report contains findings of this severity (`warning` also fails
+    /// on broken entries and scan-level warnings)
+    #[arg(long, value_enum, value_name = "LEVEL", default_value_t = FailOn::Broken)]
+    pub fail_on: FailOn,
+
+    /// Baseline file: created from the current broken entries if missing; otherwise only broken
+    /// entries not in it are reported and fail the run
+    #[arg(long, value_name = "FILE")]
+    pub baseline: Option<PathBuf>,
+
+    /// Rewrite the --baseline file from the current scan instead of comparing against it
+    #[arg(long, requires = "baseline")]
+    pub update_baseline: bool,
+
+    /// TOML file of accepted findings (paths or path and rule, optionally expiring): matching
+    /// broken entries are reported as suppressed and matching warnings are dropped
+    #[arg(long, value_name = "FILE")]
+    pub suppressions: Option<PathBuf>,
+
+    /// Look up broken Flatpak entries on Flathub to tell removed apps from damaged installations
+    /// (sends the app IDs to flathub.org)
+    #[arg(long)]
+    pub check_flathub: bool,
+
+    /// Do not record this scan in the finding history (first seen / consecutive broken scans)
+    #[arg(long)]
+    pub no_history: bool,
+
+    /// Check every file again instead of reusing the verdicts of files that did not change
+    /// since the previous scan
+    #[arg(long)]
+    pub no_cache: bool,
+
+    /// Simulated removals applied while checking (set by the `simulate` subcommand)
+    #[arg(skip)]
+    pub simulation: Option<Arc<Simulation>>,
+}
+
+/// Subcommands of `desktop-scout`.
+///
+/// Running without a subcommand performs a scan using the top-level options.
+#[derive(Subcommand, Debug, Clone)]
+pub enum Command {
+    /// Describe a reason code: meaning, typical causes and remediation
+    Explain {
+        /// Reason code, e.g. EXEC_NOT_FOUND (lists all codes when omitted)
+        code: Option<ReasonCode>,
+    },
+
+    /// Print the JSON Schema of the scan report (`--output json`) for validating and pinning
+    /// its `schema_version`
+    Schema,
+
+    /// Run as a daemon exposing findings on the session D-Bus (io.github.desktop_scout)
+    Daemon {
+        /// Scan with a single job while the daemon's resident memory exceeds MIB mebibytes
+        #[arg(long, value_name = "MIB")]
+        max_rss: Option<u64>,
+
+        /// Keep at most N file descriptors open (lowers RLIMIT_NOFILE and caps scan jobs)
+        #[arg(long, value_name = "N")]
+        max_open_files: Option<u64>,
+
+        /// Start at most N scans per second; further Scan() calls return the last result
+        #[arg(long, value_name = "N", value_parser = clap::value_parser!(u32).range(1..))]
+        max_events_per_sec: Option<u32>,
+    },
+
+    /// Compare two JSON reports: newly broken, fixed and unchanged entries
+    Diff {
+        /// Older report (from --output json)
+        old: PathBuf,
+
+        /// Newer report (from --output json)
+        new: PathBuf,
+    },
+
+    /// Remove, hide or repair broken entries, backing up changed files to
+    /// $XDG_DATA_HOME/desktop-scout/backups
+    Fix {
+        /// Also remove entries outside the home directory (e.g. in /usr/share/applications)
+        #[arg(long)]
+        system: bool,
+
+        /// How to remediate broken entries
+        #[arg(long, value_enum, default_value_t = FixMode::Delete)]
+        mode: FixMode,
+
+        /// Move entries to $XDG_DATA_HOME/desktop-scout/quarantine instead (see `restore`)
+        #[arg(long, conflicts_with = "mode")]
+        quarantine: bool,
+
+        /// Move entries to the desktop's Trash instead of deleting them (restorable from the
+        /// file manager's wastebasket)
+        #[arg(
+            long,
+            conflicts_with_all = ["mode", "quarantine", "emit_script", "emit_patch"]
+        )]
+        trash: bool,
+
+        /// Also remove or quarantine icons and MIME packages only a removed user entry used
+        #[arg(long)]
+        with_companions: bool,
+
+        /// Only remove or hide broken entries whose desktop ID has a working copy in another data
+        /// dir
+        #[arg(long)]
+        broken_duplicates: bool,
+
+        /// With --mode repair: rewrite the path prefix OLD to NEW in Exec/TryExec/Icon/Path
+        /// (can be passed multiple times)
+        #[arg(long, value_name = "OLD=NEW")]
+        remap: Vec<RemapRule>,
+
+        /// With --mode repair: read remap rules from FILE, one `OLD -> NEW` per line
+        #[arg(long, value_name = "FILE")]
+        remap_file: Option<PathBuf>,
+
+        /// Instead of fixing broken entries, strip deprecated field codes (%d %D %n %N %v %m) and
+        /// duplicate %f/%F/%u/%U from the Exec= lines of all entries
+        #[arg(
+            long,
+            conflicts_with_all = [
+                "mode",
+                "quarantine",
+                "trash",
+                "broken_duplicates",
+                "remap",
+                "remap_file"
+            ]
+        )]
+        field_codes: bool,
+
+        /// Apply the fixes without asking for confirmation (required when stdin is not a terminal)
+        #[arg(short = 'y', long)]
+        yes: bool,
+
+        /// Also change entries in directories that are not user-writable and entries that may only
+        /// be broken transiently (timed-out checks, executables on removable media)
+        #[arg(long)]
+        force: bool,
+
+        /// Write the commands the fix would run to a reviewable shell script instead of running
+        /// them
+        #[arg(long, value_name = "FILE", conflicts_with = "quarantine")]
+        emit_script: Option<PathBuf>,
+
+        /// With --mode hide, --mode repair or --field-codes: write the edits as a unified diff
+        /// (apply with `cd / && patch -p0`) instead of changing anything
+        #[arg(
+            long,
+            value_name = "FILE",
+            conflicts_with_all = ["quarantine", "emit_script"]
+        )]
+        emit_patch: Option<PathBuf>,
+    },
+
+    /// Restore a quarantined entry to its original location (lists the quarantine without ID)
+    Restore {
+        /// Quarantine ID or a unique prefix of it
+        id: Option<String>,
+    },
+
+    /// Show the journal of changes made by desktop-scout (fixes, restores, baselines)
+    History,
+
+    /// Revert fixes recorded in the journal (the most recent fix run by default)
+    Undo {
+        /// Revert the most recent fix run that has not been undone yet (the default)
+        #[arg(long, conflicts_with = "all")]
+        last: bool,
+
+        /// Revert every fix that has not been undone yet, newest first
+        #[arg(long)]
+        all: bool,
+    },
+
+    /// List the directories a scan would search and why each is included
+    ListDirs,
+
+    /// Normalize user-owned .desktop files into a canonical form (group order, key order, spacing)
+    Fmt {
+        /// Do not write anything; print a diff for every file that is not canonically formatted
+        /// and exit with 1 if there is any
+        #[arg(long)]
+        check: bool,
+
+        /// Files to format (defaults to the entries a scan finds inside the home directory)
+        files: Vec<PathBuf>,
+    },
+
+    /// Package every scanned .desktop file and the JSON report into an archive for offline analysis
+    Export {
+        /// Archive to write; compressed according to its suffix (.tar.zst, .tar.gz, .tar.xz, .tar)
+        #[arg(long, value_name = "FILE")]
+        bundle: PathBuf,
+    },
+
+    /// Predict which launchers would break if PATH entries or packages were removed
+    Simulate {
+        /// Treat this directory (and everything below it) as removed; repeatable
+        #[arg(long, value_name = "DIR")]
+        remove_path: Vec<PathBuf>,
+
+        /// Like --remove-path, and also list every launcher whose resolved executable or script
+        /// lives below DIR (e.g. an /opt/vendor tree scheduled for removal); repeatable
+        #[arg(long, value_name = "DIR")]
+        without_dir: Vec<PathBuf>,
+
+        /// Treat the files of this installed package as removed (queried via PackageKit, or dpkg,
+        /// rpm or pacman); repeatable
+        #[arg(long, value_name = "PACKAGE")]
+        without_package: Vec<String>,
+    },
+
+    /// Install a systemd user service + timer running periodic scans
+    InstallTimer {
+        /// systemd OnCalendar= expression controlling the cadence
+        #[arg(long, default_value = "daily")]
+        on_calendar: String,
+
+        /// File the JSON report is written to (defaults to
+        /// $XDG_STATE_HOME/desktop-scout/report.json)
+        #[arg(long, value_name = "FILE")]
+        output: Option<PathBuf>,
+
+        /// Extra scan arguments for the service, e.g. "--dir /opt/apps --include-hidden"
+        #[arg(long, value_name = "ARGS", allow_hyphen_values = true)]
+        scan_args: Option<String>,
+
+        /// Overwrite existing unit files
+        #[arg(long)]
+        force: bool,
+    },
+}
+
+impl Args {
+    /// Statuses selected with `--status` or `--all`; `None` keeps the default selection
+    /// ([`crate::report::Finding::is_reported`]).
+    pub fn status_filter(&self) -> Option<&[StatusFilter]> {
+        if self.all {
+            Some(StatusFilter::ALL)
+        } else if self.status.is_empty() {
+            None
+        } else {
+            Some(&self.status)
+        }
+    }
+
+    /// Whether the scan report lists `finding`: the statuses selected with `--status` or
+    /// `--all`, otherwise reported findings unless the format lists every entry. With
+    /// `--inventory`, entries skipped for their type are listed as well.
+    pub fn lists(&self, finding: &Finding) -> bool {
+        let inventoried = self.inventory
+            && matches!(
+                finding.status,
+                Status::Skipped {
+                    code: ReasonCode::UnsupportedType,
+                    ..
+                }
+            );
+        match self.status_filter() {
+            Some(statuses) => statuses.iter().any(|s| s.matches(&finding.status)),
+            None => {
+                self.output_format().lists_every_entry() || finding.is_reported() || inventoried
+            }
+        }
+    }
+
+    /// Whether warnings with `code` are kept: rules disabled with `--disable-rule` are dropped,
+    /// rules enabled with `--enable-rule` kept regardless of `--min-severity`.
+    pub fn keeps_warning(&self, code: ReasonCode) -> bool {
+        !self.disable_rule.contains(&code)
+            && (code.severity() >= self.min_severity || self.enable_rule.contains(&code))
+    }
+
+    /// Whether the opt-in heuristic reporting `code` runs, given its own flag `flag` (e.g.
+    /// `--check-script-args`).
+    pub fn runs_check(&self, code: ReasonCode, flag: bool) -> bool {
+        !self.disable_rule.contains(&code) && (flag || self.enable_rule.contains(&code))
+    }
+
+    /// The selected output format, honoring the `--json` alias and `--template`.
+    pub fn output_format(&self) -> OutputFormat {
+        if self.json {
+            OutputFormat::Json
+        } else if self.template.is_some() {
+            OutputFormat::Template
+        } else {
+            self.output
+        }
+    }
+}
+
+/// Parse a reason code that names a rule ([`ReasonCode::is_rule`]).
+fn parse_rule(s: &str) -> Result<ReasonCode> {
+    let code = s.parse::<ReasonCode>()?;
+    if !code.is_rule() {
+        bail!("{code} is always checked and cannot be enabled or disabled");
+    }
+    Ok(code)
+}
+
+/// Output formats of `--output` (see [`crate::render`]).
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputFormat {
+    /// Human-readable text.
+    Text,
+    /// A single pretty-printed JSON document.
+    Json,
+    /// One compact JSON object per line (per finding for scans).
+    Jsonl,
+    /// A YAML document.
+    Yaml,
+    /// Comma-separated values with a header row.
+    Csv,
+    /// An aligned plain-text table.
+    Table,
+    /// A SARIF 2.1.0 log for code-scanning tools (scan reports only).
+    Sarif,
+    /// A JUnit XML test report with one test case per scanned file (scan reports only).
+    Junit,
+    /// A Test Anything Protocol stream with one test point per scanned file (scan reports only).
+    Tap,
+    /// A standalone HTML page with sortable, filterable tables (scan reports only).
+    Html,
+    /// A Markdown summary table with per-entry details, for issue trackers (scan reports only).
+    Markdown,
+    /// The scan report rendered with the `--template` file.
+    Template,
+}
+
+impl OutputFormat {
+    /// Name of the format as accepted by `--output`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            OutputFormat::Text => "text",
+            OutputFormat::Json => "json",
+            OutputFormat::Jsonl => "jsonl",
+            OutputFormat::Yaml => "yaml",
+            OutputFormat::Csv => "csv",
+            OutputFormat::Table => "table",
+            OutputFormat::Sarif => "sarif",
+            OutputFormat::Junit => "junit",
+            OutputFormat::Tap => "tap",
+            OutputFormat::Html => "html",
+            OutputFormat::Markdown => "markdown",
+            OutputFormat::Template => "template",
+        }
+    }
+
+    /// Whether scan reports in this format list every scanned entry, including healthy ones
+    /// (test-report formats, where each file is a test case).
+    pub fn lists_every_entry(&self) -> bool {
+        matches!(self, OutputFormat::Junit | OutputFormat::Tap)
+    }
+}
+
+/// Entry statuses selectable with `--status`.
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StatusFilter {
+    /// Entries whose executable resolves.
+    Ok,
+    /// Broken entries.
+    Broken,
+    /// Entries that were not checked (hidden or not `Type=Application`).
+    Skipped,
+    /// Broken entries accepted by `--suppressions`.
+    Suppressed,
+    /// Entries whose inspection did not finish in time (`--timeout`, `--max-runtime`).
+    TimedOut,
+}
+
+impl StatusFilter {
+    /// All statuses (`--all`).
+    pub const ALL: &'static [StatusFilter] = &[
+        StatusFilter::Ok,
+        StatusFilter::Broken,
+        StatusFilter::Skipped,
+        StatusFilter::Suppressed,
+        StatusFilter::TimedOut,
+    ];
+
+    /// Whether `status` is of this kind.
+    pub fn matches(&self, status: &Status) -> bool {
+        matches!(
+            (self, status),
+            (StatusFilter::Ok, Status::Ok { .. })
+                | (StatusFilter::Broken, Status::Broken { .. })
+                | (StatusFilter::Skipped, Status::Skipped { .. })
+                | (StatusFilter::Suppressed, Status::Suppressed { .. })
+                | (StatusFilter::TimedOut, Status::TimedOut { .. })
+        )
+    }
+}
+
+/// Findings that make a scan exit with a failure status (`--fail-on`).
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FailOn {
+    /// Broken entries.
+    Broken,
+    /// Broken entries, entries with lint warnings and scan-level warnings (`info` hints
+    /// excluded).
+    Warning,
+    /// Nothing; only errors and empty scans exit with a failure status.
+    Never,
+}
+
+impl FailOn {
+    /// Whether `report` contains findings that fail the scan.
+    ///
+    /// The `NO_DESKTOP_FILES` warning is not counted, since empty scans have an exit status of
+    /// their own.
+    pub fn fails(&self, report: &Report) -> bool {
+        match self {
+            FailOn::Broken => report.findings.iter().any(|f| f.is_broken()),
+            FailOn::Warning => {
+                report
+                    .findings
+                    .iter()
+                    .any(|f| f.severity >= Some(Severity::Warning))
+                    || report.warnings.iter().any(|w| {
+                        w.code != ReasonCode::NoDesktopFiles
+                            && w.code.severity() >= Severity::Warning
+                    })
+            }
+            FailOn::Never => false,
+        }
+    }
+}
+
+/// Order of the findings of a scan report (`--sort`).
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortKey {
+    /// By path of the `.desktop` file.
+    Path,
+    /// By `Name=` (case-insensitive; entries without one last).
+    Name,
+    /// Broken entries, then entries with warnings, healthy and skipped ones.
+    Status,
+    /// By reason code (healthy entries last).
+    Code,
+    /// By directory containing the file, then by file name.
+    Dir,
+}
+
+/// How the report is grouped (`--group-by`).
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GroupBy {
+    /// By application identity (see `identity`).
+    App,
+    /// By directory containing the file.
+    Dir,
+    /// By source layer: user data, system data, Flatpak, Snap, `--dir` directories.
+    Source,
+}
+
+/// When text output is colored (`--color`).
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColorChoice {
+    /// Color a terminal, unless `NO_COLOR` is set.
+    Auto,
+    /// Always emit color escape sequences.
+    Always,
+    /// Never color.
+    Never,
+}
+
+/// Policy for symlinks encountered while walking scan directories.
+///
+/// Scan roots themselves are always followed; this only applies to entries found below them.
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SymlinkPolicy {
+    /// Skip all symlinks.
+    Never,
+    /// Follow symlinks to files, but not to directories.
+    Files,
+    /// Follow symlinks to files and directories (loops are detected via a visited set).
+    All,
+}
+
+/// How `fix` remediates broken entries.
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FixMode {
+    /// Back up and delete the file.
+    Delete,
+    /// Set `Hidden=true`; system entries get a user override instead of being edited.
+    Hide,
+    /// Apply --remap rules, or rewrite `Exec=` to a relocated binary with the same name.
+    Repair,
 }
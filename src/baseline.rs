@@ -0,0 +1,100 @@
+//! Baseline files for CI (`--baseline`).
+//!
+//! A baseline records the IDs of all broken findings of a scan. Later scans against the same
+//! baseline only report (and fail on) broken findings whose ID is not in it, so pipelines can
+//! gate regressions without fixing every pre-existing issue first.
+
+// -- std imports
+use std::{collections::HashSet, io::ErrorKind, path::Path};
+
+// -- crate imports
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use tokio::fs;
+
+// -- module imports
+use crate::report::{ReasonCode, Report, Status};
+
+/// On-disk baseline format.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Baseline {
+    /// Accepted broken findings.
+    pub findings: Vec<BaselineEntry>,
+}
+
+/// A single accepted finding.
+///
+/// Only `id` is used for matching; path and code are recorded to keep the file reviewable.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BaselineEntry {
+    /// Finding ID (see `report::finding_id`).
+    pub id: String,
+
+    /// Path of the `.desktop` file at the time the baseline was written.
+    pub desktop_file: String,
+
+    /// Reason code at the time the baseline was written.
+    pub code: ReasonCode,
+}
+
+/// Load a baseline, returning `None` if the file does not exist yet.
+///
+/// # Errors
+/// - [`anyhow::Error`] if the file exists but cannot be read or parsed.
+pub async fn load(path: &Path) -> Result<Option<Baseline>> {
+    let content = match fs::read(path).await {
+        Ok(c) => c,
+        Err(e) if e.kind() == ErrorKind::NotFound => return Ok(None),
+        Err(e) => {
+            return Err(e).with_context(|| format!("Failed to read baseline {}", path.display()));
+        }
+    };
+
+    serde_json::from_slice(&content)
+        .map(Some)
+        .with_context(|| format!("{} is not a valid baseline", path.display()))
+}
+
+/// Write the broken findings of `report` as a new baseline and return how many were recorded.
+///
+/// # Errors
+/// - [`anyhow::Error`] if the file cannot be written.
+pub async fn write(path: &Path, report: &Report) -> Result<usize> {
+    let mut findings = report
+        .findings
+        .iter()
+        .filter_map(|f| match &f.status {
+            Status::Broken { code, .. } => Some(BaselineEntry {
+                id: f.id.clone(),
+                desktop_file: f.desktop_file.display().to_string(),
+                code: *code,
+            }),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    findings.sort_by(|a, b| a.desktop_file.cmp(&b.desktop_file).then(a.id.cmp(&b.id)));
+
+    let count = findings.len();
+    let json = serde_json::to_string_pretty(&Baseline { findings })?;
+    fs::write(path, json + "\n")
+        .await
+        .with_context(|| format!("Failed to write baseline {}", path.display()))?;
+    Ok(count)
+}
+
+/// Remove broken findings that are recorded in `baseline` from `report`.
+///
+/// Returns the number of suppressed findings.
+pub fn apply(baseline: &Baseline, report: &mut Report) -> usize {
+    let known = baseline
+        .findings
+        .iter()
+        .map(|e| e.id.as_str())
+        .collect::<HashSet<_>>();
+
+    let before = report.findings.len();
+    report
+        .findings
+        .retain(|f| !(f.is_broken() && known.contains(f.id.as_str())));
+    before - report.findings.len()
+}
@@ -0,0 +1,493 @@
+//! Incremental scanning: verdicts of unchanged files are reused between runs (`--no-cache`).
+//!
+//! Every scan updates a store next to the finding history
+//! (`$XDG_STATE_HOME/desktop-scout/cache.json`) with the finding of each inspected file, keyed
+//! by path together with the file's modification time and size. A later scan reuses the
+//! finding instead of checking the file again as long as:
+//! - the file's modification time, size and mode are unchanged
+//! - its programs are unchanged: the absolute `Exec`/`TryExec` programs and the resolved
+//!   executable (modification time, size, mode and symlink target), the directories of the
+//!   absolute programs (installing or removing a program changes the directory it lives in), its
+//!   `Path=` directory and, with `--check-script-args`, the script an interpreter runs
+//! - nothing else a verdict depends on changed ([`Fingerprint`]): the desktop-scout version,
+//!   the checking options, `PATH` and its directories, the session environment, the boot and
+//!   (with the shell alias check) the shell rc files
+//!
+//! Findings that only record that a file could not be inspected (unreadable, timed out,
+//! panicked) are not cached, and neither are files that cannot be stat'ed.
+
+// -- std imports
+use std::{
+    collections::{BTreeMap, HashSet},
+    env,
+    io::ErrorKind,
+    os::unix::fs::MetadataExt,
+    path::{Path, PathBuf},
+    sync::Mutex,
+};
+
+// -- crate imports
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use tokio::fs;
+use xdg::BaseDirectories;
+
+// -- module imports
+use crate::{
+    args::Args,
+    check, desktop, output_file,
+    report::{Finding, ReasonCode, Status},
+    retry, shell, sysroot,
+};
+
+/// File name of the store inside the state directory.
+const CACHE_NAME: &str = "cache.json";
+
+/// Environment variables session checks and translated messages depend on.
+const SESSION_VARS: &[&str] = &[
+    "HOME",
+    "XDG_RUNTIME_DIR",
+    "WAYLAND_DISPLAY",
+    "DISPLAY",
+    "LC_ALL",
+    "LC_MESSAGES",
+    "LANG",
+];
+
+/// Modification time, size and mode of a file or directory.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Stamp {
+    /// Modification time, seconds since the epoch.
+    mtime: i64,
+
+    /// Nanoseconds of the modification time.
+    mtime_nsec: i64,
+
+    /// Size in bytes.
+    size: u64,
+
+    /// File type and permission bits (`chmod -x` makes a program unrunnable).
+    mode: u32,
+}
+
+/// The stamp of the file at `path` (following symlinks), or `None` if it cannot be stat'ed.
+pub async fn stamp(path: &Path) -> Option<Stamp> {
+    let md = retry::metadata(path).await.ok()?;
+    Some(Stamp {
+        mtime: md.mtime(),
+        mtime_nsec: md.mtime_nsec(),
+        size: md.len(),
+        mode: md.mode(),
+    })
+}
+
+/// State of a file a cached verdict depends on.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct Dependency {
+    /// Stamp of the file (following symlinks), `None` if it did not exist.
+    stamp: Option<Stamp>,
+
+    /// The file with all symlinks resolved, so retargeting a link is noticed even if the new
+    /// target has the same stamp.
+    target: Option<PathBuf>,
+}
+
+impl Dependency {
+    /// The current state of the file at `path`.
+    async fn of(path: &Path) -> Self {
+        Dependency {
+            stamp: stamp(path).await,
+            target: fs::canonicalize(path).await.ok(),
+        }
+    }
+}
+
+/// Everything besides the file and its program directories that a verdict depends on; the
+/// cache is dropped when it changes.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct Fingerprint {
+    /// desktop-scout version (rules change between releases).
+    version: String,
+
+    /// Options that change how a file is checked.
+    options: String,
+
+    /// `PATH` bare commands are resolved through.
+    path_env: String,
+
+    /// Stamps of the `PATH` directories.
+    path_dirs: Vec<Option<Stamp>>,
+
+    /// Values of [`SESSION_VARS`].
+    session: Vec<Option<String>>,
+
+    /// Boot ID; drivers and session sockets change across reboots.
+    boot_id: Option<String>,
+
+    /// Stamps of the shell rc files and the fish functions directory, if the
+    /// [`ReasonCode::ExecShellAlias`] check runs (empty otherwise).
+    shell_rc: Vec<Option<Stamp>>,
+}
+
+impl Fingerprint {
+    /// The fingerprint of a scan configured by `args` resolving commands through `path_env`.
+    async fn of(args: &Args, path_env: &str) -> Self {
+        let options = format!(
+            "{:?}",
+            (
+                args.check_script_args,
+                args.check_shell_aliases,
+                args.check_user_manager,
+                args.frozen,
+                args.include_hidden,
+                args.inventory,
+                args.min_severity,
+                &args.enable_rule,
+                &args.disable_rule,
+                &args.root,
+            )
+        );
+        let mut path_dirs = Vec::new();
+        for dir in path_env.split(':') {
+            let dir = match &args.root {
+                Some(root) => sysroot::rebase(root, Path::new(dir)),
+                None => PathBuf::from(dir),
+            };
+            path_dirs.push(stamp(&dir).await);
+        }
+        let mut shell_rc = Vec::new();
+        if args.runs_check(ReasonCode::ExecShellAlias, args.check_shell_aliases) {
+            for file in shell::rc_files()
+                .into_iter()
+                .chain(shell::fish_functions_dir())
+            {
+                shell_rc.push(stamp(&file).await);
+            }
+        }
+        Fingerprint {
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            options,
+            path_env: path_env.to_string(),
+            path_dirs,
+            session: SESSION_VARS.iter().map(|v| env::var(v).ok()).collect(),
+            boot_id: fs::read_to_string("/proc/sys/kernel/random/boot_id")
+                .await
+                .ok()
+                .map(|id| id.trim().to_string()),
+            shell_rc,
+        }
+    }
+}
+
+/// A cached verdict.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CacheEntry {
+    /// Stamp of the file when it was inspected.
+    stamp: Stamp,
+
+    /// Programs, scripts and directories (see [`ScanCache::dependencies`]) with their state when
+    /// the file was inspected.
+    deps: Vec<(PathBuf, Dependency)>,
+
+    /// The finding, before suppressions and other per-scan annotations.
+    finding: Finding,
+}
+
+/// On-disk store format.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct Store {
+    /// Fingerprint the entries were recorded under.
+    fingerprint: Option<Fingerprint>,
+
+    /// Cached verdicts by file path.
+    entries: BTreeMap<PathBuf, CacheEntry>,
+}
+
+/// The verdict cache of a scan.
+#[derive(Debug)]
+pub struct ScanCache {
+    /// Location of the store.
+    path: PathBuf,
+
+    /// Fingerprint of this scan.
+    fingerprint: Fingerprint,
+
+    /// `--root` image the scan checks.
+    root: Option<PathBuf>,
+
+    /// Whether the scan checks the scripts of interpreter launchers (`--check-script-args`).
+    check_script_args: bool,
+
+    /// Cached verdicts, updated as files are inspected.
+    entries: Mutex<BTreeMap<PathBuf, CacheEntry>>,
+
+    /// Files looked up during this scan.
+    seen: Mutex<HashSet<PathBuf>>,
+}
+
+impl ScanCache {
+    /// Load the cache for a scan configured by `args` resolving commands through `path_env`.
+    /// Entries recorded under a different fingerprint are dropped; a corrupt store is replaced.
+    ///
+    /// # Errors
+    /// - [`anyhow::Error`] if the state directory cannot be determined or the store cannot be
+    ///   read.
+    pub async fn load(args: &Args, path_env: &str) -> Result<Self> {
+        let path = BaseDirectories::with_prefix("desktop-scout")
+            .get_state_home()
+            .context("Could not determine XDG state directory for the scan cache")?
+            .join(CACHE_NAME);
+        let store: Store = match fs::read(&path).await {
+            Ok(c) => serde_json::from_slice(&c).unwrap_or_default(),
+            Err(e) if e.kind() == ErrorKind::NotFound => Store::default(),
+            Err(e) => {
+                return Err(e).with_context(|| format!("Failed to read {}", path.display()));
+            }
+        };
+
+        let fingerprint = Fingerprint::of(args, path_env).await;
+        let entries = match store.fingerprint == Some(fingerprint.clone()) {
+            true => store.entries,
+            false => BTreeMap::new(),
+        };
+        Ok(ScanCache {
+            path,
+            fingerprint,
+            root: args.root.clone(),
+            check_script_args: args.runs_check(ReasonCode::ScriptMissing, args.check_script_args),
+            entries: Mutex::new(entries),
+            seen: Mutex::new(HashSet::new()),
+        })
+    }
+
+    /// The cached finding of the file at `path` with the current `stamp`, if it is still valid.
+    pub async fn get(&self, path: &Path, stamp: Stamp) -> Option<Finding> {
+        self.seen
+            .lock()
+            .expect("cache lock poisoned")
+            .insert(path.to_path_buf());
+        let entry = self
+            .entries
+            .lock()
+            .expect("cache lock poisoned")
+            .get(path)
+            .filter(|e| e.stamp == stamp)
+            .cloned()?;
+        for (path, recorded) in &entry.deps {
+            if Dependency::of(path).await != *recorded {
+                return None;
+            }
+        }
+        Some(entry.finding)
+    }
+
+    /// Record `finding` for the file at `path`, which had `stamp` before it was inspected.
+    pub async fn put(&self, path: &Path, stamp: Stamp, finding: &Finding) {
+        let mut deps = Vec::new();
+        for path in self.dependencies(finding) {
+            let recorded = Dependency::of(&path).await;
+            deps.push((path, recorded));
+        }
+        self.entries.lock().expect("cache lock poisoned").insert(
+            path.to_path_buf(),
+            CacheEntry {
+                stamp,
+                deps,
+                finding: finding.clone(),
+            },
+        );
+    }
+
+    /// Files and directories whose change may change the verdict of `finding`: its absolute
+    /// `Exec`/`TryExec` programs and their directories, its resolved executable, its `Path=`
+    /// directory and, with `--check-script-args`, the script its interpreter runs.
+    fn dependencies(&self, finding: &Finding) -> Vec<PathBuf> {
+        let command_name = finding.exec.as_deref().and_then(check::exec_command_name);
+        let programs = [
+            command_name.clone(),
+            finding.try_exec.as_ref().map(|t| t.trim().to_string()),
+        ]
+        .into_iter()
+        .flatten()
+        .map(PathBuf::from)
+        .collect::<Vec<_>>();
+        let resolved = match &finding.status {
+            Status::Ok {
+                resolved_executable,
+            } => resolved_executable.clone(),
+            _ => None,
+        };
+
+        // A missing script makes the entry broken, so its executable is taken from `Exec=` then;
+        // only its file name matters for recognizing the interpreter.
+        let script = self
+            .check_script_args
+            .then(|| {
+                let exe = resolved
+                    .clone()
+                    .or_else(|| command_name.map(PathBuf::from))?;
+                let tokens = shlex::split(finding.exec.as_deref()?)?;
+                let (_, command) = desktop::unwrap_exec_prefixes(&tokens);
+                check::script_argument(&exe, command, finding.path_key.as_deref())
+            })
+            .flatten();
+
+        let mut paths = programs
+            .iter()
+            .filter_map(|p| Some(p.parent()?.to_path_buf()))
+            .chain(programs.iter().cloned())
+            .chain(resolved)
+            .chain(script)
+            .chain(finding.path_key.as_ref().map(PathBuf::from))
+            .filter(|p| p.is_absolute())
+            .map(|p| match &self.root {
+                Some(root) => sysroot::rebase(root, &p),
+                None => p,
+            })
+            .collect::<Vec<_>>();
+        paths.sort();
+        paths.dedup();
+        paths
+    }
+
+    /// Write the cache back. Entries of files this scan did not look at are kept while the
+    /// files exist.
+    ///
+    /// # Errors
+    /// - [`anyhow::Error`] if the store cannot be written.
+    pub async fn save(self) -> Result<()> {
+        let seen = self.seen.into_inner().expect("cache lock poisoned");
+        let mut entries = self.entries.into_inner().expect("cache lock poisoned");
+        let unseen = entries
+            .keys()
+            .filter(|p| !seen.contains(*p))
+            .cloned()
+            .collect::<Vec<_>>();
+        for path in unseen {
+            if !fs::try_exists(&path).await.unwrap_or(false) {
+                entries.remove(&path);
+            }
+        }
+
+        let store = Store {
+            fingerprint: Some(self.fingerprint),
+            entries,
+        };
+        let json = serde_json::to_string(&store)?;
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir)
+                .await
+                .with_context(|| format!("Failed to create {}", dir.display()))?;
+        }
+        output_file::write_atomic(&self.path, (json + "\n").as_bytes()).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        os::unix::fs::{PermissionsExt, symlink},
+        process,
+    };
+
+    use super::*;
+
+    fn cache(check_script_args: bool) -> ScanCache {
+        ScanCache {
+            path: PathBuf::new(),
+            fingerprint: Fingerprint {
+                version: String::new(),
+                options: String::new(),
+                path_env: String::new(),
+                path_dirs: Vec::new(),
+                session: Vec::new(),
+                boot_id: None,
+                shell_rc: Vec::new(),
+            },
+            root: None,
+            check_script_args,
+            entries: Mutex::new(BTreeMap::new()),
+            seen: Mutex::new(HashSet::new()),
+        }
+    }
+
+    fn finding(exec: String, resolved_executable: PathBuf) -> Finding {
+        Finding {
+            id: String::new(),
+            desktop_file: "/usr/share/applications/tool.desktop".into(),
+            name: None,
+            exec: Some(exec),
+            try_exec: None,
+            path_key: None,
+            entry_type: None,
+            hidden: false,
+            no_display: false,
+            status: Status::Ok {
+                resolved_executable: Some(resolved_executable),
+            },
+            warnings: Vec::new(),
+            user: None,
+            suppressed_warnings: Vec::new(),
+            severity: None,
+            suggested_exec: None,
+            history: None,
+            flathub: None,
+            identity: None,
+            same_file_as: Vec::new(),
+        }
+    }
+
+    #[tokio::test]
+    async fn verdicts_depend_on_the_program_and_script() {
+        let dir = env::temp_dir().join(format!("desktop-scout-cache-{}", process::id()));
+        let bin = dir.join("bin");
+        std::fs::create_dir_all(&bin).unwrap();
+        for name in ["tool-1", "tool-2", "sh"] {
+            std::fs::write(bin.join(name), "#!/bin/sh\n").unwrap();
+            std::fs::set_permissions(bin.join(name), std::fs::Permissions::from_mode(0o755))
+                .unwrap();
+        }
+        symlink("tool-1", bin.join("tool")).unwrap();
+        std::fs::write(dir.join("run.sh"), "").unwrap();
+
+        let path = Path::new("/usr/share/applications/tool.desktop");
+        let key = Stamp {
+            mtime: 0,
+            mtime_nsec: 0,
+            size: 0,
+            mode: 0,
+        };
+        let tool = finding(bin.join("tool").display().to_string(), bin.join("tool"));
+        let script = finding(
+            format!(
+                "{} {}",
+                bin.join("sh").display(),
+                dir.join("run.sh").display()
+            ),
+            bin.join("sh"),
+        );
+
+        let cache = cache(true);
+        cache.put(path, key, &tool).await;
+        let unchanged = cache.get(path, key).await.is_some();
+
+        std::fs::remove_file(bin.join("tool")).unwrap();
+        symlink("tool-2", bin.join("tool")).unwrap();
+        let retargeted = cache.get(path, key).await.is_some();
+
+        cache.put(path, key, &tool).await;
+        std::fs::set_permissions(bin.join("tool-2"), std::fs::Permissions::from_mode(0o644))
+            .unwrap();
+        let not_executable = cache.get(path, key).await.is_some();
+
+        cache.put(path, key, &script).await;
+        std::fs::remove_file(dir.join("run.sh")).unwrap();
+        let script_removed = cache.get(path, key).await.is_some();
+
+        std::fs::remove_dir_all(&dir).unwrap();
+        assert!(unchanged);
+        assert!(!retargeted);
+        assert!(!not_executable);
+        assert!(!script_removed);
+    }
+}
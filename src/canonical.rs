@@ -0,0 +1,305 @@
+//! Canonical formatting of `.desktop` files (`fmt` subcommand).
+//!
+//! Hand-maintained launcher collections drift: keys in random order, `Key = value` spacing,
+//! blank lines everywhere, action groups out of order. [`canonicalize`] normalizes a parsed
+//! [`DesktopEntry`] so that equivalent files are byte-identical and diffs stay small:
+//!
+//! - `[Desktop Entry]` comes first, then the `[Desktop Action ...]` groups in `Actions=` order,
+//!   then every other group in file order.
+//! - Keys follow the order of the spec's key table; unknown keys come after them and `X-`
+//!   extensions last, each alphabetically. Localized variants follow their key, by locale.
+//! - Values are written as `Key=value`, lists are terminated with `;`, trailing whitespace and
+//!   blank lines inside groups are dropped, and groups are separated by exactly one blank line.
+//!
+//! Comments move together with the key below them. Comments at the top of the file or of a
+//! group that are followed by a blank line stay at the top, followed by one blank line.
+
+// -- std imports
+use std::{
+    env, mem,
+    path::{Path, PathBuf},
+};
+
+// -- crate imports
+use anyhow::{Context, Result};
+use serde::Serialize;
+use tokio::fs;
+
+// -- module imports
+use crate::{
+    desktop::{self, DESKTOP_ENTRY_GROUP, DesktopEntry, GroupLine},
+    i18n::tr,
+    patch,
+};
+
+/// Keys in the order of the Desktop Entry spec's table of recognized keys.
+const KEY_ORDER: &[&str] = &[
+    "Type",
+    "Version",
+    "Name",
+    "GenericName",
+    "NoDisplay",
+    "Comment",
+    "Icon",
+    "Hidden",
+    "OnlyShowIn",
+    "NotShowIn",
+    "DBusActivatable",
+    "TryExec",
+    "Exec",
+    "Path",
+    "Terminal",
+    "Actions",
+    "MimeType",
+    "Categories",
+    "Implements",
+    "Keywords",
+    "StartupNotify",
+    "StartupWMClass",
+    "URL",
+    "PrefersNonDefaultGPU",
+    "SingleMainWindow",
+];
+
+/// Prefix of the names of action groups.
+const ACTION_GROUP_PREFIX: &str = "Desktop Action ";
+
+/// Outcome of formatting a single file.
+#[derive(Debug, Serialize)]
+pub struct FormatResult {
+    /// The formatted file.
+    pub file: PathBuf,
+
+    /// What happened to it.
+    #[serde(flatten)]
+    pub outcome: FormatOutcome,
+}
+
+/// What [`format_files`] did with a file.
+#[derive(Debug, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum FormatOutcome {
+    /// Already canonically formatted.
+    Unchanged,
+
+    /// Rewritten in canonical form.
+    Reformatted,
+
+    /// Not canonically formatted (`--check`); nothing was written.
+    WouldReformat {
+        /// Unified diff from the current to the canonical form.
+        diff: String,
+    },
+
+    /// Could not be read or written.
+    Failed {
+        /// Error message.
+        error: String,
+    },
+}
+
+/// Bring `entry` into canonical form (see the module documentation).
+pub fn canonicalize(entry: &mut DesktopEntry) {
+    entry.preamble.retain(|l| !l.trim().is_empty());
+    for line in &mut entry.preamble {
+        trim_end_in_place(line);
+    }
+    if !entry.preamble.is_empty() {
+        entry.preamble.push(String::new());
+    }
+
+    let actions = entry
+        .get(DESKTOP_ENTRY_GROUP, "Actions")
+        .map(|v| {
+            v.split(';')
+                .map(str::trim)
+                .filter(|a| !a.is_empty())
+                .map(str::to_string)
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+    // Stable sort: groups of the same rank keep their file order.
+    entry.groups.sort_by_key(|g| {
+        if g.name == DESKTOP_ENTRY_GROUP {
+            return 0;
+        }
+        match g.name.strip_prefix(ACTION_GROUP_PREFIX) {
+            Some(action) => {
+                1 + actions
+                    .iter()
+                    .position(|a| a == action)
+                    .unwrap_or(actions.len())
+            }
+            None => 2 + actions.len(),
+        }
+    });
+
+    let last = entry.groups.len().saturating_sub(1);
+    for (i, group) in entry.groups.iter_mut().enumerate() {
+        group.raw = None;
+        group.lines = sort_lines(mem::take(&mut group.lines));
+        if i != last {
+            group.lines.push(GroupLine::Other(String::new()));
+        }
+    }
+    entry.no_final_newline = false;
+}
+
+/// The files of `files` inside the home directory, which `fmt` formats by default (like `fix`,
+/// it leaves system entries alone).
+pub fn user_owned(files: Vec<PathBuf>) -> Vec<PathBuf> {
+    let Some(home) = env::var_os("HOME").map(PathBuf::from) else {
+        return Vec::new();
+    };
+    files.into_iter().filter(|f| f.starts_with(&home)).collect()
+}
+
+/// Format `files` in place, or with `check` only report (with a diff) which ones are not in
+/// canonical form.
+///
+/// Symlinks are followed, so the file they point to is rewritten rather than replaced.
+pub async fn format_files(files: &[PathBuf], check: bool) -> Vec<FormatResult> {
+    let mut results = Vec::with_capacity(files.len());
+    for file in files {
+        let outcome = match format_file(file, check).await {
+            Ok(outcome) => outcome,
+            Err(e) => FormatOutcome::Failed {
+                error: format!("{e:#}"),
+            },
+        };
+        results.push(FormatResult {
+            file: file.clone(),
+            outcome,
+        });
+    }
+    results
+}
+
+/// Format a single file (see [`format_files`]).
+async fn format_file(file: &Path, check: bool) -> Result<FormatOutcome> {
+    let real = fs::canonicalize(file)
+        .await
+        .with_context(|| format!("Failed to resolve {}", file.display()))?;
+    let content = fs::read_to_string(&real)
+        .await
+        .with_context(|| format!("Failed to read {}", real.display()))?;
+
+    let mut entry = DesktopEntry::parse(&content);
+    canonicalize(&mut entry);
+    let formatted = entry.to_string();
+    if formatted == content {
+        return Ok(FormatOutcome::Unchanged);
+    }
+    if check {
+        return Ok(FormatOutcome::WouldReformat {
+            diff: patch::unified_diff(file, Some(&content), &formatted),
+        });
+    }
+
+    desktop::write_entry(&real, &entry).await?;
+    Ok(FormatOutcome::Reformatted)
+}
+
+/// Print the files that were (or would be) reformatted or failed, followed by a summary.
+pub fn print_text(results: &[FormatResult]) {
+    let mut changed = 0;
+    for r in results {
+        match &r.outcome {
+            FormatOutcome::Unchanged => {}
+            FormatOutcome::Reformatted => {
+                changed += 1;
+                let path = r.file.display().to_string();
+                println!("{}", tr("fmt-reformatted", &[("path", path.into())]));
+            }
+            FormatOutcome::WouldReformat { diff } => {
+                changed += 1;
+                print!("{diff}");
+            }
+            FormatOutcome::Failed { error } => {
+                let path = r.file.display().to_string();
+                println!(
+                    "{}",
+                    tr(
+                        "fmt-failed",
+                        &[("path", path.into()), ("error", error.clone().into())]
+                    )
+                );
+            }
+        }
+    }
+
+    let checking = results
+        .iter()
+        .any(|r| matches!(r.outcome, FormatOutcome::WouldReformat { .. }));
+    let args = [("changed", changed.into()), ("files", results.len().into())];
+    if changed == 0 {
+        println!("{}", tr("fmt-all-formatted", &args));
+    } else if checking {
+        println!("\n{}", tr("fmt-would-reformat", &args));
+    } else {
+        println!("\n{}", tr("fmt-reformatted-summary", &args));
+    }
+}
+
+/// Lines of a group in canonical order: blank lines dropped, entries sorted by [`key_rank`]
+/// with the comments above them and written as `Key=value`.
+fn sort_lines(lines: Vec<GroupLine>) -> Vec<GroupLine> {
+    let mut header = Vec::new();
+    let mut chunks: Vec<((usize, String, String), Vec<GroupLine>)> = Vec::new();
+    let mut pending = Vec::new();
+
+    for line in lines {
+        match line {
+            GroupLine::Other(mut raw) => {
+                if raw.trim().is_empty() {
+                    // Comments above the first key that end in a blank line describe the group.
+                    if chunks.is_empty() {
+                        header.append(&mut pending);
+                    }
+                    continue;
+                }
+                trim_end_in_place(&mut raw);
+                pending.push(GroupLine::Other(raw));
+            }
+            GroupLine::Entry { key, value, .. } => {
+                let rank = key_rank(&key);
+                pending.push(GroupLine::Entry {
+                    key,
+                    value,
+                    raw: None,
+                });
+                chunks.push((rank, mem::take(&mut pending)));
+            }
+        }
+    }
+
+    chunks.sort_by(|a, b| a.0.cmp(&b.0));
+    if !header.is_empty() {
+        header.push(GroupLine::Other(String::new()));
+    }
+    header
+        .into_iter()
+        .chain(chunks.into_iter().flat_map(|(_, lines)| lines))
+        .chain(pending)
+        .collect()
+}
+
+/// Sort key of `key`: position in [`KEY_ORDER`] (unknown keys, then `X-` keys, after it), base
+/// name and locale (empty for the unlocalized key, so it comes first).
+fn key_rank(key: &str) -> (usize, String, String) {
+    let (base, locale) = match key.split_once('[') {
+        Some((base, rest)) => (base, rest.trim_end_matches(']')),
+        None => (key, ""),
+    };
+    let position = match KEY_ORDER.iter().position(|k| *k == base) {
+        Some(p) => p,
+        None if base.starts_with("X-") => KEY_ORDER.len() + 1,
+        None => KEY_ORDER.len(),
+    };
+    (position, base.to_string(), locale.to_string())
+}
+
+/// Remove trailing whitespace from `line`.
+fn trim_end_in_place(line: &mut String) {
+    line.truncate(line.trim_end().len());
+}
@@ -0,0 +1,304 @@
+//! C ABI for embedding the scanner (`capi` feature).
+//!
+//! The API is deliberately small and introspection-friendly: opaque handles, explicit free
+//! functions and borrowed `const char *` strings that stay valid until their owning handle is
+//! freed. The header `include/desktop_scout.h` is generated by cbindgen when building with the
+//! feature enabled.
+//!
+//! ```c
+//! DsReport *r = ds_scan(NULL, 0, DS_SCAN_DEFAULT);
+//! if (!r) { fprintf(stderr, "%s\n", ds_last_error()); return 1; }
+//! for (size_t i = 0; i < ds_report_len(r); i++) {
+//!     const DsFinding *f = ds_report_get(r, i);
+//!     printf("%s: %s\n", ds_finding_path(f), ds_finding_reason(f));
+//! }
+//! ds_report_free(r);
+//! ```
+
+// -- std imports
+use std::{
+    cell::RefCell,
+    ffi::{CStr, CString, c_char},
+    panic::{self, AssertUnwindSafe},
+    path::PathBuf,
+    ptr,
+};
+
+// -- crate imports
+use anyhow::{Result, anyhow};
+use clap::Parser;
+
+// -- module imports
+use crate::{
+    args::Args,
+    report::{Finding, Report, Status},
+    scan,
+};
+
+/// Scan the default directories with default options.
+pub const DS_SCAN_DEFAULT: u32 = 0;
+
+/// Do not scan the default XDG directories (`--no-default`).
+pub const DS_SCAN_NO_DEFAULT: u32 = 1 << 0;
+
+/// Include `Hidden`/`NoDisplay` entries (`--include-hidden`).
+pub const DS_SCAN_INCLUDE_HIDDEN: u32 = 1 << 1;
+
+/// Enable the interpreter script heuristic (`--check-script-args`).
+pub const DS_SCAN_CHECK_SCRIPT_ARGS: u32 = 1 << 2;
+
+thread_local! {
+    /// Message of the last failed call on this thread.
+    static LAST_ERROR: RefCell<Option<CString>> = const { RefCell::new(None) };
+}
+
+/// Result of [`ds_scan`]: the reported findings of one scan.
+pub struct DsReport {
+    report: Report,
+    findings: Vec<DsFinding>,
+}
+
+/// A single finding with its fields pre-converted to C strings.
+pub struct DsFinding {
+    id: CString,
+    path: CString,
+    name: Option<CString>,
+    kind: CString,
+    code: Option<CString>,
+    reason: Option<CString>,
+}
+
+impl From<&Finding> for DsFinding {
+    fn from(f: &Finding) -> Self {
+        let (code, reason) = match &f.status {
+            Status::Ok { .. } => (None, None),
+            Status::Broken { code, reason }
+            | Status::Skipped { code, reason }
+            | Status::Suppressed { code, reason, .. }
+            | Status::TimedOut { code, reason } => {
+                (Some(c_string(code.as_str())), Some(c_string(reason)))
+            }
+        };
+
+        DsFinding {
+            id: c_string(&f.id),
+            path: c_string(&f.desktop_file.display().to_string()),
+            name: f.name.as_deref().map(c_string),
+            kind: c_string(f.status.kind()),
+            code,
+            reason,
+        }
+    }
+}
+
+/// Convert to a C string, dropping interior NUL bytes (which cannot be represented).
+fn c_string(s: &str) -> CString {
+    CString::new(s.replace('\0', "")).unwrap_or_default()
+}
+
+/// Record `message` as the last error of this thread.
+fn set_last_error(message: String) {
+    LAST_ERROR.with(|e| *e.borrow_mut() = Some(c_string(&message)));
+}
+
+/// Build scan arguments from C inputs and run a blocking scan.
+fn scan_blocking(dirs: Vec<PathBuf>, flags: u32) -> Result<DsReport> {
+    let mut args = Args::try_parse_from(["desktop-scout"])?;
+    args.no_log = true;
+    args.no_cache = true;
+    args.no_default = flags & DS_SCAN_NO_DEFAULT != 0;
+    args.include_hidden = flags & DS_SCAN_INCLUDE_HIDDEN != 0;
+    args.check_script_args = flags & DS_SCAN_CHECK_SCRIPT_ARGS != 0;
+    args.extra_dirs = dirs;
+
+    let mut report = scan::run_blocking(&args)?;
+    report.findings.retain(|f| f.is_reported());
+
+    Ok(DsReport {
+        findings: report.findings.iter().map(DsFinding::from).collect(),
+        report,
+    })
+}
+
+/// Run a scan and return its reported findings, or `NULL` on failure (see [`ds_last_error`]).
+///
+/// `dirs` is an array of `n_dirs` extra directories (`--dir`) and may be `NULL` if `n_dirs` is
+/// 0. `flags` is a bitwise OR of the `DS_SCAN_*` constants. The call blocks until the scan is
+/// finished. The result must be released with [`ds_report_free`].
+///
+/// # Safety
+/// `dirs` must point to `n_dirs` valid, NUL-terminated strings (or be `NULL` with `n_dirs == 0`).
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn ds_scan(
+    dirs: *const *const c_char,
+    n_dirs: usize,
+    flags: u32,
+) -> *mut DsReport {
+    let mut extra = Vec::with_capacity(n_dirs);
+    for i in 0..n_dirs {
+        // SAFETY: the caller guarantees `dirs` holds `n_dirs` valid C strings.
+        let dir = unsafe { CStr::from_ptr(*dirs.add(i)) };
+        extra.push(PathBuf::from(dir.to_string_lossy().into_owned()));
+    }
+
+    let result = panic::catch_unwind(AssertUnwindSafe(|| scan_blocking(extra, flags)))
+        .unwrap_or_else(|_| Err(anyhow!("Scan panicked")));
+    match result {
+        Ok(report) => Box::into_raw(Box::new(report)),
+        Err(e) => {
+            set_last_error(format!("{e:#}"));
+            ptr::null_mut()
+        }
+    }
+}
+
+/// Message of the last failed call on this thread, or `NULL` if there was none.
+///
+/// The string is owned by the library and valid until the next failing call on this thread.
+#[unsafe(no_mangle)]
+pub extern "C" fn ds_last_error() -> *const c_char {
+    LAST_ERROR.with(|e| e.borrow().as_ref().map_or(ptr::null(), |s| s.as_ptr()))
+}
+
+/// Number of findings in `report`.
+///
+/// # Safety
+/// `report` must be a valid pointer returned by [`ds_scan`].
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn ds_report_len(report: *const DsReport) -> usize {
+    // SAFETY: the caller guarantees `report` is a live handle from `ds_scan`.
+    unsafe { &*report }.findings.len()
+}
+
+/// Finding at `index`, or `NULL` if out of range. Borrowed from `report`.
+///
+/// # Safety
+/// `report` must be a valid pointer returned by [`ds_scan`].
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn ds_report_get(report: *const DsReport, index: usize) -> *const DsFinding {
+    // SAFETY: the caller guarantees `report` is a live handle from `ds_scan`.
+    unsafe { &*report }
+        .findings
+        .get(index)
+        .map_or(ptr::null(), |f| f as *const DsFinding)
+}
+
+/// Serialize `report` as the JSON document `--output json` prints. Free with [`ds_string_free`].
+///
+/// Returns `NULL` on failure (see [`ds_last_error`]).
+///
+/// # Safety
+/// `report` must be a valid pointer returned by [`ds_scan`].
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn ds_report_to_json(report: *const DsReport) -> *mut c_char {
+    // SAFETY: the caller guarantees `report` is a live handle from `ds_scan`.
+    match serde_json::to_string_pretty(&unsafe { &*report }.report) {
+        Ok(json) => c_string(&json).into_raw(),
+        Err(e) => {
+            set_last_error(e.to_string());
+            ptr::null_mut()
+        }
+    }
+}
+
+/// Free a report returned by [`ds_scan`]. `NULL` is ignored.
+///
+/// # Safety
+/// `report` must be `NULL` or a pointer returned by [`ds_scan`] that was not freed yet.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn ds_report_free(report: *mut DsReport) {
+    if !report.is_null() {
+        // SAFETY: the caller guarantees ownership is handed back exactly once.
+        drop(unsafe { Box::from_raw(report) });
+    }
+}
+
+/// Free a string returned by [`ds_report_to_json`]. `NULL` is ignored.
+///
+/// # Safety
+/// `s` must be `NULL` or a string returned by this library that was not freed yet.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn ds_string_free(s: *mut c_char) {
+    if !s.is_null() {
+        // SAFETY: the caller guarantees ownership is handed back exactly once.
+        drop(unsafe { CString::from_raw(s) });
+    }
+}
+
+/// Pointer to an optional borrowed string, `NULL` if absent.
+fn opt_ptr(s: &Option<CString>) -> *const c_char {
+    s.as_ref().map_or(ptr::null(), |s| s.as_ptr())
+}
+
+/// Stable finding ID.
+///
+/// # Safety
+/// `finding` must be a valid pointer returned by [`ds_report_get`].
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn ds_finding_id(finding: *const DsFinding) -> *const c_char {
+    // SAFETY: the caller guarantees `finding` is borrowed from a live report.
+    unsafe { &*finding }.id.as_ptr()
+}
+
+/// Path of the `.desktop` file.
+///
+/// # Safety
+/// `finding` must be a valid pointer returned by [`ds_report_get`].
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn ds_finding_path(finding: *const DsFinding) -> *const c_char {
+    // SAFETY: the caller guarantees `finding` is borrowed from a live report.
+    unsafe { &*finding }.path.as_ptr()
+}
+
+/// `Name=` value, or `NULL` if absent.
+///
+/// # Safety
+/// `finding` must be a valid pointer returned by [`ds_report_get`].
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn ds_finding_name(finding: *const DsFinding) -> *const c_char {
+    // SAFETY: the caller guarantees `finding` is borrowed from a live report.
+    opt_ptr(&unsafe { &*finding }.name)
+}
+
+/// Status kind: `ok`, `broken`, `skipped`, `suppressed` or `timed_out`.
+///
+/// # Safety
+/// `finding` must be a valid pointer returned by [`ds_report_get`].
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn ds_finding_kind(finding: *const DsFinding) -> *const c_char {
+    // SAFETY: the caller guarantees `finding` is borrowed from a live report.
+    unsafe { &*finding }.kind.as_ptr()
+}
+
+/// Reason code (e.g. `EXEC_NOT_FOUND`), or `NULL` for `ok` entries.
+///
+/// # Safety
+/// `finding` must be a valid pointer returned by [`ds_report_get`].
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn ds_finding_code(finding: *const DsFinding) -> *const c_char {
+    // SAFETY: the caller guarantees `finding` is borrowed from a live report.
+    opt_ptr(&unsafe { &*finding }.code)
+}
+
+/// Human-readable reason, or `NULL` for `ok` entries.
+///
+/// # Safety
+/// `finding` must be a valid pointer returned by [`ds_report_get`].
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn ds_finding_reason(finding: *const DsFinding) -> *const c_char {
+    // SAFETY: the caller guarantees `finding` is borrowed from a live report.
+    opt_ptr(&unsafe { &*finding }.reason)
+}
+
+#[cfg(test)]
+mod tests {
+    /// The committed header matches the one generated from this module.
+    #[test]
+    fn header_is_up_to_date() {
+        assert!(
+            include_str!("../include/desktop_scout.h")
+                == include_str!(concat!(env!("OUT_DIR"), "/desktop_scout.h")),
+            "include/desktop_scout.h is stale; regenerate it with `just update-header`"
+        );
+    }
+}
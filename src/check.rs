@@ -4,10 +4,19 @@
 //! it resolves to a runnable executable on the current machine. Checks are async
 //! (Tokio filesystem operations).
 
-use crate::desktop::extract_executable_from_tokens;
+use crate::{
+    desktop::{self, SystemdRun, extract_executable_from_tokens},
+    i18n::tr,
+    report::{ReasonCode, Warning},
+    retry,
+    simulate::Simulation,
+    sysroot,
+};
 use anyhow::Result;
-use shlex;
-use std::path::{Path, PathBuf};
+use std::{
+    env, fmt,
+    path::{Path, PathBuf},
+};
 use tokio::fs;
 
 /// Context required to validate a `.desktop` entry.
@@ -25,53 +34,274 @@ pub struct CheckContext<'a> {
     /// If true, run a conservative heuristic that flags missing scripts when the
     /// executable is an interpreter (python/node/bash/etc).
     pub check_script_args: bool,
+
+    /// If true, `systemd-run --user` prefixes also require a reachable user manager.
+    pub check_user_manager: bool,
+
+    /// Simulated removals (`simulate` subcommand); hidden files count as missing.
+    pub simulation: Option<&'a Simulation>,
+
+    /// Root of the system image being checked (`--root`); absolute paths are resolved below it.
+    pub root: Option<&'a Path>,
+}
+
+/// Directories searched for relocated binaries in addition to `PATH` (see
+/// [`suggest_relocated_exec`]).
+const RELOCATION_DIRS: &[&str] = &[
+    "/usr/bin",
+    "/usr/local/bin",
+    "/bin",
+    "/usr/sbin",
+    "/usr/games",
+    "/snap/bin",
+    "/var/lib/flatpak/exports/bin",
+];
+
+/// Like [`RELOCATION_DIRS`], relative to `$HOME`.
+const USER_RELOCATION_DIRS: &[&str] = &[".local/bin", ".local/share/flatpak/exports/bin"];
+
+/// A check failure carrying a stable [`ReasonCode`].
+///
+/// Returned (wrapped in [`anyhow::Error`]) by [`validate_exec`] so callers can classify the
+/// failure via `downcast_ref` instead of matching on message text.
+#[derive(Debug)]
+pub struct CheckError {
+    /// Code classifying the failure.
+    pub code: ReasonCode,
+
+    /// Human-readable explanation.
+    pub message: String,
+}
+
+impl CheckError {
+    /// Create a [`CheckError`] wrapped in an [`anyhow::Error`].
+    pub fn err(code: ReasonCode, message: impl Into<String>) -> anyhow::Error {
+        anyhow::Error::new(CheckError {
+            code,
+            message: message.into(),
+        })
+    }
 }
 
+impl fmt::Display for CheckError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl std::error::Error for CheckError {}
+
 /// Validate a `TryExec=` value.
 ///
 /// `TryExec` is specifically meant to test program presence. We try to resolve it
 /// either as a filesystem path (if it contains `/`) or by searching `PATH`.
 pub async fn validate_tryexec(try_exec: &str, ctx: &CheckContext<'_>) -> Result<Option<PathBuf>> {
-    resolve_executable(try_exec, ctx.path_env, ctx.path_key).await
+    resolve_executable(try_exec, ctx).await
 }
 
 /// Validate an `Exec=` command line.
 ///
 /// Steps:
 /// 1. Shell-split `Exec`
-/// 2. Extract the executable token (with `env VAR=...` handling)
+/// 2. Extract the executable token (with `env VAR=...` and `systemd-run ...` handling)
 /// 3. Resolve it as a path or via `PATH`
-/// 4. (Optional) run script-argument heuristic for interpreters.
+/// 4. Check the `systemd-run` prefix, if any (see [`check_systemd_run`])
+/// 5. (Optional) run script-argument heuristic for interpreters.
 ///
 /// Returns `Ok(Some(path))` if the executable resolves and is runnable,
-/// `Ok(None)` if it does not resolve, and `Err` for parse/heuristic failures.
+/// `Ok(None)` if it does not resolve, and `Err` (a [`CheckError`]) for parse/heuristic failures.
 pub async fn validate_exec(exec_line: &str, ctx: &CheckContext<'_>) -> Result<Option<PathBuf>> {
-    let tokens =
-        shlex::split(exec_line).ok_or_else(|| anyhow::anyhow!("Failed to shell-split Exec"))?;
-    let extracted = extract_executable_from_tokens(&tokens)
-        .ok_or_else(|| anyhow::anyhow!("Could not extract executable from Exec"))?;
+    let tokens = split_exec(exec_line)?;
+    let (systemd_run, command) = desktop::unwrap_exec_prefixes(&tokens);
+    let extracted = command_executable(command)?;
 
     // If the "executable" is actually a field code marker, it's not meaningful.
     if extracted.starts_with('%') {
         return Ok(None);
     }
 
-    let resolved = resolve_executable(&extracted, ctx.path_env, ctx.path_key).await?;
+    let resolved = resolve_executable(extracted, ctx).await?;
+
+    if resolved.is_some()
+        && let Some(run) = &systemd_run
+    {
+        check_systemd_run(run, ctx).await?;
+    }
 
     // Optional: check missing script arguments for interpreter launchers.
-    if ctx.check_script_args {
-        if let Some(resolved_exe) = &resolved {
-            if let Some(reason) =
-                heuristic_script_missing(resolved_exe, &tokens, ctx.path_key).await?
-            {
-                return Err(anyhow::anyhow!(reason));
-            }
-        }
+    if ctx.check_script_args
+        && let Some(resolved_exe) = &resolved
+        && let Some(reason) = heuristic_script_missing(resolved_exe, command, ctx).await?
+    {
+        return Err(CheckError::err(ReasonCode::ScriptMissing, reason));
     }
 
     Ok(resolved)
 }
 
+/// The executable token of an `Exec=` line, checking only the line itself (`--frozen`).
+///
+/// Performs steps 1 and 2 of [`validate_exec`] without touching the filesystem. Returns `None`
+/// if the executable is a field code (`%U`), which can never resolve.
+///
+/// # Errors
+/// - [`CheckError`] with [`ReasonCode::ExecParseError`] if the line cannot be shell-split or
+///   names no executable.
+pub fn exec_syntax(exec_line: &str) -> Result<Option<String>> {
+    let tokens = split_exec(exec_line)?;
+    let (_, command) = desktop::unwrap_exec_prefixes(&tokens);
+    let extracted = command_executable(command)?;
+    Ok((!extracted.starts_with('%')).then(|| extracted.clone()))
+}
+
+/// Shell-split an `Exec=` line.
+fn split_exec(exec_line: &str) -> Result<Vec<String>> {
+    shlex::split(exec_line).ok_or_else(|| {
+        CheckError::err(
+            ReasonCode::ExecParseError,
+            tr("reason-exec-unparsable", &[]),
+        )
+    })
+}
+
+/// The executable token of an unwrapped `Exec=` command.
+fn command_executable(command: &[String]) -> Result<&String> {
+    command.first().ok_or_else(|| {
+        CheckError::err(
+            ReasonCode::ExecParseError,
+            tr("reason-exec-no-executable", &[]),
+        )
+    })
+}
+
+/// Check that the `systemd-run` prefix of an `Exec=` line can launch the command.
+///
+/// `systemd-run` itself must resolve, and with [`CheckContext::check_user_manager`] a `--user`
+/// prefix needs a running user manager, recognized by its private socket in
+/// `$XDG_RUNTIME_DIR/systemd/` (which is what `systemd-run --user` connects to first).
+///
+/// # Errors
+/// - [`CheckError`] with [`ReasonCode::SystemdRunUnavailable`] if either is missing.
+async fn check_systemd_run(run: &SystemdRun, ctx: &CheckContext<'_>) -> Result<()> {
+    if resolve_executable(&run.program, ctx).await?.is_none() {
+        return Err(CheckError::err(
+            ReasonCode::SystemdRunUnavailable,
+            tr(
+                "reason-systemd-run-missing",
+                &[("program", run.program.as_str().into())],
+            ),
+        ));
+    }
+
+    if ctx.check_user_manager && run.user && !user_manager_running().await {
+        return Err(CheckError::err(
+            ReasonCode::SystemdRunUnavailable,
+            tr("reason-user-manager-unavailable", &[]),
+        ));
+    }
+    Ok(())
+}
+
+/// Whether the systemd user manager of this session is reachable.
+async fn user_manager_running() -> bool {
+    let Some(runtime_dir) = env::var_os("XDG_RUNTIME_DIR").filter(|d| !d.is_empty()) else {
+        return false;
+    };
+    let socket = Path::new(&runtime_dir).join("systemd/private");
+    retry::metadata(&socket).await.is_ok_and(|md| {
+        use std::os::unix::fs::FileTypeExt;
+        md.file_type().is_socket()
+    })
+}
+
+/// The executable token of an `Exec=` line (after `env` handling), if it can be extracted.
+pub fn exec_command_name(exec_line: &str) -> Option<String> {
+    extract_executable_from_tokens(&shlex::split(exec_line)?)
+}
+
+/// Suggest a rewritten `Exec=` line for an absolute executable that no longer exists.
+///
+/// Looks for an executable with the same basename in `PATH`, [`RELOCATION_DIRS`] and
+/// [`USER_RELOCATION_DIRS`] and substitutes the first match for the missing path. Returns `None`
+/// if the executable is not an absolute path or nothing suitable is found.
+pub async fn suggest_relocated_exec(exec_line: &str, ctx: &CheckContext<'_>) -> Option<String> {
+    let token = exec_command_name(exec_line)?;
+    let missing = Path::new(&token);
+    if !missing.is_absolute() || !exec_line.contains(&token) {
+        return None;
+    }
+    let name = missing.file_name()?;
+
+    let mut dirs = ctx
+        .path_env
+        .split(':')
+        .filter_map(normalize_path_component)
+        .collect::<Vec<_>>();
+    dirs.extend(RELOCATION_DIRS.iter().map(PathBuf::from));
+    if ctx.root.is_none()
+        && let Some(home) = env::var_os("HOME").map(PathBuf::from)
+    {
+        dirs.extend(USER_RELOCATION_DIRS.iter().map(|d| home.join(d)));
+    }
+
+    for dir in dirs {
+        let candidate = dir.join(name);
+        // Paths that would need quoting are not worth the risk of a wrong rewrite.
+        let Some(replacement) = candidate
+            .to_str()
+            .filter(|c| !c.contains(|ch: char| ch.is_whitespace() || "\"'\\$`".contains(ch)))
+        else {
+            continue;
+        };
+        if candidate != missing && is_available_executable(&candidate, ctx).await {
+            return Some(exec_line.replacen(&token, replacement, 1));
+        }
+    }
+    None
+}
+
+/// Detect an unquoted absolute executable path with spaces (`Exec=/opt/My Apps/app %U`).
+///
+/// Such a path is split into several arguments, so the first one does not resolve. Re-joins the
+/// executable argument with the following ones (keeping the original whitespace) until the result
+/// is an available executable, and returns it with the `Exec=` line that quotes it. Returns `None`
+/// if no such path exists.
+pub async fn unquoted_spaced_exec(
+    exec_line: &str,
+    ctx: &CheckContext<'_>,
+) -> Option<(PathBuf, String)> {
+    let tokens = shlex::split(exec_line)?;
+    let spans = desktop::exec_arg_spans(exec_line);
+    if tokens.len() != spans.len() {
+        return None;
+    }
+    let token = extract_executable_from_tokens(&tokens)?;
+    let start = tokens.iter().position(|t| *t == token)?;
+    if !token.starts_with('/') {
+        return None;
+    }
+
+    for end in start + 1..spans.len() {
+        let raw = &exec_line[spans[start].start..spans[end].end];
+        // Quoted or escaped arguments were written deliberately and field codes end the path.
+        // Characters that would need escaping inside the quotes are not worth a rewrite.
+        if raw.contains(['"', '\\', '\'', '%', '$', '`']) {
+            return None;
+        }
+        let candidate = Path::new(raw);
+        if is_available_executable(candidate, ctx).await {
+            let fixed = format!(
+                "{}\"{}\"{}",
+                &exec_line[..spans[start].start],
+                raw,
+                &exec_line[spans[end].end..]
+            );
+            return Some((candidate.to_path_buf(), fixed));
+        }
+    }
+    None
+}
+
 /// Resolve an executable token to an on-disk executable path, if possible.
 ///
 /// Rules:
@@ -79,17 +309,13 @@ pub async fn validate_exec(exec_line: &str, ctx: &CheckContext<'_>) -> Result<Op
 ///   - Absolute: validate directly.
 ///   - Relative: if `Path=` exists, resolve relative to that working dir.
 /// - Otherwise (no `/`), search `PATH`.
-pub async fn resolve_executable(
-    token: &str,
-    path_env: &str,
-    path_key: Option<&str>,
-) -> Result<Option<PathBuf>> {
+pub async fn resolve_executable(token: &str, ctx: &CheckContext<'_>) -> Result<Option<PathBuf>> {
     // If token includes a '/', treat it as a path.
     if token.contains('/') {
         let p = Path::new(token);
 
         if p.is_absolute() {
-            return Ok(if is_executable_file(p).await {
+            return Ok(if is_available_executable(p, ctx).await {
                 Some(p.to_path_buf())
             } else {
                 None
@@ -97,9 +323,9 @@ pub async fn resolve_executable(
         }
 
         // Relative path: try resolve via Path= (working dir)
-        if let Some(wd) = path_key {
+        if let Some(wd) = ctx.path_key {
             let candidate = Path::new(wd).join(p);
-            return Ok(if is_executable_file(&candidate).await {
+            return Ok(if is_available_executable(&candidate, ctx).await {
                 Some(candidate)
             } else {
                 None
@@ -111,25 +337,109 @@ pub async fn resolve_executable(
     }
 
     // Bare cmd: search PATH
-    Ok(which_in_path(token, path_env).await)
+    Ok(which_in_path(token, ctx).await)
 }
 
 /// Search for `cmd` in the given PATH string.
 ///
-/// Returns the first match that is an executable file.
-async fn which_in_path(cmd: &str, path_env: &str) -> Option<PathBuf> {
-    for dir in path_env.split(':').filter(|s| !s.is_empty()) {
-        let candidate = Path::new(dir).join(cmd);
-        if is_executable_file(&candidate).await {
+/// Returns the first match that is an executable file. Degenerate PATH components are
+/// resolved via [`normalize_path_component`].
+async fn which_in_path(cmd: &str, ctx: &CheckContext<'_>) -> Option<PathBuf> {
+    for component in ctx.path_env.split(':') {
+        let Some(dir) = normalize_path_component(component) else {
+            continue;
+        };
+        let candidate = dir.join(cmd);
+        if is_available_executable(&candidate, ctx).await {
             return Some(candidate);
         }
     }
     None
 }
 
+/// Resolve a single PATH component to a directory, predictably.
+///
+/// - empty component → current working directory (POSIX `execvp` semantics)
+/// - `~` or `~/...` → expanded using `$HOME` (as interactive shells do, but `execvp` does not)
+/// - relative → joined onto the current working directory
+/// - absolute → used verbatim
+///
+/// Returns `None` if the component cannot be resolved (e.g. `~` without `$HOME`).
+fn normalize_path_component(component: &str) -> Option<PathBuf> {
+    if component.is_empty() {
+        return env::current_dir().ok();
+    }
+
+    if component == "~" || component.starts_with("~/") {
+        let home = env::var_os("HOME")?;
+        let rest = component.trim_start_matches('~').trim_start_matches('/');
+        return Some(PathBuf::from(home).join(rest));
+    }
+
+    let p = Path::new(component);
+    if p.is_absolute() {
+        Some(p.to_path_buf())
+    } else {
+        env::current_dir().ok().map(|cwd| cwd.join(p))
+    }
+}
+
+/// Diagnose PATH components that make launcher behavior session-dependent.
+///
+/// Empty, `~`-prefixed and relative components resolve differently depending on the launcher's
+/// working directory and on whether a shell expands them, so the same `.desktop` file may work in
+/// a terminal but not from the application menu. Returns one warning per offending component.
+pub fn diagnose_path_env(path_env: &str) -> Vec<Warning> {
+    let mut out = Vec::new();
+
+    for (idx, component) in path_env.split(':').enumerate() {
+        let id = if component.is_empty() {
+            "path-entry-empty"
+        } else if component.starts_with('~') {
+            "path-entry-tilde"
+        } else if !Path::new(component).is_absolute() {
+            "path-entry-relative"
+        } else {
+            continue;
+        };
+
+        out.push(Warning {
+            code: ReasonCode::UnsafePathEntry,
+            message: tr(id, &[("index", idx.into()), ("entry", component.into())]),
+            key: None,
+        });
+    }
+
+    out
+}
+
+/// Whether `p` is gone under the simulated removals of `ctx` (checked before and after
+/// resolving symlinks, so `/usr/bin/foo -> /opt/foo/bin/foo` is hidden by removing `/opt/foo`).
+async fn is_simulated_missing(p: &Path, ctx: &CheckContext<'_>) -> bool {
+    let Some(sim) = ctx.simulation else {
+        return false;
+    };
+    sim.hides(p) || fs::canonicalize(p).await.is_ok_and(|c| sim.hides(&c))
+}
+
+/// [`is_executable_file`] of `p` inside the `--root` image, additionally treating simulated
+/// removals as missing.
+async fn is_available_executable(p: &Path, ctx: &CheckContext<'_>) -> bool {
+    !is_simulated_missing(p, ctx).await && is_executable_file(&host_path(p, ctx).await).await
+}
+
+/// Where the file at `p` is on this machine: below the `--root` image for absolute paths (see
+/// [`sysroot::resolve`]), otherwise `p` itself.
+async fn host_path(p: &Path, ctx: &CheckContext<'_>) -> PathBuf {
+    match ctx.root {
+        Some(root) if p.is_absolute() => sysroot::resolve(root, p).await,
+        _ => p.to_path_buf(),
+    }
+}
+
 /// Check whether `p` exists, is a regular file, and has any executable bit set.
 async fn is_executable_file(p: &Path) -> bool {
-    let md = match fs::metadata(p).await {
+    let md = match retry::metadata(p).await {
         Ok(m) => m,
         Err(_) => return false,
     };
@@ -157,14 +467,50 @@ async fn is_executable_file(p: &Path) -> bool {
 ///
 /// Example it catches:
 /// - `python3 /home/user/bin/foo.py` (script missing)
+async fn heuristic_script_missing(
+    resolved_exe: &Path,
+    tokens: &[String],
+    ctx: &CheckContext<'_>,
+) -> Result<Option<String>> {
+    let Some(candidate) = script_argument(resolved_exe, tokens, ctx.path_key) else {
+        return Ok(None);
+    };
+
+    if retry::metadata(&host_path(&candidate, ctx).await)
+        .await
+        .is_err()
+        || is_simulated_missing(&candidate, ctx).await
+    {
+        let exe_name = resolved_exe
+            .file_name()
+            .and_then(|s| s.to_str())
+            .unwrap_or("")
+            .to_ascii_lowercase();
+        return Ok(Some(tr(
+            "reason-script-missing",
+            &[
+                ("interpreter", exe_name.into()),
+                ("path", candidate.display().to_string().into()),
+            ],
+        )));
+    }
+
+    Ok(None)
+}
+
+/// Script path passed to an interpreter launcher (`python3 /opt/foo/app.py`).
+///
+/// `tokens` is the shell-split `Exec=` line and `resolved_exe` its resolved executable; returns
+/// `None` unless that is a known interpreter followed by a path-like argument. Relative paths are
+/// resolved against `path_key` (`Path=`).
 ///
 /// This is intentionally conservative and does not attempt to parse all interpreter flags.
-/// It tries to find the first "non-option" argument and verifies it exists if it looks like a path.
-async fn heuristic_script_missing(
+/// It takes the first "non-option" argument if it looks like a path.
+pub fn script_argument(
     resolved_exe: &Path,
     tokens: &[String],
     path_key: Option<&str>,
-) -> Result<Option<String>> {
+) -> Option<PathBuf> {
     let exe_name = resolved_exe
         .file_name()
         .and_then(|s| s.to_str())
@@ -176,50 +522,25 @@ async fn heuristic_script_missing(
         "python" | "python3" | "node" | "bash" | "sh" | "ruby" | "perl"
     );
     if !is_interpreter || tokens.is_empty() {
-        return Ok(None);
+        return None;
     }
 
     // Find first non-option argument after interpreter.
-    let mut i = 1;
-    while i < tokens.len() {
-        let t = &tokens[i];
-        if t.starts_with('%') {
-            i += 1;
-            continue;
-        }
-        if t.starts_with('-') {
-            i += 1;
-            continue;
-        }
-        break;
-    }
-
-    let arg = match tokens.get(i) {
-        Some(a) => a,
-        None => return Ok(None),
-    };
+    let arg = tokens
+        .iter()
+        .skip(1)
+        .find(|t| !t.starts_with('%') && !t.starts_with('-'))?;
 
     // Only care if it looks path-like.
     if !arg.contains('/') {
-        return Ok(None);
+        return None;
     }
 
     let p = Path::new(arg);
-    let candidate = if p.is_absolute() {
-        p.to_path_buf()
-    } else if let Some(wd) = path_key {
-        Path::new(wd).join(p)
+    if p.is_absolute() {
+        Some(p.to_path_buf())
     } else {
         // Relative without Path= is ambiguous.
-        return Ok(None);
-    };
-
-    if fs::metadata(&candidate).await.is_err() {
-        return Ok(Some(format!(
-            "Interpreter {exe_name} exists, but script/path argument is missing: {}",
-            candidate.display()
-        )));
+        path_key.map(|wd| Path::new(wd).join(p))
     }
-
-    Ok(None)
 }
@@ -0,0 +1,185 @@
+//! Companion artifacts of user-local entries (`fix --with-companions`).
+//!
+//! Applications installed into the home directory by hand or by AppImage integrators usually
+//! drop more than a `.desktop` file: icons below `$XDG_DATA_HOME/icons` and MIME package XML in
+//! `$XDG_DATA_HOME/mime/packages`. When the entry is removed these are left behind. This module
+//! finds artifacts that belong to a single entry only, so they can be cleaned in the same fix.
+
+// -- std imports
+use std::{
+    collections::{HashMap, HashSet},
+    path::{Path, PathBuf},
+};
+
+// -- crate imports
+use tokio::fs;
+
+// -- module imports
+use crate::{
+    desktop,
+    report::{Finding, Report},
+};
+
+/// Icon file extensions launchers look up.
+const ICON_EXTENSIONS: &[&str] = &["png", "svg", "svgz", "xpm"];
+
+/// Maximum directory depth searched below `$XDG_DATA_HOME/icons` (theme/size/context/file).
+const ICON_SEARCH_DEPTH: usize = 4;
+
+/// `Icon=` and `MimeType=` values of every scanned entry.
+#[derive(Debug, Default)]
+pub struct CompanionIndex {
+    entries: HashMap<PathBuf, EntryRefs>,
+}
+
+/// Artifact references of a single entry.
+#[derive(Debug, Default)]
+struct EntryRefs {
+    icon: Option<String>,
+    mime_types: Vec<String>,
+}
+
+impl CompanionIndex {
+    /// Read the `Icon=` and `MimeType=` keys of all findings of `report`.
+    pub async fn build(report: &Report) -> Self {
+        let mut entries = HashMap::new();
+        for f in &report.findings {
+            let Ok(content) = fs::read_to_string(&f.desktop_file).await else {
+                continue;
+            };
+            let kv = desktop::parse_desktop_entry_section(&content);
+            entries.insert(
+                f.desktop_file.clone(),
+                EntryRefs {
+                    icon: kv.get("Icon").cloned().filter(|i| !i.is_empty()),
+                    mime_types: kv
+                        .get("MimeType")
+                        .map(|m| split_list(m))
+                        .unwrap_or_default(),
+                },
+            );
+        }
+        CompanionIndex { entries }
+    }
+
+    /// Find artifacts below `data_home` that belong to `finding` and no other entry.
+    ///
+    /// Returns icon files named after the entry's `Icon=` (or the icon path itself if it is
+    /// absolute and inside `data_home`) and MIME package files that only define types the entry
+    /// declares. Artifacts referenced by any other scanned entry are never returned.
+    pub async fn companions_of(&self, finding: &Finding, data_home: &Path) -> Vec<PathBuf> {
+        let Some(refs) = self.entries.get(&finding.desktop_file) else {
+            return Vec::new();
+        };
+        let others = self
+            .entries
+            .iter()
+            .filter(|(p, _)| **p != finding.desktop_file)
+            .map(|(_, r)| r);
+
+        let mut shared_icons = HashSet::new();
+        let mut shared_types = HashSet::new();
+        for r in others {
+            shared_icons.extend(r.icon.as_deref());
+            shared_types.extend(r.mime_types.iter().map(String::as_str));
+        }
+
+        let mut out = Vec::new();
+        if let Some(icon) = refs.icon.as_deref()
+            && !shared_icons.contains(icon)
+        {
+            let p = Path::new(icon);
+            if p.is_absolute() {
+                if p.starts_with(data_home) && fs::metadata(p).await.is_ok_and(|m| m.is_file()) {
+                    out.push(p.to_path_buf());
+                }
+            } else {
+                find_icons(&data_home.join("icons"), icon, &mut out).await;
+            }
+        }
+
+        let own_types = refs
+            .mime_types
+            .iter()
+            .map(String::as_str)
+            .filter(|t| !shared_types.contains(t))
+            .collect::<HashSet<_>>();
+        if !own_types.is_empty() {
+            find_mime_packages(&data_home.join("mime/packages"), &own_types, &mut out).await;
+        }
+
+        out.sort();
+        out
+    }
+}
+
+/// Split a `;`-separated desktop entry list.
+fn split_list(value: &str) -> Vec<String> {
+    value
+        .split(';')
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// Collect icon files named `<name>.<ext>` below `root`.
+async fn find_icons(root: &Path, name: &str, out: &mut Vec<PathBuf>) {
+    let mut stack = vec![(root.to_path_buf(), 0)];
+    while let Some((dir, depth)) = stack.pop() {
+        let Ok(mut rd) = fs::read_dir(&dir).await else {
+            continue;
+        };
+        while let Ok(Some(entry)) = rd.next_entry().await {
+            let Ok(ft) = entry.file_type().await else {
+                continue;
+            };
+            let path = entry.path();
+            if ft.is_dir() && depth < ICON_SEARCH_DEPTH {
+                stack.push((path, depth + 1));
+            } else if ft.is_file()
+                && path.file_stem().is_some_and(|s| s == name)
+                && path
+                    .extension()
+                    .and_then(|e| e.to_str())
+                    .is_some_and(|e| ICON_EXTENSIONS.contains(&e))
+            {
+                out.push(path);
+            }
+        }
+    }
+}
+
+/// Collect MIME package files in `dir` whose defined types are all in `types`.
+async fn find_mime_packages(dir: &Path, types: &HashSet<&str>, out: &mut Vec<PathBuf>) {
+    let Ok(mut rd) = fs::read_dir(dir).await else {
+        return;
+    };
+    while let Ok(Some(entry)) = rd.next_entry().await {
+        let path = entry.path();
+        if path.extension().is_none_or(|e| e != "xml") {
+            continue;
+        }
+        let Ok(content) = fs::read_to_string(&path).await else {
+            continue;
+        };
+
+        let defined = defined_mime_types(&content);
+        if !defined.is_empty() && defined.iter().all(|t| types.contains(t.as_str())) {
+            out.push(path);
+        }
+    }
+}
+
+/// Extract the `type` attributes of `<mime-type>` elements from shared-mime-info XML.
+fn defined_mime_types(xml: &str) -> Vec<String> {
+    xml.split("<mime-type")
+        .skip(1)
+        .filter_map(|elem| {
+            let rest = elem.split_once("type=")?.1;
+            let quote = rest.chars().next().filter(|c| *c == '"' || *c == '\'')?;
+            let value = &rest[1..];
+            Some(value[..value.find(quote)?].to_string())
+        })
+        .collect()
+}
@@ -0,0 +1,518 @@
+//! Configuration files (`/etc/desktop-scout/config.toml`, `~/.config/desktop-scout/config.toml`
+//! or `--config`) and environment variables (`DESKTOP_SCOUT_*`).
+//!
+//! The files provide defaults for the top-level options. Keys are the long option names and
+//! values what the option takes:
+//!
+//! ```toml
+//! dir = ["/opt/apps", "/srv/launchers"]
+//! jobs = 8
+//! output = "json"
+//! check-script-args = true
+//! ```
+//!
+//! Lists stand for a repeated option and `true` for a flag (`false` leaves it unset). Options
+//! given on the command line win: a file value is dropped if its option, or an option it
+//! conflicts with, was passed. Since clap parses the result, file values are validated exactly
+//! like command-line arguments.
+//!
+//! Administrators ship organization-wide defaults in the system-wide file. The user's file is
+//! laid over it and replaces its settings the same way, so users can still override them.
+//!
+//! The `[rules]` table enables or disables rules by reason code:
+//!
+//! ```toml
+//! [rules]
+//! SCRIPT_MISSING = false
+//! MISSING_ICON = true
+//! ```
+//!
+//! It adds to `--enable-rule` and `--disable-rule`; a rule named on the command line keeps the
+//! setting given there.
+//!
+//! `[profile.<name>]` tables bundle settings for one workflow and are applied on top of the
+//! other settings of both files with `--profile <name>`:
+//!
+//! ```toml
+//! [profile.ci]
+//! output = "sarif"
+//! fail-on = "warning"
+//! rules = { MISSING_ICON = true }
+//! ```
+//!
+//! Every top-level option can also be set through an environment variable named after it,
+//! e.g. `DESKTOP_SCOUT_JOBS=8` or `DESKTOP_SCOUT_NO_HISTORY=1`, for containers and systemd units.
+//! Environment variables replace settings of the files the same way command-line options do,
+//! and command-line options in turn replace environment variables.
+
+// -- std imports
+use std::{
+    ffi::OsString,
+    fs,
+    path::{Path, PathBuf},
+};
+
+// -- crate imports
+use anyhow::{Context, Result, bail};
+use clap::{
+    Arg, ArgAction, ArgMatches, Command, CommandFactory, FromArgMatches,
+    builder::BoolishValueParser, error::ErrorKind, parser::ValueSource,
+};
+use toml::{Table, Value};
+use xdg::BaseDirectories;
+
+// -- module imports
+use crate::{args::Args, report::ReasonCode};
+
+/// Name of the configuration file in `$XDG_CONFIG_HOME/desktop-scout`.
+const CONFIG_NAME: &str = "config.toml";
+
+/// System-wide configuration file, laid under the user's.
+const SYSTEM_CONFIG_PATH: &str = "/etc/desktop-scout/config.toml";
+
+/// Table of the configuration file enabling and disabling rules.
+const RULES_TABLE: &str = "rules";
+
+/// Table of the configuration file holding the named profiles.
+const PROFILE_TABLE: &str = "profile";
+
+/// Prefix of the environment variables setting options.
+const ENV_PREFIX: &str = "DESKTOP_SCOUT_";
+
+/// Parse the command line with the environment variables and the defaults of the configuration
+/// files applied: the system-wide file with the user's (`--config`, or `config.toml` in the
+/// configuration directory) laid over it, each used if it exists.
+///
+/// Settings of the files show up as [`ValueSource::CommandLine`] in the returned matches,
+/// environment variables as [`ValueSource::EnvVariable`]. Build [`Args`] from them with
+/// [`args_from_matches`].
+///
+/// Exits with clap's usage message on invalid arguments, like [`clap::Parser::parse`].
+///
+/// # Errors
+/// - [`anyhow::Error`] if a configuration file cannot be read or parsed, or names an unknown
+///   option or profile.
+pub fn parse_matches() -> Result<ArgMatches> {
+    let argv = std::env::args_os().collect::<Vec<_>>();
+
+    // Environment variables of options replaced by command-line options must not be read at
+    // all, or clap would report them as conflicting; so find the command-line options first.
+    let base = Args::command();
+    let probe = with_env(Args::command(), |_| true)
+        .ignore_errors(true)
+        .get_matches_from(&argv);
+    let on_command_line = given(&base, &probe, &[ValueSource::CommandLine]);
+    let cmd = with_env(Args::command(), |arg| {
+        !on_command_line.iter().any(|g| overrides(&base, g, arg))
+    });
+    let cli = cmd.clone().get_matches_from(&argv);
+    // clap only knows conflicts between arguments, so report this one the way clap would.
+    if let Some(subcommand) = cli.subcommand_name()
+        && cli.value_source("hosts") == Some(ValueSource::CommandLine)
+    {
+        cmd.clone()
+            .error(
+                ErrorKind::ArgumentConflict,
+                format!(
+                    "the argument '--hosts <FILE>' cannot be used with subcommand '{subcommand}'"
+                ),
+            )
+            .exit();
+    }
+
+    let user = match cli.get_one::<PathBuf>("config") {
+        Some(path) => Some(path.clone()),
+        None => default_path().filter(|p| p.exists()),
+    };
+    let paths = Some(PathBuf::from(SYSTEM_CONFIG_PATH))
+        .filter(|p| p.exists())
+        .into_iter()
+        .chain(user)
+        .collect::<Vec<_>>();
+    let profile = cli.get_one::<String>("profile");
+    if paths.is_empty() {
+        if let Some(name) = profile {
+            bail!("--profile {name} needs a configuration file, but none was found");
+        }
+        return Ok(cli);
+    }
+
+    let layers = paths
+        .iter()
+        .map(|path| load(path))
+        .collect::<Result<Vec<_>>>()?;
+    let defaults = merge(&cmd, layers, profile.map(String::as_str))
+        .and_then(|table| config_args(&cmd, &cli, &table))
+        .with_context(|| {
+            let paths = paths
+                .iter()
+                .map(|p| p.display().to_string())
+                .collect::<Vec<_>>();
+            format!("Invalid configuration in {}", paths.join(" and "))
+        })?;
+    if defaults.is_empty() {
+        return Ok(cli);
+    }
+
+    // Options of the file go first, so they stay in front of a subcommand.
+    let mut merged = Vec::with_capacity(argv.len() + defaults.len());
+    merged.extend(argv.first().cloned());
+    merged.extend(defaults);
+    merged.extend(argv.into_iter().skip(1));
+    Ok(cmd.get_matches_from(merged))
+}
+
+/// `cmd` with an environment variable for each option `read_env` accepts (see [`env_var`]).
+fn with_env(cmd: Command, read_env: impl Fn(&Arg) -> bool) -> Command {
+    cmd.mut_args(|arg| {
+        let Some(var) = env_var(&arg).filter(|_| read_env(&arg)) else {
+            return arg;
+        };
+        if arg.get_action().takes_values() {
+            arg.env(var)
+        } else {
+            // Accept the usual spellings (`1`, `yes`, `off`, ...) for flags.
+            arg.env(var).value_parser(BoolishValueParser::new())
+        }
+    })
+}
+
+/// The environment variable setting `arg`: its long name upper-cased and prefixed with
+/// [`ENV_PREFIX`] (`--no-history` is `DESKTOP_SCOUT_NO_HISTORY`).
+pub fn env_var(arg: &Arg) -> Option<String> {
+    if matches!(
+        arg.get_action(),
+        ArgAction::Help | ArgAction::HelpLong | ArgAction::Version
+    ) {
+        return None;
+    }
+    let long = arg.get_long()?;
+    Some(format!(
+        "{ENV_PREFIX}{}",
+        long.to_ascii_uppercase().replace('-', "_")
+    ))
+}
+
+/// Options of `cmd` whose values in `matches` come from one of `sources`.
+fn given<'a>(cmd: &'a Command, matches: &ArgMatches, sources: &[ValueSource]) -> Vec<&'a Arg> {
+    cmd.get_arguments()
+        .filter(|a| {
+            matches
+                .value_source(a.get_id().as_str())
+                .is_some_and(|s| sources.contains(&s))
+        })
+        .collect()
+}
+
+/// Location of the user's configuration file.
+pub fn default_path() -> Option<PathBuf> {
+    BaseDirectories::with_prefix("desktop-scout")
+        .get_config_home()
+        .map(|dir| dir.join(CONFIG_NAME))
+}
+
+/// Read and parse the configuration file at `path`.
+fn load(path: &Path) -> Result<Table> {
+    let text = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read configuration file {}", path.display()))?;
+    text.parse::<Table>()
+        .with_context(|| format!("Failed to parse configuration file {}", path.display()))
+}
+
+/// Merge the configuration `layers` (lowest first): the settings outside of profiles, then
+/// those of the profile `name` in each layer, each laid over the previous ones ([`overlay`]).
+fn merge(cmd: &Command, layers: Vec<Table>, name: Option<&str>) -> Result<Table> {
+    let mut merged = Table::new();
+    let mut profiles = Vec::new();
+    for mut table in layers {
+        match table.remove(PROFILE_TABLE) {
+            Some(Value::Table(p)) => profiles.push(p),
+            Some(_) => bail!("`{PROFILE_TABLE}` must be a table of profiles"),
+            None => {}
+        }
+        overlay(cmd, &mut merged, &table);
+    }
+    let Some(name) = name else {
+        return Ok(merged);
+    };
+
+    let selected = profiles
+        .iter()
+        .filter_map(|p| p.get(name))
+        .collect::<Vec<_>>();
+    if selected.is_empty() {
+        let mut known = profiles.iter().flat_map(|p| p.keys()).collect::<Vec<_>>();
+        known.sort();
+        known.dedup();
+        if known.is_empty() {
+            bail!("Unknown profile `{name}` (no profiles are defined)");
+        }
+        let known = known.into_iter().cloned().collect::<Vec<_>>();
+        bail!("Unknown profile `{name}` (defined: {})", known.join(", "));
+    }
+    for profile in selected {
+        let Value::Table(profile) = profile else {
+            bail!("Profile `{name}` must be a table");
+        };
+        overlay(cmd, &mut merged, profile);
+    }
+    Ok(merged)
+}
+
+/// Lay the settings of `upper` over `table`: a setting replaces the one for the same option and
+/// those for options it conflicts with, and a `rules` table adds to the one of `table`.
+fn overlay(cmd: &Command, table: &mut Table, upper: &Table) {
+    for (key, value) in upper {
+        if key == RULES_TABLE
+            && let (Some(Value::Table(rules)), Value::Table(overrides)) =
+                (table.get_mut(key), value)
+        {
+            rules.extend(overrides.clone());
+            continue;
+        }
+        if let Some(arg) = option(cmd, key) {
+            table.retain(|k, _| option(cmd, k).is_none_or(|a| !overrides(cmd, arg, a)));
+        }
+        table.insert(key.clone(), value.clone());
+    }
+}
+
+/// Command-line arguments for the values of `table` whose options were not given in `cli`
+/// (on the command line or through the environment).
+fn config_args(cmd: &Command, cli: &ArgMatches, table: &Table) -> Result<Vec<OsString>> {
+    let given = given(
+        cmd,
+        cli,
+        &[ValueSource::CommandLine, ValueSource::EnvVariable],
+    );
+
+    let mut out = Vec::new();
+    for (key, value) in table {
+        if key == RULES_TABLE {
+            out.extend(rule_args(cli, value)?);
+            continue;
+        }
+        let Some(arg) = option(cmd, key) else {
+            bail!("Unknown option `{key}`");
+        };
+        if given.iter().any(|g| overrides(cmd, g, arg)) {
+            continue;
+        }
+
+        let flag = format!("--{key}");
+        if !arg.get_action().takes_values() {
+            match value {
+                Value::Boolean(true) => out.push(flag.into()),
+                Value::Boolean(false) => {}
+                _ => bail!("`{key}` is a flag and takes `true` or `false`"),
+            }
+            continue;
+        }
+        let values = match value {
+            Value::Array(values) => values.iter().collect(),
+            value => vec![value],
+        };
+        for value in values {
+            out.push(flag.clone().into());
+            out.push(scalar(key, value)?.into());
+        }
+    }
+    Ok(out)
+}
+
+/// `--enable-rule`/`--disable-rule` arguments for the `[rules]` table, leaving out rules named
+/// on the command line or in the environment.
+fn rule_args(cli: &ArgMatches, rules: &Value) -> Result<Vec<OsString>> {
+    let Value::Table(rules) = rules else {
+        bail!("`{RULES_TABLE}` must be a table of reason codes");
+    };
+    let given = ["enable_rule", "disable_rule"]
+        .into_iter()
+        .flat_map(|id| cli.get_many::<ReasonCode>(id).into_iter().flatten())
+        .collect::<Vec<_>>();
+
+    let mut out = Vec::new();
+    for (code, enabled) in rules {
+        let Value::Boolean(enabled) = enabled else {
+            bail!("Rule `{code}` takes `true` or `false`");
+        };
+        let parsed = code.parse::<ReasonCode>()?;
+        if given.contains(&&parsed) {
+            continue;
+        }
+        out.push(
+            if *enabled {
+                "--enable-rule"
+            } else {
+                "--disable-rule"
+            }
+            .into(),
+        );
+        out.push(code.into());
+    }
+    Ok(out)
+}
+
+/// The option a configuration key sets.
+fn option<'a>(cmd: &'a Command, key: &str) -> Option<&'a Arg> {
+    cmd.get_arguments()
+        .find(|a| a.get_long() == Some(key) && configurable(a))
+}
+
+/// Whether setting `arg` replaces a setting of `other`: they are the same option, or one
+/// conflicts with the other.
+fn overrides(cmd: &Command, arg: &Arg, other: &Arg) -> bool {
+    let conflicts = |a: &Arg, b: &Arg| {
+        cmd.get_arg_conflicts_with(a)
+            .iter()
+            .any(|c| c.get_id() == b.get_id())
+    };
+    arg.get_id() == other.get_id() || conflicts(arg, other) || conflicts(other, arg)
+}
+
+/// Whether `arg` may be set in the configuration file (not `--config`, `--profile`, `--help` or
+/// `--version`).
+fn configurable(arg: &Arg) -> bool {
+    !matches!(arg.get_id().as_str(), "config" | "profile")
+        && !matches!(
+            arg.get_action(),
+            ArgAction::Help | ArgAction::HelpLong | ArgAction::Version
+        )
+}
+
+/// The command-line spelling of a single option value.
+fn scalar(key: &str, value: &Value) -> Result<String> {
+    match value {
+        Value::String(s) => Ok(s.clone()),
+        Value::Integer(n) => Ok(n.to_string()),
+        Value::Float(n) => Ok(n.to_string()),
+        Value::Boolean(b) => Ok(b.to_string()),
+        _ => bail!("`{key}` takes a string, a number or a list of them"),
+    }
+}
+
+/// Build [`Args`] from matches of [`Args::command`] (see [`parse_matches`]).
+pub fn args_from_matches(matches: &ArgMatches) -> Args {
+    Args::from_arg_matches(matches).unwrap_or_else(|e| e.exit())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn table(toml: &str) -> Table {
+        toml.parse().unwrap()
+    }
+
+    fn strings(args: Vec<OsString>) -> Vec<String> {
+        args.into_iter().map(|a| a.into_string().unwrap()).collect()
+    }
+
+    #[test]
+    fn upper_layers_and_profiles_replace_settings() {
+        let cmd = Args::command();
+        let system = table(
+            "jobs = 4\n\
+             output = \"yaml\"\n\
+             dir = [\"/opt/a\"]\n\
+             [rules]\n\
+             MISSING_ICON = true\n\
+             [profile.ci]\n\
+             jobs = 1\n",
+        );
+        let user = table(
+            "json = true\n\
+             [rules]\n\
+             SCRIPT_MISSING = false\n\
+             [profile.ci]\n\
+             dir = [\"/opt/b\"]\n",
+        );
+
+        let merged = merge(&cmd, vec![system.clone(), user.clone()], None).unwrap();
+        assert_eq!(
+            merged,
+            table(
+                "jobs = 4\n\
+                 dir = [\"/opt/a\"]\n\
+                 json = true\n\
+                 [rules]\n\
+                 MISSING_ICON = true\n\
+                 SCRIPT_MISSING = false\n"
+            )
+        );
+
+        let ci = merge(&cmd, vec![system, user], Some("ci")).unwrap();
+        assert_eq!(ci["jobs"].as_integer(), Some(1));
+        assert_eq!(ci["dir"], table("dir = [\"/opt/b\"]")["dir"]);
+    }
+
+    #[test]
+    fn unknown_profiles_are_rejected() {
+        let cmd = Args::command();
+        let layers = vec![table("[profile.ci]\njobs = 1\n")];
+        let err = merge(&cmd, layers, Some("nightly")).unwrap_err();
+        assert_eq!(err.to_string(), "Unknown profile `nightly` (defined: ci)");
+        let err = merge(&cmd, Vec::new(), Some("ci")).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Unknown profile `ci` (no profiles are defined)"
+        );
+    }
+
+    #[test]
+    fn command_line_options_win_over_the_file() {
+        let cmd = Args::command();
+        let cli = cmd.clone().get_matches_from([
+            "desktop-scout",
+            "--jobs",
+            "2",
+            "--output",
+            "csv",
+            "--enable-rule",
+            "SCRIPT_MISSING",
+        ]);
+        let file = table(
+            "jobs = 8\n\
+             json = true\n\
+             dir = [\"/opt/a\", \"/opt/b\"]\n\
+             no-cache = true\n\
+             include-hidden = false\n\
+             [rules]\n\
+             SCRIPT_MISSING = false\n\
+             MISSING_ICON = true\n",
+        );
+        assert_eq!(
+            strings(config_args(&cmd, &cli, &file).unwrap()),
+            [
+                "--dir",
+                "/opt/a",
+                "--dir",
+                "/opt/b",
+                "--no-cache",
+                "--enable-rule",
+                "MISSING_ICON",
+            ]
+        );
+    }
+
+    #[test]
+    fn invalid_settings_are_rejected() {
+        let cmd = Args::command();
+        let cli = cmd.clone().get_matches_from(["desktop-scout"]);
+        for (toml, message) in [
+            ("no-such-option = 1", "Unknown option `no-such-option`"),
+            (
+                "no-cache = \"yes\"",
+                "`no-cache` is a flag and takes `true` or `false`",
+            ),
+            ("config = \"x\"", "Unknown option `config`"),
+            (
+                "[rules]\nMISSING_ICON = 1",
+                "Rule `MISSING_ICON` takes `true` or `false`",
+            ),
+        ] {
+            let err = config_args(&cmd, &cli, &table(toml)).unwrap_err();
+            assert_eq!(err.to_string(), message, "{toml}");
+        }
+    }
+}
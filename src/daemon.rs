@@ -0,0 +1,196 @@
+//! Long-running daemon exposing scan results on the session D-Bus.
+//!
+//! The daemon owns the well-known name [`BUS_NAME`] and serves the [`BUS_NAME`] interface at
+//! [`OBJECT_PATH`]:
+//! - `Scan() -> u` runs a scan and returns the number of broken entries
+//! - `GetFindings() -> a(ssssss)` returns the reported findings of the last scan
+//! - `FindingsChanged(u)` is emitted whenever a scan changes the set of reported findings
+//!
+//! Desktop widgets and settings panels can use this instead of spawning the CLI. Resource use is
+//! bounded by the [`Limits`] given on the command line.
+//!
+//! Every scan updates the persistent scan cache and the finding history like a one-shot `scan`
+//! does, so a later CLI scan reuses the daemon's verdicts and continues its broken streaks.
+//! Scans are serialized so that concurrent `Scan()` calls never race on those stores.
+
+// -- std imports
+use std::{collections::BTreeSet, sync::Mutex as StdMutex};
+
+// -- crate imports
+use anyhow::{Context, Result};
+use serde::Serialize;
+use tokio::sync::Mutex;
+use tracing::{info, warn};
+use zbus::{interface, object_server::SignalEmitter, zvariant::Type};
+
+// -- module imports
+use crate::{
+    args::Args,
+    history,
+    limits::{Limits, RateLimiter},
+    report::{Finding, Status},
+    scan,
+};
+
+/// Well-known bus name (and interface name) of the daemon.
+pub const BUS_NAME: &str = "io.github.desktop_scout";
+
+/// Object path the interface is served at.
+pub const OBJECT_PATH: &str = "/io/github/desktop_scout";
+
+/// Flattened representation of a [`Finding`] for D-Bus consumers.
+///
+/// Serialized as `(ssssss)`: id, desktop file, name, status kind, reason code, reason text.
+/// Fields that do not apply (e.g. the code of an `ok` entry) are empty strings.
+#[derive(Debug, Clone, Serialize, Type)]
+pub struct DbusFinding {
+    id: String,
+    desktop_file: String,
+    name: String,
+    kind: String,
+    code: String,
+    reason: String,
+}
+
+impl From<&Finding> for DbusFinding {
+    fn from(f: &Finding) -> Self {
+        let (code, reason) = match &f.status {
+            Status::Ok { .. } => (String::new(), String::new()),
+            Status::Broken { code, reason }
+            | Status::Skipped { code, reason }
+            | Status::Suppressed { code, reason, .. }
+            | Status::TimedOut { code, reason } => (code.to_string(), reason.clone()),
+        };
+
+        DbusFinding {
+            id: f.id.clone(),
+            desktop_file: f.desktop_file.display().to_string(),
+            name: f.name.clone().unwrap_or_default(),
+            kind: f.status.kind().to_string(),
+            code,
+            reason,
+        }
+    }
+}
+
+/// D-Bus object holding the scan configuration and the findings of the last scan.
+struct ScoutService {
+    args: Args,
+    limits: Limits,
+    events: StdMutex<RateLimiter>,
+    findings: Mutex<Vec<DbusFinding>>,
+    /// Held while a scan runs; the scan cache and history are read and written per scan.
+    scanning: Mutex<()>,
+}
+
+impl ScoutService {
+    /// Run a scan and store its reported findings.
+    ///
+    /// Returns the number of broken entries and whether the set of findings changed.
+    async fn rescan(&self) -> Result<(u32, bool)> {
+        let _scanning = self.scanning.lock().await;
+        let jobs = self
+            .limits
+            .scan_jobs(self.args.jobs.unwrap_or_else(scan::default_jobs));
+        let mut report = scan::run(&Args {
+            jobs: Some(jobs),
+            ..self.args.clone()
+        })
+        .await?;
+        if !self.args.no_history
+            && !self.args.frozen
+            && let Err(e) = history::record(&mut report).await
+        {
+            warn!("Failed to update finding history: {e:#}");
+        }
+        let reported = report
+            .findings
+            .iter()
+            .filter(|f| f.is_reported())
+            .map(DbusFinding::from)
+            .collect::<Vec<_>>();
+        let broken = report.findings.iter().filter(|f| f.is_broken()).count() as u32;
+
+        let mut findings = self.findings.lock().await;
+        let ids = |v: &[DbusFinding]| v.iter().map(|f| f.id.clone()).collect::<BTreeSet<_>>();
+        let changed = ids(&findings) != ids(&reported);
+        *findings = reported;
+
+        info!(broken, changed, "Daemon scan finished");
+        Ok((broken, changed))
+    }
+}
+
+#[interface(name = "io.github.desktop_scout")]
+impl ScoutService {
+    /// Run a new scan and return the number of broken entries.
+    async fn scan(
+        &self,
+        #[zbus(signal_emitter)] emitter: SignalEmitter<'_>,
+    ) -> zbus::fdo::Result<u32> {
+        let admitted = self
+            .events
+            .lock()
+            .expect("event limiter lock poisoned")
+            .admit();
+        if !admitted {
+            let findings = self.findings.lock().await;
+            return Ok(findings.iter().filter(|f| f.kind == "broken").count() as u32);
+        }
+
+        let (broken, changed) = self
+            .rescan()
+            .await
+            .map_err(|e| zbus::fdo::Error::Failed(format!("{e:#}")))?;
+
+        if changed {
+            emitter.findings_changed(broken).await?;
+        }
+        Ok(broken)
+    }
+
+    /// Return the reported findings of the last scan.
+    async fn get_findings(&self) -> Vec<DbusFinding> {
+        self.findings.lock().await.clone()
+    }
+
+    /// Emitted when a scan changed the set of reported findings.
+    #[zbus(signal)]
+    async fn findings_changed(emitter: &SignalEmitter<'_>, broken: u32) -> zbus::Result<()>;
+}
+
+/// Run the daemon until interrupted (Ctrl-C / SIGINT).
+///
+/// Applies the process-wide `limits`, performs an initial scan, then serves requests on the
+/// session bus.
+///
+/// # Errors
+/// - [`anyhow::Error`] if the limits cannot be applied, the session bus is unavailable, the bus
+///   name is already taken, or the initial scan fails.
+pub async fn run(args: Args, limits: Limits) -> Result<()> {
+    limits
+        .apply()
+        .context("Failed to apply the daemon resource limits")?;
+    let service = ScoutService {
+        args,
+        limits,
+        events: StdMutex::new(limits.event_limiter()),
+        findings: Mutex::new(Vec::new()),
+        scanning: Mutex::new(()),
+    };
+    service.rescan().await?;
+
+    let _conn = zbus::connection::Builder::session()?
+        .name(BUS_NAME)?
+        .serve_at(OBJECT_PATH, service)?
+        .build()
+        .await
+        .with_context(|| format!("Failed to register {BUS_NAME} on the session bus"))?;
+    info!("Daemon listening on the session bus as {BUS_NAME}");
+
+    if let Err(e) = tokio::signal::ctrl_c().await {
+        warn!(error = %e, "Failed to wait for Ctrl-C; shutting down");
+    }
+    info!("Daemon shutting down");
+    Ok(())
+}
@@ -1,9 +1,16 @@
-//! Minimal `.desktop` parsing helpers.
+//! Minimal `.desktop` parsing helpers and a writer for in-place fixes.
 //!
-//! This project intentionally avoids a full spec-compliant parser and instead extracts
-//! the essentials from `[Desktop Entry]` for the purpose of CLI health checks.
+//! Checks intentionally avoid a full spec-compliant parser and instead extract the essentials
+//! from `[Desktop Entry]` ([`parse_desktop_entry_section`]). Fixes that rewrite a file go
+//! through [`DesktopEntry`] and [`write_entry`]: lines that are not changed are written back
+//! byte for byte, and only set keys are serialized (with spec-compliant escaping and list
+//! termination), so diffs of a fix touch nothing but the lines it changed.
 
-use std::collections::HashMap;
+use std::{borrow::Cow, collections::HashMap, fmt, ops::Range, path::Path};
+
+use anyhow::Result;
+
+use crate::output_file;
 
 /// Parse only the `[Desktop Entry]` section into a key-value map.
 ///
@@ -38,6 +45,389 @@ pub fn parse_desktop_entry_section(content: &str) -> HashMap<String, String> {
     map
 }
 
+/// Name of the main group, which the spec requires to come first.
+pub const DESKTOP_ENTRY_GROUP: &str = "Desktop Entry";
+
+/// Keys whose values are `;`-separated lists (compared without a `[locale]` suffix).
+const LIST_KEYS: &[&str] = &[
+    "Actions",
+    "Categories",
+    "Implements",
+    "Keywords",
+    "MimeType",
+    "NotShowIn",
+    "OnlyShowIn",
+];
+
+/// A whole `.desktop` file, kept close to its on-disk form so it can be written back.
+///
+/// Values are stored escaped, exactly as they appear in the file; use [`escape_string`] and
+/// [`escape_list`] (or [`DesktopEntry::set_string`] / [`DesktopEntry::set_list`]) for plain
+/// values. Comments, blank lines and unknown lines are kept verbatim in place, and so are
+/// headers and entries until they are changed.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DesktopEntry {
+    /// Comments and blank lines before the first group header.
+    pub preamble: Vec<String>,
+
+    /// Groups in file order.
+    pub groups: Vec<Group>,
+
+    /// Whether the file lacks a newline after its last line.
+    pub no_final_newline: bool,
+}
+
+/// A `[Group Name]` and its lines.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Group {
+    /// Group name without brackets (e.g. `Desktop Entry`, `Desktop Action new-window`).
+    pub name: String,
+
+    /// The header line as read (`None` for new or normalized groups).
+    pub raw: Option<String>,
+
+    /// Lines below the header, in file order.
+    pub lines: Vec<GroupLine>,
+}
+
+/// A line inside a group.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GroupLine {
+    /// A `key=value` pair; `value` is escaped. `raw` is the line as read, written back while
+    /// the entry is unchanged (`None` for set or normalized entries).
+    Entry {
+        key: String,
+        value: String,
+        raw: Option<String>,
+    },
+
+    /// A comment, blank or malformed line, kept verbatim.
+    Other(String),
+}
+
+impl DesktopEntry {
+    /// Parse a whole `.desktop` file. Never fails: lines that are not group headers or
+    /// `key=value` pairs are kept as [`GroupLine::Other`].
+    pub fn parse(content: &str) -> Self {
+        let mut entry = DesktopEntry::default();
+
+        for raw in content.lines() {
+            let line = raw.trim();
+            if line.starts_with('[') && line.ends_with(']') {
+                entry.groups.push(Group {
+                    name: line[1..line.len() - 1].to_string(),
+                    raw: Some(raw.to_string()),
+                    lines: Vec::new(),
+                });
+                continue;
+            }
+
+            let Some(group) = entry.groups.last_mut() else {
+                entry.preamble.push(raw.to_string());
+                continue;
+            };
+            let is_comment = line.is_empty() || line.starts_with('#') || line.starts_with(';');
+            group.lines.push(match line.split_once('=') {
+                Some((k, v)) if !is_comment => GroupLine::Entry {
+                    key: k.trim().to_string(),
+                    value: v.trim().to_string(),
+                    raw: Some(raw.to_string()),
+                },
+                _ => GroupLine::Other(raw.to_string()),
+            });
+        }
+        entry.no_final_newline = !content.is_empty() && !content.ends_with('\n');
+
+        entry
+    }
+
+    /// Escaped value of `key` in `group`.
+    pub fn get(&self, group: &str, key: &str) -> Option<&str> {
+        self.groups
+            .iter()
+            .filter(|g| g.name == group)
+            .flat_map(|g| &g.lines)
+            .find_map(|l| match l {
+                GroupLine::Entry { key: k, value, .. } if k == key => Some(value.as_str()),
+                _ => None,
+            })
+    }
+
+    /// Set `key` in `group` to the already escaped `value`.
+    ///
+    /// The first existing `key` line is replaced in place (unless it already has `value`) and
+    /// duplicates are dropped; otherwise the key is appended after the last entry of the group.
+    /// A missing group is created (the main group in front, others at the end), separated from
+    /// its neighbours by a blank line.
+    pub fn set(&mut self, group: &str, key: &str, value: &str) {
+        let idx = match self.groups.iter().position(|g| g.name == group) {
+            Some(idx) => idx,
+            None => self.add_group(group),
+        };
+        let lines = &mut self.groups[idx].lines;
+
+        let mut replaced = false;
+        lines.retain_mut(|l| match l {
+            GroupLine::Entry {
+                key: k,
+                value: v,
+                raw,
+            } if k == key => {
+                if replaced {
+                    return false;
+                }
+                if v != value {
+                    *v = value.to_string();
+                    *raw = None;
+                }
+                replaced = true;
+                true
+            }
+            _ => true,
+        });
+        if !replaced {
+            let at = lines
+                .iter()
+                .rposition(|l| matches!(l, GroupLine::Entry { .. }))
+                .map_or(0, |i| i + 1);
+            lines.insert(
+                at,
+                GroupLine::Entry {
+                    key: key.to_string(),
+                    value: value.to_string(),
+                    raw: None,
+                },
+            );
+        }
+    }
+
+    /// Insert an empty `group` (the main group in front, others at the end) and return its
+    /// index.
+    fn add_group(&mut self, group: &str) -> usize {
+        let mut new = Group {
+            name: group.to_string(),
+            raw: None,
+            lines: Vec::new(),
+        };
+        if group == DESKTOP_ENTRY_GROUP {
+            if !self.groups.is_empty() {
+                new.lines.push(GroupLine::Other(String::new()));
+            }
+            self.groups.insert(0, new);
+            return 0;
+        }
+
+        if let Some(last) = self.groups.last_mut()
+            && !matches!(last.lines.last(), Some(GroupLine::Other(l)) if l.trim().is_empty())
+        {
+            last.lines.push(GroupLine::Other(String::new()));
+        }
+        self.groups.push(new);
+        self.groups.len() - 1
+    }
+
+    /// Set `key` in `group` to the plain string `value` (see [`escape_string`]).
+    pub fn set_string(&mut self, group: &str, key: &str, value: &str) {
+        self.set(group, key, &escape_string(value));
+    }
+
+    /// Set `key` in `group` to the plain list `items` (see [`escape_list`]).
+    pub fn set_list(&mut self, group: &str, key: &str, items: &[&str]) {
+        self.set(group, key, &escape_list(items));
+    }
+
+    /// Remove every `key` line from `group`. Returns whether anything was removed.
+    pub fn remove(&mut self, group: &str, key: &str) -> bool {
+        let mut removed = false;
+        for g in self.groups.iter_mut().filter(|g| g.name == group) {
+            g.lines.retain(|l| {
+                let hit = matches!(l, GroupLine::Entry { key: k, .. } if k == key);
+                removed |= hit;
+                !hit
+            });
+        }
+        removed
+    }
+}
+
+impl fmt::Display for DesktopEntry {
+    /// Serialize the file: unchanged lines as read, set entries as `key=value` with list values
+    /// terminated by `;`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut lines = self.preamble.iter().map(Cow::from).collect::<Vec<_>>();
+        for group in &self.groups {
+            lines.push(match &group.raw {
+                Some(raw) => Cow::from(raw),
+                None => Cow::from(format!("[{}]", group.name)),
+            });
+            for line in &group.lines {
+                lines.push(match line {
+                    GroupLine::Entry { raw: Some(raw), .. } | GroupLine::Other(raw) => {
+                        Cow::from(raw)
+                    }
+                    GroupLine::Entry { key, value, .. } if is_list_key(key) => {
+                        Cow::from(format!("{key}={}", terminate_list(value)))
+                    }
+                    GroupLine::Entry { key, value, .. } => Cow::from(format!("{key}={value}")),
+                });
+            }
+        }
+
+        for (i, line) in lines.iter().enumerate() {
+            f.write_str(line)?;
+            if i + 1 < lines.len() || !self.no_final_newline {
+                f.write_str("\n")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Whether `key` (optionally with a `[locale]` suffix) holds a list.
+fn is_list_key(key: &str) -> bool {
+    let base = key.split_once('[').map_or(key, |(b, _)| b);
+    LIST_KEYS.contains(&base)
+}
+
+/// Append the terminating `;` to a non-empty list value that lacks it (an escaped `\;` does
+/// not count).
+fn terminate_list(value: &str) -> Cow<'_, str> {
+    let trailing_backslashes = value
+        .strip_suffix(';')
+        .map(|v| v.chars().rev().take_while(|&c| c == '\\').count());
+    match trailing_backslashes {
+        Some(n) if n % 2 == 0 => Cow::Borrowed(value),
+        _ if value.is_empty() => Cow::Borrowed(value),
+        _ => Cow::Owned(format!("{value};")),
+    }
+}
+
+/// Escape a plain string value: `\\`, newline, tab, carriage return and leading spaces
+/// (`\s`, which would otherwise be trimmed).
+pub fn escape_string(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    let mut leading = true;
+    for c in value.chars() {
+        match c {
+            ' ' if leading => out.push_str(r"\s"),
+            '\\' => out.push_str(r"\\"),
+            '\n' => out.push_str(r"\n"),
+            '\t' => out.push_str(r"\t"),
+            '\r' => out.push_str(r"\r"),
+            c => out.push(c),
+        }
+        leading &= c == ' ';
+    }
+    out
+}
+
+/// Escape a plain list: each item as in [`escape_string`] with `;` escaped as `\;`, every
+/// item terminated by `;`.
+pub fn escape_list(items: &[&str]) -> String {
+    items
+        .iter()
+        .map(|i| format!("{};", escape_string(i).replace(';', r"\;")))
+        .collect()
+}
+
+/// Write `entry` to `path` atomically (see [`output_file::write_atomic`]), keeping the
+/// permissions of the file it replaces; a symlinked `path` is written through.
+///
+/// # Errors
+/// - [`anyhow::Error`] if the temporary file cannot be written or renamed over `path`.
+pub async fn write_entry(path: &Path, entry: &DesktopEntry) -> Result<()> {
+    output_file::write_atomic(path, entry.to_string().as_bytes()).await
+}
+
+/// Field codes the spec marks as deprecated (launchers drop them).
+const DEPRECATED_FIELD_CODES: &[char] = &['d', 'D', 'n', 'N', 'v', 'm'];
+
+/// File/URL field codes, of which an `Exec=` line may contain at most one.
+const FILE_FIELD_CODES: &[char] = &['f', 'F', 'u', 'U'];
+
+/// Remove deprecated field codes (`%d %D %n %N %v %m`) from an `Exec=` value and keep only the
+/// first of `%f %F %u %U`. Returns `None` if the value is already clean.
+///
+/// Codes inside double-quoted arguments are left alone (the spec does not allow field codes
+/// there), `%%` is kept, and arguments that consisted only of removed codes are dropped. The
+/// remaining arguments are joined with single spaces.
+pub fn strip_field_codes(exec: &str) -> Option<String> {
+    let mut args = Vec::new();
+    let mut changed = false;
+    let mut file_code_seen = false;
+
+    for raw in exec_arg_spans(exec).into_iter().map(|r| &exec[r]) {
+        let mut arg = String::with_capacity(raw.len());
+        let mut in_quotes = false;
+        let mut chars = raw.chars();
+        while let Some(c) = chars.next() {
+            match c {
+                '"' => in_quotes = !in_quotes,
+                '\\' if in_quotes => {
+                    arg.push(c);
+                    if let Some(next) = chars.next() {
+                        arg.push(next);
+                    }
+                    continue;
+                }
+                '%' if !in_quotes => {
+                    let Some(code) = chars.next() else {
+                        arg.push(c);
+                        continue;
+                    };
+                    let is_file = FILE_FIELD_CODES.contains(&code);
+                    if DEPRECATED_FIELD_CODES.contains(&code) || (is_file && file_code_seen) {
+                        changed = true;
+                    } else {
+                        file_code_seen |= is_file;
+                        arg.push(c);
+                        arg.push(code);
+                    }
+                    continue;
+                }
+                _ => {}
+            }
+            arg.push(c);
+        }
+        if !arg.is_empty() {
+            args.push(arg);
+        }
+    }
+
+    changed.then(|| args.join(" "))
+}
+
+/// Byte ranges of the raw arguments of an `Exec=` value (quotes and escapes kept verbatim).
+pub fn exec_arg_spans(exec: &str) -> Vec<Range<usize>> {
+    let mut args = Vec::new();
+    let mut start = None;
+    let mut in_quotes = false;
+    let mut escaped = false;
+
+    for (i, c) in exec.char_indices() {
+        if escaped {
+            escaped = false;
+            continue;
+        }
+        match c {
+            '\\' if in_quotes => escaped = true,
+            '"' => in_quotes = !in_quotes,
+            c if c.is_whitespace() && !in_quotes => {
+                if let Some(s) = start.take() {
+                    args.push(s..i);
+                }
+                continue;
+            }
+            _ => {}
+        }
+        start.get_or_insert(i);
+    }
+    if let Some(s) = start {
+        args.push(s..exec.len());
+    }
+    args
+}
+
 /// Parse a `.desktop` boolean string.
 ///
 /// Accepts common truthy values:
@@ -49,33 +439,282 @@ pub fn parse_bool(v: Option<&String>) -> bool {
     )
 }
 
+/// Options of `systemd-run` that take their value as a separate argument (`-p KEY=VALUE`).
+const SYSTEMD_RUN_VALUE_OPTIONS: &[&str] = &[
+    "-p",
+    "--property",
+    "-u",
+    "--unit",
+    "--description",
+    "--slice",
+    "-E",
+    "--setenv",
+    "--uid",
+    "--gid",
+    "--nice",
+    "--working-directory",
+    "-M",
+    "--machine",
+    "-H",
+    "--host",
+    "-C",
+    "--capsule",
+    "--service-type",
+    "--expand-environment",
+    "--background",
+    "--on-active",
+    "--on-boot",
+    "--on-startup",
+    "--on-unit-active",
+    "--on-unit-inactive",
+    "--on-calendar",
+    "--timer-property",
+    "--path-property",
+    "--socket-property",
+];
+
+/// A `systemd-run` prefix in front of the launched command (`systemd-run --user --scope cmd`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SystemdRun {
+    /// The `systemd-run` token as written (a bare name or a path).
+    pub program: String,
+
+    /// Whether the command runs in the user manager (`--user`).
+    pub user: bool,
+}
+
+/// Split the `env` and `systemd-run` prefixes off a shell-split `Exec=` line.
+///
+/// Returns the `systemd-run` prefix (if any) and the launched command with its arguments:
+/// - `cmd arg` → `cmd arg`
+/// - `env VAR=1 cmd arg` → `cmd arg`
+/// - `systemd-run --user --scope -p Nice=5 cmd arg` → `cmd arg`
+///
+/// Prefixes may be nested (`env VAR=1 systemd-run --user cmd`). `systemd-run` is recognized by
+/// its file name, so `/usr/bin/systemd-run` is unwrapped as well.
+pub fn unwrap_exec_prefixes(mut tokens: &[String]) -> (Option<SystemdRun>, &[String]) {
+    let mut systemd_run = None;
+    loop {
+        let Some(first) = tokens.first() else {
+            return (systemd_run, tokens);
+        };
+
+        if first == "env" {
+            // Skip env options and assignments
+            let skip = tokens[1..]
+                .iter()
+                .take_while(|t| t.starts_with('-') || t.contains('='))
+                .count();
+            tokens = &tokens[1 + skip..];
+            continue;
+        }
+
+        if Path::new(first)
+            .file_name()
+            .is_some_and(|n| n == "systemd-run")
+        {
+            let mut run = SystemdRun {
+                program: first.clone(),
+                user: false,
+            };
+            let mut i = 1;
+            while let Some(t) = tokens.get(i) {
+                if t == "--" {
+                    i += 1;
+                    break;
+                }
+                if !t.starts_with('-') {
+                    break;
+                }
+                run.user |= t == "--user";
+                i += if SYSTEMD_RUN_VALUE_OPTIONS.contains(&t.as_str()) {
+                    2
+                } else {
+                    1
+                };
+            }
+            systemd_run = Some(run);
+            tokens = tokens.get(i..).unwrap_or_default();
+            continue;
+        }
+
+        return (systemd_run, tokens);
+    }
+}
+
 /// Extract the executable token from `Exec=` after shell-splitting.
 ///
 /// Handles typical patterns:
 /// - `cmd arg1 arg2` → `cmd`
 /// - `env VAR=1 VAR2=2 cmd arg` → `cmd`
+/// - `systemd-run --user --scope cmd arg` → `cmd`
 ///
 /// Returns `None` if no plausible token exists.
 pub fn extract_executable_from_tokens(tokens: &[String]) -> Option<String> {
-    if tokens.is_empty() {
-        return None;
+    unwrap_exec_prefixes(tokens).1.first().cloned()
+}
+
+/// Compute the desktop file ID of a `.desktop` file.
+///
+/// Per the spec, the ID is the path relative to the enclosing `applications` directory with `/`
+/// replaced by `-` (e.g. `applications/kde/foo.desktop` → `kde-foo.desktop`). Files outside an
+/// `applications` tree fall back to their file name.
+pub fn desktop_id(path: &Path) -> String {
+    match applications_dir(path).and_then(|r| path.strip_prefix(r).ok()) {
+        Some(rel) => rel
+            .components()
+            .map(|c| c.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("-"),
+        None => path
+            .file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_default(),
     }
+}
 
-    let mut i = 0;
+/// The `applications` directory enclosing the `.desktop` file at `path`, if any.
+pub fn applications_dir(path: &Path) -> Option<&Path> {
+    path.ancestors()
+        .skip(1)
+        .find(|a| a.file_name().is_some_and(|n| n == "applications"))
+}
 
-    // Handle `env ... cmd`
-    if tokens.get(0).map(|s| s.as_str()) == Some("env") {
-        i = 1;
-        // Skip env options and assignments
-        while i < tokens.len() {
-            let t = &tokens[i];
-            if t.starts_with('-') || t.contains('=') {
-                i += 1;
-                continue;
-            }
-            break;
-        }
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MESSY: &str = "# comment\n\
+                         [Desktop Entry]\n\
+                         Name = Foo  \n\
+                         Categories=Utility\n\
+                         Exec=foo %U\n\
+                         \n\
+                         [Desktop Action new]  \n\
+                         Name=New\n\
+                         Exec=foo --new";
+
+    #[test]
+    fn unchanged_entry_round_trips_byte_for_byte() {
+        assert_eq!(DesktopEntry::parse(MESSY).to_string(), MESSY);
+        let with_newline = format!("{MESSY}\n");
+        assert_eq!(DesktopEntry::parse(&with_newline).to_string(), with_newline);
+    }
+
+    #[test]
+    fn set_only_rewrites_the_changed_line() {
+        let mut entry = DesktopEntry::parse(MESSY);
+        entry.set(DESKTOP_ENTRY_GROUP, "Exec", "bar %U");
+        entry.set(DESKTOP_ENTRY_GROUP, "Name", "Foo");
+        entry.set(DESKTOP_ENTRY_GROUP, "Hidden", "true");
+        assert_eq!(
+            entry.to_string(),
+            MESSY
+                .replace("Exec=foo %U\n", "Exec=bar %U\nHidden=true\n")
+                .as_str()
+        );
+        assert_eq!(entry.get(DESKTOP_ENTRY_GROUP, "Name"), Some("Foo"));
     }
 
-    tokens.get(i).cloned()
+    #[test]
+    fn set_terminates_lists_and_drops_duplicates() {
+        let mut entry = DesktopEntry::parse("[Desktop Entry]\nMimeType=a\nMimeType=b\n");
+        entry.set(DESKTOP_ENTRY_GROUP, "MimeType", "text/plain");
+        assert_eq!(entry.to_string(), "[Desktop Entry]\nMimeType=text/plain;\n");
+    }
+
+    #[test]
+    fn set_creates_separated_groups() {
+        let mut entry = DesktopEntry::parse("[Desktop Action new]\nExec=foo\n");
+        entry.set(DESKTOP_ENTRY_GROUP, "Hidden", "true");
+        entry.set("X-Extra", "Key", "value");
+        assert_eq!(
+            entry.to_string(),
+            "[Desktop Entry]\nHidden=true\n\n[Desktop Action new]\nExec=foo\n\n[X-Extra]\nKey=value\n"
+        );
+        assert!(entry.remove("X-Extra", "Key"));
+        assert!(!entry.remove("X-Extra", "Key"));
+    }
+
+    #[test]
+    fn escapes_strings_and_lists() {
+        assert_eq!(escape_string("  a\\b\n\tc "), r"\s\sa\\b\n\tc ");
+        assert_eq!(escape_list(&["a;b", " c"]), r"a\;b;\sc;");
+        assert_eq!(terminate_list("a;b"), "a;b;");
+        assert_eq!(terminate_list(r"a\;"), r"a\;;");
+        assert_eq!(terminate_list(r"a\\;"), r"a\\;");
+        assert_eq!(terminate_list(""), "");
+    }
+
+    #[test]
+    fn strips_deprecated_and_extra_field_codes() {
+        assert_eq!(strip_field_codes("foo %U"), None);
+        assert_eq!(strip_field_codes("foo %d %U").as_deref(), Some("foo %U"));
+        assert_eq!(strip_field_codes("foo %f %U").as_deref(), Some("foo %f"));
+        assert_eq!(
+            strip_field_codes("foo --name=%N %k %i").as_deref(),
+            Some("foo --name= %k %i")
+        );
+        assert_eq!(
+            strip_field_codes("foo   100%% %D").as_deref(),
+            Some("foo 100%%")
+        );
+    }
+
+    #[test]
+    fn keeps_field_codes_in_quotes() {
+        assert_eq!(strip_field_codes(r#""/opt/My App/foo" "%d" %U"#), None);
+        assert_eq!(
+            strip_field_codes(r#""/opt/My App/foo" "a \" %m" %v %F"#).as_deref(),
+            Some(r#""/opt/My App/foo" "a \" %m" %F"#)
+        );
+    }
+
+    #[test]
+    fn splits_exec_arguments_verbatim() {
+        let exec = r#"env A="x y" foo\ bar "q \" %u""#;
+        let args = exec_arg_spans(exec)
+            .into_iter()
+            .map(|r| &exec[r])
+            .collect::<Vec<_>>();
+        assert_eq!(args, ["env", r#"A="x y""#, r"foo\", "bar", r#""q \" %u""#]);
+    }
+
+    #[test]
+    fn desktop_ids_are_relative_to_the_applications_tree() {
+        assert_eq!(
+            desktop_id(Path::new("/usr/share/applications/kde/foo.desktop")),
+            "kde-foo.desktop"
+        );
+        assert_eq!(desktop_id(Path::new("/tmp/foo.desktop")), "foo.desktop");
+        assert_eq!(
+            applications_dir(Path::new("/usr/share/applications/kde/foo.desktop")),
+            Some(Path::new("/usr/share/applications"))
+        );
+        assert_eq!(applications_dir(Path::new("/tmp/foo.desktop")), None);
+    }
+
+    #[tokio::test]
+    async fn write_entry_writes_through_symlinks() {
+        let dir =
+            std::env::temp_dir().join(format!("desktop-scout-desktop-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let target = dir.join("target.desktop");
+        let link = dir.join("link.desktop");
+        std::fs::write(&target, MESSY).unwrap();
+        std::os::unix::fs::symlink(&target, &link).unwrap();
+
+        let mut entry = DesktopEntry::parse(MESSY);
+        entry.set(DESKTOP_ENTRY_GROUP, "Hidden", "true");
+        let written = write_entry(&link, &entry).await;
+        let is_link = std::fs::symlink_metadata(&link).map(|m| m.file_type().is_symlink());
+        let content = std::fs::read_to_string(&target);
+        let leftovers = std::fs::read_dir(&dir).unwrap().count();
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        written.unwrap();
+        assert!(is_link.unwrap());
+        assert_eq!(content.unwrap(), entry.to_string());
+        assert_eq!(leftovers, 2);
+    }
 }
@@ -0,0 +1,94 @@
+//! Comparison of two JSON reports (`diff` subcommand).
+//!
+//! Findings are matched by `.desktop` file path rather than finding ID, since the ID changes
+//! whenever the file content changes, while "the same launcher" is what users compare across
+//! upgrades.
+
+// -- std imports
+use std::{collections::BTreeMap, path::Path};
+
+// -- crate imports
+use anyhow::{Context, Result};
+use serde::Serialize;
+use tokio::fs;
+
+// -- module imports
+use crate::report::{Finding, Report, Status};
+
+/// Differences in broken entries between two reports.
+#[derive(Debug, Serialize)]
+pub struct ReportDiff<'a> {
+    /// Broken in the new report, but not in the old one.
+    pub newly_broken: Vec<&'a Finding>,
+
+    /// Broken in the old report, but no longer in the new one (taken from the old report).
+    pub fixed: Vec<&'a Finding>,
+
+    /// Broken in both reports (taken from the new report).
+    pub unchanged: Vec<&'a Finding>,
+}
+
+/// Read a JSON report written by `--output json`.
+///
+/// # Errors
+/// - [`anyhow::Error`] if the file cannot be read or is not a valid report.
+pub async fn read_report(path: &Path) -> Result<Report> {
+    let content = fs::read(path)
+        .await
+        .with_context(|| format!("Failed to read report {}", path.display()))?;
+    serde_json::from_slice(&content)
+        .with_context(|| format!("{} is not a valid desktop-scout report", path.display()))
+}
+
+/// Compare the broken entries of `old` and `new`.
+///
+/// All lists are sorted by path.
+pub fn diff_reports<'a>(old: &'a Report, new: &'a Report) -> ReportDiff<'a> {
+    let broken = |r: &'a Report| {
+        r.findings
+            .iter()
+            .filter(|f| f.is_broken())
+            .map(|f| (f.desktop_file.as_path(), f))
+            .collect::<BTreeMap<_, _>>()
+    };
+    let old_broken = broken(old);
+    let new_broken = broken(new);
+
+    ReportDiff {
+        newly_broken: new_broken
+            .iter()
+            .filter(|(p, _)| !old_broken.contains_key(*p))
+            .map(|(_, f)| *f)
+            .collect(),
+        fixed: old_broken
+            .iter()
+            .filter(|(p, _)| !new_broken.contains_key(*p))
+            .map(|(_, f)| *f)
+            .collect(),
+        unchanged: new_broken
+            .iter()
+            .filter(|(p, _)| old_broken.contains_key(*p))
+            .map(|(_, f)| *f)
+            .collect(),
+    }
+}
+
+/// Print a human-readable diff.
+pub fn print_text(diff: &ReportDiff<'_>) {
+    print_section("Newly broken", '+', &diff.newly_broken);
+    print_section("Fixed", '-', &diff.fixed);
+    print_section("Unchanged (still broken)", ' ', &diff.unchanged);
+}
+
+/// Print one section of [`print_text`].
+fn print_section(title: &str, marker: char, findings: &[&Finding]) {
+    println!("{title} ({}):", findings.len());
+    for f in findings {
+        print!("{marker} {}", f.desktop_file.display());
+        if let Status::Broken { code, .. } = &f.status {
+            print!(" [{code}]");
+        }
+        println!();
+    }
+    println!();
+}
@@ -0,0 +1,67 @@
+//! Long-form documentation for reason codes (`explain` subcommand).
+//!
+//! Each [`ReasonCode`] maps to a short summary, typical causes and suggested remediation steps.
+//! The text lives in the Fluent resources (`explain-<code>`, `-causes` and `-remediation`, one
+//! item per line) rather than in `report`, so the report types stay lean and the text is
+//! translated like every other message.
+
+use crate::{i18n::tr, report::ReasonCode};
+
+/// Human-oriented description of a reason code.
+pub struct Explanation {
+    /// One-line summary of what the code means.
+    pub summary: String,
+
+    /// Typical situations that lead to this code.
+    pub causes: Vec<String>,
+
+    /// Suggested steps to resolve the problem.
+    pub remediation: Vec<String>,
+}
+
+/// Look up the explanation for `code` in the user's locale.
+pub fn explain(code: ReasonCode) -> Explanation {
+    let id = message_id(code);
+    let lines = |suffix: &str| {
+        tr(&format!("{id}-{suffix}"), &[])
+            .lines()
+            .map(str::to_string)
+            .collect()
+    };
+    Explanation {
+        summary: tr(&id, &[]),
+        causes: lines("causes"),
+        remediation: lines("remediation"),
+    }
+}
+
+/// The Fluent message ID of the summary of `code` (e.g. `explain-exec-not-found`).
+pub(crate) fn message_id(code: ReasonCode) -> String {
+    format!(
+        "explain-{}",
+        code.as_str().to_ascii_lowercase().replace('_', "-")
+    )
+}
+
+/// Print the explanation for `code`, or a list of all codes when `code` is `None`.
+pub fn print(code: Option<ReasonCode>) {
+    let Some(code) = code else {
+        for c in ReasonCode::ALL {
+            println!("{:<34} {}", c.as_str(), explain(*c).summary);
+        }
+        return;
+    };
+
+    let e = explain(code);
+    println!("{code}\n\n{}\n", e.summary);
+
+    println!("{}", tr("explain-causes-header", &[]));
+    for cause in e.causes {
+        println!("  - {cause}");
+    }
+
+    println!("\n{}", tr("explain-remediation-header", &[]));
+    for step in e.remediation {
+        println!("  - {step}");
+    }
+}
@@ -0,0 +1,147 @@
+//! Snapshot bundles of scanned entries (`export --bundle`).
+//!
+//! When a user reports odd results, the exact files are needed to reproduce them. A bundle is a
+//! tar archive holding every scanned `.desktop` file below `files/` (with its absolute path
+//! preserved), the full JSON report and the environment variables that influence a scan. The
+//! archive is written by `tar`, which picks the compression from the file name (`.tar.zst`,
+//! `.tar.gz`, `.tar.xz`, or none for `.tar`).
+
+// -- std imports
+use std::{
+    collections::BTreeMap,
+    env,
+    ffi::{CString, OsString},
+    io,
+    os::unix::ffi::{OsStrExt, OsStringExt},
+    path::{Path, PathBuf},
+    process::Stdio,
+};
+
+// -- crate imports
+use anyhow::{Context, Result, bail};
+use tokio::{fs, process::Command};
+use tracing::warn;
+
+// -- module imports
+use crate::report::Report;
+
+/// Top-level directory inside the archive.
+const BUNDLE_ROOT: &str = "desktop-scout-export";
+
+/// Environment variables recorded in `environment.json`.
+const RECORDED_ENV_VARS: &[&str] = &[
+    "PATH",
+    "HOME",
+    "XDG_DATA_HOME",
+    "XDG_DATA_DIRS",
+    "XDG_CONFIG_HOME",
+    "XDG_RUNTIME_DIR",
+    "XDG_CURRENT_DESKTOP",
+    "XDG_SESSION_TYPE",
+    "WAYLAND_DISPLAY",
+    "DISPLAY",
+    "LANG",
+];
+
+/// Write `report` and every `.desktop` file it covers to the archive `bundle`. Returns the
+/// number of files included.
+///
+/// Files that can no longer be read are left out with a warning; their findings stay in the
+/// report.
+///
+/// # Errors
+/// - [`anyhow::Error`] if the staging directory cannot be created or written or `tar` fails.
+pub async fn write_bundle(report: &Report, bundle: &Path) -> Result<usize> {
+    let staging = create_staging_dir()?;
+    let result = stage_and_pack(report, bundle, &staging).await;
+    let _ = fs::remove_dir_all(&staging).await;
+    result
+}
+
+/// Create a private staging directory (mode `0700`) below the temporary directory with
+/// mkdtemp(3): its name is random and creation fails instead of reusing a directory or symlink
+/// another user planted in the shared `/tmp`.
+fn create_staging_dir() -> Result<PathBuf> {
+    let template = env::temp_dir().join(format!("{BUNDLE_ROOT}.XXXXXX"));
+    let mut template = CString::new(template.as_os_str().as_bytes())
+        .context("Temporary directory path contains a NUL byte")?
+        .into_bytes_with_nul();
+    // SAFETY: `template` is a writable, NUL-terminated path ending in `XXXXXX`, which mkdtemp
+    // replaces in place.
+    if unsafe { libc::mkdtemp(template.as_mut_ptr().cast()) }.is_null() {
+        return Err(io::Error::last_os_error()).context("Failed to create a staging directory");
+    }
+    template.pop();
+    Ok(PathBuf::from(OsString::from_vec(template)))
+}
+
+/// Copy the bundle contents below `staging` and archive them to `bundle`.
+async fn stage_and_pack(report: &Report, bundle: &Path, staging: &Path) -> Result<usize> {
+    let root = staging.join(BUNDLE_ROOT);
+    let files = root.join("files");
+    fs::create_dir_all(&files)
+        .await
+        .with_context(|| format!("Failed to create {}", files.display()))?;
+
+    let mut copied = 0;
+    for f in &report.findings {
+        let target = files.join(f.desktop_file.strip_prefix("/").unwrap_or(&f.desktop_file));
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent)
+                .await
+                .with_context(|| format!("Failed to create {}", parent.display()))?;
+        }
+        match fs::copy(&f.desktop_file, &target).await {
+            Ok(_) => copied += 1,
+            Err(e) => warn!(
+                "Leaving {} out of the bundle: {e}",
+                f.desktop_file.display()
+            ),
+        }
+    }
+
+    write_json(&root.join("report.json"), report).await?;
+    let environment = RECORDED_ENV_VARS
+        .iter()
+        .filter_map(|k| env::var(k).ok().map(|v| (*k, v)))
+        .chain([(
+            "DESKTOP_SCOUT_VERSION",
+            env!("CARGO_PKG_VERSION").to_string(),
+        )])
+        .collect::<BTreeMap<_, _>>();
+    write_json(&root.join("environment.json"), &environment).await?;
+
+    pack(staging, bundle).await?;
+    Ok(copied)
+}
+
+/// Write `value` as pretty-printed JSON to `path`.
+async fn write_json<T: serde::Serialize>(path: &Path, value: &T) -> Result<()> {
+    fs::write(path, serde_json::to_string_pretty(value)? + "\n")
+        .await
+        .with_context(|| format!("Failed to write {}", path.display()))
+}
+
+/// Archive [`BUNDLE_ROOT`] below `staging` to `bundle`, compressed according to its suffix.
+async fn pack(staging: &Path, bundle: &Path) -> Result<()> {
+    let output = Command::new("tar")
+        .arg("--create")
+        .arg("--auto-compress")
+        .arg("--file")
+        .arg(bundle)
+        .arg("--directory")
+        .arg(staging)
+        .arg(BUNDLE_ROOT)
+        .stdin(Stdio::null())
+        .output()
+        .await
+        .context("Failed to run tar")?;
+    if !output.status.success() {
+        bail!(
+            "tar failed to write {}: {}",
+            bundle.display(),
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(())
+}
@@ -0,0 +1,1350 @@
+//! Remediation of broken entries (`fix` subcommand).
+//!
+//! Broken `.desktop` files are copied into a timestamped backup directory below
+//! `$XDG_DATA_HOME/desktop-scout/backups/` and then removed (`--mode delete`) or rewritten with
+//! `Hidden=true` (`--mode hide`), rewritten with `--remap` prefix rules or to a relocated binary
+//! found during the scan (`--mode repair`), or moved into the quarantine (see
+//! [`crate::quarantine`]) with `--quarantine`. `--trash` moves entries to the desktop's wastebasket
+//! (see [`crate::trash`]) instead of deleting them. Only files inside the user's home directory are
+//! touched unless `--system` is given; `--mode hide` instead shadows system entries with a user
+//! override. `--emit-script` writes the same changes as a shell script instead of performing them,
+//! `--emit-patch` the edits of hide and repair fixes as a unified diff (see [`crate::patch`]).
+//! `--broken-duplicates` restricts all of this to broken copies of desktop IDs that also have a
+//! working copy (the one earliest in XDG lookup order is reported). `--field-codes` instead
+//! rewrites the `Exec=` lines of all entries that use deprecated or duplicate field codes.
+//!
+//! Changes are listed and confirmed interactively unless `--yes` is given. Entries in
+//! directories the user cannot write to, and entries that may only be broken transiently (timed
+//! out checks, executables on removable media), are skipped unless `--force` is given. A file
+//! reachable through several scanned paths (same device and inode) is only changed once.
+
+// -- std imports
+use std::{
+    collections::{BTreeMap, HashMap},
+    env,
+    ffi::CString,
+    io::{self, BufRead, IsTerminal, Write},
+    os::unix::{
+        ffi::OsStrExt,
+        fs::{MetadataExt, PermissionsExt},
+    },
+    path::{Path, PathBuf},
+    ptr,
+};
+
+// -- crate imports
+use anyhow::{Context, Result, bail};
+use chrono::Local;
+use serde::{Deserialize, Serialize};
+use tokio::fs;
+use tracing::{info, warn};
+use xdg::BaseDirectories;
+
+// -- module imports
+use crate::{
+    args::FixMode,
+    companions::CompanionIndex,
+    desktop::{self, DESKTOP_ENTRY_GROUP, DesktopEntry},
+    i18n::tr,
+    linux_fs, patch,
+    quarantine::Quarantine,
+    remap::{self, RemapRule},
+    report::{FileIdentity, Finding, Report, Status},
+    trash,
+};
+
+/// Options for [`run`].
+#[derive(Debug)]
+pub struct FixOptions {
+    /// Also operate on files outside the user's home directory.
+    pub system: bool,
+
+    /// Whether to delete or hide broken entries.
+    pub mode: FixMode,
+
+    /// Move broken entries into the quarantine instead of deleting them.
+    pub quarantine: bool,
+
+    /// Move broken entries to the freedesktop.org Trash instead of deleting them.
+    pub trash: bool,
+
+    /// Also remove (or quarantine) companion artifacts of user-local entries.
+    pub with_companions: bool,
+
+    /// Prefix rewrites applied by [`FixMode::Repair`] before falling back to relocated binaries.
+    pub remap: Vec<RemapRule>,
+
+    /// Only fix broken entries whose desktop ID also has a working copy in another directory.
+    pub broken_duplicates: bool,
+
+    /// Rewrite `Exec=` lines with deprecated or duplicate field codes instead of fixing broken
+    /// entries (see [`desktop::strip_field_codes`]).
+    pub field_codes: bool,
+
+    /// Also change entries in directories that are not user-writable and entries that may only
+    /// be broken transiently.
+    pub force: bool,
+}
+
+/// Mount roots of removable media; executables below them are missing whenever the medium is not
+/// mounted.
+const REMOVABLE_MEDIA_ROOTS: &[&str] = &["/media/", "/run/media/", "/mnt/"];
+
+/// Outcome of fixing all broken entries of a report.
+#[derive(Debug, Serialize)]
+pub struct FixReport {
+    /// Directory backups were written to (`None` if nothing was removed).
+    pub backup_dir: Option<PathBuf>,
+
+    /// One outcome per broken entry.
+    pub outcomes: Vec<FixOutcome>,
+}
+
+impl FixReport {
+    /// Whether any fix failed, even partially.
+    pub fn has_failures(&self) -> bool {
+        self.outcomes.iter().any(|o| {
+            matches!(
+                o.result,
+                FixResult::Failed { .. } | FixResult::PartiallyDeleted { .. }
+            )
+        })
+    }
+}
+
+/// Outcome for a single broken entry.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FixOutcome {
+    /// The broken `.desktop` file.
+    pub desktop_file: PathBuf,
+
+    /// What happened to it.
+    #[serde(flatten)]
+    pub result: FixResult,
+
+    /// Companion artifacts (icons, MIME packages) only this entry used.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub companions: Vec<PathBuf>,
+
+    /// Whether `companions` were removed along with the entry (`--with-companions`).
+    pub companions_removed: bool,
+
+    /// Working entry with the same desktop ID in another directory, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub working_copy: Option<PathBuf>,
+}
+
+/// What happened to a broken entry.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "result", rename_all = "snake_case")]
+pub enum FixResult {
+    /// Backed up to `backup` and removed.
+    Deleted { backup: PathBuf },
+
+    /// Backed up to `backup` and removed, but removing its companions stopped with `error`;
+    /// `removed_companions` were backed up and removed before that.
+    PartiallyDeleted {
+        backup: PathBuf,
+        removed_companions: Vec<PathBuf>,
+        error: String,
+    },
+
+    /// Rewritten with `Hidden=true` after backing it up to `backup`.
+    Hidden { backup: PathBuf },
+
+    /// Shadowed by a user override with `Hidden=true` at `override_file`.
+    Overridden { override_file: PathBuf },
+
+    /// Moved into the quarantine; restorable with `restore <id>`.
+    Quarantined { id: String },
+
+    /// Moved to the trash at `trash_file`; companions moved along are at `companion_trash_files`
+    /// (in the order of [`FixOutcome::companions`]).
+    Trashed {
+        trash_file: PathBuf,
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        companion_trash_files: Vec<PathBuf>,
+    },
+
+    /// Keys rewritten to the values in `changes` (remapped prefixes, a relocated binary or a
+    /// cleaned-up `Exec=` line) after backing it up to `backup`.
+    Repaired {
+        backup: PathBuf,
+        changes: BTreeMap<String, String>,
+    },
+
+    /// Left untouched (e.g. a system file without `--system`).
+    Skipped { reason: String },
+
+    /// Backing up or removing failed.
+    Failed { error: String },
+}
+
+/// What [`run`] does with a broken entry.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Action {
+    /// Back up and remove the file.
+    Delete,
+    /// Back up the file and set `Hidden=true` in it.
+    HideInPlace,
+    /// Shadow the file with a `Hidden=true` user override.
+    Override,
+    /// Move the file into the quarantine.
+    Quarantine,
+    /// Move the file to the trash.
+    Trash,
+    /// Back up the file and set the given keys.
+    Repair(BTreeMap<String, String>),
+    /// Leave the file untouched.
+    Skip(String),
+}
+
+impl Action {
+    /// Short verb shown when confirming the action (the selector of the `fix-action` message).
+    fn verb(&self) -> &'static str {
+        match self {
+            Action::Delete => "delete",
+            Action::HideInPlace => "hide",
+            Action::Override => "override",
+            Action::Quarantine => "quarantine",
+            Action::Trash => "trash",
+            Action::Repair(_) => "repair",
+            Action::Skip(_) => "skip",
+        }
+    }
+}
+
+/// A broken entry and what to do with it.
+#[derive(Debug)]
+struct PlannedFix<'a> {
+    finding: &'a Finding,
+    action: Action,
+    /// Companion artifacts only this entry used.
+    companions: Vec<PathBuf>,
+    /// Working entry with the same desktop ID, if any.
+    working_copy: Option<PathBuf>,
+}
+
+/// Locations shared by [`run`] and [`write_script`].
+#[derive(Debug)]
+struct FixPaths {
+    data_home: PathBuf,
+    backup_dir: PathBuf,
+}
+
+impl FixPaths {
+    fn new() -> Result<Self> {
+        let data_home = BaseDirectories::new()
+            .get_data_home()
+            .context("Could not determine XDG data directory")?;
+        let backup_dir = data_home
+            .join("desktop-scout/backups")
+            .join(Local::now().format("%Y%m%dT%H%M%S").to_string());
+        Ok(FixPaths {
+            data_home,
+            backup_dir,
+        })
+    }
+
+    /// Directory of user overrides (`$XDG_DATA_HOME/applications`).
+    fn user_apps(&self) -> PathBuf {
+        self.data_home.join("applications")
+    }
+}
+
+/// Decide the [`Action`] for every broken entry of `report` and find its companions.
+///
+/// With `--field-codes`, every entry whose `Exec=` line needs cleaning is planned as a repair
+/// instead, broken or not.
+async fn plan<'a>(report: &'a Report, opts: &FixOptions, paths: &FixPaths) -> Vec<PlannedFix<'a>> {
+    let home = env::var_os("HOME").map(PathBuf::from);
+    let mut seen = HashMap::new();
+
+    if opts.field_codes {
+        let mut planned = Vec::new();
+        for f in &report.findings {
+            let Some(exec) = f.exec.as_deref().and_then(desktop::strip_field_codes) else {
+                continue;
+            };
+            let writable = opts.system
+                || home
+                    .as_deref()
+                    .is_some_and(|h| f.desktop_file.starts_with(h));
+            let action = if !writable {
+                Action::Skip("outside the home directory, use --system".into())
+            } else if let Some(reason) = guard(f, opts, true).await {
+                Action::Skip(reason)
+            } else {
+                Action::Repair(BTreeMap::from([("Exec".to_string(), exec)]))
+            };
+            planned.push(PlannedFix {
+                finding: f,
+                action: dedup_identity(f, action, &mut seen),
+                companions: Vec::new(),
+                working_copy: None,
+            });
+        }
+        return planned;
+    }
+
+    let hide_system = opts.mode == FixMode::Hide && !opts.quarantine;
+    let index = CompanionIndex::build(report).await;
+
+    // The working copy launchers actually resolve: the one earliest in XDG lookup order.
+    let xdg = BaseDirectories::new();
+    let precedence = |p: &Path| linux_fs::data_dir_precedence(&xdg, p);
+    let mut working = HashMap::<_, &PathBuf>::new();
+    for f in &report.findings {
+        if matches!(f.status, Status::Ok { .. }) {
+            working
+                .entry(desktop::desktop_id(&f.desktop_file))
+                .and_modify(|p| {
+                    if precedence(&f.desktop_file) < precedence(p) {
+                        *p = &f.desktop_file;
+                    }
+                })
+                .or_insert(&f.desktop_file);
+        }
+    }
+
+    let mut planned = Vec::new();
+    for f in report.findings.iter().filter(|f| f.is_broken()) {
+        let path = &f.desktop_file;
+        let working_copy = working
+            .get(&desktop::desktop_id(path))
+            .map(|p| p.to_path_buf());
+        if opts.broken_duplicates && working_copy.is_none() {
+            continue;
+        }
+
+        let writable = opts.system || home.as_deref().is_some_and(|h| path.starts_with(h));
+        let action = match (opts.quarantine, opts.mode) {
+            _ if !writable && !hide_system => {
+                Action::Skip("outside the home directory, use --system".into())
+            }
+            (true, _) => Action::Quarantine,
+            (false, FixMode::Delete) if opts.trash => Action::Trash,
+            (false, FixMode::Delete) => Action::Delete,
+            (false, FixMode::Hide) if writable => Action::HideInPlace,
+            (false, FixMode::Hide) => Action::Override,
+            (false, FixMode::Repair) => {
+                let changes = repair_changes(f, &opts.remap).await;
+                if changes.is_empty() {
+                    Action::Skip("no remap rule or relocated binary applies".into())
+                } else {
+                    Action::Repair(changes)
+                }
+            }
+        };
+
+        let action = match &action {
+            Action::Skip(_) => action,
+            // Overrides only write to the user's own data directory.
+            a => match guard(f, opts, *a != Action::Override).await {
+                Some(reason) => Action::Skip(reason),
+                None => dedup_identity(f, action, &mut seen),
+            },
+        };
+
+        // Hidden entries still exist, so only removal cleans up companions.
+        let companions = if path.starts_with(&paths.data_home)
+            && matches!(action, Action::Delete | Action::Quarantine | Action::Trash)
+        {
+            index.companions_of(f, &paths.data_home).await
+        } else {
+            Vec::new()
+        };
+
+        planned.push(PlannedFix {
+            finding: f,
+            action,
+            companions,
+            working_copy,
+        });
+    }
+    planned
+}
+
+/// Skip `action` if the file of `finding` was already planned for through another path (bind
+/// mounts, overlayfs); `seen` maps file identities to the path they are fixed through.
+fn dedup_identity<'a>(
+    finding: &'a Finding,
+    action: Action,
+    seen: &mut HashMap<FileIdentity, &'a Path>,
+) -> Action {
+    let Some(identity) = finding.identity else {
+        return action;
+    };
+    if matches!(action, Action::Skip(_)) {
+        return action;
+    }
+    match seen.get(&identity) {
+        Some(first) => Action::Skip(format!("same file as {}", first.display())),
+        None => {
+            seen.insert(identity, &finding.desktop_file);
+            action
+        }
+    }
+}
+
+/// Why `finding` must not be changed without `--force`, if anything speaks against it.
+/// `check_dir` also requires the directory of the file to be user-writable.
+async fn guard(finding: &Finding, opts: &FixOptions, check_dir: bool) -> Option<String> {
+    if opts.force {
+        return None;
+    }
+    if let Some(reason) = transient_reason(finding) {
+        return Some(format!("{reason}, use --force"));
+    }
+    let dir = finding.desktop_file.parent().filter(|_| check_dir)?;
+    (!user_writable(dir).await).then(|| {
+        format!(
+            "{} is not writable by the current user, use --force",
+            dir.display()
+        )
+    })
+}
+
+/// Why `finding` may only be broken transiently (e.g. an unmounted medium), if it may.
+fn transient_reason(finding: &Finding) -> Option<String> {
+    if !matches!(finding.status, Status::Broken { .. }) {
+        return None;
+    }
+
+    let tokens = finding
+        .exec
+        .as_deref()
+        .and_then(shlex::split)
+        .unwrap_or_default();
+    tokens
+        .iter()
+        .map(String::as_str)
+        .chain(finding.try_exec.as_deref())
+        .find(|t| REMOVABLE_MEDIA_ROOTS.iter().any(|root| t.starts_with(root)))
+        .map(|t| format!("{t} is on removable media that may not be mounted"))
+}
+
+/// Whether the current user may create and remove files in `dir` (write and search permission,
+/// as the kernel checks it for the effective user and all of its groups).
+///
+/// Root's ability to bypass permissions is deliberately ignored: for root the permission bits
+/// decide, so a root-owned `0755` directory counts as writable for root, but a package-managed
+/// `0555` one does not.
+async fn user_writable(dir: &Path) -> bool {
+    let Ok(md) = fs::metadata(dir).await else {
+        return false;
+    };
+    // SAFETY: geteuid has no preconditions and cannot fail.
+    let euid = unsafe { libc::geteuid() };
+    if euid != 0 {
+        let Ok(path) = CString::new(dir.as_os_str().as_bytes()) else {
+            return false;
+        };
+        // SAFETY: `path` is a valid NUL-terminated string that outlives the call.
+        return unsafe {
+            libc::faccessat(
+                libc::AT_FDCWD,
+                path.as_ptr(),
+                libc::W_OK | libc::X_OK,
+                libc::AT_EACCESS,
+            )
+        } == 0;
+    }
+
+    let shift = if md.uid() == euid {
+        6
+    } else if in_groups(md.gid()) {
+        3
+    } else {
+        0
+    };
+    (md.mode() >> shift) & 0o3 == 0o3
+}
+
+/// Whether `gid` is the effective or a supplementary group of the current process.
+fn in_groups(gid: u32) -> bool {
+    // SAFETY: getegid has no preconditions and cannot fail.
+    if unsafe { libc::getegid() } == gid {
+        return true;
+    }
+    // SAFETY: with a size of 0, getgroups only returns the number of groups.
+    let count = unsafe { libc::getgroups(0, ptr::null_mut()) };
+    let mut groups = vec![0; count.max(0) as usize];
+    // SAFETY: `groups` has room for `count` entries.
+    let count = unsafe { libc::getgroups(count, groups.as_mut_ptr()) };
+    groups.truncate(count.max(0) as usize);
+    groups.contains(&gid)
+}
+
+/// List the changes [`run`] would make, one `verb path` line per changed entry.
+pub async fn pending_changes(report: &Report, opts: &FixOptions) -> Result<Vec<String>> {
+    let paths = FixPaths::new()?;
+    Ok(plan(report, opts, &paths)
+        .await
+        .iter()
+        .filter(|p| !matches!(p.action, Action::Skip(_)))
+        .map(|p| {
+            let verb = tr("fix-action", &[("action", p.action.verb().into())]);
+            format!("{verb:<10} {}", p.finding.desktop_file.display())
+        })
+        .collect())
+}
+
+/// Show `pending` changes on stderr and ask whether to apply them.
+///
+/// # Errors
+/// - [`anyhow::Error`] if stdin is not a terminal (use `--yes` non-interactively) or cannot be
+///   read.
+pub fn confirm(pending: &[String]) -> Result<bool> {
+    if !io::stdin().is_terminal() {
+        bail!(
+            "Refusing to fix without confirmation ({} changes pending); pass --yes to fix \
+             non-interactively",
+            pending.len()
+        );
+    }
+
+    let mut stderr = io::stderr().lock();
+    for line in pending {
+        writeln!(stderr, "  {line}")?;
+    }
+    write!(stderr, "{} ", tr("fix-confirm", &[]))?;
+    stderr.flush()?;
+
+    let mut answer = String::new();
+    io::stdin().lock().read_line(&mut answer)?;
+    Ok(matches!(answer.trim(), "y" | "Y" | "yes"))
+}
+
+/// Keys to rewrite when repairing `finding`: values matched by a `remap` rule, otherwise the
+/// relocated binary suggested for `Exec=`.
+async fn repair_changes(finding: &Finding, remap: &[RemapRule]) -> BTreeMap<String, String> {
+    let mut changes = BTreeMap::new();
+    if !remap.is_empty()
+        && let Ok(content) = fs::read_to_string(&finding.desktop_file).await
+    {
+        let kv = desktop::parse_desktop_entry_section(&content);
+        for key in remap::REMAPPED_KEYS {
+            if let Some(new) = kv.get(*key).and_then(|v| remap::apply(v, remap)) {
+                changes.insert(key.to_string(), new);
+            }
+        }
+    }
+
+    if changes.is_empty()
+        && let Some(exec) = &finding.suggested_exec
+    {
+        changes.insert("Exec".into(), exec.clone());
+    }
+    changes
+}
+
+/// Back up and remove, hide or quarantine every broken entry of `report`.
+///
+/// # Errors
+/// - [`anyhow::Error`] if the backup directory cannot be determined or the quarantine cannot be
+///   opened. Per-file failures are recorded in the returned [`FixReport`] instead.
+pub async fn run(report: &Report, opts: &FixOptions) -> Result<FixReport> {
+    let paths = FixPaths::new()?;
+    let backup_dir = &paths.backup_dir;
+
+    let mut quarantine = if opts.quarantine {
+        Some(Quarantine::open().await?)
+    } else {
+        None
+    };
+
+    let mut outcomes = Vec::new();
+    for p in plan(report, opts, &paths).await {
+        let (f, path) = (p.finding, &p.finding.desktop_file);
+        let clean = if opts.with_companions {
+            p.companions.as_slice()
+        } else {
+            &[]
+        };
+
+        let result = match (&p.action, &mut quarantine) {
+            (Action::Skip(reason), _) => Ok(FixResult::Skipped {
+                reason: reason.clone(),
+            }),
+            (Action::Quarantine, Some(q)) => q
+                .add(f, clean)
+                .await
+                .map(|id| FixResult::Quarantined { id }),
+            (Action::Quarantine, None) => unreachable!("quarantine is opened with --quarantine"),
+            (Action::Trash, _) => trash_with_companions(path, clean).await.inspect(|_| {
+                info!(file = %path.display(), "Moved broken entry to the trash");
+            }),
+            (Action::Delete, _) => delete_with_companions(path, clean, backup_dir)
+                .await
+                .inspect(|r| match r {
+                    FixResult::PartiallyDeleted { error, .. } => warn!(
+                        file = %path.display(),
+                        error,
+                        "Removed broken entry, but not all of its companions"
+                    ),
+                    _ => info!(file = %path.display(), "Removed broken entry"),
+                }),
+            (Action::HideInPlace, _) => set_keys_in_place(path, &[("Hidden", "true")], backup_dir)
+                .await
+                .map(|backup| {
+                    info!(file = %path.display(), "Hid broken entry");
+                    FixResult::Hidden { backup }
+                }),
+            (Action::Repair(changes), _) => {
+                let keys = changes
+                    .iter()
+                    .map(|(k, v)| (k.as_str(), v.as_str()))
+                    .collect::<Vec<_>>();
+                set_keys_in_place(path, &keys, backup_dir)
+                    .await
+                    .map(|backup| {
+                        info!(file = %path.display(), ?changes, "Repaired broken entry");
+                        FixResult::Repaired {
+                            backup,
+                            changes: changes.clone(),
+                        }
+                    })
+            }
+            (Action::Override, _) => {
+                hide_with_override(f, &paths.user_apps())
+                    .await
+                    .map(|override_file| {
+                        info!(
+                            file = %path.display(),
+                            override_file = %override_file.display(),
+                            "Hid broken entry with a user override"
+                        );
+                        FixResult::Overridden { override_file }
+                    })
+            }
+        };
+        let (result, companions_removed) = match result {
+            Ok(r @ (FixResult::Skipped { .. } | FixResult::PartiallyDeleted { .. })) => (r, false),
+            Ok(r) => (r, !clean.is_empty()),
+            Err(e) => {
+                warn!(file = %path.display(), error = %e, "Failed to fix broken entry");
+                let error = FixResult::Failed {
+                    error: format!("{e:#}"),
+                };
+                (error, false)
+            }
+        };
+        outcomes.push(FixOutcome {
+            desktop_file: path.clone(),
+            result,
+            companions: p.companions,
+            companions_removed,
+            working_copy: p.working_copy,
+        });
+    }
+
+    let any_backup = outcomes.iter().any(|o| {
+        matches!(
+            o.result,
+            FixResult::Deleted { .. }
+                | FixResult::PartiallyDeleted { .. }
+                | FixResult::Hidden { .. }
+                | FixResult::Repaired { .. }
+        )
+    });
+    Ok(FixReport {
+        backup_dir: any_backup.then(|| paths.backup_dir.clone()),
+        outcomes,
+    })
+}
+
+/// Write a reviewable shell script to `out` with the commands [`run`] would perform, instead of
+/// performing them. Returns the number of entries the script changes.
+///
+/// Removal is expressed as a move into the backup directory, hiding as a `sed` edit (after a
+/// backup copy) or a heredoc writing the user override. Skipped entries and companions left in
+/// place are listed as comments.
+///
+/// # Errors
+/// - [`anyhow::Error`] if the backup directory cannot be determined or `out` cannot be written.
+pub async fn write_script(report: &Report, opts: &FixOptions, out: &Path) -> Result<usize> {
+    let paths = FixPaths::new()?;
+    let planned = plan(report, opts, &paths).await;
+
+    let mut script = format!(
+        "#!/bin/sh\n\
+         # Fixes for broken desktop entries, generated by desktop-scout {} on {}.\n\
+         # Review before running. Changed files are backed up below BACKUP_DIR.\n\
+         set -eu\n\n\
+         BACKUP_DIR={}\n",
+        env!("CARGO_PKG_VERSION"),
+        Local::now().format("%Y-%m-%d %H:%M:%S"),
+        sh_quote(&paths.backup_dir)
+    );
+
+    let mut changed = 0;
+    for p in &planned {
+        let path = &p.finding.desktop_file;
+        script.push_str(&format!("\n# {}", path.display()));
+        if let Status::Broken { code, reason } = &p.finding.status {
+            script.push_str(&format!(" [{code}] {reason}"));
+        }
+        script.push('\n');
+        if let Some(w) = &p.working_copy {
+            script.push_str(&format!("# working copy: {}\n", w.display()));
+        }
+
+        match &p.action {
+            Action::Skip(reason) => {
+                script.push_str(&format!("# skipped ({reason})\n"));
+                continue;
+            }
+            Action::Quarantine => unreachable!("--emit-script conflicts with --quarantine"),
+            Action::Trash => unreachable!("--emit-script conflicts with --trash"),
+            Action::Delete => {
+                script.push_str(&script_move_to_backup(path));
+                for c in p.companions.iter().filter(|_| opts.with_companions) {
+                    script.push_str(&script_move_to_backup(c));
+                }
+            }
+            Action::HideInPlace => {
+                script.push_str(&script_backup_copy(path));
+                script.push_str(&script_set_key(path, "Hidden", "true"));
+            }
+            Action::Repair(changes) => {
+                script.push_str(&script_backup_copy(path));
+                for (key, value) in changes {
+                    script.push_str(&script_set_key(path, key, value));
+                }
+            }
+            Action::Override => {
+                let user_apps = paths.user_apps();
+                let override_file = user_apps.join(desktop::desktop_id(path));
+                if fs::symlink_metadata(&override_file).await.is_ok() {
+                    script.push_str(&format!(
+                        "# skipped ({} already exists)\n",
+                        override_file.display()
+                    ));
+                    continue;
+                }
+                script.push_str(&format!(
+                    "mkdir -p {}\ncat > {} <<'EOF'\n{}EOF\n",
+                    sh_quote(&user_apps),
+                    sh_quote(&override_file),
+                    override_content(p.finding)
+                ));
+            }
+        }
+        changed += 1;
+
+        if !opts.with_companions {
+            for c in &p.companions {
+                script.push_str(&format!(
+                    "# companion left in place (use --with-companions): {}\n",
+                    c.display()
+                ));
+            }
+        }
+    }
+
+    fs::write(out, script)
+        .await
+        .with_context(|| format!("Failed to write {}", out.display()))?;
+    fs::set_permissions(out, std::fs::Permissions::from_mode(0o755))
+        .await
+        .with_context(|| format!("Failed to make {} executable", out.display()))?;
+    Ok(changed)
+}
+
+/// Write the edits [`run`] would make as a unified diff to `out` (applicable with
+/// `cd / && patch -p0`), instead of making them. Returns the number of entries the patch
+/// changes.
+///
+/// Only edit-style fixes have a diff; entries that would be removed or skipped are left out.
+///
+/// # Errors
+/// - [`anyhow::Error`] if the XDG data directory cannot be determined or `out` cannot be
+///   written.
+pub async fn write_patch(report: &Report, opts: &FixOptions, out: &Path) -> Result<usize> {
+    let paths = FixPaths::new()?;
+    let mut diff = String::new();
+    let mut changed = 0;
+    for p in plan(report, opts, &paths).await {
+        let path = &p.finding.desktop_file;
+        let keys = match &p.action {
+            Action::HideInPlace => vec![("Hidden".to_string(), "true".to_string())],
+            Action::Repair(changes) => changes.clone().into_iter().collect(),
+            Action::Override => {
+                let override_file = paths.user_apps().join(desktop::desktop_id(path));
+                if fs::symlink_metadata(&override_file).await.is_err() {
+                    diff.push_str(&patch::unified_diff(
+                        &override_file,
+                        None,
+                        &override_content(p.finding),
+                    ));
+                    changed += 1;
+                }
+                continue;
+            }
+            Action::Delete | Action::Quarantine | Action::Trash | Action::Skip(_) => continue,
+        };
+
+        let content = match fs::read_to_string(path).await {
+            Ok(c) => c,
+            Err(e) => {
+                warn!(file = %path.display(), error = %e, "Failed to read file for the patch");
+                continue;
+            }
+        };
+        let mut entry = DesktopEntry::parse(&content);
+        for (key, value) in &keys {
+            entry.set(DESKTOP_ENTRY_GROUP, key, value);
+        }
+        let file_diff = patch::unified_diff(path, Some(&content), &entry.to_string());
+        if !file_diff.is_empty() {
+            diff.push_str(&file_diff);
+            changed += 1;
+        }
+    }
+
+    fs::write(out, diff)
+        .await
+        .with_context(|| format!("Failed to write {}", out.display()))?;
+    Ok(changed)
+}
+
+/// Path of the backup of `path` relative to the backup directory (its absolute path).
+fn backup_rel(path: &Path) -> &Path {
+    path.strip_prefix("/").unwrap_or(path)
+}
+
+/// Script lines creating the backup directory for `path`.
+fn script_backup_dir(path: &Path) -> String {
+    match backup_rel(path).parent() {
+        Some(parent) => format!("mkdir -p \"$BACKUP_DIR\"/{}\n", sh_quote(parent)),
+        None => "mkdir -p \"$BACKUP_DIR\"\n".to_string(),
+    }
+}
+
+/// Script line setting `key=value` in the `[Desktop Entry]` group of `path`.
+fn script_set_key(path: &Path, key: &str, value: &str) -> String {
+    // `sed a` text unescapes backslashes; the whole sed program is single-quoted.
+    let text = format!("{key}={value}")
+        .replace('\\', "\\\\")
+        .replace('\'', r"'\''");
+    format!(
+        "sed -i -e '/^\\[Desktop Entry\\]/,/^\\[/{{/^{key} *=/d}}' \
+         -e '/^\\[Desktop Entry\\]/a {text}' {}\n",
+        sh_quote(path)
+    )
+}
+
+/// Script lines copying `path` into the backup directory.
+fn script_backup_copy(path: &Path) -> String {
+    format!(
+        "{}cp -p {} \"$BACKUP_DIR\"/{}\n",
+        script_backup_dir(path),
+        sh_quote(path),
+        sh_quote(backup_rel(path))
+    )
+}
+
+/// Script lines moving `path` into the backup directory.
+fn script_move_to_backup(path: &Path) -> String {
+    format!(
+        "{}mv {} \"$BACKUP_DIR\"/{}\n",
+        script_backup_dir(path),
+        sh_quote(path),
+        sh_quote(backup_rel(path))
+    )
+}
+
+/// Quote `path` for a POSIX shell.
+fn sh_quote(path: &Path) -> String {
+    let s = path.to_string_lossy();
+    shlex::try_quote(&s)
+        .map(|q| q.into_owned())
+        .unwrap_or_else(|_| format!("'{}'", s.replace('\'', r"'\''")))
+}
+
+/// Copy the content of `path` below `backup_dir`, mirroring its absolute path, and return the
+/// backup location. Used before editing in place, which writes through a symlinked entry.
+async fn backup_file(path: &Path, backup_dir: &Path) -> Result<PathBuf> {
+    let backup = backup_location(path, backup_dir).await?;
+    fs::copy(path, &backup)
+        .await
+        .with_context(|| format!("Failed to back up to {}", backup.display()))?;
+    Ok(backup)
+}
+
+/// Where `path` is backed up below `backup_dir`, with its parent directory created.
+async fn backup_location(path: &Path, backup_dir: &Path) -> Result<PathBuf> {
+    let backup = backup_dir.join(backup_rel(path));
+    if let Some(parent) = backup.parent() {
+        fs::create_dir_all(parent)
+            .await
+            .with_context(|| format!("Failed to create {}", parent.display()))?;
+    }
+    Ok(backup)
+}
+
+/// Back up `path`, then remove it. Returns the backup location.
+///
+/// A symlinked entry is backed up as the link itself, so a dangling link can be removed and undo
+/// recreates the link rather than a copy of its target.
+async fn delete_with_backup(path: &Path, backup_dir: &Path) -> Result<PathBuf> {
+    let backup = backup_location(path, backup_dir).await?;
+    copy_file_or_link(path, &backup)
+        .await
+        .with_context(|| format!("Failed to back up to {}", backup.display()))?;
+    fs::remove_file(path)
+        .await
+        .context("Failed to remove file")?;
+    Ok(backup)
+}
+
+/// Copy `from` to `to`; a symlink is recreated as a symlink with the same target instead of
+/// copying what it points to.
+///
+/// # Errors
+/// - [`anyhow::Error`] if `from` cannot be read or `to` cannot be created.
+pub async fn copy_file_or_link(from: &Path, to: &Path) -> Result<()> {
+    let meta = fs::symlink_metadata(from)
+        .await
+        .with_context(|| format!("Failed to stat {}", from.display()))?;
+    if meta.file_type().is_symlink() {
+        let target = fs::read_link(from)
+            .await
+            .with_context(|| format!("Failed to read link {}", from.display()))?;
+        fs::symlink(&target, to)
+            .await
+            .with_context(|| format!("Failed to create link {}", to.display()))?;
+    } else {
+        fs::copy(from, to)
+            .await
+            .with_context(|| format!("Failed to copy {}", from.display()))?;
+    }
+    Ok(())
+}
+
+/// Back up and remove `path` and its `companions`.
+///
+/// Once `path` is removed, a failing companion yields [`FixResult::PartiallyDeleted`] rather
+/// than an error, so the removal is still journaled and can be undone.
+async fn delete_with_companions(
+    path: &Path,
+    companions: &[PathBuf],
+    backup_dir: &Path,
+) -> Result<FixResult> {
+    let backup = delete_with_backup(path, backup_dir).await?;
+    let mut removed_companions = Vec::new();
+    for c in companions {
+        if let Err(e) = delete_with_backup(c, backup_dir).await {
+            return Ok(FixResult::PartiallyDeleted {
+                backup,
+                removed_companions,
+                error: format!("Failed to remove companion {}: {e:#}", c.display()),
+            });
+        }
+        removed_companions.push(c.clone());
+    }
+    Ok(FixResult::Deleted { backup })
+}
+
+/// Move `path` and its `companions` to the trash.
+async fn trash_with_companions(path: &Path, companions: &[PathBuf]) -> Result<FixResult> {
+    let trash_file = trash::trash(path).await?;
+    let mut companion_trash_files = Vec::new();
+    for c in companions {
+        let trashed = trash::trash(c)
+            .await
+            .with_context(|| format!("Failed to move companion {} to the trash", c.display()))?;
+        companion_trash_files.push(trashed);
+    }
+    Ok(FixResult::Trashed {
+        trash_file,
+        companion_trash_files,
+    })
+}
+
+/// Back up `path`, then set each `key=value` of `keys` in its `[Desktop Entry]` group. Returns
+/// the backup location.
+async fn set_keys_in_place(
+    path: &Path,
+    keys: &[(&str, &str)],
+    backup_dir: &Path,
+) -> Result<PathBuf> {
+    let content = fs::read_to_string(path)
+        .await
+        .context("Failed to read file")?;
+    let backup = backup_file(path, backup_dir).await?;
+
+    let mut entry = DesktopEntry::parse(&content);
+    for (key, value) in keys {
+        entry.set(DESKTOP_ENTRY_GROUP, key, value);
+    }
+    desktop::write_entry(path, &entry).await?;
+    Ok(backup)
+}
+
+/// Create a user override with `Hidden=true` for the desktop ID of `finding` in `user_apps`.
+///
+/// Per the desktop entry spec, an entry with the same desktop ID in a directory earlier in
+/// `$XDG_DATA_DIRS` (here `$XDG_DATA_HOME/applications`) shadows the system one, and
+/// `Hidden=true` makes launchers treat the application as deleted.
+async fn hide_with_override(finding: &Finding, user_apps: &Path) -> Result<PathBuf> {
+    let override_file = user_apps.join(desktop::desktop_id(&finding.desktop_file));
+    if fs::symlink_metadata(&override_file).await.is_ok() {
+        bail!("{} already exists", override_file.display());
+    }
+
+    fs::create_dir_all(user_apps)
+        .await
+        .with_context(|| format!("Failed to create {}", user_apps.display()))?;
+    fs::write(&override_file, override_content(finding))
+        .await
+        .with_context(|| format!("Failed to write {}", override_file.display()))?;
+    Ok(override_file)
+}
+
+/// Contents of a user override hiding `finding`.
+fn override_content(finding: &Finding) -> String {
+    let mut entry = DesktopEntry::default();
+    entry.set(DESKTOP_ENTRY_GROUP, "Type", "Application");
+    match &finding.name {
+        // Taken verbatim from the hidden file, so already escaped.
+        Some(name) => entry.set(DESKTOP_ENTRY_GROUP, "Name", name),
+        None => entry.set_string(DESKTOP_ENTRY_GROUP, "Name", "Hidden by desktop-scout"),
+    }
+    entry.set(DESKTOP_ENTRY_GROUP, "Hidden", "true");
+    entry.to_string()
+}
+
+/// Print a human-readable summary of a [`FixReport`].
+pub fn print_text(fix: &FixReport) {
+    if fix.outcomes.is_empty() {
+        println!("{}", tr("fix-nothing-to-fix", &[]));
+        return;
+    }
+
+    let mut left_behind = false;
+    for o in &fix.outcomes {
+        let path = o.desktop_file.display().to_string();
+        let line = match &o.result {
+            FixResult::Deleted { .. } => tr("fix-removed", &[("path", path.into())]),
+            FixResult::PartiallyDeleted { error, .. } => tr(
+                "fix-partially-removed",
+                &[("path", path.into()), ("error", error.clone().into())],
+            ),
+            FixResult::Trashed { .. } => tr("fix-trashed", &[("path", path.into())]),
+            FixResult::Hidden { .. } => tr("fix-hidden", &[("path", path.into())]),
+            FixResult::Repaired { changes, .. } => {
+                let changes = changes
+                    .iter()
+                    .map(|(k, v)| format!("{k}={v}"))
+                    .collect::<Vec<_>>();
+                tr(
+                    "fix-repaired",
+                    &[
+                        ("path", path.into()),
+                        ("changes", changes.join(", ").into()),
+                    ],
+                )
+            }
+            FixResult::Overridden { override_file } => tr(
+                "fix-overridden",
+                &[
+                    ("path", path.into()),
+                    ("override", override_file.display().to_string().into()),
+                ],
+            ),
+            FixResult::Quarantined { id } => tr(
+                "fix-quarantined",
+                &[("path", path.into()), ("id", id.clone().into())],
+            ),
+            FixResult::Skipped { reason } => tr(
+                "fix-skipped",
+                &[("path", path.into()), ("reason", reason.clone().into())],
+            ),
+            FixResult::Failed { error } => tr(
+                "fix-failed",
+                &[("path", path.into()), ("error", error.clone().into())],
+            ),
+        };
+        println!("{line}");
+        if let Some(w) = &o.working_copy {
+            let w = w.display().to_string();
+            println!("  {}", tr("fix-working-copy", &[("path", w.into())]));
+        }
+        let removed = match &o.result {
+            FixResult::PartiallyDeleted {
+                removed_companions, ..
+            } => removed_companions.as_slice(),
+            _ if o.companions_removed => o.companions.as_slice(),
+            _ => &[],
+        };
+        for c in &o.companions {
+            let id = if removed.contains(c) {
+                "fix-removed-companion"
+            } else {
+                left_behind = true;
+                "fix-left-companion"
+            };
+            println!("  {}", tr(id, &[("path", c.display().to_string().into())]));
+        }
+    }
+
+    if left_behind {
+        println!("\n{}", tr("fix-companions-left", &[]));
+    }
+
+    if let Some(dir) = &fix.backup_dir {
+        let dir = dir.display().to_string();
+        println!("\n{}", tr("fix-backups-written", &[("path", dir.into())]));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::process;
+
+    use super::*;
+    use crate::report::ReasonCode;
+
+    fn finding(path: &str, exec: &str, status: Status) -> Finding {
+        Finding {
+            id: String::new(),
+            desktop_file: PathBuf::from(path),
+            name: None,
+            exec: Some(exec.to_string()),
+            try_exec: None,
+            path_key: None,
+            entry_type: None,
+            hidden: false,
+            no_display: false,
+            status,
+            warnings: Vec::new(),
+            user: None,
+            suppressed_warnings: Vec::new(),
+            severity: None,
+            suggested_exec: None,
+            history: None,
+            flathub: None,
+            identity: None,
+            same_file_as: Vec::new(),
+        }
+    }
+
+    fn broken(path: &str, exec: &str) -> Finding {
+        let status = Status::Broken {
+            code: ReasonCode::ExecNotFound,
+            reason: String::new(),
+        };
+        finding(path, exec, status)
+    }
+
+    fn ok(path: &str, exec: &str) -> Finding {
+        let status = Status::Ok {
+            resolved_executable: None,
+        };
+        finding(path, exec, status)
+    }
+
+    fn report(findings: Vec<Finding>) -> Report {
+        Report {
+            schema_version: 0,
+            warnings: Vec::new(),
+            findings,
+            summary: None,
+            applications: Vec::new(),
+            groups: Vec::new(),
+        }
+    }
+
+    fn options(mode: FixMode) -> FixOptions {
+        FixOptions {
+            system: true,
+            mode,
+            quarantine: false,
+            trash: false,
+            with_companions: false,
+            remap: Vec::new(),
+            broken_duplicates: false,
+            field_codes: false,
+            force: true,
+        }
+    }
+
+    fn paths() -> FixPaths {
+        let data_home = env::temp_dir().join(format!("desktop-scout-plan-{}", process::id()));
+        FixPaths {
+            backup_dir: data_home.join("backups"),
+            data_home,
+        }
+    }
+
+    /// Planned actions by file.
+    async fn actions(report: &Report, opts: &FixOptions) -> Vec<(String, Action)> {
+        plan(report, opts, &paths())
+            .await
+            .into_iter()
+            .map(|p| (p.finding.desktop_file.display().to_string(), p.action))
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn plans_one_action_per_broken_file() {
+        let identity = Some(FileIdentity {
+            device: 1,
+            inode: 2,
+        });
+        let mut bound = broken("/srv/apps/applications/a.desktop", "a");
+        bound.identity = identity;
+        let mut mounted = broken("/mnt/apps/applications/a.desktop", "a");
+        mounted.identity = identity;
+        let report = report(vec![
+            bound,
+            mounted,
+            broken("/opt/applications/b.desktop", "b"),
+            ok("/opt/applications/c.desktop", "c"),
+        ]);
+
+        assert_eq!(
+            actions(&report, &options(FixMode::Delete)).await,
+            [
+                ("/srv/apps/applications/a.desktop".into(), Action::Delete),
+                (
+                    "/mnt/apps/applications/a.desktop".into(),
+                    Action::Skip("same file as /srv/apps/applications/a.desktop".into())
+                ),
+                ("/opt/applications/b.desktop".into(), Action::Delete),
+            ]
+        );
+
+        let mut opts = options(FixMode::Repair);
+        opts.quarantine = true;
+        assert_eq!(
+            actions(&report, &opts).await[2],
+            ("/opt/applications/b.desktop".into(), Action::Quarantine)
+        );
+        opts.quarantine = false;
+        assert_eq!(
+            actions(&report, &opts).await[2],
+            (
+                "/opt/applications/b.desktop".into(),
+                Action::Skip("no remap rule or relocated binary applies".into())
+            )
+        );
+    }
+
+    #[tokio::test]
+    async fn broken_duplicates_need_a_working_copy() {
+        let report = report(vec![
+            broken("/usr/local/share/applications/a.desktop", "a"),
+            ok("/usr/share/applications/a.desktop", "a"),
+            broken("/usr/share/applications/b.desktop", "b"),
+        ]);
+        let mut opts = options(FixMode::Hide);
+        opts.broken_duplicates = true;
+
+        let planned = plan(&report, &opts, &paths()).await;
+        assert_eq!(planned.len(), 1);
+        assert_eq!(planned[0].action, Action::HideInPlace);
+        assert_eq!(
+            planned[0].working_copy.as_deref(),
+            Some(Path::new("/usr/share/applications/a.desktop"))
+        );
+    }
+
+    #[tokio::test]
+    async fn field_codes_repair_every_entry_that_needs_cleaning() {
+        let report = report(vec![
+            ok("/usr/share/applications/a.desktop", "a %U %F"),
+            ok("/usr/share/applications/b.desktop", "b %U"),
+            broken("/usr/share/applications/c.desktop", "c %d"),
+        ]);
+        let mut opts = options(FixMode::Delete);
+        opts.field_codes = true;
+
+        assert_eq!(
+            actions(&report, &opts).await,
+            [
+                (
+                    "/usr/share/applications/a.desktop".into(),
+                    Action::Repair(BTreeMap::from([("Exec".into(), "a %U".into())]))
+                ),
+                (
+                    "/usr/share/applications/c.desktop".into(),
+                    Action::Repair(BTreeMap::from([("Exec".into(), "c".into())]))
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn removable_media_are_transient() {
+        let on_medium = broken("/usr/share/applications/a.desktop", "/media/usb/app --x");
+        assert_eq!(
+            transient_reason(&on_medium).as_deref(),
+            Some("/media/usb/app is on removable media that may not be mounted")
+        );
+        let mut try_exec = broken("/usr/share/applications/a.desktop", "app");
+        try_exec.try_exec = Some("/run/media/me/disk/app".into());
+        assert!(transient_reason(&try_exec).is_some());
+        assert_eq!(
+            transient_reason(&broken("/usr/share/applications/a.desktop", "/usr/bin/app")),
+            None
+        );
+        assert_eq!(
+            transient_reason(&ok("/usr/share/applications/a.desktop", "/media/usb/app")),
+            None
+        );
+    }
+
+    #[test]
+    fn backup_paths_mirror_the_absolute_path() {
+        let path = Path::new("/home/me/.local/share/applications/it's.desktop");
+        assert_eq!(
+            backup_rel(path),
+            Path::new("home/me/.local/share/applications/it's.desktop")
+        );
+        assert_eq!(
+            script_move_to_backup(path),
+            "mkdir -p \"$BACKUP_DIR\"/home/me/.local/share/applications\n\
+             mv \"/home/me/.local/share/applications/it's.desktop\" \
+             \"$BACKUP_DIR\"/\"home/me/.local/share/applications/it's.desktop\"\n"
+        );
+        assert_eq!(
+            script_set_key(Path::new("/a b.desktop"), "Exec", r"sh -c 'x\y'"),
+            "sed -i -e '/^\\[Desktop Entry\\]/,/^\\[/{/^Exec *=/d}' \
+             -e '/^\\[Desktop Entry\\]/a Exec=sh -c '\\''x\\\\y'\\''' '/a b.desktop'\n"
+        );
+    }
+
+    #[tokio::test]
+    async fn backups_keep_symlinks_as_links() {
+        let dir = env::temp_dir().join(format!("desktop-scout-backup-{}", process::id()));
+        let apps = dir.join("applications");
+        let backups = dir.join("backups");
+        std::fs::create_dir_all(&apps).unwrap();
+        std::fs::write(apps.join("real.desktop"), "[Desktop Entry]\n").unwrap();
+        std::os::unix::fs::symlink("missing.desktop", apps.join("dangling.desktop")).unwrap();
+        std::os::unix::fs::symlink("real.desktop", apps.join("link.desktop")).unwrap();
+
+        let dangling = delete_with_backup(&apps.join("dangling.desktop"), &backups).await;
+        let copied = backup_file(&apps.join("link.desktop"), &backups).await;
+
+        let dangling = dangling.unwrap();
+        let link = std::fs::read_link(&dangling);
+        let removed = std::fs::symlink_metadata(apps.join("dangling.desktop")).is_err();
+        let copied = copied.unwrap();
+        let copy = std::fs::symlink_metadata(&copied).unwrap();
+        let content = std::fs::read_to_string(&copied).unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(
+            dangling,
+            backups.join(backup_rel(&apps.join("dangling.desktop")))
+        );
+        assert_eq!(link.unwrap(), Path::new("missing.desktop"));
+        assert!(removed);
+        assert!(copy.file_type().is_file());
+        assert_eq!(content, "[Desktop Entry]\n");
+    }
+}
@@ -0,0 +1,159 @@
+//! Opt-in upstream check of broken Flatpak entries against Flathub (`--check-flathub`).
+//!
+//! Broken entries with a recognizable Flatpak app ID (`X-Flatpak=` or a file in a Flatpak
+//! exports directory) are looked up in Flathub's AppStream API. An app that is still published
+//! points to a damaged local installation (reinstall it), one that is gone was removed upstream
+//! (uninstall it). Requests are made with `curl`, so nothing is sent unless the flag is given.
+
+// -- std imports
+use std::{
+    collections::{HashMap, HashSet},
+    path::Path,
+    process::Stdio,
+};
+
+// -- crate imports
+use anyhow::{Context, Result, bail};
+use futures::{
+    future,
+    stream::{self, StreamExt},
+};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use tokio::{fs, process::Command};
+use tracing::{debug, warn};
+
+// -- module imports
+use crate::{desktop, i18n::tr, report::Report};
+
+/// Flathub AppStream endpoint; the app ID is appended.
+const FLATHUB_APPSTREAM_URL: &str = "https://flathub.org/api/v2/appstream";
+
+/// Maximum number of concurrent requests.
+const FLATHUB_CONCURRENCY: usize = 8;
+
+/// Timeout per request, in seconds (passed to `curl --max-time`).
+const REQUEST_TIMEOUT_SECS: &str = "10";
+
+/// Directory suffix of Flatpak desktop exports (user, system and custom installations).
+const FLATPAK_EXPORTS: &str = "exports/share/applications";
+
+/// Upstream state of a broken Flatpak entry.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct FlathubStatus {
+    /// Flatpak app ID that was looked up.
+    pub app_id: String,
+
+    /// Whether Flathub still publishes the app (the local installation is damaged) or not (the
+    /// app was removed from Flathub).
+    pub on_flathub: bool,
+}
+
+impl FlathubStatus {
+    /// Localized one-line verdict for reports.
+    pub fn message(&self) -> String {
+        let id = if self.on_flathub {
+            "report-flathub-available"
+        } else {
+            "report-flathub-removed"
+        };
+        tr(id, &[("app_id", self.app_id.as_str().into())])
+    }
+}
+
+/// Look up the app IDs of all broken findings in `report` and attach the result.
+///
+/// Lookups that fail (no `curl`, no network, unexpected answers) are logged and leave the
+/// finding unannotated.
+pub async fn annotate(report: &mut Report) {
+    let mut targets = Vec::new();
+    for (idx, f) in report.findings.iter().enumerate() {
+        if f.is_broken()
+            && let Some(id) = flatpak_app_id(&f.desktop_file).await
+        {
+            targets.push((idx, id));
+        }
+    }
+
+    let ids = targets
+        .iter()
+        .map(|(_, id)| id.clone())
+        .collect::<HashSet<_>>();
+    let results = stream::iter(ids)
+        .map(|id| async move {
+            match is_on_flathub(&id).await {
+                Ok(on_flathub) => Some((id, on_flathub)),
+                Err(e) => {
+                    warn!(app_id = id, error = %e, "Failed to query Flathub");
+                    None
+                }
+            }
+        })
+        .buffer_unordered(FLATHUB_CONCURRENCY)
+        .filter_map(future::ready)
+        .collect::<HashMap<_, _>>()
+        .await;
+
+    for (idx, app_id) in targets {
+        if let Some(&on_flathub) = results.get(&app_id) {
+            report.findings[idx].flathub = Some(FlathubStatus { app_id, on_flathub });
+        }
+    }
+}
+
+/// Flatpak app ID of the entry at `path`: its `X-Flatpak=` key, or the desktop ID of a file in
+/// a Flatpak exports directory. Returns `None` for other entries and IDs that are not valid
+/// Flatpak app IDs.
+async fn flatpak_app_id(path: &Path) -> Option<String> {
+    let content = fs::read_to_string(path).await.ok()?;
+    let kv = desktop::parse_desktop_entry_section(&content);
+
+    let id = match kv.get("X-Flatpak") {
+        Some(id) => id.trim().to_string(),
+        None if path.parent().is_some_and(|p| p.ends_with(FLATPAK_EXPORTS)) => {
+            desktop::desktop_id(path)
+                .strip_suffix(".desktop")?
+                .to_string()
+        }
+        None => return None,
+    };
+
+    let valid = id.contains('.')
+        && id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '_' | '-'));
+    valid.then_some(id)
+}
+
+/// Ask Flathub whether it publishes `app_id`.
+async fn is_on_flathub(app_id: &str) -> Result<bool> {
+    let url = format!("{FLATHUB_APPSTREAM_URL}/{app_id}");
+    debug!(url, "Querying Flathub");
+
+    let output = Command::new("curl")
+        .args([
+            "--silent",
+            "--show-error",
+            "--location",
+            "--max-time",
+            REQUEST_TIMEOUT_SECS,
+            "--output",
+            "/dev/null",
+            "--write-out",
+            "%{http_code}",
+            &url,
+        ])
+        .stdin(Stdio::null())
+        .output()
+        .await
+        .context("Failed to run curl")?;
+
+    match String::from_utf8_lossy(&output.stdout).trim() {
+        "200" => Ok(true),
+        "404" => Ok(false),
+        code => bail!(
+            "Flathub answered with HTTP status {code}: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ),
+    }
+}
@@ -0,0 +1,433 @@
+//! Fleet mode: scan many hosts over SSH and aggregate the results.
+//!
+//! Each host from the `--hosts` inventory is scanned by running `desktop-scout --json` on it via
+//! `ssh` (non-interactively, `BatchMode=yes`). Reports are parsed back into [`Report`] values and
+//! aggregated into a [`FleetReport`] keyed by host. Versions that predate the report object print
+//! a bare array of broken entries instead; those are converted into a report, with reason codes
+//! recovered from the reason messages.
+
+// -- std imports
+use std::{
+    path::{Path, PathBuf},
+    process::Stdio,
+};
+
+// -- crate imports
+use anyhow::{Context, Result, bail};
+use clap::{ArgMatches, CommandFactory, parser::ValueSource};
+use futures::stream::{self, StreamExt};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tokio::{fs, process::Command};
+use tracing::{debug, info, warn};
+
+// -- module imports
+use crate::{
+    args::Args,
+    i18n::tr,
+    report::{self, Finding, ReasonCode, Report, Status},
+};
+
+/// Maximum number of concurrent SSH sessions.
+const FLEET_CONCURRENCY: usize = 16;
+
+/// Remote exit codes that still carry a valid JSON report (success, no files found, findings
+/// at the `--fail-on` level).
+const REMOTE_REPORT_EXIT_CODES: &[i32] = &[0, 3, 4];
+
+/// Aggregated results of a fleet scan.
+#[derive(Debug, Serialize)]
+pub struct FleetReport {
+    /// Per-host results, in inventory order.
+    pub hosts: Vec<HostReport>,
+}
+
+/// Result of scanning a single host.
+#[derive(Debug, Serialize)]
+pub struct HostReport {
+    /// Host as written in the inventory.
+    pub host: String,
+
+    /// Number of broken entries (0 if the scan failed).
+    pub broken: usize,
+
+    /// Error message if the host could not be scanned.
+    pub error: Option<String>,
+
+    /// The host's report, if the scan succeeded.
+    pub report: Option<Report>,
+}
+
+/// Read a host inventory: one host per line, `#` starts a comment, blank lines are ignored.
+///
+/// # Errors
+/// - [`anyhow::Error`] if the file cannot be read.
+pub async fn read_inventory(path: &Path) -> Result<Vec<String>> {
+    let content = fs::read_to_string(path)
+        .await
+        .with_context(|| format!("Failed to read host inventory {}", path.display()))?;
+
+    Ok(content
+        .lines()
+        .map(|l| l.split('#').next().unwrap_or("").trim())
+        .filter(|l| !l.is_empty())
+        .map(str::to_string)
+        .collect())
+}
+
+/// Scan all `hosts` concurrently and aggregate the results.
+///
+/// `remote_command` is the desktop-scout executable on the remote side, `scan_args` the scan
+/// options forwarded to it (see [`forwarded_scan_args`]).
+pub async fn scan_hosts(
+    hosts: Vec<String>,
+    remote_command: &str,
+    scan_args: &[String],
+) -> FleetReport {
+    info!(hosts = hosts.len(), "Starting fleet scan");
+
+    let mut results = stream::iter(hosts.into_iter().enumerate())
+        .map(|(idx, host)| async move {
+            let result = match scan_host(&host, remote_command, scan_args).await {
+                Ok(report) => HostReport {
+                    host,
+                    broken: report.findings.iter().filter(|f| f.is_broken()).count(),
+                    error: None,
+                    report: Some(report),
+                },
+                Err(e) => {
+                    warn!(host, error = %e, "Failed to scan host");
+                    HostReport {
+                        host,
+                        broken: 0,
+                        error: Some(format!("{e:#}")),
+                        report: None,
+                    }
+                }
+            };
+            (idx, result)
+        })
+        .buffer_unordered(FLEET_CONCURRENCY)
+        .collect::<Vec<_>>()
+        .await;
+
+    results.sort_by_key(|(idx, _)| *idx);
+    FleetReport {
+        hosts: results.into_iter().map(|(_, r)| r).collect(),
+    }
+}
+
+/// Run a JSON scan on `host` via `ssh` and parse its report.
+async fn scan_host(host: &str, remote_command: &str, scan_args: &[String]) -> Result<Report> {
+    // `--json` rather than `--output json`, which older versions do not know.
+    let mut words = vec![
+        remote_command.to_string(),
+        "--no-log".into(),
+        "--json".into(),
+    ];
+    words.extend(scan_args.iter().cloned());
+    let remote = shlex::try_join(words.iter().map(String::as_str))
+        .context("Scan arguments cannot be quoted for the remote shell")?;
+    debug!(host, remote, "Running remote scan");
+
+    let output = Command::new("ssh")
+        .args(["-o", "BatchMode=yes", "--", host, &remote])
+        .stdin(Stdio::null())
+        .output()
+        .await
+        .context("Failed to run ssh")?;
+
+    let code = output.status.code().unwrap_or(-1);
+    if !REMOTE_REPORT_EXIT_CODES.contains(&code) {
+        bail!(
+            "Remote scan exited with status {code}: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+
+    parse_remote_report(&output.stdout).context("Remote scan did not produce a valid report")
+}
+
+/// Parse the `--json` output of a remote scan: a report, or the bare array of broken entries
+/// printed by older versions.
+fn parse_remote_report(stdout: &[u8]) -> Result<Report> {
+    let value = serde_json::from_slice::<Value>(stdout)?;
+    if !value.is_array() {
+        return Ok(serde_json::from_value(value)?);
+    }
+
+    let findings = serde_json::from_value::<Vec<LegacyFinding>>(value)?;
+    Ok(Report {
+        schema_version: 0,
+        warnings: Vec::new(),
+        findings: findings
+            .into_iter()
+            .map(LegacyFinding::into_finding)
+            .collect(),
+        summary: None,
+        applications: Vec::new(),
+        groups: Vec::new(),
+    })
+}
+
+/// An entry of the `--json` array printed by versions before the report object.
+#[derive(Debug, Deserialize)]
+struct LegacyFinding {
+    desktop_file: PathBuf,
+    name: Option<String>,
+    exec: Option<String>,
+    try_exec: Option<String>,
+    path_key: Option<String>,
+    hidden: bool,
+    no_display: bool,
+    status: LegacyStatus,
+}
+
+/// Outcome of a [`LegacyFinding`], which had no reason codes yet.
+#[derive(Debug, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+enum LegacyStatus {
+    Ok {
+        resolved_executable: Option<PathBuf>,
+    },
+    Broken {
+        reason: String,
+    },
+    Skipped {
+        reason: String,
+    },
+}
+
+impl LegacyFinding {
+    /// The finding of the current report format, with the reason code that the reason message
+    /// stands for.
+    fn into_finding(self) -> Finding {
+        let status = match self.status {
+            LegacyStatus::Ok {
+                resolved_executable,
+            } => Status::Ok {
+                resolved_executable,
+            },
+            LegacyStatus::Broken { reason } => Status::Broken {
+                code: legacy_reason_code(&reason),
+                reason,
+            },
+            LegacyStatus::Skipped { reason } => Status::Skipped {
+                code: if reason.starts_with("Type=") {
+                    ReasonCode::UnsupportedType
+                } else {
+                    ReasonCode::HiddenEntry
+                },
+                reason,
+            },
+        };
+        Finding {
+            id: report::finding_id(&self.desktop_file, &status, &[]),
+            desktop_file: self.desktop_file,
+            name: self.name,
+            exec: self.exec,
+            try_exec: self.try_exec,
+            path_key: self.path_key,
+            entry_type: None,
+            hidden: self.hidden,
+            no_display: self.no_display,
+            status,
+            warnings: Vec::new(),
+            user: None,
+            suppressed_warnings: Vec::new(),
+            severity: None,
+            suggested_exec: None,
+            history: None,
+            flathub: None,
+            identity: None,
+            same_file_as: Vec::new(),
+        }
+    }
+}
+
+/// Reason code of a broken entry from its legacy (always English) reason message.
+fn legacy_reason_code(reason: &str) -> ReasonCode {
+    const PREFIXES: &[(&str, ReasonCode)] = &[
+        ("Failed to read/parse file", ReasonCode::UnreadableFile),
+        ("TryExec does not resolve", ReasonCode::TryexecUnresolved),
+        (
+            "Exec does not resolve (even though TryExec does)",
+            ReasonCode::ExecUnresolvedDespiteTryexec,
+        ),
+        ("No Exec key found", ReasonCode::ExecMissing),
+        ("Exec check failed", ReasonCode::ExecParseError),
+    ];
+    PREFIXES
+        .iter()
+        .find(|(prefix, _)| reason.starts_with(prefix))
+        .map_or(ReasonCode::ExecNotFound, |(_, code)| *code)
+}
+
+/// Long names of the options forwarded to remote hosts: those selecting and checking entries.
+/// Output, fleet, baseline and configuration options, and options naming local files, are not.
+const FORWARDED_OPTIONS: &[&str] = &[
+    "all-users",
+    "allow",
+    "check-script-args",
+    "check-shell-aliases",
+    "check-user-manager",
+    "detect-misnamed",
+    "dir",
+    "dir-jobs",
+    "disable-rule",
+    "enable-rule",
+    "exclude",
+    "follow-symlinks",
+    "frozen",
+    "ignore",
+    "include",
+    "include-hidden",
+    "inventory",
+    "jobs",
+    "max-depth",
+    "max-runtime",
+    "min-severity",
+    "newer-than",
+    "no-cache",
+    "no-common-extras",
+    "no-default",
+    "no-history",
+    "one-file-system",
+    "root",
+    "timeout",
+];
+
+/// Scan options to forward to remote hosts: the [`FORWARDED_OPTIONS`] set in `matches` (from
+/// [`crate::config::parse_matches`]) on the command line, in a configuration file or profile, or
+/// through an environment variable, as they were written there.
+pub fn forwarded_scan_args(matches: &ArgMatches) -> Vec<String> {
+    let mut out = Vec::new();
+    for arg in Args::command().get_arguments() {
+        let Some(long) = arg.get_long().filter(|l| FORWARDED_OPTIONS.contains(l)) else {
+            continue;
+        };
+        let id = arg.get_id().as_str();
+        if !matches!(
+            matches.value_source(id),
+            Some(ValueSource::CommandLine | ValueSource::EnvVariable)
+        ) {
+            continue;
+        }
+        if !arg.get_action().takes_values() {
+            // Flags set through the environment may also be switched off (`=0`).
+            if matches.get_flag(id) {
+                out.push(format!("--{long}"));
+            }
+            continue;
+        }
+        for value in matches.get_raw(id).into_iter().flatten() {
+            out.push(format!("--{long}"));
+            out.push(value.to_string_lossy().into_owned());
+        }
+    }
+    out
+}
+
+/// Print a human-readable fleet summary followed by the broken entries of each host.
+pub fn print_text(fleet: &FleetReport) {
+    let width = fleet.hosts.iter().map(|h| h.host.len()).max().unwrap_or(0);
+
+    let hosts = fleet.hosts.len();
+    println!("{}\n", tr("fleet-summary", &[("hosts", hosts.into())]));
+    for h in &fleet.hosts {
+        let status = match &h.error {
+            Some(e) => tr("fleet-host-error", &[("error", e.clone().into())]),
+            None => tr("fleet-host-broken", &[("count", h.broken.into())]),
+        };
+        println!("  {:<width$}  {status}", h.host);
+    }
+
+    for h in &fleet.hosts {
+        let Some(report) = &h.report else {
+            continue;
+        };
+        if h.broken == 0 {
+            continue;
+        }
+
+        println!(
+            "\n{}",
+            tr(
+                "fleet-host-header",
+                &[("host", h.host.clone().into()), ("count", h.broken.into())]
+            )
+        );
+        for f in report.findings.iter().filter(|f| f.is_broken()) {
+            println!("- {}", f.desktop_file.display());
+            if let Status::Broken { code, reason } = &f.status {
+                let reason = tr(
+                    "fleet-reason",
+                    &[
+                        ("code", code.to_string().into()),
+                        ("reason", reason.clone().into()),
+                    ],
+                );
+                println!("  {reason}");
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_legacy_finding_arrays() {
+        let stdout = br#"[
+            {
+                "desktop_file": "/usr/share/applications/foo.desktop",
+                "name": "Foo",
+                "exec": "foo %U",
+                "try_exec": "foo",
+                "path_key": null,
+                "hidden": false,
+                "no_display": false,
+                "status": { "kind": "broken", "reason": "TryExec does not resolve: foo" }
+            },
+            {
+                "desktop_file": "/usr/share/applications/bar.desktop",
+                "name": null,
+                "exec": "bar",
+                "try_exec": null,
+                "path_key": null,
+                "hidden": false,
+                "no_display": false,
+                "status": { "kind": "broken", "reason": "Exec does not resolve" }
+            }
+        ]"#;
+        let report = parse_remote_report(stdout).unwrap();
+        assert_eq!(report.schema_version, 0);
+
+        let codes = report
+            .findings
+            .iter()
+            .map(|f| match &f.status {
+                Status::Broken { code, .. } => *code,
+                other => panic!("unexpected status {other:?}"),
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            codes,
+            [ReasonCode::TryexecUnresolved, ReasonCode::ExecNotFound]
+        );
+        assert_eq!(report.findings[0].name.as_deref(), Some("Foo"));
+        assert_eq!(report.findings[0].id.len(), 16);
+    }
+
+    #[test]
+    fn parses_reports_and_rejects_other_output() {
+        let report =
+            parse_remote_report(br#"{ "schema_version": 1, "warnings": [], "findings": [] }"#)
+                .unwrap();
+        assert_eq!(report.schema_version, 1);
+
+        assert!(parse_remote_report(b"No broken desktop entries found.").is_err());
+        assert!(parse_remote_report(br#"[{ "desktop_file": "/a.desktop" }]"#).is_err());
+    }
+}
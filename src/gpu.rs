@@ -0,0 +1,165 @@
+//! GPU environment checks for `Exec=` lines.
+//!
+//! Launchers written for hybrid-graphics laptops pin a GPU through environment assignments
+//! (`env __NV_PRIME_RENDER_OFFLOAD=1 app`, `DRI_PRIME=1`, `VK_ICD_FILENAMES=/usr/share/vulkan/
+//! icd.d/nvidia_icd.json`). When the GPU setup changes — the NVIDIA driver is removed, the
+//! discrete GPU is disabled, another Vulkan driver is installed — the binary still resolves but
+//! the application fails to start or silently renders on the wrong device. Such assignments are
+//! reported as `GPU_ENV_UNAVAILABLE` warnings, since the breakage depends on the hardware rather
+//! than on the entry itself.
+
+// -- std imports
+use std::path::Path;
+
+// -- crate imports
+use tokio::fs;
+
+// -- module imports
+use crate::{
+    desktop,
+    i18n::tr,
+    report::{ReasonCode, Warning},
+};
+
+/// Variables that select the NVIDIA driver (PRIME render offload, GLVND vendor selection).
+const NVIDIA_ENV_VARS: &[(&str, &str)] = &[
+    ("__NV_PRIME_RENDER_OFFLOAD", "1"),
+    ("__GLX_VENDOR_LIBRARY_NAME", "nvidia"),
+    ("__VK_LAYER_NV_optimus", "NVIDIA_only"),
+];
+
+/// Variables holding a `:`-separated list of ICD or vendor JSON manifests.
+const MANIFEST_ENV_VARS: &[&str] = &[
+    "VK_ICD_FILENAMES",
+    "VK_DRIVER_FILES",
+    "VK_ADD_DRIVER_FILES",
+    "__EGL_VENDOR_LIBRARY_FILENAMES",
+];
+
+/// Present while the NVIDIA kernel driver is loaded.
+const NVIDIA_MODULE_DIR: &str = "/sys/module/nvidia";
+
+/// DRM device directory holding one `renderD<N>` node per GPU.
+const DRI_DIR: &str = "/dev/dri";
+
+/// Check the GPU environment assignments of `exec_line` against the current machine.
+///
+/// Assignments are taken from the `env` and `systemd-run` prefixes (`-E`/`--setenv`). Returns
+/// one warning per assignment that requires a driver, GPU or manifest that is not present. Lines
+/// that cannot be shell-split yield no warnings; the executable checks report those.
+pub async fn check_exec(exec_line: &str) -> Vec<Warning> {
+    let Some(tokens) = shlex::split(exec_line) else {
+        return Vec::new();
+    };
+    let (_, command) = desktop::unwrap_exec_prefixes(&tokens);
+    let prefix = &tokens[..tokens.len() - command.len()];
+
+    let mut warnings = Vec::new();
+    for token in prefix {
+        let token = token.strip_prefix("--setenv=").unwrap_or(token);
+        let Some((name, value)) = token.split_once('=') else {
+            continue;
+        };
+        if let Some(message) = check_assignment(name, value).await {
+            warnings.push(Warning {
+                code: ReasonCode::GpuEnvUnavailable,
+                message,
+                key: Some("Exec".into()),
+            });
+        }
+    }
+    warnings
+}
+
+/// The warning message for the assignment `name=value`, if it requires something that is
+/// missing.
+async fn check_assignment(name: &str, value: &str) -> Option<String> {
+    if NVIDIA_ENV_VARS.contains(&(name, value)) {
+        return (!exists(Path::new(NVIDIA_MODULE_DIR)).await).then(|| {
+            tr(
+                "gpu-nvidia-not-loaded",
+                &[("name", name.into()), ("value", value.into())],
+            )
+        });
+    }
+
+    if MANIFEST_ENV_VARS.contains(&name) {
+        let mut missing = Vec::new();
+        for manifest in value.split(':').filter(|m| !m.is_empty()) {
+            if !exists(Path::new(manifest)).await {
+                missing.push(manifest);
+            }
+        }
+        return (!missing.is_empty()).then(|| {
+            tr(
+                "gpu-manifest-missing",
+                &[
+                    ("name", name.into()),
+                    ("value", value.into()),
+                    ("count", missing.len().into()),
+                    ("manifests", missing.join(", ").into()),
+                ],
+            )
+        });
+    }
+
+    if name == "DRI_PRIME" {
+        return check_dri_prime(value).await;
+    }
+    None
+}
+
+/// The warning message for `DRI_PRIME=value` if the GPU with that index (`1`) or PCI address
+/// (`pci-0000_01_00_0`) is missing. Other forms (`vendor_id:device_id`) are not checked, and
+/// neither are indices on machines without render nodes (containers, headless servers).
+async fn check_dri_prime(value: &str) -> Option<String> {
+    if let Ok(index) = value.parse::<usize>() {
+        let gpus = render_node_count().await;
+        return (gpus > 0 && index >= gpus).then(|| {
+            tr(
+                "gpu-too-few-gpus",
+                &[
+                    ("name", "DRI_PRIME".into()),
+                    ("value", value.into()),
+                    ("count", gpus.into()),
+                ],
+            )
+        });
+    }
+
+    let address = value.strip_prefix("pci-")?;
+    // pci-0000_01_00_0 names the device 0000:01:00.0.
+    let mut parts = address.rsplitn(2, '_');
+    let (function, rest) = (parts.next()?, parts.next()?);
+    let device = format!("{}.{function}", rest.replace('_', ":"));
+    let sysfs = Path::new("/sys/bus/pci/devices").join(&device);
+    (!exists(&sysfs).await).then(|| {
+        tr(
+            "gpu-pci-device-missing",
+            &[
+                ("name", "DRI_PRIME".into()),
+                ("value", value.into()),
+                ("device", device.into()),
+            ],
+        )
+    })
+}
+
+/// Number of DRM render nodes (one per GPU with a render-capable driver).
+async fn render_node_count() -> usize {
+    let Ok(mut entries) = fs::read_dir(DRI_DIR).await else {
+        return 0;
+    };
+    let mut count = 0;
+    while let Ok(Some(entry)) = entries.next_entry().await {
+        if entry.file_name().to_string_lossy().starts_with("renderD") {
+            count += 1;
+        }
+    }
+    count
+}
+
+/// Whether a file or directory exists at `path` (following symlinks).
+async fn exists(path: &Path) -> bool {
+    fs::metadata(path).await.is_ok()
+}
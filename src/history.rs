@@ -0,0 +1,110 @@
+//! Finding history: when each broken entry was first seen and how long it has stayed broken.
+//!
+//! Every scan updates a small store in the XDG state directory
+//! (`$XDG_STATE_HOME/desktop-scout/history.json`), keyed by `.desktop` file path. A file that
+//! stays broken keeps its `first_seen` time (even if the reason changes) and extends its streak
+//! of consecutive broken scans; once it scans healthy again its record is dropped. Files that
+//! were not part of a scan (e.g. a narrower `--dir`) are left untouched.
+
+// -- std imports
+use std::{
+    collections::{BTreeMap, HashSet},
+    io::ErrorKind,
+    mem,
+    path::PathBuf,
+};
+
+// -- crate imports
+use anyhow::{Context, Result};
+use chrono::{DateTime, Local};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use tokio::fs;
+use xdg::BaseDirectories;
+
+// -- module imports
+use crate::{output_file, report::Report};
+
+/// File name of the store inside the state directory.
+const HISTORY_NAME: &str = "history.json";
+
+/// History of a broken `.desktop` file, as attached to its finding.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct FindingHistory {
+    /// When the file was first observed broken (in the current streak).
+    pub first_seen: DateTime<Local>,
+
+    /// When the file was last observed broken (this scan, for annotated findings).
+    pub last_seen: DateTime<Local>,
+
+    /// Number of consecutive scans (including this one) in which the file was broken.
+    pub broken_scans: u32,
+}
+
+/// On-disk store format.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct Store {
+    /// Currently broken files.
+    entries: BTreeMap<PathBuf, FindingHistory>,
+}
+
+/// Record the broken findings of `report` and annotate each with its history.
+///
+/// # Errors
+/// - [`anyhow::Error`] if the state directory cannot be determined or the store cannot be read
+///   or written.
+pub async fn record(report: &mut Report) -> Result<()> {
+    let path = BaseDirectories::with_prefix("desktop-scout")
+        .get_state_home()
+        .context("Could not determine XDG state directory for the finding history")?
+        .join(HISTORY_NAME);
+
+    let mut store: Store = match fs::read(&path).await {
+        Ok(c) => {
+            serde_json::from_slice(&c).with_context(|| format!("{} is corrupt", path.display()))?
+        }
+        Err(e) if e.kind() == ErrorKind::NotFound => Store::default(),
+        Err(e) => {
+            return Err(e).with_context(|| format!("Failed to read {}", path.display()));
+        }
+    };
+
+    let now = Local::now();
+    let scanned = report
+        .findings
+        .iter()
+        .map(|f| f.desktop_file.clone())
+        .collect::<HashSet<_>>();
+    let (mut previous, kept): (BTreeMap<_, _>, BTreeMap<_, _>) = mem::take(&mut store.entries)
+        .into_iter()
+        .partition(|(p, _)| scanned.contains(p));
+    store.entries = kept;
+
+    // A file can yield several findings (e.g. duplicates); count each file once per scan.
+    for f in report.findings.iter_mut().filter(|f| f.is_broken()) {
+        let history = store
+            .entries
+            .entry(f.desktop_file.clone())
+            .or_insert_with(|| match previous.remove(&f.desktop_file) {
+                Some(h) => FindingHistory {
+                    last_seen: now,
+                    broken_scans: h.broken_scans + 1,
+                    ..h
+                },
+                None => FindingHistory {
+                    first_seen: now,
+                    last_seen: now,
+                    broken_scans: 1,
+                },
+            });
+        f.history = Some(history.clone());
+    }
+
+    let json = serde_json::to_string_pretty(&store)?;
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)
+            .await
+            .with_context(|| format!("Failed to create {}", dir.display()))?;
+    }
+    output_file::write_atomic(&path, (json + "\n").as_bytes()).await
+}
@@ -0,0 +1,206 @@
+//! Self-contained HTML reports of scans (`--output html`).
+//!
+//! The page has no external resources, so it can be attached to a ticket as a single file. The
+//! findings are grouped into one table per source directory in report order (`--sort`); every table
+//! can be sorted by clicking a column header, and a search box filters the rows of all tables. Each
+//! row embeds the raw `.desktop` file as a collapsible preview, read again when the report is
+//! rendered.
+
+// -- std imports
+use std::{collections::BTreeMap, fmt::Write as _, path::Path};
+
+// -- crate imports
+use chrono::Local;
+use tokio::fs;
+
+// -- module imports
+use crate::report::{Finding, Report, Status};
+
+/// Largest file embedded as a preview; longer files are cut off.
+const MAX_PREVIEW_BYTES: usize = 64 * 1024;
+
+/// Inline stylesheet.
+const STYLE: &str = "\
+body{font-family:system-ui,sans-serif;margin:2em;color:#222}\
+h1{font-size:1.5em}h2{font-size:1.1em;margin-top:2em;font-family:monospace}\
+input{padding:.4em;width:24em;margin-bottom:1em}\
+table{border-collapse:collapse;width:100%}\
+th,td{border:1px solid #ccc;padding:.3em .5em;text-align:left;vertical-align:top}\
+th{background:#f0f0f0;cursor:pointer;user-select:none}\
+tr.broken td.status{color:#b00020;font-weight:bold}\
+tr.skipped td.status,tr.suppressed td.status{color:#777}\
+tr.timed_out td.status{color:#b26a00}\
+ul{margin:0;padding-left:1.2em}\
+pre{background:#f8f8f8;padding:.5em;overflow:auto;max-height:30em}\
+.warnings{background:#fff8e1;padding:.5em 1em}";
+
+/// Inline script: column sorting and the search filter.
+const SCRIPT: &str = "\
+document.querySelectorAll('th').forEach(function(th){th.addEventListener('click',function(){\
+var table=th.closest('table'),body=table.tBodies[0],idx=th.cellIndex,\
+asc=th.dataset.order!=='asc';th.dataset.order=asc?'asc':'desc';\
+Array.from(body.rows).sort(function(a,b){\
+var x=a.cells[idx].textContent,y=b.cells[idx].textContent;\
+return asc?x.localeCompare(y):y.localeCompare(x);}).forEach(function(r){body.appendChild(r);});});});\
+document.getElementById('filter').addEventListener('input',function(e){\
+var q=e.target.value.toLowerCase();\
+document.querySelectorAll('tbody tr').forEach(function(r){\
+r.style.display=r.textContent.toLowerCase().indexOf(q)<0?'none':'';});});";
+
+/// Render `report` as a standalone HTML page.
+pub async fn render(report: &Report) -> String {
+    let mut sources = BTreeMap::<&Path, Vec<&Finding>>::new();
+    for f in &report.findings {
+        let dir = f.desktop_file.parent().unwrap_or(Path::new(""));
+        sources.entry(dir).or_default().push(f);
+    }
+    let broken = report.findings.iter().filter(|f| f.is_broken()).count();
+
+    let mut out =
+        String::from("<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n");
+    let _ = writeln!(
+        out,
+        "<title>desktop-scout report</title>\n<style>{STYLE}</style>"
+    );
+    out.push_str("</head>\n<body>\n<h1>desktop-scout report</h1>\n");
+    let _ = writeln!(
+        out,
+        "<p>Generated {} by desktop-scout {}: {} reported entries, {broken} broken.</p>",
+        Local::now().format("%Y-%m-%d %H:%M"),
+        env!("CARGO_PKG_VERSION"),
+        report.findings.len()
+    );
+
+    if !report.warnings.is_empty() {
+        out.push_str("<div class=\"warnings\"><strong>Scan warnings</strong><ul>\n");
+        for w in &report.warnings {
+            let _ = writeln!(
+                out,
+                "<li><code>{}</code> {}</li>",
+                w.code,
+                escape(&w.message)
+            );
+        }
+        out.push_str("</ul></div>\n");
+    }
+
+    out.push_str("<input id=\"filter\" type=\"search\" placeholder=\"Filter entries...\">\n");
+    for (dir, findings) in sources {
+        let _ = writeln!(
+            out,
+            "<h2>{} ({})</h2>",
+            escape(&dir.display().to_string()),
+            findings.len()
+        );
+        out.push_str(
+            "<table>\n<thead><tr><th>File</th><th>Name</th><th>Status</th><th>Code</th>\
+             <th>Details</th></tr></thead>\n<tbody>\n",
+        );
+        for f in findings {
+            row(f, &mut out).await;
+        }
+        out.push_str("</tbody>\n</table>\n");
+    }
+
+    let _ = writeln!(out, "<script>{SCRIPT}</script>\n</body>\n</html>");
+    out
+}
+
+/// Append the table row of `f`, with its raw file as a collapsible preview.
+async fn row(f: &Finding, out: &mut String) {
+    let (code, reason) = match &f.status {
+        Status::Ok { .. } => (String::new(), None),
+        Status::Broken { code, reason }
+        | Status::Skipped { code, reason }
+        | Status::Suppressed { code, reason, .. }
+        | Status::TimedOut { code, reason } => (code.to_string(), Some(reason.as_str())),
+    };
+    let file_name = f
+        .desktop_file
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default();
+
+    let _ = writeln!(
+        out,
+        "<tr class=\"{kind}\"><td title=\"{path}\">{name}</td><td>{label}</td>\
+         <td class=\"status\">{kind}</td><td><code>{code}</code></td><td>",
+        kind = f.status.kind(),
+        path = escape(&f.desktop_file.display().to_string()),
+        name = escape(&file_name),
+        label = escape(f.name.as_deref().unwrap_or_default()),
+    );
+    if let Some(reason) = reason {
+        let _ = writeln!(out, "<div>{}</div>", escape(reason));
+    }
+    if let Some(exec) = &f.exec {
+        let _ = writeln!(out, "<div>Exec: <code>{}</code></div>", escape(exec));
+    }
+    if let Some(exec) = &f.suggested_exec {
+        let _ = writeln!(
+            out,
+            "<div>Suggested Exec: <code>{}</code></div>",
+            escape(exec)
+        );
+    }
+    if !f.warnings.is_empty() {
+        out.push_str("<ul>\n");
+        for w in &f.warnings {
+            let _ = writeln!(
+                out,
+                "<li><code>{}</code> {}</li>",
+                w.code,
+                escape(&w.message)
+            );
+        }
+        out.push_str("</ul>\n");
+    }
+    let _ = writeln!(
+        out,
+        "<details><summary>File</summary><pre>{}</pre></details></td></tr>",
+        escape(&preview(&f.desktop_file).await)
+    );
+}
+
+/// Content of `path` for the preview, cut off after [`MAX_PREVIEW_BYTES`].
+async fn preview(path: &Path) -> String {
+    match fs::read(path).await {
+        Ok(bytes) if bytes.len() > MAX_PREVIEW_BYTES => format!(
+            "{}\n[... {} more bytes]",
+            String::from_utf8_lossy(&bytes[..MAX_PREVIEW_BYTES]),
+            bytes.len() - MAX_PREVIEW_BYTES
+        ),
+        Ok(bytes) => String::from_utf8_lossy(&bytes).into_owned(),
+        Err(e) => format!("(could not read the file: {e})"),
+    }
+}
+
+/// Escape `s` for HTML text and attribute values.
+fn escape(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn escapes_text_and_attributes() {
+        assert_eq!(
+            escape(r#"<a href="x">'Tom' & Jerry</a>"#),
+            "&lt;a href=&quot;x&quot;&gt;&#39;Tom&#39; &amp; Jerry&lt;/a&gt;"
+        );
+        assert_eq!(escape("plain Exec=foo %U"), "plain Exec=foo %U");
+    }
+}
@@ -0,0 +1,168 @@
+//! Localization of human-readable output.
+//!
+//! Messages are [Fluent](https://projectfluent.org) resources embedded from
+//! `i18n/<locale>/desktop-scout.ftl`. The locale is taken from `LC_ALL`, `LC_MESSAGES` or `LANG`
+//! (in that order, as gettext does); unknown locales fall back to English. Reason codes, JSON keys
+//! and finding IDs are never translated, so scripts keep working in every locale.
+
+// -- std imports
+use std::{env, sync::OnceLock};
+
+// -- crate imports
+use fluent_bundle::{FluentArgs, FluentResource, FluentValue, concurrent::FluentBundle};
+use tracing::{debug, warn};
+use unic_langid::LanguageIdentifier;
+
+/// Locale and messages used when no translation matches.
+const FALLBACK: (&str, &str) = ("en-US", include_str!("../i18n/en-US/desktop-scout.ftl"));
+
+/// Available translations besides [`FALLBACK`].
+const TRANSLATIONS: &[(&str, &str)] = &[("de", include_str!("../i18n/de/desktop-scout.ftl"))];
+
+/// Bundles for the user's locale, loaded on first use.
+static LOCALIZER: OnceLock<Localizer> = OnceLock::new();
+
+/// Message bundles in lookup order: the user's translation (if any), then [`FALLBACK`].
+struct Localizer {
+    bundles: Vec<FluentBundle<FluentResource>>,
+}
+
+impl Localizer {
+    fn new() -> Self {
+        let mut bundles = Vec::new();
+        if let Some(requested) = requested_locale()
+            && let Some((locale, source)) = TRANSLATIONS
+                .iter()
+                .find(|(l, _)| matches_locale(l, &requested))
+        {
+            debug!(locale, "Using translation");
+            bundles.extend(bundle(locale, source));
+        }
+        bundles.extend(bundle(FALLBACK.0, FALLBACK.1));
+        Localizer { bundles }
+    }
+}
+
+/// Format the message `id` with `args` in the user's locale.
+///
+/// Falls back to English for messages missing from the translation, and to `id` itself if the
+/// message does not exist at all.
+pub fn tr(id: &str, args: &[(&str, FluentValue<'_>)]) -> String {
+    let localizer = LOCALIZER.get_or_init(Localizer::new);
+
+    let mut fluent_args = FluentArgs::new();
+    for (name, value) in args {
+        fluent_args.set(*name, value.clone());
+    }
+
+    for bundle in &localizer.bundles {
+        if let Some(pattern) = bundle.get_message(id).and_then(|m| m.value()) {
+            let mut errors = Vec::new();
+            let text = bundle.format_pattern(pattern, Some(&fluent_args), &mut errors);
+            if !errors.is_empty() {
+                debug!(id, ?errors, "Errors while formatting message");
+            }
+            return text.into_owned();
+        }
+    }
+
+    warn!(id, "Missing message");
+    id.to_string()
+}
+
+/// Build a bundle for `locale` from Fluent `source`.
+fn bundle(locale: &str, source: &str) -> Option<FluentBundle<FluentResource>> {
+    let langid = locale.parse::<LanguageIdentifier>().ok()?;
+    let resource = FluentResource::try_new(source.to_string()).unwrap_or_else(|(res, errors)| {
+        warn!(locale, ?errors, "Translation contains errors");
+        res
+    });
+
+    let mut bundle = FluentBundle::new_concurrent(vec![langid]);
+    // Unicode isolation marks show up as garbage in many terminals.
+    bundle.set_use_isolating(false);
+    if let Err(errors) = bundle.add_resource(resource) {
+        warn!(locale, ?errors, "Translation contains duplicate messages");
+    }
+    Some(bundle)
+}
+
+/// The locale requested via `LC_ALL`, `LC_MESSAGES` or `LANG` (e.g. `de_DE.UTF-8` → `de-DE`).
+///
+/// Returns `None` for the `C`/`POSIX` locales and unparsable values.
+fn requested_locale() -> Option<LanguageIdentifier> {
+    let value = ["LC_ALL", "LC_MESSAGES", "LANG"]
+        .iter()
+        .filter_map(|v| env::var(v).ok())
+        .find(|v| !v.is_empty())?;
+
+    // Strip codeset and modifier: `de_DE.UTF-8@euro` → `de_DE`.
+    let tag = value.split(['.', '@']).next().unwrap_or_default();
+    if tag == "C" || tag == "POSIX" {
+        return None;
+    }
+    tag.replace('_', "-").parse().ok()
+}
+
+/// Whether the translation `locale` serves `requested` (same language, and same region if the
+/// translation has one).
+fn matches_locale(locale: &str, requested: &LanguageIdentifier) -> bool {
+    let Ok(available) = locale.parse::<LanguageIdentifier>() else {
+        return false;
+    };
+    available.language == requested.language
+        && available.region.is_none_or(|r| Some(r) == requested.region)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{explain, report::ReasonCode};
+
+    #[test]
+    fn translations_parse_and_cover_every_reason_code() {
+        for (locale, source) in TRANSLATIONS.iter().chain([&FALLBACK]) {
+            let resource = FluentResource::try_new(source.to_string())
+                .unwrap_or_else(|(_, errors)| panic!("{locale}: {errors:?}"));
+            let mut bundle = FluentBundle::new_concurrent(vec![locale.parse().unwrap()]);
+            bundle
+                .add_resource(resource)
+                .unwrap_or_else(|errors| panic!("{locale}: {errors:?}"));
+
+            for code in ReasonCode::ALL {
+                let id = explain::message_id(*code);
+                for id in [
+                    id.clone(),
+                    format!("{id}-causes"),
+                    format!("{id}-remediation"),
+                ] {
+                    assert!(bundle.has_message(&id), "{locale}: missing {id}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn translations_have_the_messages_of_the_fallback() {
+        let ids = |source: &str| {
+            source
+                .lines()
+                .filter_map(|l| l.split_once(" =").map(|(id, _)| id))
+                .filter(|id| !id.is_empty() && !id.starts_with([' ', '#']))
+                .map(str::to_string)
+                .collect::<Vec<_>>()
+        };
+        let fallback = ids(FALLBACK.1);
+        for (locale, source) in TRANSLATIONS {
+            assert_eq!(ids(source), fallback, "{locale}");
+        }
+    }
+
+    #[test]
+    fn matches_language_and_region() {
+        let de_at = "de-AT".parse().unwrap();
+        assert!(matches_locale("de", &de_at));
+        assert!(!matches_locale("de-DE", &de_at));
+        assert!(!matches_locale("fr", &de_at));
+    }
+}
@@ -0,0 +1,349 @@
+//! Application identities across origins (`--group-by app`).
+//!
+//! One application often has several entries: the launcher of the distribution package, a
+//! Flatpak export of the same app, a user override in `~/.local/share/applications`, an autostart
+//! copy. Entries are taken to belong to the same application if they share any of:
+//!
+//! - the desktop ID (overrides and autostart copies keep the file name)
+//! - `StartupWMClass=` (the windows of both entries are matched to the same launcher)
+//! - the executable, if the entry runs it without extra arguments and it is not a launch
+//!   wrapper or interpreter (`flatpak`, `env`, `python3`), which many unrelated apps share
+//! - the AppStream component ID, from `X-Flatpak=` of Flatpak exports or from the
+//!   `<launchable type="desktop-id">` of the installed AppStream metainfo files
+//!
+//! The relation is transitive, so a native entry and a Flatpak export with different desktop IDs
+//! are joined through the metainfo file of the native package.
+
+// -- std imports
+use std::{
+    collections::HashMap,
+    fmt,
+    path::{Path, PathBuf},
+};
+
+// -- crate imports
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use tokio::fs;
+use xdg::BaseDirectories;
+
+// -- module imports
+use crate::{
+    check, desktop,
+    report::{Finding, Status},
+};
+
+/// Executables that launch other applications, so sharing one says nothing about identity.
+const LAUNCH_WRAPPERS: &[&str] = &[
+    "bash",
+    "dash",
+    "electron",
+    "env",
+    "exo-open",
+    "flatpak",
+    "gio",
+    "gtk-launch",
+    "java",
+    "kioclient",
+    "kioclient5",
+    "lutris",
+    "mono",
+    "node",
+    "perl",
+    "python",
+    "python3",
+    "sh",
+    "snap",
+    "steam",
+    "systemd-run",
+    "wine",
+    "xdg-open",
+    "zsh",
+];
+
+/// One logical application and its entries.
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct Application {
+    /// The AppStream component ID if one is known, otherwise the desktop ID (without
+    /// `.desktop`) of the first entry.
+    pub id: String,
+
+    /// `Name=` of the first entry that has one.
+    pub name: Option<String>,
+
+    /// The entries, sorted by path.
+    pub entries: Vec<AppEntry>,
+}
+
+/// An entry of an [`Application`].
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct AppEntry {
+    /// Path to the `.desktop` file.
+    pub desktop_file: PathBuf,
+
+    /// Where the entry comes from.
+    pub origin: Origin,
+
+    /// Inspection outcome of the entry.
+    pub status: Status,
+
+    /// Number of lint warnings of the entry.
+    pub warnings: usize,
+}
+
+/// Where an entry of an [`Application`] comes from, judged by its path.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Origin {
+    /// A data directory of the system or the user.
+    Native,
+    /// Flatpak exports.
+    Flatpak,
+    /// Snap desktop exports.
+    Snap,
+    /// An autostart directory.
+    Autostart,
+}
+
+impl Origin {
+    /// Origin of the entry at `path`.
+    pub fn of(path: &Path) -> Self {
+        let path = path.to_string_lossy();
+        // Every Flatpak installation, custom ones included, exports to `<dir>/exports/share`.
+        if path.contains("/exports/share/applications/") {
+            Origin::Flatpak
+        } else if path.contains("/snapd/desktop/") {
+            Origin::Snap
+        } else if path.contains("/autostart/") {
+            Origin::Autostart
+        } else {
+            Origin::Native
+        }
+    }
+}
+
+impl fmt::Display for Origin {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Origin::Native => "native",
+            Origin::Flatpak => "flatpak",
+            Origin::Snap => "snap",
+            Origin::Autostart => "autostart",
+        })
+    }
+}
+
+/// Group `findings` into applications, sorted by ID.
+///
+/// Files are read again for `StartupWMClass=` and `X-Flatpak=`; unreadable files are grouped by
+/// their desktop ID and executable only.
+pub async fn group(findings: &[Finding]) -> Vec<Application> {
+    let metainfo = metainfo_ids().await;
+
+    let mut sets = DisjointSets::new(findings.len());
+    let mut owners = HashMap::<String, usize>::new();
+    let mut appstream_ids = vec![None; findings.len()];
+
+    for (idx, f) in findings.iter().enumerate() {
+        let kv = match fs::read_to_string(&f.desktop_file).await {
+            Ok(content) => desktop::parse_desktop_entry_section(&content),
+            Err(_) => HashMap::new(),
+        };
+        let desktop_id = normalized_id(&desktop::desktop_id(&f.desktop_file));
+        let appstream_id = kv
+            .get("X-Flatpak")
+            .map(|id| normalized_id(id))
+            .or_else(|| metainfo.get(&desktop_id).cloned());
+
+        let mut keys = vec![format!("desktop:{desktop_id}")];
+        if let Some(class) = kv.get("StartupWMClass").filter(|c| !c.trim().is_empty()) {
+            keys.push(format!("wm:{}", class.trim().to_lowercase()));
+        }
+        if let Some(exe) = identifying_executable(f) {
+            keys.push(format!("exec:{}", exe.display()));
+        }
+        if let Some(id) = &appstream_id {
+            keys.push(format!("appstream:{id}"));
+        }
+        appstream_ids[idx] = appstream_id;
+
+        for key in keys {
+            match owners.get(&key) {
+                Some(&owner) => sets.union(owner, idx),
+                None => {
+                    owners.insert(key, idx);
+                }
+            }
+        }
+    }
+
+    let mut members = HashMap::<usize, Vec<usize>>::new();
+    for idx in 0..findings.len() {
+        members.entry(sets.find(idx)).or_default().push(idx);
+    }
+
+    let mut apps = members
+        .into_values()
+        .map(|mut idxs| {
+            idxs.sort_by(|a, b| findings[*a].desktop_file.cmp(&findings[*b].desktop_file));
+            let id = idxs
+                .iter()
+                .find_map(|i| appstream_ids[*i].clone())
+                .unwrap_or_else(|| {
+                    normalized_id(&desktop::desktop_id(&findings[idxs[0]].desktop_file))
+                });
+            Application {
+                id,
+                name: idxs.iter().find_map(|i| findings[*i].name.clone()),
+                entries: idxs
+                    .into_iter()
+                    .map(|i| {
+                        let f = &findings[i];
+                        AppEntry {
+                            desktop_file: f.desktop_file.clone(),
+                            origin: Origin::of(&f.desktop_file),
+                            status: f.status.clone(),
+                            warnings: f.warnings.len(),
+                        }
+                    })
+                    .collect(),
+            }
+        })
+        .collect::<Vec<_>>();
+    apps.sort_by(|a, b| a.id.cmp(&b.id));
+    apps
+}
+
+/// `id` lowercased and without a `.desktop` suffix.
+fn normalized_id(id: &str) -> String {
+    let id = id.trim();
+    id.strip_suffix(".desktop").unwrap_or(id).to_lowercase()
+}
+
+/// The executable identifying the app of `f`: the resolved (or, for broken entries, the
+/// absolute) executable of an `Exec=` line without arguments other than field codes, unless it
+/// is one of the [`LAUNCH_WRAPPERS`].
+fn identifying_executable(f: &Finding) -> Option<PathBuf> {
+    let tokens = shlex::split(f.exec.as_deref()?)?;
+    let (_, command) = desktop::unwrap_exec_prefixes(&tokens);
+    if command.iter().skip(1).any(|arg| !arg.starts_with('%')) {
+        return None;
+    }
+
+    let exe = match &f.status {
+        Status::Ok {
+            resolved_executable,
+        } => resolved_executable.clone()?,
+        _ => PathBuf::from(check::exec_command_name(f.exec.as_deref()?)?),
+    };
+    let name = exe.file_name()?.to_string_lossy();
+    (exe.is_absolute() && !LAUNCH_WRAPPERS.contains(&name.as_ref())).then_some(exe)
+}
+
+/// Map from desktop ID to AppStream component ID (both normalized, see [`normalized_id`]) of
+/// the metainfo files in the XDG data directories.
+async fn metainfo_ids() -> HashMap<String, String> {
+    let xdg = BaseDirectories::new();
+    let mut roots = xdg.get_data_home().into_iter().collect::<Vec<_>>();
+    roots.extend(xdg.get_data_dirs());
+
+    let mut ids = HashMap::new();
+    for root in roots {
+        for dir in [root.join("metainfo"), root.join("appdata")] {
+            let Ok(mut entries) = fs::read_dir(&dir).await else {
+                continue;
+            };
+            while let Ok(Some(entry)) = entries.next_entry().await {
+                if entry.path().extension().is_none_or(|e| e != "xml") {
+                    continue;
+                }
+                let Ok(xml) = fs::read_to_string(entry.path()).await else {
+                    continue;
+                };
+                if let Some((id, launchables)) = parse_metainfo(&xml) {
+                    for desktop_id in launchables {
+                        ids.entry(desktop_id).or_insert_with(|| id.clone());
+                    }
+                }
+            }
+        }
+    }
+    ids
+}
+
+/// Component ID and desktop IDs launched by an AppStream metainfo document.
+///
+/// Old documents without `<launchable>` name the desktop file in their ID (`foo.desktop`).
+fn parse_metainfo(xml: &str) -> Option<(String, Vec<String>)> {
+    let id = element_text(xml, "id")?.0;
+    let mut launchables = Vec::new();
+
+    let mut rest = xml;
+    while let Some((text, open_tag, after)) = element_text(rest, "launchable") {
+        if open_tag.contains("desktop-id") {
+            launchables.push(normalized_id(&text));
+        }
+        rest = after;
+    }
+    if launchables.is_empty() && id.trim().ends_with(".desktop") {
+        launchables.push(normalized_id(&id));
+    }
+    Some((normalized_id(&id), launchables))
+}
+
+/// Text of the first `<name>` element in `xml`, with its opening tag and the rest of the
+/// document after it.
+fn element_text<'a>(xml: &'a str, name: &str) -> Option<(String, &'a str, &'a str)> {
+    let open = format!("<{name}");
+    let close = format!("</{name}>");
+
+    let mut rest = xml;
+    loop {
+        let start = rest.find(&open)?;
+        let after_name = &rest[start + open.len()..];
+        // `<id` must not match `<identifier>`.
+        if !after_name.starts_with(['>', ' ', '\t', '\n']) {
+            rest = after_name;
+            continue;
+        }
+        let tag_end = after_name.find('>')?;
+        let body = &after_name[tag_end + 1..];
+        let end = body.find(&close)?;
+        return Some((
+            body[..end].trim().to_string(),
+            &after_name[..tag_end],
+            &body[end + close.len()..],
+        ));
+    }
+}
+
+/// Union-find over entry indices.
+struct DisjointSets {
+    parent: Vec<usize>,
+}
+
+impl DisjointSets {
+    /// `n` singleton sets.
+    fn new(n: usize) -> Self {
+        DisjointSets {
+            parent: (0..n).collect(),
+        }
+    }
+
+    /// Representative of the set containing `i`.
+    fn find(&mut self, mut i: usize) -> usize {
+        while self.parent[i] != i {
+            self.parent[i] = self.parent[self.parent[i]];
+            i = self.parent[i];
+        }
+        i
+    }
+
+    /// Merge the sets containing `a` and `b`.
+    fn union(&mut self, a: usize, b: usize) {
+        let (a, b) = (self.find(a), self.find(b));
+        if a != b {
+            self.parent[b] = a;
+        }
+    }
+}
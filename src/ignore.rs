@@ -0,0 +1,233 @@
+//! Ignore and allowlist glob patterns (`--ignore`, `--allow`, `.desktopscoutignore`) and file
+//! name filters (`--include`, `--exclude`).
+//!
+//! Generated launchers (Steam's `steam_app_*.desktop`, Wine's `wine-extension-*.desktop`) come
+//! and go with their applications and are often broken in bulk. `--ignore` drops them from the
+//! directory walk; `--allow` keeps selected files an ignore pattern would drop.
+//!
+//! `--include` and `--exclude` narrow a scan by file name only (`--include 'org.kde.*'` scans
+//! the KDE applications); `--allow` does not override them.
+//!
+//! Patterns without a `/` match the file name, patterns with one the full path, so
+//! `steam_app_*.desktop` works in every directory and `/opt/vendor/**` only below `/opt/vendor`.
+//!
+//! A [`IGNORE_FILE_NAME`] file in a scanned directory lists patterns for that directory and
+//! everything below it, like `.gitignore`:
+//! - blank lines and lines starting with `#` are skipped
+//! - `!pattern` includes files again that an earlier pattern ignored
+//! - `pattern/` only matches directories, which are then not descended into
+//! - patterns with a `/` match the path relative to the directory of the file, others the name
+//!
+//! The last matching pattern decides, and files in deeper directories take precedence.
+
+// -- std imports
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+// -- crate imports
+use anyhow::{Context, Result};
+use globset::{Glob, GlobBuilder, GlobMatcher, GlobSet, GlobSetBuilder};
+use tracing::warn;
+
+// -- module imports
+use crate::retry;
+
+/// Name of the per-directory ignore file.
+pub const IGNORE_FILE_NAME: &str = ".desktopscoutignore";
+
+/// Compiled patterns, split by what they match against.
+#[derive(Debug)]
+struct Patterns {
+    /// Patterns matching the file name.
+    names: GlobSet,
+
+    /// Patterns matching the full path.
+    paths: GlobSet,
+}
+
+impl Patterns {
+    /// Compile `globs`.
+    fn new(globs: &[Glob]) -> Result<Self> {
+        let mut names = GlobSetBuilder::new();
+        let mut paths = GlobSetBuilder::new();
+        for glob in globs {
+            if glob.glob().contains('/') {
+                paths.add(glob.clone());
+            } else {
+                names.add(glob.clone());
+            }
+        }
+        Ok(Patterns {
+            names: names.build().context("Failed to compile glob patterns")?,
+            paths: paths.build().context("Failed to compile glob patterns")?,
+        })
+    }
+
+    /// Whether any pattern matches `path`.
+    fn matches(&self, path: &Path) -> bool {
+        self.paths.is_match(path) || path.file_name().is_some_and(|n| self.names.is_match(n))
+    }
+}
+
+/// Ignore patterns with their allowlist.
+#[derive(Debug)]
+pub struct IgnoreRules {
+    /// Files to leave out of the scan.
+    ignore: Patterns,
+
+    /// Files to scan even though an ignore pattern matches.
+    allow: Patterns,
+}
+
+impl IgnoreRules {
+    /// Compile the `ignore` and `allow` patterns.
+    ///
+    /// # Errors
+    /// - [`anyhow::Error`] if the patterns cannot be compiled.
+    pub fn new(ignore: &[Glob], allow: &[Glob]) -> Result<Self> {
+        Ok(IgnoreRules {
+            ignore: Patterns::new(ignore)?,
+            allow: Patterns::new(allow)?,
+        })
+    }
+
+    /// Whether the file or directory `path` is left out of the scan, given the ignore files of
+    /// its directory and the directories above it (outermost first). `--ignore` and `--allow`
+    /// only apply to files; `--allow` also keeps files ignored by an ignore file.
+    pub fn is_ignored(&self, path: &Path, is_dir: bool, files: &[Arc<IgnoreFile>]) -> bool {
+        if !is_dir && self.allow.matches(path) {
+            return false;
+        }
+        (!is_dir && self.ignore.matches(path))
+            || files.iter().rev().find_map(|f| f.decide(path, is_dir)) == Some(true)
+    }
+}
+
+/// File name filters (`--include`, `--exclude`).
+#[derive(Debug)]
+pub struct NameFilter {
+    /// Names to scan (every name if `None`).
+    include: Option<GlobSet>,
+
+    /// Names to leave out, even if included.
+    exclude: GlobSet,
+}
+
+impl NameFilter {
+    /// Compile the `include` and `exclude` patterns.
+    ///
+    /// # Errors
+    /// - [`anyhow::Error`] if the patterns cannot be compiled.
+    pub fn new(include: &[Glob], exclude: &[Glob]) -> Result<Self> {
+        let build = |globs: &[Glob]| {
+            let mut set = GlobSetBuilder::new();
+            for glob in globs {
+                set.add(glob.clone());
+            }
+            set.build().context("Failed to compile glob patterns")
+        };
+        Ok(NameFilter {
+            include: (!include.is_empty()).then(|| build(include)).transpose()?,
+            exclude: build(exclude)?,
+        })
+    }
+
+    /// Whether the file at `path` is scanned.
+    pub fn keeps(&self, path: &Path) -> bool {
+        let Some(name) = path.file_name() else {
+            return false;
+        };
+        self.include.as_ref().is_none_or(|i| i.is_match(name)) && !self.exclude.is_match(name)
+    }
+}
+
+/// A pattern of an ignore file.
+#[derive(Debug)]
+struct IgnorePattern {
+    /// The compiled glob.
+    matcher: GlobMatcher,
+
+    /// Whether the pattern includes matches again (`!pattern`).
+    negated: bool,
+
+    /// Whether the pattern only matches directories (`pattern/`).
+    dir_only: bool,
+
+    /// Whether the pattern matches the relative path instead of the name (contains a `/`).
+    anchored: bool,
+}
+
+/// The patterns of a [`IGNORE_FILE_NAME`] file.
+#[derive(Debug)]
+pub struct IgnoreFile {
+    /// Directory containing the file; anchored patterns are relative to it.
+    dir: PathBuf,
+
+    /// Patterns in file order.
+    patterns: Vec<IgnorePattern>,
+}
+
+impl IgnoreFile {
+    /// Load the ignore file of `dir`, if it has one with any patterns. Invalid patterns are
+    /// logged and skipped.
+    pub async fn load(dir: &Path) -> Option<Self> {
+        let path = dir.join(IGNORE_FILE_NAME);
+        let text = retry::read_to_string(&path).await.ok()?;
+        let file = IgnoreFile::parse(dir, &text, &path);
+        (!file.patterns.is_empty()).then_some(file)
+    }
+
+    /// Parse the contents `text` of the ignore file at `path` in `dir`.
+    fn parse(dir: &Path, text: &str, path: &Path) -> Self {
+        let mut patterns = Vec::new();
+        for (n, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (negated, pattern) = match line.strip_prefix('!') {
+                Some(rest) => (true, rest),
+                None => (false, line),
+            };
+            let (dir_only, pattern) = match pattern.strip_suffix('/') {
+                Some(rest) => (true, rest),
+                None => (false, pattern),
+            };
+            let anchored = pattern.contains('/');
+            let pattern = pattern.strip_prefix('/').unwrap_or(pattern);
+
+            match GlobBuilder::new(pattern).literal_separator(true).build() {
+                Ok(glob) => patterns.push(IgnorePattern {
+                    matcher: glob.compile_matcher(),
+                    negated,
+                    dir_only,
+                    anchored,
+                }),
+                Err(e) => warn!(
+                    file = %path.display(),
+                    line = n + 1,
+                    "Skipping invalid ignore pattern: {e}"
+                ),
+            }
+        }
+        IgnoreFile {
+            dir: dir.to_path_buf(),
+            patterns,
+        }
+    }
+
+    /// Whether this file ignores (`Some(true)`) or includes again (`Some(false)`) `path`;
+    /// `None` if no pattern matches.
+    fn decide(&self, path: &Path, is_dir: bool) -> Option<bool> {
+        let relative = path.strip_prefix(&self.dir).ok()?;
+        let name = Path::new(path.file_name()?);
+        self.patterns
+            .iter()
+            .rev()
+            .filter(|p| is_dir || !p.dir_only)
+            .find(|p| p.matcher.is_match(if p.anchored { relative } else { name }))
+            .map(|p| !p.negated)
+    }
+}
@@ -0,0 +1,293 @@
+//! Append-only journal of mutating actions (`history` subcommand).
+//!
+//! Every change desktop-scout makes to the system (fixes and their undos, quarantine restores,
+//! baseline acceptances) is appended as one JSON line to
+//! `$XDG_STATE_HOME/desktop-scout/journal.jsonl`, together with when, by whom and on which host it
+//! happened. Entries of one invocation share a run ID. This matters once scans and fixes run from
+//! timers on shared machines.
+
+// -- std imports
+use std::{
+    env,
+    io::ErrorKind,
+    os::unix::fs::MetadataExt,
+    path::{Path, PathBuf},
+    process,
+};
+
+// -- crate imports
+use anyhow::{Context, Result};
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+use tokio::{fs, io::AsyncWriteExt};
+use xdg::BaseDirectories;
+
+// -- module imports
+use crate::{
+    fix::{FixOutcome, FixReport, FixResult},
+    i18n::tr,
+};
+
+/// File name of the journal inside the state directory.
+const JOURNAL_NAME: &str = "journal.jsonl";
+
+/// A single journaled action.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JournalEntry {
+    /// When the action was performed.
+    pub time: DateTime<Local>,
+
+    /// ID shared by all entries of one invocation.
+    pub run: String,
+
+    /// User who ran desktop-scout.
+    pub user: String,
+
+    /// Host desktop-scout ran on.
+    pub host: String,
+
+    /// What was done.
+    #[serde(flatten)]
+    pub action: JournalAction,
+}
+
+/// A mutating action.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum JournalAction {
+    /// A broken entry was changed by `fix` (skipped entries are not journaled).
+    Fix {
+        /// What happened to the entry.
+        #[serde(flatten)]
+        outcome: FixOutcome,
+    },
+
+    /// A quarantined entry was restored.
+    Restore {
+        /// Quarantine ID of the entry.
+        id: String,
+
+        /// Where the entry was restored to.
+        path: PathBuf,
+    },
+
+    /// A journaled fix was reverted by `undo`.
+    Undo {
+        /// Run ID of the reverted fix.
+        fix_run: String,
+
+        /// The `.desktop` file the reverted fix changed.
+        desktop_file: PathBuf,
+    },
+
+    /// The current broken entries were accepted into a baseline file.
+    Baseline {
+        /// The baseline file that was written.
+        path: PathBuf,
+
+        /// Number of accepted findings.
+        recorded: usize,
+    },
+}
+
+/// Writer for the journal of the current invocation.
+#[derive(Debug)]
+pub struct Journal {
+    path: PathBuf,
+    run: String,
+    user: String,
+    host: String,
+}
+
+impl Journal {
+    /// Prepare journaling for this invocation; nothing is written until [`Journal::append`].
+    ///
+    /// # Errors
+    /// - [`anyhow::Error`] if the XDG state directory cannot be determined.
+    pub async fn open() -> Result<Self> {
+        Ok(Journal {
+            path: journal_path()?,
+            run: format!("{}-{}", Local::now().format("%Y%m%dT%H%M%S"), process::id()),
+            user: current_user().await,
+            host: current_host().await,
+        })
+    }
+
+    /// Append `action` to the journal.
+    ///
+    /// # Errors
+    /// - [`anyhow::Error`] if the journal cannot be created or written.
+    pub async fn append(&self, action: JournalAction) -> Result<()> {
+        let entry = JournalEntry {
+            time: Local::now(),
+            run: self.run.clone(),
+            user: self.user.clone(),
+            host: self.host.clone(),
+            action,
+        };
+        let line = serde_json::to_string(&entry)? + "\n";
+
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir)
+                .await
+                .with_context(|| format!("Failed to create {}", dir.display()))?;
+        }
+        // A single write of one line with O_APPEND keeps concurrent runs from interleaving.
+        let mut file = fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .await
+            .with_context(|| format!("Failed to open {}", self.path.display()))?;
+        file.write_all(line.as_bytes())
+            .await
+            .with_context(|| format!("Failed to write {}", self.path.display()))
+    }
+
+    /// Append one entry per changed entry of `fix`.
+    ///
+    /// # Errors
+    /// - [`anyhow::Error`] if the journal cannot be written.
+    pub async fn append_fix(&self, fix: &FixReport) -> Result<()> {
+        for outcome in &fix.outcomes {
+            if !matches!(outcome.result, FixResult::Skipped { .. }) {
+                self.append(JournalAction::Fix {
+                    outcome: outcome.clone(),
+                })
+                .await?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Read all journal entries, oldest first (empty if nothing was journaled yet).
+///
+/// # Errors
+/// - [`anyhow::Error`] if the journal exists but cannot be read or a line is not a valid entry.
+pub async fn read() -> Result<Vec<JournalEntry>> {
+    let path = journal_path()?;
+    let content = match fs::read_to_string(&path).await {
+        Ok(c) => c,
+        Err(e) if e.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => {
+            return Err(e).with_context(|| format!("Failed to read {}", path.display()));
+        }
+    };
+
+    content
+        .lines()
+        .enumerate()
+        .filter(|(_, l)| !l.trim().is_empty())
+        .map(|(n, l)| {
+            serde_json::from_str(l).with_context(|| {
+                format!("{}:{} is not a valid journal entry", path.display(), n + 1)
+            })
+        })
+        .collect()
+}
+
+/// Location of the journal file.
+fn journal_path() -> Result<PathBuf> {
+    Ok(BaseDirectories::with_prefix("desktop-scout")
+        .get_state_home()
+        .context("Could not determine XDG state directory for the journal")?
+        .join(JOURNAL_NAME))
+}
+
+/// Name of the invoking user (`$USER`, `$LOGNAME`, or the numeric UID).
+async fn current_user() -> String {
+    if let Some(user) = ["USER", "LOGNAME"]
+        .iter()
+        .find_map(|v| env::var(v).ok().filter(|u| !u.is_empty()))
+    {
+        return user;
+    }
+    match fs::metadata("/proc/self").await {
+        Ok(meta) => format!("uid {}", meta.uid()),
+        Err(_) => "unknown".into(),
+    }
+}
+
+/// Host name of this machine.
+async fn current_host() -> String {
+    fs::read_to_string(Path::new("/proc/sys/kernel/hostname"))
+        .await
+        .ok()
+        .or_else(|| env::var("HOSTNAME").ok())
+        .map(|h| h.trim().to_string())
+        .filter(|h| !h.is_empty())
+        .unwrap_or_else(|| "unknown".into())
+}
+
+/// Print journal entries as a human-readable table, oldest first. The action labels are the
+/// journal's `action` values and stay untranslated.
+pub fn print_text(entries: &[JournalEntry]) {
+    if entries.is_empty() {
+        println!("{}", tr("journal-empty", &[]));
+        return;
+    }
+
+    for e in entries {
+        let (label, detail) = match &e.action {
+            JournalAction::Fix { outcome } => (
+                format!("fix/{}", fix_verb(&outcome.result)),
+                outcome.desktop_file.display().to_string(),
+            ),
+            JournalAction::Restore { id, path } => (
+                "restore".into(),
+                tr(
+                    "journal-restore",
+                    &[
+                        ("path", path.display().to_string().into()),
+                        ("id", id.clone().into()),
+                    ],
+                ),
+            ),
+            JournalAction::Undo {
+                fix_run,
+                desktop_file,
+            } => (
+                "undo".into(),
+                tr(
+                    "journal-undo",
+                    &[
+                        ("path", desktop_file.display().to_string().into()),
+                        ("run", fix_run.clone().into()),
+                    ],
+                ),
+            ),
+            JournalAction::Baseline { path, recorded } => (
+                "baseline".into(),
+                tr(
+                    "journal-baseline",
+                    &[
+                        ("path", path.display().to_string().into()),
+                        ("count", (*recorded).into()),
+                    ],
+                ),
+            ),
+        };
+        println!(
+            "{}  {}@{}  {label:<16} {detail}",
+            e.time.format("%Y-%m-%d %H:%M:%S"),
+            e.user,
+            e.host
+        );
+    }
+}
+
+/// Short label of a fix result.
+fn fix_verb(result: &FixResult) -> &'static str {
+    match result {
+        FixResult::Deleted { .. } => "deleted",
+        FixResult::PartiallyDeleted { .. } => "partially_deleted",
+        FixResult::Hidden { .. } => "hidden",
+        FixResult::Overridden { .. } => "overridden",
+        FixResult::Quarantined { .. } => "quarantined",
+        FixResult::Trashed { .. } => "trashed",
+        FixResult::Repaired { .. } => "repaired",
+        FixResult::Skipped { .. } => "skipped",
+        FixResult::Failed { .. } => "failed",
+    }
+}
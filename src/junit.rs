@@ -0,0 +1,160 @@
+//! JUnit XML reports of scans (`--output junit`).
+//!
+//! Every scanned `.desktop` file is a test case, grouped into one test suite per directory in
+//! report order (`--sort`): broken entries fail with their reason, skipped entries are skipped and
+//! lint warnings are attached as `system-out`, so CI systems can render a scan like any other test
+//! run. Each test case carries the finding ID as its `id` property, to match results to baselines
+//! and suppressions. Scan-level warnings go into the `system-err` of a separate `scan` suite
+//! without test cases.
+
+// -- std imports
+use std::{collections::BTreeMap, fmt::Write as _, path::Path};
+
+// -- module imports
+use crate::report::{Finding, Report, Status};
+
+/// Render `report` as a JUnit XML document.
+pub fn render(report: &Report) -> String {
+    let mut suites = BTreeMap::<&Path, Vec<&Finding>>::new();
+    for f in &report.findings {
+        let dir = f.desktop_file.parent().unwrap_or(Path::new(""));
+        suites.entry(dir).or_default().push(f);
+    }
+
+    let failures = report.findings.iter().filter(|f| f.is_broken()).count();
+    let errors = report.findings.iter().filter(|f| f.is_timed_out()).count();
+    let skipped = count_skipped(report.findings.iter());
+
+    let mut out = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    let _ = writeln!(
+        out,
+        "<testsuites name=\"desktop-scout\" tests=\"{}\" failures=\"{failures}\" errors=\"{errors}\" skipped=\"{skipped}\">",
+        report.findings.len()
+    );
+
+    if !report.warnings.is_empty() {
+        out.push_str(
+            "  <testsuite name=\"scan\" tests=\"0\" failures=\"0\" errors=\"0\" skipped=\"0\">\n",
+        );
+        out.push_str("    <system-err>");
+        for w in &report.warnings {
+            out.push_str(&escape(&format!("{}: {}\n", w.code, w.message)));
+        }
+        out.push_str("</system-err>\n  </testsuite>\n");
+    }
+
+    for (dir, findings) in suites {
+        let dir = dir.display().to_string();
+        let _ = writeln!(
+            out,
+            "  <testsuite name=\"{}\" tests=\"{}\" failures=\"{}\" errors=\"{}\" skipped=\"{}\">",
+            escape(&dir),
+            findings.len(),
+            findings.iter().filter(|f| f.is_broken()).count(),
+            findings.iter().filter(|f| f.is_timed_out()).count(),
+            count_skipped(findings.iter().copied())
+        );
+        for f in findings {
+            test_case(f, &dir, &mut out);
+        }
+        out.push_str("  </testsuite>\n");
+    }
+
+    out.push_str("</testsuites>\n");
+    out
+}
+
+/// Append the `testcase` element of `f` (in the suite of directory `dir`).
+fn test_case(f: &Finding, dir: &str, out: &mut String) {
+    let name = f
+        .desktop_file
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_else(|| f.desktop_file.display().to_string());
+    let _ = write!(
+        out,
+        "    <testcase classname=\"{}\" name=\"{}\"",
+        escape(dir),
+        escape(&name)
+    );
+
+    let outcome = match &f.status {
+        Status::Ok { .. } => None,
+        Status::Broken { code, reason } => Some(format!(
+            "<failure type=\"{code}\" message=\"{}\">{}</failure>",
+            escape(reason),
+            escape(&format!("{code}: {reason}"))
+        )),
+        Status::Skipped { code, reason } => Some(format!(
+            "<skipped message=\"{}\"/>",
+            escape(&format!("{code}: {reason}"))
+        )),
+        Status::Suppressed { code, reason, .. } => Some(format!(
+            "<skipped message=\"{}\"/>",
+            escape(&format!("suppressed {code}: {reason}"))
+        )),
+        // The test could not complete, which JUnit reports as an error rather than a failure.
+        Status::TimedOut { code, reason } => Some(format!(
+            "<error type=\"{code}\" message=\"{}\">{}</error>",
+            escape(reason),
+            escape(&format!("{code}: {reason}"))
+        )),
+    };
+
+    out.push_str(">\n");
+    let _ = writeln!(
+        out,
+        "      <properties><property name=\"id\" value=\"{}\"/></properties>",
+        escape(&f.id)
+    );
+    if let Some(outcome) = outcome {
+        let _ = writeln!(out, "      {outcome}");
+    }
+    if !f.warnings.is_empty() {
+        out.push_str("      <system-out>");
+        for w in &f.warnings {
+            out.push_str(&escape(&format!("{}: {}\n", w.code, w.message)));
+        }
+        out.push_str("</system-out>\n");
+    }
+    out.push_str("    </testcase>\n");
+}
+
+/// Number of skipped and suppressed findings in `findings` (both are reported as `<skipped>`).
+fn count_skipped<'a>(findings: impl Iterator<Item = &'a Finding>) -> usize {
+    findings
+        .filter(|f| matches!(f.status, Status::Skipped { .. } | Status::Suppressed { .. }))
+        .count()
+}
+
+/// Escape `s` for XML text and attribute values, dropping characters XML 1.0 cannot represent.
+fn escape(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&apos;"),
+            '\t' | '\n' | '\r' => out.push(c),
+            c if c.is_control() => {}
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn escapes_markup_and_drops_invalid_characters() {
+        assert_eq!(
+            escape("<a b=\"c\">'d' & e</a>"),
+            "&lt;a b=&quot;c&quot;&gt;&apos;d&apos; &amp; e&lt;/a&gt;"
+        );
+        assert_eq!(escape("a\tb\nc\u{1}\u{7f}d"), "a\tb\ncd");
+    }
+}
@@ -0,0 +1,64 @@
+//! desktop-scout library.
+//!
+//! The `desktop-scout` binary is a thin CLI on top of these modules. The optional `capi` and
+//! `python` features expose the scanner to C and Python programs (see `capi` and `python`).
+
+// -- module definitions
+pub mod args;
+pub mod baseline;
+pub mod cache;
+pub mod canonical;
+#[cfg(feature = "capi")]
+pub mod capi;
+pub mod check;
+pub mod companions;
+pub mod config;
+pub mod daemon;
+pub mod desktop;
+pub mod diff;
+pub mod explain;
+pub mod export;
+pub mod fix;
+pub mod flathub;
+pub mod fleet;
+pub mod gpu;
+pub mod history;
+pub mod html;
+pub mod i18n;
+pub mod identity;
+pub mod ignore;
+pub mod journal;
+pub mod junit;
+pub mod limits;
+pub mod line_format;
+pub mod lint;
+pub mod linux_fs;
+pub mod log;
+pub mod markdown;
+pub mod notify;
+pub mod output_file;
+pub mod packagekit;
+pub mod patch;
+pub mod plain;
+pub mod progress;
+#[cfg(feature = "python")]
+pub mod python;
+pub mod quarantine;
+pub mod remap;
+pub mod render;
+pub mod report;
+pub mod retry;
+pub mod sarif;
+pub mod scan;
+pub mod schema;
+pub mod session;
+pub mod shell;
+pub mod simulate;
+pub mod suppressions;
+pub mod sysroot;
+pub mod tap;
+pub mod template;
+pub mod term;
+pub mod timer;
+pub mod trash;
+pub mod undo;
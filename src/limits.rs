@@ -0,0 +1,177 @@
+//! Resource limits for long-running modes (`daemon`).
+//!
+//! Thin clients and other low-memory machines run the daemon next to the desktop session, so it
+//! must not grow without bound. Limits degrade service instead of failing it:
+//! - above `--max-rss`, scans run with a single inspection job
+//! - `--max-open-files` lowers the soft `RLIMIT_NOFILE` and caps the jobs of a scan by the file
+//!   descriptors still available
+//! - beyond `--max-events-per-sec`, requests are answered from the last scan instead of
+//!   starting a new one
+//!
+//! Every degradation is logged as a warning.
+
+// -- std imports
+use std::{
+    fs, io,
+    time::{Duration, Instant},
+};
+
+// -- crate imports
+use anyhow::Result;
+use tracing::{debug, warn};
+
+/// File descriptors a single inspection may hold at once (the file, a directory, a pipe).
+const FDS_PER_JOB: u64 = 2;
+
+/// File descriptors kept free for the bus connection, logging and the runtime.
+const RESERVED_FDS: u64 = 16;
+
+/// Limits of a long-running mode.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Limits {
+    /// Resident memory in MiB above which scans run with a single job.
+    pub max_rss_mib: Option<u64>,
+
+    /// Maximum number of open file descriptors.
+    pub max_open_files: Option<u64>,
+
+    /// Maximum number of events (scan requests) handled per second.
+    pub max_events_per_sec: Option<u32>,
+}
+
+impl Limits {
+    /// Apply the process-wide limits: lower the soft `RLIMIT_NOFILE` to
+    /// [`Limits::max_open_files`].
+    ///
+    /// # Errors
+    /// - [`anyhow::Error`] if the limit cannot be read or set.
+    pub fn apply(&self) -> Result<()> {
+        let Some(max) = self.max_open_files else {
+            return Ok(());
+        };
+
+        let mut limit = libc::rlimit {
+            rlim_cur: 0,
+            rlim_max: 0,
+        };
+        // SAFETY: getrlimit only writes an `rlimit` to the valid pointer passed.
+        if unsafe { libc::getrlimit(libc::RLIMIT_NOFILE, &mut limit) } != 0 {
+            return Err(io::Error::last_os_error().into());
+        }
+        limit.rlim_cur = max.min(limit.rlim_max);
+        // SAFETY: setrlimit only reads the valid `rlimit` passed.
+        if unsafe { libc::setrlimit(libc::RLIMIT_NOFILE, &limit) } != 0 {
+            return Err(io::Error::last_os_error().into());
+        }
+        debug!(soft = limit.rlim_cur, "Lowered RLIMIT_NOFILE");
+        Ok(())
+    }
+
+    /// Number of inspection jobs for the next scan, `requested` reduced as the limits demand.
+    pub fn scan_jobs(&self, requested: usize) -> usize {
+        let mut jobs = requested;
+
+        if let Some(max) = self.max_rss_mib
+            && let Some(rss) = resident_mib()
+            && rss > max
+        {
+            warn!(
+                rss_mib = rss,
+                max_rss_mib = max,
+                "Resident memory above --max-rss; scanning with a single job"
+            );
+            jobs = 1;
+        }
+
+        if let Some(max) = self.max_open_files
+            && let Some(open) = open_fds()
+        {
+            let available = max.saturating_sub(open + RESERVED_FDS) / FDS_PER_JOB;
+            let capped = usize::try_from(available).unwrap_or(usize::MAX).max(1);
+            if capped < jobs {
+                warn!(
+                    open_fds = open,
+                    max_open_files = max,
+                    jobs = capped,
+                    "Few file descriptors left under --max-open-files; reducing scan jobs"
+                );
+                jobs = capped;
+            }
+        }
+        jobs
+    }
+
+    /// A rate limiter for [`Limits::max_events_per_sec`].
+    pub fn event_limiter(&self) -> RateLimiter {
+        RateLimiter {
+            per_sec: self.max_events_per_sec,
+            window: Instant::now(),
+            count: 0,
+            dropped: 0,
+        }
+    }
+}
+
+/// Fixed-window limiter of events per second.
+#[derive(Debug)]
+pub struct RateLimiter {
+    /// Events allowed per window (`None`: unlimited).
+    per_sec: Option<u32>,
+
+    /// Start of the current window.
+    window: Instant,
+
+    /// Events admitted in the current window.
+    count: u32,
+
+    /// Events rejected in the current window.
+    dropped: u32,
+}
+
+impl RateLimiter {
+    /// Whether another event may be handled now; rejections are logged once per window.
+    pub fn admit(&mut self) -> bool {
+        let Some(per_sec) = self.per_sec else {
+            return true;
+        };
+
+        if self.window.elapsed() >= Duration::from_secs(1) {
+            if self.dropped > 0 {
+                debug!(
+                    dropped = self.dropped,
+                    "Events rejected by --max-events-per-sec"
+                );
+            }
+            self.window = Instant::now();
+            self.count = 0;
+            self.dropped = 0;
+        }
+        if self.count < per_sec {
+            self.count += 1;
+            return true;
+        }
+
+        if self.dropped == 0 {
+            warn!(
+                max_events_per_sec = per_sec,
+                "Event rate above --max-events-per-sec; answering from the last scan"
+            );
+        }
+        self.dropped += 1;
+        false
+    }
+}
+
+/// Resident memory of this process in MiB (Linux `/proc/self/statm`).
+fn resident_mib() -> Option<u64> {
+    let statm = fs::read_to_string("/proc/self/statm").ok()?;
+    let pages = statm.split_whitespace().nth(1)?.parse::<u64>().ok()?;
+    // SAFETY: sysconf has no memory-safety preconditions.
+    let page_size = u64::try_from(unsafe { libc::sysconf(libc::_SC_PAGESIZE) }).ok()?;
+    Some(pages * page_size / (1024 * 1024))
+}
+
+/// Number of open file descriptors of this process (Linux `/proc/self/fd`).
+fn open_fds() -> Option<u64> {
+    Some(fs::read_dir("/proc/self/fd").ok()?.count() as u64)
+}
@@ -0,0 +1,206 @@
+//! One-line-per-finding output from a format string (`--format`).
+//!
+//! `--format '{path}\t{status}\t{reason}'` prints a line per listed finding, for one-liners that
+//! would otherwise need `jq`:
+//!
+//! - `{field}` is replaced by a field of the finding (see [`Field`]); absent values are empty
+//! - `{{` and `}}` print literal braces
+//! - `\t`, `\n` and `\\` print a tab, a newline and a backslash, since shells pass them on
+//!   verbatim inside single quotes
+//!
+//! Unknown fields are rejected when the format is parsed, before the scan starts.
+
+// -- crate imports
+use anyhow::{Result, bail};
+
+// -- module imports
+use crate::report::{Finding, Status};
+
+/// A finding field usable as `{name}`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Field {
+    /// Path to the `.desktop` file.
+    Path,
+    /// Stable finding ID.
+    Id,
+    /// `Name=`.
+    Name,
+    /// `Type=`.
+    Type,
+    /// `Exec=`.
+    Exec,
+    /// `TryExec=`.
+    TryExec,
+    /// `ok`, `broken`, `skipped`, `suppressed` or `timed_out`.
+    Status,
+    /// Reason code of an entry that is not ok.
+    Code,
+    /// Reason of an entry that is not ok.
+    Reason,
+    /// Resolved executable of a healthy entry.
+    Resolved,
+    /// Most severe problem (`info`, `warning`, `error`).
+    Severity,
+    /// Codes of the lint warnings, comma-separated.
+    Warnings,
+    /// Suggested replacement `Exec=` line.
+    SuggestedExec,
+    /// User whose home directory contains the file (`--all-users`).
+    User,
+}
+
+impl Field {
+    /// All fields with their placeholder names.
+    const ALL: &'static [(&'static str, Field)] = &[
+        ("path", Field::Path),
+        ("id", Field::Id),
+        ("name", Field::Name),
+        ("type", Field::Type),
+        ("exec", Field::Exec),
+        ("try_exec", Field::TryExec),
+        ("status", Field::Status),
+        ("code", Field::Code),
+        ("reason", Field::Reason),
+        ("resolved", Field::Resolved),
+        ("severity", Field::Severity),
+        ("warnings", Field::Warnings),
+        ("suggested_exec", Field::SuggestedExec),
+        ("user", Field::User),
+    ];
+
+    /// Value of this field for `f` (empty if absent).
+    fn value(self, f: &Finding) -> String {
+        let text = |value: &Option<String>| value.clone().unwrap_or_default();
+        match (self, &f.status) {
+            (Field::Path, _) => f.desktop_file.display().to_string(),
+            (Field::Id, _) => f.id.clone(),
+            (Field::Name, _) => text(&f.name),
+            (Field::Type, _) => text(&f.entry_type),
+            (Field::Exec, _) => text(&f.exec),
+            (Field::TryExec, _) => text(&f.try_exec),
+            (Field::Status, status) => status.kind().to_string(),
+            (
+                Field::Code,
+                Status::Broken { code, .. }
+                | Status::Skipped { code, .. }
+                | Status::Suppressed { code, .. }
+                | Status::TimedOut { code, .. },
+            ) => code.to_string(),
+            (
+                Field::Reason,
+                Status::Broken { reason, .. }
+                | Status::Skipped { reason, .. }
+                | Status::Suppressed { reason, .. }
+                | Status::TimedOut { reason, .. },
+            ) => reason.clone(),
+            (
+                Field::Resolved,
+                Status::Ok {
+                    resolved_executable: Some(exe),
+                },
+            ) => exe.display().to_string(),
+            (Field::Severity, _) => f.severity.map(|s| s.to_string()).unwrap_or_default(),
+            (Field::Warnings, _) => f
+                .warnings
+                .iter()
+                .map(|w| w.code.as_str())
+                .collect::<Vec<_>>()
+                .join(","),
+            (Field::SuggestedExec, _) => text(&f.suggested_exec),
+            (Field::User, _) => text(&f.user),
+            (Field::Code | Field::Reason | Field::Resolved, _) => String::new(),
+        }
+    }
+}
+
+/// A piece of a parsed format string.
+#[derive(Debug)]
+enum Piece {
+    /// Literal text (escapes already resolved).
+    Text(String),
+    /// A `{field}` placeholder.
+    Field(Field),
+}
+
+/// A parsed `--format` string.
+#[derive(Debug)]
+pub struct LineFormat {
+    /// Literal text and placeholders, in order.
+    pieces: Vec<Piece>,
+}
+
+impl LineFormat {
+    /// Parse a format string.
+    ///
+    /// # Errors
+    /// - [`anyhow::Error`] if a placeholder is unknown or not closed, a `}` is not doubled, or
+    ///   the string ends in a lone `\`.
+    pub fn parse(format: &str) -> Result<Self> {
+        let mut pieces = Vec::new();
+        let mut text = String::new();
+        let mut chars = format.chars().peekable();
+
+        while let Some(c) = chars.next() {
+            match c {
+                '\\' => match chars.next() {
+                    Some('t') => text.push('\t'),
+                    Some('n') => text.push('\n'),
+                    Some('\\') => text.push('\\'),
+                    Some(other) => {
+                        text.push('\\');
+                        text.push(other);
+                    }
+                    None => bail!("Format string ends in a lone `\\`"),
+                },
+                '{' if chars.peek() == Some(&'{') => {
+                    chars.next();
+                    text.push('{');
+                }
+                '}' if chars.peek() == Some(&'}') => {
+                    chars.next();
+                    text.push('}');
+                }
+                '}' => bail!("Unmatched `}}` in format string (write `}}}}` for a literal brace)"),
+                '{' => {
+                    let mut name = String::new();
+                    loop {
+                        match chars.next() {
+                            Some('}') => break,
+                            Some(c) => name.push(c),
+                            None => bail!("Unclosed `{{{name}` in format string"),
+                        }
+                    }
+                    let Some((_, field)) = Field::ALL.iter().find(|(n, _)| *n == name.trim())
+                    else {
+                        let known = Field::ALL
+                            .iter()
+                            .map(|(n, _)| *n)
+                            .collect::<Vec<_>>()
+                            .join(", ");
+                        bail!("Unknown format field `{{{name}}}` (known fields: {known})");
+                    };
+                    if !text.is_empty() {
+                        pieces.push(Piece::Text(std::mem::take(&mut text)));
+                    }
+                    pieces.push(Piece::Field(*field));
+                }
+                c => text.push(c),
+            }
+        }
+        if !text.is_empty() {
+            pieces.push(Piece::Text(text));
+        }
+        Ok(LineFormat { pieces })
+    }
+
+    /// The line for `f` (without a trailing newline).
+    pub fn render(&self, f: &Finding) -> String {
+        self.pieces
+            .iter()
+            .map(|piece| match piece {
+                Piece::Text(text) => text.clone(),
+                Piece::Field(field) => field.value(f),
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,384 @@
+//! File-intrinsic lints for `.desktop` entries.
+//!
+//! Lints never make an entry broken on their own. They flag values or key combinations the
+//! Desktop Entry spec forbids, which launchers may silently ignore or misinterpret, and
+//! leftovers of hand-written launchers that were never finished.
+
+use std::{collections::HashMap, path::Path};
+
+use crate::{
+    desktop::{self, DESKTOP_ENTRY_GROUP},
+    i18n::tr,
+    report::{ReasonCode, Status, Warning},
+};
+
+/// Keys that the spec only defines for `Type=Application`.
+const APPLICATION_ONLY_KEYS: &[&str] = &[
+    "SingleMainWindow",
+    "PrefersNonDefaultGPU",
+    "DBusActivatable",
+];
+
+/// `Name=` values that come straight out of launcher templates/tutorials (compared lowercased).
+const TEMPLATE_NAMES: &[&str] = &[
+    "my application",
+    "my app",
+    "application name",
+    "app name",
+    "your application",
+    "your app",
+    "example application",
+    "name of the application",
+];
+
+/// Path fragments that only appear in copy-pasted example values.
+const TEMPLATE_PATH_MARKERS: &[&str] = &["/path/to/", "/your/path/", "/path/of/"];
+
+/// Roots below which the first directory belongs to a single package (`/usr/share/<package>/`).
+const PACKAGE_DIR_ROOTS: &[&str] = &[
+    "/usr/share/",
+    "/usr/local/share/",
+    "/usr/lib/",
+    "/usr/lib64/",
+    "/opt/",
+];
+
+/// Directories below [`PACKAGE_DIR_ROOTS`] that are shared by design.
+const SHARED_ICON_DIRS: &[&str] = &["icons", "pixmaps", "app-install", "themes"];
+
+/// Keys the spec deprecated (Appendix C) or that only KDE 3 understood.
+const DEPRECATED_KEYS: &[&str] = &[
+    "BinaryPattern",
+    "DefaultApp",
+    "Encoding",
+    "Extensions",
+    "FilePattern",
+    "MapNotify",
+    "MiniIcon",
+    "Patterns",
+    "Protocols",
+    "SortOrder",
+    "SwallowExec",
+    "SwallowTitle",
+    "TerminalOptions",
+];
+
+/// Markers in comments that indicate an unfinished launcher.
+const TEMPLATE_COMMENT_MARKERS: &[&str] = &["TODO", "FIXME", "XXX"];
+
+/// Run all lints over a `.desktop` file.
+///
+/// `content` is the raw file content (needed for comment-based lints), `kv` its parsed
+/// `[Desktop Entry]` section and `status` the result of checking it. Returns warnings in a
+/// stable order (empty if the entry is clean).
+pub fn lint_entry(
+    path: &Path,
+    content: &str,
+    kv: &HashMap<String, String>,
+    status: &Status,
+) -> Vec<Warning> {
+    let mut warnings = Vec::new();
+    lint_syntax(content, &mut warnings);
+    lint_spec_keys(path, kv, status, &mut warnings);
+    lint_template_leftovers(content, kv, &mut warnings);
+    lint_icon_location(path, kv, &mut warnings);
+    lint_hints(kv, &mut warnings);
+    warnings
+}
+
+/// The warning for a file that is not valid UTF-8 (only linted with `--inventory`; such files
+/// are unreadable otherwise).
+pub fn invalid_encoding() -> Warning {
+    Warning {
+        code: ReasonCode::InvalidEncoding,
+        message: tr("lint-invalid-encoding", &[]),
+        key: None,
+    }
+}
+
+/// Flag lines the parser has to guess about: text that is not a group header, comment or
+/// `key=value` pair, and a file that does not start with `[Desktop Entry]`.
+///
+/// Launchers skip malformed lines or reject the whole file, depending on the implementation.
+fn lint_syntax(content: &str, warnings: &mut Vec<Warning>) {
+    let mut first_group = None;
+
+    for (idx, raw) in content.lines().enumerate() {
+        let line = raw.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        if line.starts_with('[') && line.ends_with(']') {
+            first_group.get_or_insert_with(|| line[1..line.len() - 1].to_string());
+            continue;
+        }
+        if line.contains('=') && first_group.is_some() {
+            continue;
+        }
+        warnings.push(Warning {
+            code: ReasonCode::MalformedLine,
+            message: tr(
+                match first_group {
+                    Some(_) => "lint-not-key-value",
+                    None => "lint-before-first-group",
+                },
+                &[("line", (idx + 1).into()), ("text", line.into())],
+            ),
+            key: None,
+        });
+    }
+
+    if first_group.as_deref() != Some(DESKTOP_ENTRY_GROUP) {
+        warnings.push(Warning {
+            code: ReasonCode::MissingDesktopEntryGroup,
+            message: match first_group {
+                Some(group) => tr("lint-wrong-first-group", &[("group", group.into())]),
+                None => tr("lint-no-desktop-entry-group", &[]),
+            },
+            key: None,
+        });
+    }
+}
+
+/// Validate values and `Type` combinations of the newer spec keys.
+///
+/// Hidden entries that were skipped are not linted. Of entries skipped for their `Type`, only
+/// the `Type` combination is: their values are never used.
+fn lint_spec_keys(
+    path: &Path,
+    kv: &HashMap<String, String>,
+    status: &Status,
+    warnings: &mut Vec<Warning>,
+) {
+    let checked = match status {
+        Status::Skipped {
+            code: ReasonCode::HiddenEntry,
+            ..
+        } => return,
+        Status::Skipped { .. } => false,
+        _ => true,
+    };
+    let typ = kv.get("Type").map(|s| s.trim());
+
+    for key in APPLICATION_ONLY_KEYS {
+        let Some(value) = kv.get(*key) else {
+            continue;
+        };
+
+        if checked && !matches!(value.trim(), "true" | "false") {
+            warnings.push(Warning {
+                code: ReasonCode::InvalidBoolean,
+                message: tr(
+                    "lint-invalid-boolean",
+                    &[("key", (*key).into()), ("value", value.as_str().into())],
+                ),
+                key: Some(key.to_string()),
+            });
+        }
+        if let Some(t) = typ
+            && t != "Application"
+        {
+            warnings.push(Warning {
+                code: ReasonCode::KeyNotAllowedForType,
+                message: tr(
+                    "lint-key-not-allowed-for-type",
+                    &[("key", (*key).into()), ("type", t.into())],
+                ),
+                key: Some(key.to_string()),
+            });
+        }
+    }
+
+    if checked && desktop::parse_bool(kv.get("DBusActivatable")) {
+        let stem = path
+            .file_stem()
+            .map(|s| s.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        if !is_dbus_well_known_name(&stem) {
+            warnings.push(Warning {
+                code: ReasonCode::InvalidDbusName,
+                message: tr("lint-invalid-dbus-name", &[("name", stem.as_str().into())]),
+                key: Some("DBusActivatable".into()),
+            });
+        }
+    }
+}
+
+/// Flag placeholders left over from launcher templates.
+///
+/// Such entries are usually not broken in the "binary vanished" sense, but were never finished
+/// by whoever wrote them.
+fn lint_template_leftovers(
+    content: &str,
+    kv: &HashMap<String, String>,
+    warnings: &mut Vec<Warning>,
+) {
+    if let Some(name) = kv.get("Name")
+        && TEMPLATE_NAMES.contains(&name.trim().to_ascii_lowercase().as_str())
+    {
+        warnings.push(Warning {
+            code: ReasonCode::TemplatePlaceholder,
+            message: tr("lint-template-name", &[("name", name.as_str().into())]),
+            key: Some("Name".into()),
+        });
+    }
+
+    for key in ["Exec", "TryExec", "Path", "Icon"] {
+        if let Some(value) = kv.get(key)
+            && let Some(marker) = TEMPLATE_PATH_MARKERS.iter().find(|m| value.contains(*m))
+        {
+            warnings.push(Warning {
+                code: ReasonCode::TemplatePlaceholder,
+                message: tr(
+                    "lint-template-path",
+                    &[
+                        ("key", key.into()),
+                        ("marker", (*marker).into()),
+                        ("value", value.as_str().into()),
+                    ],
+                ),
+                key: Some(key.to_string()),
+            });
+        }
+    }
+
+    let todo = content
+        .lines()
+        .map(str::trim)
+        .filter(|l| l.starts_with('#'))
+        .find(|l| TEMPLATE_COMMENT_MARKERS.iter().any(|m| l.contains(m)));
+    if let Some(comment) = todo {
+        warnings.push(Warning {
+            code: ReasonCode::TemplatePlaceholder,
+            message: tr("lint-unfinished-comment", &[("comment", comment.into())]),
+            key: None,
+        });
+    }
+}
+
+/// Info-level hints: application entries shown in menus without `Icon=`, and deprecated keys.
+fn lint_hints(kv: &HashMap<String, String>, warnings: &mut Vec<Warning>) {
+    let application = kv.get("Type").is_none_or(|t| t.trim() == "Application");
+    let shown = !["Hidden", "NoDisplay"]
+        .iter()
+        .any(|key| desktop::parse_bool(kv.get(*key)));
+    if application && shown && kv.get("Icon").is_none_or(|v| v.trim().is_empty()) {
+        warnings.push(Warning {
+            code: ReasonCode::MissingIcon,
+            message: tr("lint-missing-icon", &[]),
+            key: None,
+        });
+    }
+
+    let mut deprecated = kv
+        .keys()
+        .filter(|key| {
+            let base = key.split_once('[').map_or(key.as_str(), |(base, _)| base);
+            DEPRECATED_KEYS.contains(&base)
+        })
+        .collect::<Vec<_>>();
+    deprecated.sort();
+    for key in deprecated {
+        warnings.push(Warning {
+            code: ReasonCode::DeprecatedKey,
+            message: tr("lint-deprecated-key", &[("key", key.as_str().into())]),
+            key: Some(key.clone()),
+        });
+    }
+}
+
+/// Flag absolute `Icon=` paths into the private directory of another package.
+///
+/// Whether the directory belongs to the entry itself is guessed from the desktop ID and the
+/// `Exec=`/`TryExec=` command: `/usr/share/foo/foo.png` is fine for `foo.desktop` or
+/// `Exec=/opt/foo/bin/foo`, but not for `bar.desktop`.
+fn lint_icon_location(path: &Path, kv: &HashMap<String, String>, warnings: &mut Vec<Warning>) {
+    let Some(icon) = kv.get("Icon").map(|i| i.trim()) else {
+        return;
+    };
+    let Some(owner) = package_dir(icon) else {
+        return;
+    };
+    if SHARED_ICON_DIRS.contains(&owner) {
+        return;
+    }
+
+    let mut own_names = Vec::new();
+    if let Some(stem) = path.file_stem() {
+        let stem = stem.to_string_lossy();
+        // Reverse-DNS IDs (org.example.Foo) are named after their last element.
+        own_names.extend(stem.rsplit('.').next().map(str::to_string));
+        own_names.push(stem.into_owned());
+    }
+    for key in ["Exec", "TryExec"] {
+        let Some(command) = kv
+            .get(key)
+            .and_then(|v| shlex::split(v))
+            .and_then(|t| t.into_iter().next())
+        else {
+            continue;
+        };
+        own_names.extend(package_dir(&command).map(str::to_string));
+        own_names.extend(
+            Path::new(&command)
+                .file_name()
+                .map(|n| n.to_string_lossy().into_owned()),
+        );
+    }
+
+    let owner_key = name_key(owner);
+    let related = own_names.iter().map(|n| name_key(n)).any(|n| {
+        let (short, long) = if n.len() <= owner_key.len() {
+            (&n, &owner_key)
+        } else {
+            (&owner_key, &n)
+        };
+        short.len() >= 3 && long.contains(short.as_str())
+    });
+    if !related {
+        warnings.push(Warning {
+            code: ReasonCode::IconInForeignPackageDir,
+            message: tr(
+                "lint-foreign-icon-dir",
+                &[("icon", icon.into()), ("owner", owner.into())],
+            ),
+            key: Some("Icon".into()),
+        });
+    }
+}
+
+/// First directory below a [`PACKAGE_DIR_ROOTS`] root, if `path` has one and continues below it.
+fn package_dir(path: &str) -> Option<&str> {
+    let rest = PACKAGE_DIR_ROOTS
+        .iter()
+        .find_map(|root| path.strip_prefix(root))?;
+    let (dir, below) = rest.split_once('/')?;
+    (!dir.is_empty() && !below.is_empty()).then_some(dir)
+}
+
+/// Lowercased alphanumeric form of a package or command name, for fuzzy comparison.
+fn name_key(name: &str) -> String {
+    name.chars()
+        .filter(char::is_ascii_alphanumeric)
+        .map(|c| c.to_ascii_lowercase())
+        .collect()
+}
+
+/// Check whether `name` is a valid D-Bus well-known bus name.
+///
+/// Rules: at least two dot-separated elements, each non-empty, made of `[A-Za-z0-9_-]`,
+/// not starting with a digit, and at most 255 characters in total.
+fn is_dbus_well_known_name(name: &str) -> bool {
+    if name.is_empty() || name.len() > 255 {
+        return false;
+    }
+
+    let elements = name.split('.').collect::<Vec<_>>();
+    elements.len() >= 2
+        && elements.iter().all(|e| {
+            !e.is_empty()
+                && !e.starts_with(|c: char| c.is_ascii_digit())
+                && e.chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+        })
+}
@@ -1,61 +1,461 @@
 //! Directory discovery for `.desktop` files.
 //!
-//! This module collects application directories following XDG conventions plus
-//! common extras (Flatpak/Snap exports) and any user-provided directories.
+//! This module collects application directories following XDG conventions, the user's desktop
+//! folder, common extras (Flatpak exports of every installation, Snap exports), the directories
+//! of every user (`--all-users`) and any user-provided directories.
 
 // -- std imports
-use std::{collections::BTreeSet, path::PathBuf};
+use std::{
+    collections::BTreeMap,
+    env, fmt, fs,
+    path::{Path, PathBuf},
+};
 
 // -- crate imports
-use tracing::debug;
+use serde::Serialize;
+use tracing::{debug, warn};
 use xdg::BaseDirectories;
 
 // -- module imports
-use crate::args::Args;
+use crate::{args::Args, desktop::DesktopEntry, report::GroupKey, sysroot};
+
+/// Why a directory is part of the scan set.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "source", rename_all = "snake_case")]
+pub enum DirSource {
+    /// `$XDG_DATA_HOME/applications`.
+    XdgDataHome,
+
+    /// The user's desktop folder (`XDG_DESKTOP_DIR` of xdg-user-dirs).
+    XdgDesktop,
+
+    /// `applications` below the `index`-th entry of `$XDG_DATA_DIRS`.
+    XdgDataDirs { index: usize },
+
+    /// Per-user Flatpak exports below `$XDG_DATA_HOME`.
+    FlatpakUser,
+
+    /// System-wide Flatpak exports.
+    FlatpakSystem,
+
+    /// Exports of the custom Flatpak installation `id` (see [`flatpak_installations`]).
+    FlatpakInstallation { id: String },
+
+    /// Snap desktop exports.
+    Snap,
+
+    /// Below the home directory of another user (`--all-users`).
+    AllUsers,
+
+    /// Passed via `--dir` or `--dirs-from`.
+    Extra,
+}
+
+impl fmt::Display for DirSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DirSource::XdgDataHome => f.write_str("XDG_DATA_HOME"),
+            DirSource::XdgDesktop => f.write_str("XDG_DESKTOP_DIR"),
+            DirSource::XdgDataDirs { index } => write!(f, "XDG_DATA_DIRS[{index}]"),
+            DirSource::FlatpakUser => f.write_str("flatpak (user)"),
+            DirSource::FlatpakSystem => f.write_str("flatpak (system)"),
+            DirSource::FlatpakInstallation { id } => write!(f, "flatpak ({id})"),
+            DirSource::Snap => f.write_str("snap"),
+            DirSource::AllUsers => f.write_str("--all-users"),
+            DirSource::Extra => f.write_str("--dir"),
+        }
+    }
+}
+
+/// A directory that would be scanned, with every reason it was included.
+#[derive(Debug, Serialize)]
+pub struct ScanDir {
+    /// The directory.
+    pub path: PathBuf,
+
+    /// Sources that contributed it, in discovery order.
+    pub sources: Vec<DirSource>,
+
+    /// Whether the directory currently exists.
+    pub exists: bool,
+}
+
+/// Source layer of the file at `path` (`--group-by source`): the first source of the most
+/// specific scan directory in `dirs` containing it.
+pub fn source_group(path: &Path, dirs: &[ScanDir]) -> GroupKey {
+    let Some(dir) = dirs
+        .iter()
+        .filter(|d| path.starts_with(&d.path))
+        .max_by_key(|d| d.path.components().count())
+    else {
+        return GroupKey::Other;
+    };
+    match dir.sources.first() {
+        Some(DirSource::XdgDataHome | DirSource::AllUsers) => GroupKey::UserData {
+            path: dir.path.clone(),
+        },
+        Some(DirSource::XdgDesktop) => GroupKey::Desktop {
+            path: dir.path.clone(),
+        },
+        Some(DirSource::XdgDataDirs { .. }) => GroupKey::SystemData,
+        Some(
+            DirSource::FlatpakUser
+            | DirSource::FlatpakSystem
+            | DirSource::FlatpakInstallation { .. },
+        ) => GroupKey::Flatpak,
+        Some(DirSource::Snap) => GroupKey::Snap,
+        Some(DirSource::Extra) | None => GroupKey::Extra {
+            path: dir.path.clone(),
+        },
+    }
+}
 
 /// Collect a list of directories that may contain `.desktop` files.
 ///
+/// See [`collect_application_dirs_with_provenance`] for the sources.
+pub fn collect_application_dirs(args: &Args) -> Vec<PathBuf> {
+    let dirs = collect_application_dirs_with_provenance(args)
+        .into_iter()
+        .map(|d| d.path)
+        .collect::<Vec<_>>();
+
+    debug!(
+        count = dirs.len(),
+        "Collected application dirs to scan: {dirs:#?}"
+    );
+    dirs
+}
+
+/// Collect the scan directories together with why each was included, sorted by path.
+///
 /// Primary sources (unless `--no-default`):
 /// - `$XDG_DATA_HOME/applications` (default: `~/.local/share/applications`)
+/// - the desktop folder (see [`desktop_dir`]; default: `~/Desktop`)
 /// - `$XDG_DATA_DIRS/applications` (default: `/usr/local/share/applications:/usr/share/applications`)
 ///
 /// Extras (unless `--no-common-extras`):
-/// - Flatpak exports (user + system)
+/// - Flatpak exports (user, system and custom installations, see [`flatpak_installations`])
 /// - Snap desktop exports
 ///
-/// Always includes `--dir` values verbatim.
-pub fn collect_application_dirs(args: &Args) -> Vec<PathBuf> {
+/// With `--root`, the data directories of the user running the scan (`$XDG_DATA_HOME`, the desktop
+/// folder, user Flatpak exports) are left out, `$XDG_DATA_DIRS` is replaced by its default and
+/// system directories are resolved inside the image.
+///
+/// With `--all-users`, for every user of [`user_homes`]:
+/// - `~/.local/share/applications`, `~/.config/autostart` and the desktop folder
+/// - the user's Flatpak exports (unless `--no-common-extras`)
+///
+/// Always includes `--dir` (and `--dirs-from`) values verbatim.
+pub fn collect_application_dirs_with_provenance(args: &Args) -> Vec<ScanDir> {
     let xdg = BaseDirectories::new();
-    let mut set = BTreeSet::<PathBuf>::new();
+    let mut map = BTreeMap::<PathBuf, Vec<DirSource>>::new();
+    let mut add = |path: PathBuf, source: DirSource| map.entry(path).or_default().push(source);
+    let image = |path: &str| match &args.root {
+        Some(root) => sysroot::rebase(root, Path::new(path)),
+        None => PathBuf::from(path),
+    };
 
-    // Default dirs (can be disabled)
-    if !args.no_default {
+    // Default dirs (can be disabled); the session's own directories belong to the host, not to
+    // a --root image.
+    if !args.no_default && args.root.is_some() {
+        for (index, dir) in sysroot::IMAGE_DATA_DIRS.iter().enumerate() {
+            add(
+                image(dir).join("applications"),
+                DirSource::XdgDataDirs { index },
+            );
+        }
+    } else if !args.no_default {
         if let Some(data_home) = xdg.get_data_home() {
-            set.insert(data_home.join("applications"));
+            add(data_home.join("applications"), DirSource::XdgDataHome);
 
             if !args.no_common_extras {
-                set.insert(data_home.join("flatpak/exports/share/applications"));
+                add(
+                    data_home.join("flatpak/exports/share/applications"),
+                    DirSource::FlatpakUser,
+                );
             }
         } else {
             debug!("XDG data home unavailable; skipping ~/.local/share candidates");
         }
 
-        for dir in xdg.get_data_dirs() {
-            set.insert(dir.join("applications"));
+        match desktop_dir(&xdg) {
+            Some(desktop) => add(desktop, DirSource::XdgDesktop),
+            None => debug!("Desktop folder unavailable or set to $HOME; skipping it"),
+        }
+
+        for (index, dir) in xdg.get_data_dirs().into_iter().enumerate() {
+            add(dir.join("applications"), DirSource::XdgDataDirs { index });
+        }
+    }
+    if !args.no_default && !args.no_common_extras {
+        add(
+            image("/var/lib/flatpak/exports/share/applications"),
+            DirSource::FlatpakSystem,
+        );
+        for installation in flatpak_installations(args.root.as_deref()) {
+            add(
+                installation.path.join("exports/share/applications"),
+                DirSource::FlatpakInstallation {
+                    id: installation.id,
+                },
+            );
         }
+        add(
+            image("/var/lib/snapd/desktop/applications"),
+            DirSource::Snap,
+        );
+    }
 
-        if !args.no_common_extras {
-            set.insert(PathBuf::from("/var/lib/flatpak/exports/share/applications"));
-            set.insert(PathBuf::from("/var/lib/snapd/desktop/applications"));
+    if args.all_users {
+        for user in user_homes(args.root.as_deref()) {
+            let home = &user.home;
+            add(home.join(".local/share/applications"), DirSource::AllUsers);
+            add(home.join(".config/autostart"), DirSource::AllUsers);
+            if let Some(desktop) = resolve_desktop_dir(home, &home.join(".config"), None) {
+                // Absolute folders outside the home directory are paths in the image.
+                match &args.root {
+                    Some(root) if !desktop.starts_with(home) => {
+                        add(sysroot::rebase(root, &desktop), DirSource::AllUsers)
+                    }
+                    _ => add(desktop, DirSource::AllUsers),
+                }
+            }
+            if !args.no_common_extras {
+                add(
+                    home.join(".local/share/flatpak/exports/share/applications"),
+                    DirSource::AllUsers,
+                );
+            }
         }
     }
 
     // User-provided extra dirs
-    set.extend(args.extra_dirs.iter().cloned());
+    for dir in &args.extra_dirs {
+        add(dir.clone(), DirSource::Extra);
+    }
 
+    map.into_iter()
+        .map(|(path, sources)| ScanDir {
+            exists: path.is_dir(),
+            path,
+            sources,
+        })
+        .collect()
+}
+
+/// The user's desktop folder as xdg-user-dirs resolves it: `XDG_DESKTOP_DIR` from
+/// `$XDG_CONFIG_HOME/user-dirs.dirs`, else from the environment, else `~/Desktop`.
+///
+/// `None` without a home directory, and if the folder is the home directory itself, which
+/// xdg-user-dirs uses to disable it (scanning all of `$HOME` is never intended).
+pub fn desktop_dir(xdg: &BaseDirectories) -> Option<PathBuf> {
+    let home = env::var_os("HOME")
+        .filter(|h| !h.is_empty())
+        .map(PathBuf::from)?;
+    let config_home = xdg.get_config_home()?;
+    let from_env = env::var("XDG_DESKTOP_DIR").ok().filter(|v| !v.is_empty());
+    resolve_desktop_dir(&home, &config_home, from_env)
+}
+
+/// Lookup precedence of the file at `path` among files with the same desktop ID: the position of
+/// the data directory whose `applications` tree contains it, `$XDG_DATA_HOME` first and then
+/// `$XDG_DATA_DIRS` in order (lower shadows higher). Files outside these trees come last.
+pub fn data_dir_precedence(xdg: &BaseDirectories, path: &Path) -> usize {
+    xdg.get_data_home()
+        .into_iter()
+        .chain(xdg.get_data_dirs())
+        .position(|d| path.starts_with(d.join("applications")))
+        .unwrap_or(usize::MAX)
+}
+
+/// The desktop folder of the user with `home` and `config_home`, falling back to `from_env`
+/// if `user-dirs.dirs` does not set it (see [`desktop_dir`]).
+fn resolve_desktop_dir(
+    home: &Path,
+    config_home: &Path,
+    from_env: Option<String>,
+) -> Option<PathBuf> {
+    let configured = fs::read_to_string(config_home.join("user-dirs.dirs"))
+        .ok()
+        .and_then(|text| user_dir(&text, "XDG_DESKTOP_DIR"))
+        .or(from_env);
+
+    let dir = match configured {
+        Some(value) => match value.strip_prefix("$HOME") {
+            Some(rest) => home.join(rest.trim_start_matches('/')),
+            None => PathBuf::from(value),
+        },
+        None => home.join("Desktop"),
+    };
+    (dir.is_absolute() && dir != home).then_some(dir)
+}
+
+/// The value of `key` in a `user-dirs.dirs` file (shell assignments like
+/// `XDG_DESKTOP_DIR="$HOME/Desktop"`); later assignments win.
+fn user_dir(text: &str, key: &str) -> Option<String> {
+    text.lines()
+        .filter_map(|line| line.trim().strip_prefix(key)?.strip_prefix('='))
+        .map(|value| value.trim().trim_matches('"').to_string())
+        .next_back()
+}
+
+/// Directory of the configuration files of custom Flatpak installations.
+const FLATPAK_INSTALLATIONS_DIR: &str = "/etc/flatpak/installations.d";
+
+/// A custom Flatpak installation (besides the user and the system one).
+#[derive(Debug, Clone)]
+pub struct FlatpakInstallation {
+    /// Installation ID (`extra` for `[Installation "extra"]`).
+    pub id: String,
+
+    /// Installation directory (`Path=`), containing `exports`.
+    pub path: PathBuf,
+}
+
+/// The custom Flatpak installations configured in the `*.conf` files of
+/// [`FLATPAK_INSTALLATIONS_DIR`], in file name order as Flatpak reads them. Installations
+/// without an absolute `Path=` are skipped.
+///
+/// With `root` (`--root`), the installations of the image, with their directories in it.
+pub fn flatpak_installations(root: Option<&Path>) -> Vec<FlatpakInstallation> {
+    let image = |path: &Path| match root {
+        Some(root) => sysroot::rebase(root, path),
+        None => path.to_path_buf(),
+    };
+    let Ok(entries) = fs::read_dir(image(Path::new(FLATPAK_INSTALLATIONS_DIR))) else {
+        return Vec::new();
+    };
+    let mut files = entries
+        .filter_map(|e| e.ok().map(|e| e.path()))
+        .filter(|p| p.extension().is_some_and(|e| e == "conf"))
+        .collect::<Vec<_>>();
+    files.sort();
+
+    let mut installations = Vec::new();
+    for file in files {
+        let text = match fs::read_to_string(&file) {
+            Ok(text) => text,
+            Err(e) => {
+                warn!(
+                    "Failed to read Flatpak installation {}: {e}",
+                    file.display()
+                );
+                continue;
+            }
+        };
+        let entry = DesktopEntry::parse(&text);
+        for group in &entry.groups {
+            let Some(id) = group
+                .name
+                .strip_prefix("Installation \"")
+                .and_then(|rest| rest.strip_suffix('"'))
+            else {
+                continue;
+            };
+            match entry.get(&group.name, "Path").map(Path::new) {
+                Some(path) if path.is_absolute() => installations.push(FlatpakInstallation {
+                    id: id.to_string(),
+                    path: image(path),
+                }),
+                _ => warn!(
+                    "Skipping Flatpak installation {id} in {}: no absolute Path=",
+                    file.display()
+                ),
+            }
+        }
+    }
     debug!(
-        count = set.len(),
-        "Collected application dirs to scan: {set:#?}"
+        count = installations.len(),
+        "Found custom Flatpak installations"
     );
-    set.into_iter().collect::<Vec<_>>()
+    installations
+}
+
+/// Lowest UID of regular accounts (`UID_MIN` of `useradd`).
+const UID_MIN: u32 = 1000;
+
+/// Highest UID of regular accounts (`nobody` and the ranges above are left out).
+const UID_MAX: u32 = 60000;
+
+/// A regular account whose home directory is scanned with `--all-users`.
+#[derive(Debug, Clone)]
+pub struct UserHome {
+    /// Login name.
+    pub name: String,
+
+    /// Home directory.
+    pub home: PathBuf,
+}
+
+/// The regular accounts of `/etc/passwd` (UIDs between [`UID_MIN`] and [`UID_MAX`]) whose home
+/// directory exists, in file order. Accounts sharing a home directory are listed once.
+///
+/// With `root` (`--root`), the accounts of the image, with their home directories in it.
+pub fn user_homes(root: Option<&Path>) -> Vec<UserHome> {
+    let passwd_path = match root {
+        Some(root) => sysroot::rebase(root, Path::new("/etc/passwd")),
+        None => PathBuf::from("/etc/passwd"),
+    };
+    let passwd = match fs::read_to_string(&passwd_path) {
+        Ok(text) => text,
+        Err(e) => {
+            warn!(
+                "Failed to read {}, --all-users finds no users: {e}",
+                passwd_path.display()
+            );
+            return Vec::new();
+        }
+    };
+
+    let mut users = Vec::<UserHome>::new();
+    for line in passwd.lines() {
+        // name:password:uid:gid:gecos:home:shell
+        let fields = line.split(':').collect::<Vec<_>>();
+        let [name, _, uid, _, _, home, ..] = fields[..] else {
+            continue;
+        };
+        let Ok(uid) = uid.parse::<u32>() else {
+            continue;
+        };
+        if !(UID_MIN..=UID_MAX).contains(&uid) || home == "/" {
+            continue;
+        }
+        let home = match root {
+            Some(root) => sysroot::rebase(root, Path::new(home)),
+            None => PathBuf::from(home),
+        };
+        if !home.is_dir() || users.iter().any(|u| u.home == home) {
+            continue;
+        }
+        users.push(UserHome {
+            name: name.to_string(),
+            home,
+        });
+    }
+    debug!(count = users.len(), "Found user homes for --all-users");
+    users
+}
+
+/// Login name of the user in `users` whose home directory contains `path` (the most specific
+/// one, for nested homes).
+pub fn owning_user<'a>(path: &Path, users: &'a [UserHome]) -> Option<&'a str> {
+    users
+        .iter()
+        .filter(|u| path.starts_with(&u.home))
+        .max_by_key(|u| u.home.components().count())
+        .map(|u| u.name.as_str())
+}
+
+/// Print the scan directories with their provenance, marking directories that do not exist.
+pub fn print_dirs(dirs: &[ScanDir]) {
+    for d in dirs {
+        let sources = d
+            .sources
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join(", ");
+        let missing = if d.exists { "" } else { " (missing)" };
+        println!("{}{missing}  [{sources}]", d.path.display());
+    }
 }
@@ -83,31 +83,36 @@ fn build_file_writer() -> Result<NonBlocking> {
     Ok(file_writer)
 }
 
-/// Initializes global tracing with stdout and file logging.
+/// Initializes global tracing with stdout and file logging; `quiet` (`--quiet`) leaves out
+/// stdout.
 ///
 /// # Errors
 /// - [`anyhow::Error`] if the global tracing subscriber cannot be installed.
-pub fn init_tracing() -> Result<()> {
+pub fn init_tracing(quiet: bool) -> Result<()> {
     let env_filter = EnvFilter::builder()
         .with_default_directive(LOG_LEVEL.into())
         .from_env_lossy();
 
     #[cfg(debug_assertions)]
-    let stdout_layer = fmt::layer()
-        // .pretty()
-        .with_thread_ids(true)
-        // .with_thread_names(true)
-        .with_file(true)
-        .with_line_number(true)
-        .with_target(false)
-        .with_filter(env_filter.clone());
+    let stdout_layer = (!quiet).then(|| {
+        fmt::layer()
+            // .pretty()
+            .with_thread_ids(true)
+            // .with_thread_names(true)
+            .with_file(true)
+            .with_line_number(true)
+            .with_target(false)
+            .with_filter(env_filter.clone())
+    });
 
     #[cfg(not(debug_assertions))]
-    let stdout_layer = fmt::layer()
-        .with_thread_ids(true)
-        // .with_thread_names(true)
-        .with_target(false)
-        .with_filter(env_filter.clone());
+    let stdout_layer = (!quiet).then(|| {
+        fmt::layer()
+            .with_thread_ids(true)
+            // .with_thread_names(true)
+            .with_target(false)
+            .with_filter(env_filter.clone())
+    });
 
     match build_file_writer() {
         Ok(writer) => {
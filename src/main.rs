@@ -1,77 +1,553 @@
+// -- std imports
+use std::{collections::HashSet, path::PathBuf, process::ExitCode, sync::Arc};
+
 // -- crate imports
-use anyhow::Result;
-use clap::Parser;
+use anyhow::{Context, Result, bail};
+use indicatif::ProgressBar;
 use tracing::{debug, info, warn};
 
-// -- module definitions
-mod args;
-mod check;
-mod desktop;
-mod linux_fs;
-mod log;
-mod report;
-mod scan;
-
 // -- module imports
-use crate::args::Args;
+use desktop_scout::{
+    args::{Args, Command, FixMode, GroupBy, OutputFormat},
+    baseline, canonical, config, daemon, diff, explain, export, fix, flathub, fleet, history,
+    i18n::tr,
+    identity,
+    journal::{self, Journal, JournalAction},
+    limits::Limits,
+    line_format::LineFormat,
+    linux_fs, log, notify,
+    output_file::OutputFile,
+    plain, progress, quarantine, remap, render,
+    report::{self, FindingGroup, GroupKey, Report},
+    scan, schema, simulate, template,
+    term::{self, Terminal},
+    timer, undo,
+};
 
-#[tokio::main(flavor = "multi_thread")]
-async fn main() -> Result<()> {
-    let args = Args::parse();
+/// Exit code used when no `.desktop` files were found at all.
+const EXIT_NO_FILES: u8 = 3;
+
+/// Exit code used when findings at the `--fail-on` level (and not covered by the `--baseline`)
+/// were found.
+const EXIT_FINDINGS: u8 = 4;
+
+fn main() -> Result<ExitCode> {
+    let runtime = tokio::runtime::Builder::new_multi_thread()
+        .enable_all()
+        .build()
+        .context("Failed to start Tokio runtime")?;
+    let code = runtime.block_on(run());
+    // Timed-out filesystem calls (a dead NFS mount) may still block their threads; don't wait.
+    runtime.shutdown_background();
+    code
+}
+
+/// The command line interface.
+async fn run() -> Result<ExitCode> {
+    let matches = config::parse_matches()?;
+    let mut args = config::args_from_matches(&matches);
 
     if args.no_log {
         tracing::subscriber::set_global_default(tracing::subscriber::NoSubscriber::default())
             .expect("Failed to set no-op subscriber");
     } else {
-        log::init_tracing()?;
+        log::init_tracing(args.quiet)?;
         info!("desktop-scout started");
         debug!("Parsed args: {args:#?}");
     }
 
-    let dirs = linux_fs::collect_application_dirs(&args);
-    let files = scan::collect_desktop_files(&dirs).await?;
-    let reports = scan::inspect_files_concurrently(files, &args).await;
+    if let Some(Command::Explain { code }) = &args.command {
+        explain::print(*code);
+        return Ok(ExitCode::SUCCESS);
+    }
+    if let Some(Command::Schema) = &args.command {
+        render::print(&schema::report_schema(), args.output_format())?;
+        return Ok(ExitCode::SUCCESS);
+    }
 
-    let broken: Vec<_> = reports
-        .into_iter()
-        .filter(|r| matches!(r.status, report::Status::Broken { .. }))
-        .collect();
+    if let Some(Command::Diff { old, new }) = &args.command {
+        let old = diff::read_report(old).await?;
+        let new = diff::read_report(new).await?;
+        let d = diff::diff_reports(&old, &new);
 
-    if !args.json && broken.is_empty() {
-        println!("No broken desktop entries found.");
-        return Ok(());
+        if args.output_format() != OutputFormat::Text {
+            render::print(&d, args.output_format())?;
+        } else {
+            diff::print_text(&d);
+        }
+        return Ok(ExitCode::SUCCESS);
     }
 
-    if args.json {
-        println!("{}", serde_json::to_string_pretty(&broken)?);
-        return Ok(());
+    if let Some(Command::InstallTimer {
+        on_calendar,
+        output,
+        scan_args,
+        force,
+    }) = &args.command
+    {
+        timer::install(&timer::TimerOptions {
+            on_calendar,
+            output: output.as_deref(),
+            scan_args: scan_args.as_deref(),
+            force: *force,
+        })?;
+        return Ok(ExitCode::SUCCESS);
+    }
+
+    if let Some(Command::History) = &args.command {
+        let entries = journal::read().await?;
+        if args.output_format() != OutputFormat::Text {
+            render::print(&entries, args.output_format())?;
+        } else {
+            journal::print_text(&entries);
+        }
+        return Ok(ExitCode::SUCCESS);
+    }
+
+    if let Some(Command::Undo { all, .. }) = &args.command {
+        let undone = undo::run(*all).await?;
+        if args.output_format() != OutputFormat::Text {
+            render::print(&undone, args.output_format())?;
+        } else {
+            undo::print_text(&undone);
+        }
+        return Ok(if undone.has_failures() {
+            ExitCode::FAILURE
+        } else {
+            ExitCode::SUCCESS
+        });
+    }
+
+    if let Some(Command::Restore { id }) = &args.command {
+        let mut q = quarantine::Quarantine::open().await?;
+        match id {
+            Some(id) => {
+                let journal = Journal::open().await?;
+                let entry = q.restore(id).await?;
+                journaled(
+                    journal
+                        .append(JournalAction::Restore {
+                            id: entry.id.clone(),
+                            path: entry.original_path.clone(),
+                        })
+                        .await,
+                );
+                println!(
+                    "{}",
+                    tr(
+                        "restore-done",
+                        &[("path", entry.original_path.display().to_string().into())]
+                    )
+                );
+            }
+            None if args.output_format() != OutputFormat::Text => {
+                render::print(q.entries(), args.output_format())?
+            }
+            None => quarantine::print_entries(q.entries()),
+        }
+        return Ok(ExitCode::SUCCESS);
+    }
+
+    // `--hosts` from a configuration file or the environment does not apply to subcommands.
+    if args.command.is_none()
+        && let Some(inventory) = &args.hosts
+    {
+        let hosts = fleet::read_inventory(inventory).await?;
+        let fleet = fleet::scan_hosts(
+            hosts,
+            &args.remote_command,
+            &fleet::forwarded_scan_args(&matches),
+        )
+        .await;
+
+        let output_file = output_file(&args)?;
+        if args.output_format() != OutputFormat::Text {
+            render::print(&fleet, args.output_format())?;
+        } else if args.plain {
+            plain::print_fleet(&fleet);
+        } else {
+            fleet::print_text(&fleet);
+        }
+        if let Some(output_file) = output_file {
+            output_file.commit()?;
+        }
+        return Ok(if fleet.hosts.iter().any(|h| h.error.is_some()) {
+            ExitCode::FAILURE
+        } else {
+            ExitCode::SUCCESS
+        });
+    }
+
+    if let Some(source) = args.dirs_from.clone() {
+        args.extra_dirs.extend(scan::read_path_list(&source).await?);
+    }
+
+    if let Some(Command::Fmt { check, files }) = &args.command {
+        let files = if files.is_empty() {
+            canonical::user_owned(scan::discover(&args).await?.0)
+        } else {
+            files.clone()
+        };
+        let results = canonical::format_files(&files, *check).await;
+
+        if args.output_format() != OutputFormat::Text {
+            render::print(&results, args.output_format())?;
+        } else {
+            canonical::print_text(&results);
+        }
+        let dirty = results.iter().any(|r| {
+            matches!(
+                r.outcome,
+                canonical::FormatOutcome::WouldReformat { .. }
+                    | canonical::FormatOutcome::Failed { .. }
+            )
+        });
+        return Ok(if dirty {
+            ExitCode::FAILURE
+        } else {
+            ExitCode::SUCCESS
+        });
+    }
+
+    if let Some(Command::ListDirs) = &args.command {
+        let dirs = linux_fs::collect_application_dirs_with_provenance(&args);
+
+        if args.output_format() != OutputFormat::Text {
+            render::print(&dirs, args.output_format())?;
+        } else {
+            linux_fs::print_dirs(&dirs);
+        }
+        return Ok(ExitCode::SUCCESS);
+    }
+
+    if let Some(Command::Export { bundle }) = &args.command {
+        let report = scan::run(&args).await?;
+        let copied = export::write_bundle(&report, bundle).await?;
+        println!(
+            "{}",
+            tr(
+                "export-written",
+                &[
+                    ("path", bundle.display().to_string().into()),
+                    ("count", copied.into()),
+                ]
+            )
+        );
+        return Ok(ExitCode::SUCCESS);
+    }
+
+    if let Some(Command::Simulate {
+        remove_path,
+        without_dir,
+        without_package,
+    }) = &args.command
+    {
+        let sim = simulate::Simulation::new(remove_path, without_dir, without_package).await?;
+        let current = scan::run(&args).await?;
+        let simulated = scan::run(&Args {
+            simulation: Some(Arc::new(sim)),
+            ..args.clone()
+        })
+        .await?;
+        let mut result = simulate::compare(&current, &simulated);
+        result.dependents = simulate::dependents(&current, without_dir).await;
+
+        if args.output_format() != OutputFormat::Text {
+            render::print(&result, args.output_format())?;
+        } else {
+            simulate::print_text(&result);
+        }
+        return Ok(ExitCode::SUCCESS);
     }
 
-    println!("Broken .desktop entries ({}):\n", broken.len());
-    for f in broken {
-        println!("- {}", f.desktop_file.display());
-        if let Some(name) = &f.name {
-            println!("  Name: {name}");
+    if let Some(Command::Fix {
+        system,
+        mode,
+        quarantine,
+        trash,
+        with_companions,
+        broken_duplicates,
+        field_codes,
+        remap: remap_rules,
+        remap_file,
+        yes,
+        force,
+        emit_script,
+        emit_patch,
+    }) = &args.command
+    {
+        let mut remap_rules = remap_rules.clone();
+        if let Some(path) = remap_file {
+            remap_rules.extend(remap::load_file(path).await?);
+        }
+        if !remap_rules.is_empty() && *mode != FixMode::Repair {
+            bail!("--remap and --remap-file require --mode repair");
         }
-        if let Some(exec) = &f.exec {
-            println!("  Exec: {exec}");
+        if emit_patch.is_some() && *mode == FixMode::Delete && !*field_codes {
+            bail!("--emit-patch requires --mode hide, --mode repair or --field-codes");
         }
-        if let Some(tx) = &f.try_exec {
-            println!("  TryExec: {tx}");
+
+        let report = scan::run(&args).await?;
+        let opts = fix::FixOptions {
+            system: *system,
+            mode: *mode,
+            quarantine: *quarantine,
+            trash: *trash,
+            with_companions: *with_companions,
+            remap: remap_rules,
+            broken_duplicates: *broken_duplicates,
+            field_codes: *field_codes,
+            force: *force,
+        };
+
+        if let Some(out) = emit_script {
+            let changed = fix::write_script(&report, &opts, out).await?;
+            println!(
+                "{}",
+                tr(
+                    "fix-script-written",
+                    &[
+                        ("path", out.display().to_string().into()),
+                        ("count", changed.into()),
+                    ]
+                )
+            );
+            return Ok(ExitCode::SUCCESS);
         }
-        if let Some(p) = &f.path_key {
-            println!("  Path: {p}");
+
+        if let Some(out) = emit_patch {
+            let changed = fix::write_patch(&report, &opts, out).await?;
+            println!(
+                "{}",
+                tr(
+                    "fix-patch-written",
+                    &[
+                        ("path", out.display().to_string().into()),
+                        ("count", changed.into()),
+                    ]
+                )
+            );
+            return Ok(ExitCode::SUCCESS);
+        }
+
+        if !*yes {
+            let pending = fix::pending_changes(&report, &opts).await?;
+            if !pending.is_empty() && !fix::confirm(&pending)? {
+                eprintln!("{}", tr("fix-aborted", &[]));
+                return Ok(ExitCode::FAILURE);
+            }
         }
-        println!("  Hidden: {} | NoDisplay: {}", f.hidden, f.no_display);
 
-        if let report::Status::Broken { reason } = &f.status {
-            println!("  Reason: {reason}");
+        let journal = Journal::open().await?;
+        let fixed = fix::run(&report, &opts).await?;
+        journaled(journal.append_fix(&fixed).await);
+
+        if args.output_format() != OutputFormat::Text {
+            render::print(&fixed, args.output_format())?;
+        } else {
+            fix::print_text(&fixed);
+        }
+        return Ok(if fixed.has_failures() {
+            ExitCode::FAILURE
         } else {
-            warn!("Unexpected non-broken in broken list?");
+            ExitCode::SUCCESS
+        });
+    }
+
+    if let Some(Command::Daemon {
+        max_rss,
+        max_open_files,
+        max_events_per_sec,
+    }) = &args.command
+    {
+        let limits = Limits {
+            max_rss_mib: *max_rss,
+            max_open_files: *max_open_files,
+            max_events_per_sec: *max_events_per_sec,
+        };
+        daemon::run(args, limits).await?;
+        return Ok(ExitCode::SUCCESS);
+    }
+
+    let line_format = args
+        .format
+        .as_deref()
+        .map(LineFormat::parse)
+        .transpose()
+        .context("Invalid --format")?;
+    let mut report = if args.stream {
+        if args.output_format() != OutputFormat::Jsonl {
+            bail!("--stream requires --output jsonl");
+        }
+        scan::run_with(&args, &ProgressBar::hidden(), &mut |f| {
+            if args.lists(f) {
+                render::print_tagged_line("finding", f);
+            }
+        })
+        .await?
+    } else {
+        scan::run_with(&args, &progress::scan_bar(&args), &mut |_| {}).await?
+    };
+    if args.group_by == Some(GroupBy::App) {
+        report.applications = identity::group(&report.findings).await;
+    }
+    let no_files = report
+        .warnings
+        .iter()
+        .any(|w| w.code == report::ReasonCode::NoDesktopFiles);
+    // Record before filtering, so entries a narrower listing hides keep their streaks.
+    if !args.no_history
+        && !args.frozen
+        && let Err(e) = history::record(&mut report).await
+    {
+        warn!("Failed to update finding history: {e:#}");
+    }
+    report.findings.retain(|r| args.lists(r));
+    report.sort(args.sort);
+    if args.check_flathub {
+        flathub::annotate(&mut report).await;
+    }
+
+    let mut suppressed = 0;
+    // Runs that record the baseline succeed; it exists to accept the current findings.
+    let mut gated = args.baseline.is_none();
+    if let Some(path) = &args.baseline {
+        match baseline::load(path).await? {
+            Some(b) if !args.update_baseline => {
+                suppressed = baseline::apply(&b, &mut report);
+                gated = true;
+                info!(suppressed, "Applied baseline {}", path.display());
+            }
+            _ => {
+                let journal = Journal::open().await?;
+                let recorded = baseline::write(path, &report).await?;
+                journaled(
+                    journal
+                        .append(JournalAction::Baseline {
+                            path: path.clone(),
+                            recorded,
+                        })
+                        .await,
+                );
+                info!(recorded, "Wrote baseline {}", path.display());
+            }
         }
-        println!();
+    }
+
+    match args.group_by {
+        Some(GroupBy::Dir) => {
+            report.groups = FindingGroup::group(&report.findings, |f| GroupKey::Directory {
+                path: f
+                    .desktop_file
+                    .parent()
+                    .map(PathBuf::from)
+                    .unwrap_or_default(),
+            });
+        }
+        Some(GroupBy::Source) => {
+            let dirs = linux_fs::collect_application_dirs_with_provenance(&args);
+            report.groups = FindingGroup::group(&report.findings, |f| {
+                linux_fs::source_group(&f.desktop_file, &dirs)
+            });
+        }
+        Some(GroupBy::App) | None => {}
+    }
+    let listed = report
+        .findings
+        .iter()
+        .map(|f| f.desktop_file.clone())
+        .collect::<HashSet<_>>();
+    report
+        .applications
+        .retain(|app| app.entries.iter().any(|e| listed.contains(&e.desktop_file)));
+
+    let exit_code = if no_files {
+        ExitCode::from(EXIT_NO_FILES)
+    } else if gated && args.fail_on.fails(&report) {
+        ExitCode::from(EXIT_FINDINGS)
+    } else {
+        ExitCode::SUCCESS
+    };
+
+    if args.notify
+        && let Err(e) = notify::send(&report).await
+    {
+        warn!("Failed to send the desktop notification: {e:#}");
+    }
+
+    if args.stream {
+        render::print_stream_tail(&report)?;
+        return Ok(exit_code);
+    }
+    let output_file = output_file(&args)?;
+    print_scan_report(&args, &report, suppressed, no_files, line_format.as_ref()).await?;
+    if let Some(output_file) = output_file {
+        output_file.commit()?;
     }
 
     info!("desktop-scout done!");
+    Ok(exit_code)
+}
+
+/// The `--output-file` the report is printed into, if any (`-` is stdout).
+///
+/// # Errors
+/// - [`anyhow::Error`] if the file cannot be created.
+fn output_file(args: &Args) -> Result<Option<OutputFile>> {
+    Ok(match &args.output_file {
+        Some(path) if path.as_os_str() != "-" => Some(OutputFile::create(path)?),
+        _ => None,
+    })
+}
+
+/// Print the scan report in the format chosen by `args`.
+///
+/// # Errors
+/// - [`anyhow::Error`] if the report cannot be rendered.
+async fn print_scan_report(
+    args: &Args,
+    report: &Report,
+    suppressed: usize,
+    no_files: bool,
+    line_format: Option<&LineFormat>,
+) -> Result<()> {
+    if args.output_format() == OutputFormat::Template {
+        let Some(path) = &args.template else {
+            bail!("--output template needs a template file (--template FILE)");
+        };
+        print!("{}", template::render_file(path, report).await?);
+        return Ok(());
+    }
+    if let Some(format) = line_format {
+        for f in &report.findings {
+            println!("{}", format.render(f));
+        }
+        return Ok(());
+    }
+    if args.output_format() != OutputFormat::Text {
+        return render::print_report(report, args.output_format()).await;
+    }
+    if args.plain {
+        plain::print_report(report, suppressed);
+        return Ok(());
+    }
+    if args.quiet {
+        term::print_quiet(report, args.fail_on);
+        return Ok(());
+    }
+
+    let terminal = Terminal::detect(args.color);
+    if no_files {
+        term::print_warnings(report, &terminal);
+        return Ok(());
+    }
+    term::print_report(report, suppressed, &terminal);
     Ok(())
 }
+
+/// Log a failure to journal a change that was already made; the change itself stands.
+fn journaled(result: Result<()>) {
+    if let Err(e) = result {
+        warn!("Failed to write the journal: {e:#}");
+    }
+}
@@ -0,0 +1,165 @@
+//! Markdown reports of scans (`--output markdown`).
+//!
+//! Meant to be pasted into GitHub or GitLab issues: a summary table with one row per reported entry
+//! in report order (`--sort`), followed by a detail section per entry with its reason, finding ID,
+//! `Exec=` line, suggested fix and lint warnings. Scan-level warnings are listed before the table.
+
+// -- std imports
+use std::fmt::Write as _;
+
+// -- module imports
+use crate::report::{Finding, Report, Status};
+
+/// Render `report` as a Markdown document.
+pub fn render(report: &Report) -> String {
+    let findings = report.findings.iter().collect::<Vec<_>>();
+    let broken = findings.iter().filter(|f| f.is_broken()).count();
+
+    let mut out = String::from("# desktop-scout report\n\n");
+    let _ = writeln!(
+        out,
+        "{} reported entries, {broken} broken.\n",
+        findings.len()
+    );
+
+    if !report.warnings.is_empty() {
+        out.push_str("## Scan warnings\n\n");
+        for w in &report.warnings {
+            let _ = writeln!(out, "- `{}` {}", w.code, inline(&w.message));
+        }
+        out.push('\n');
+    }
+
+    if findings.is_empty() {
+        return out;
+    }
+
+    out.push_str("| File | Name | Status | Code | ID | Warnings |\n");
+    out.push_str("| --- | --- | --- | --- | --- | --- |\n");
+    for f in &findings {
+        let _ = writeln!(
+            out,
+            "| {} | {} | {} | {} | `{}` | {} |",
+            code_span(&f.desktop_file.display().to_string()).replace('|', "\\|"),
+            cell(f.name.as_deref().unwrap_or_default()),
+            f.status.kind(),
+            status_code(f).map(|c| format!("`{c}`")).unwrap_or_default(),
+            f.id,
+            f.warnings.len()
+        );
+    }
+
+    out.push_str("\n## Details\n");
+    for f in findings {
+        details(f, &mut out);
+    }
+    out
+}
+
+/// Append the detail section of `f`.
+fn details(f: &Finding, out: &mut String) {
+    let _ = writeln!(
+        out,
+        "\n### {}\n",
+        code_span(&f.desktop_file.display().to_string())
+    );
+    if let Some(name) = &f.name {
+        let _ = writeln!(out, "- **Name:** {}", inline(name));
+    }
+    let _ = write!(out, "- **Status:** {}", f.status.kind());
+    match &f.status {
+        Status::Broken { code, reason }
+        | Status::Skipped { code, reason }
+        | Status::Suppressed { code, reason, .. }
+        | Status::TimedOut { code, reason } => {
+            let _ = writeln!(out, " (`{code}`): {}", inline(reason));
+        }
+        Status::Ok { .. } => out.push('\n'),
+    }
+    let _ = writeln!(out, "- **ID:** `{}`", f.id);
+    if let Some(exec) = &f.exec {
+        let _ = writeln!(out, "- **Exec:** {}", code_span(exec));
+    }
+    if let Some(exec) = &f.suggested_exec {
+        let _ = writeln!(out, "- **Suggested Exec:** {}", code_span(exec));
+    }
+    if !f.warnings.is_empty() {
+        out.push_str("- **Warnings:**\n");
+        for w in &f.warnings {
+            let _ = writeln!(out, "  - `{}` {}", w.code, inline(&w.message));
+        }
+    }
+}
+
+/// Code of the status of `f`, unless it is ok.
+fn status_code(f: &Finding) -> Option<String> {
+    match &f.status {
+        Status::Ok { .. } => None,
+        Status::Broken { code, .. }
+        | Status::Skipped { code, .. }
+        | Status::Suppressed { code, .. }
+        | Status::TimedOut { code, .. } => Some(code.to_string()),
+    }
+}
+
+/// `s` as a code span, with a fence long enough for the backticks it contains.
+fn code_span(s: &str) -> String {
+    let s = s.replace(['\n', '\r'], " ");
+    let longest = s
+        .split(|c| c != '`')
+        .map(str::len)
+        .max()
+        .unwrap_or_default();
+    let fence = "`".repeat(longest + 1);
+    // A space keeps backticks at either end from merging with the fence.
+    if s.starts_with('`') || s.ends_with('`') {
+        format!("{fence} {s} {fence}")
+    } else {
+        format!("{fence}{s}{fence}")
+    }
+}
+
+/// `s` as a table cell: on one line, with `|` escaped (GitHub and GitLab also unescape it inside
+/// code spans in tables).
+fn cell(s: &str) -> String {
+    inline(s).replace('|', "\\|")
+}
+
+/// `s` on a single line, with characters that would start Markdown or HTML markup escaped.
+fn inline(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '\n' | '\r' => out.push(' '),
+            '\\' | '`' | '*' | '_' | '[' | ']' | '<' | '>' | '#' => {
+                out.push('\\');
+                out.push(c);
+            }
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn code_spans_outgrow_their_backticks() {
+        assert_eq!(code_span("foo %U"), "`foo %U`");
+        assert_eq!(code_span("a `b` c"), "``a `b` c``");
+        assert_eq!(code_span("`x``"), "``` `x`` ```");
+        assert_eq!(code_span("two\nlines"), "`two lines`");
+    }
+
+    #[test]
+    fn inline_text_cannot_start_markup() {
+        assert_eq!(
+            inline("*My* [App] <b>#1_x\\"),
+            r"\*My\* \[App\] \<b\>\#1\_x\\"
+        );
+        assert_eq!(inline("a\r\nb"), "a  b");
+        assert_eq!(cell("a | b `c`"), r"a \| b \`c\`");
+    }
+}
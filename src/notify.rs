@@ -0,0 +1,122 @@
+//! Desktop notifications of scan results (`--notify`).
+//!
+//! Scans started by timers or login scripts run without a terminal, so their results would only
+//! show up in the log. With `--notify`, a scan with broken entries or entries with warnings sends
+//! a notification through the freedesktop notification service on the session bus; clean scans
+//! stay silent.
+
+// -- std imports
+use std::{collections::HashMap, time::Duration};
+
+// -- crate imports
+use anyhow::{Context, Result, bail};
+use tokio::time;
+use zbus::{Connection, proxy, zvariant::Value};
+
+// -- module imports
+use crate::{i18n::tr, report::Report};
+
+/// Files named in the notification body; further ones are only counted.
+const MAX_LISTED_FILES: usize = 5;
+
+/// Upper bound for reaching the notification service (it may be activated on demand).
+const NOTIFY_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Icon of the notification (a themed icon name).
+const ICON: &str = "dialog-warning";
+
+/// The freedesktop notification service.
+#[proxy(
+    interface = "org.freedesktop.Notifications",
+    default_service = "org.freedesktop.Notifications",
+    default_path = "/org/freedesktop/Notifications"
+)]
+trait Notifications {
+    /// Show a notification; returns its ID.
+    #[allow(clippy::too_many_arguments)]
+    fn notify(
+        &self,
+        app_name: &str,
+        replaces_id: u32,
+        app_icon: &str,
+        summary: &str,
+        body: &str,
+        actions: &[&str],
+        hints: HashMap<&str, Value<'_>>,
+        expire_timeout: i32,
+    ) -> zbus::Result<u32>;
+}
+
+/// Send a notification summarizing `report`, unless it has neither broken entries nor entries
+/// with warnings.
+///
+/// # Errors
+/// - [`anyhow::Error`] if the session bus or the notification service is unavailable or does
+///   not answer in time.
+pub async fn send(report: &Report) -> Result<()> {
+    let broken = report.findings.iter().filter(|f| f.is_broken()).count();
+    let warned = report
+        .findings
+        .iter()
+        .filter(|f| !f.is_broken() && !f.warnings.is_empty())
+        .count();
+    if broken == 0 && warned == 0 {
+        return Ok(());
+    }
+
+    let summary = if broken > 0 {
+        tr("notify-broken", &[("count", broken.into())])
+    } else {
+        tr("notify-warned", &[("count", warned.into())])
+    };
+    let mut files = report
+        .findings
+        .iter()
+        .filter(|f| f.is_reported())
+        .map(|f| {
+            f.desktop_file
+                .file_name()
+                .map(|n| n.to_string_lossy().into_owned())
+                .unwrap_or_default()
+        })
+        .collect::<Vec<_>>();
+    let more = files.len().saturating_sub(MAX_LISTED_FILES);
+    files.truncate(MAX_LISTED_FILES);
+    let mut body = files.join("\n");
+    if more > 0 {
+        body.push('\n');
+        body.push_str(&tr("notify-more", &[("count", more.into())]));
+    }
+
+    time::timeout(NOTIFY_TIMEOUT, show(&summary, &body))
+        .await
+        .unwrap_or_else(|_| {
+            bail!(
+                "The notification service did not answer within {}s",
+                NOTIFY_TIMEOUT.as_secs()
+            )
+        })
+}
+
+/// Show a notification with `summary` and `body`.
+async fn show(summary: &str, body: &str) -> Result<()> {
+    let conn = Connection::session()
+        .await
+        .context("Failed to connect to the session bus")?;
+    let hints = HashMap::from([("urgency", Value::U8(1))]);
+    NotificationsProxy::new(&conn)
+        .await?
+        .notify(
+            env!("CARGO_PKG_NAME"),
+            0,
+            ICON,
+            summary,
+            body,
+            &[],
+            hints,
+            -1,
+        )
+        .await
+        .context("Failed to send the notification")?;
+    Ok(())
+}
@@ -0,0 +1,248 @@
+//! Atomic report files (`--output-file`).
+//!
+//! Every output format prints to stdout, so instead of threading a writer through all of them,
+//! stdout itself is pointed at a temporary file next to the target while the report is printed.
+//! [`OutputFile::commit`] then moves the complete file into place with a single rename, so
+//! readers of the target (dashboards, `jq` in cron jobs) never see a partial report and a failed
+//! run leaves the previous report untouched.
+//!
+//! [`write_atomic`] does the same for files written in one go: state files (the scan cache and
+//! the finding history), which concurrent runs (daemon, timer, interactive scans) may update at
+//! the same time, and `.desktop` files rewritten by `fix` and `fmt`.
+
+// -- std imports
+use std::{
+    fs::{self, File},
+    io::{self, Write},
+    os::fd::{AsRawFd, RawFd},
+    path::{Path, PathBuf},
+    process,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+// -- crate imports
+use anyhow::{Context, Result};
+use tokio::io::AsyncWriteExt;
+
+/// Stdout redirected into a temporary file until [`OutputFile::commit`].
+///
+/// Dropping it without a commit restores stdout and removes the temporary file.
+#[derive(Debug)]
+pub struct OutputFile {
+    /// The file the report is written to.
+    path: PathBuf,
+
+    /// The temporary file in the same directory.
+    tmp: PathBuf,
+
+    /// The temporary file (kept open to sync it).
+    file: File,
+
+    /// Duplicate of the original stdout, restored when done (`None` once restored).
+    saved_stdout: Option<RawFd>,
+
+    /// Whether the temporary file was renamed over [`OutputFile::path`]; until then, dropping
+    /// removes it.
+    renamed: bool,
+}
+
+impl OutputFile {
+    /// Redirect stdout into a temporary file next to `path`.
+    ///
+    /// # Errors
+    /// - [`anyhow::Error`] if the temporary file cannot be created or stdout cannot be
+    ///   redirected.
+    pub fn create(path: &Path) -> Result<Self> {
+        let tmp = temp_path(path)?;
+        let file =
+            File::create(&tmp).with_context(|| format!("Failed to create {}", tmp.display()))?;
+
+        io::stdout().flush()?;
+        // SAFETY: dup only duplicates the open stdout descriptor.
+        let saved = unsafe { libc::dup(libc::STDOUT_FILENO) };
+        if saved < 0 {
+            let _ = fs::remove_file(&tmp);
+            return Err(io::Error::last_os_error()).context("Failed to duplicate stdout");
+        }
+        // SAFETY: both descriptors are open; dup2 atomically replaces stdout.
+        if unsafe { libc::dup2(file.as_raw_fd(), libc::STDOUT_FILENO) } < 0 {
+            let e = io::Error::last_os_error();
+            // SAFETY: `saved` was returned by dup above and is not used afterwards.
+            unsafe { libc::close(saved) };
+            let _ = fs::remove_file(&tmp);
+            return Err(e).context("Failed to redirect stdout");
+        }
+
+        Ok(OutputFile {
+            path: path.to_path_buf(),
+            tmp,
+            file,
+            saved_stdout: Some(saved),
+            renamed: false,
+        })
+    }
+
+    /// Restore stdout and move the written report over the target file, keeping the
+    /// permissions of the file it replaces. The temporary file is removed if any step fails.
+    ///
+    /// # Errors
+    /// - [`anyhow::Error`] if the report cannot be flushed to disk or renamed over the target.
+    pub fn commit(mut self) -> Result<()> {
+        self.restore_stdout()?;
+        self.file
+            .sync_all()
+            .with_context(|| format!("Failed to write {}", self.tmp.display()))?;
+        if let Ok(meta) = fs::metadata(&self.path) {
+            fs::set_permissions(&self.tmp, meta.permissions())
+                .with_context(|| format!("Failed to set permissions of {}", self.tmp.display()))?;
+        }
+        fs::rename(&self.tmp, &self.path)
+            .with_context(|| format!("Failed to replace {}", self.path.display()))?;
+        self.renamed = true;
+        Ok(())
+    }
+
+    /// Flush what was printed into the temporary file and point stdout back at the original.
+    fn restore_stdout(&mut self) -> io::Result<()> {
+        let Some(saved) = self.saved_stdout.take() else {
+            return Ok(());
+        };
+        let flushed = io::stdout().flush();
+        // SAFETY: `saved` is the open duplicate of the original stdout; it is closed right after
+        // being restored and never used again.
+        let restored = unsafe { libc::dup2(saved, libc::STDOUT_FILENO) };
+        unsafe { libc::close(saved) };
+        if restored < 0 {
+            return Err(io::Error::last_os_error());
+        }
+        flushed
+    }
+}
+
+impl Drop for OutputFile {
+    fn drop(&mut self) {
+        let _ = self.restore_stdout();
+        if !self.renamed {
+            let _ = fs::remove_file(&self.tmp);
+        }
+    }
+}
+
+/// Replace the file at `path` with `contents` through a temporary file and a rename, so readers
+/// and concurrent writers never see a partially written file.
+///
+/// The new file keeps the permissions of the one it replaces. If `path` is a symlink, the file
+/// it points to is replaced and the link is kept.
+///
+/// # Errors
+/// - [`anyhow::Error`] if the temporary file cannot be written or renamed over `path`.
+pub async fn write_atomic(path: &Path, contents: &[u8]) -> Result<()> {
+    let path = match tokio::fs::symlink_metadata(path).await {
+        Ok(meta) if meta.file_type().is_symlink() => tokio::fs::canonicalize(path)
+            .await
+            .with_context(|| format!("Failed to resolve {}", path.display()))?,
+        _ => path.to_path_buf(),
+    };
+    let tmp = temp_path(&path)?;
+    let written = async {
+        let mut file = tokio::fs::File::create(&tmp).await?;
+        file.write_all(contents).await?;
+        if let Ok(meta) = tokio::fs::metadata(&path).await {
+            file.set_permissions(meta.permissions()).await?;
+        }
+        file.sync_all().await
+    }
+    .await
+    .with_context(|| format!("Failed to write {}", tmp.display()));
+    let replaced = match written {
+        Ok(()) => tokio::fs::rename(&tmp, &path)
+            .await
+            .with_context(|| format!("Failed to replace {}", path.display())),
+        Err(e) => Err(e),
+    };
+    if replaced.is_err() {
+        let _ = tokio::fs::remove_file(&tmp).await;
+    }
+    replaced
+}
+
+/// A temporary file next to `path`, unique to this process and call so concurrent writers
+/// (other runs, or tasks of the daemon) never share it.
+fn temp_path(path: &Path) -> Result<PathBuf> {
+    static COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+    let name = path
+        .file_name()
+        .with_context(|| format!("{} is not a file path", path.display()))?;
+    let n = COUNTER.fetch_add(1, Ordering::Relaxed);
+    Ok(path.with_file_name(format!(
+        ".{}.{}.{n}.tmp",
+        name.to_string_lossy(),
+        process::id()
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+
+    use super::*;
+
+    /// Names of the files in `dir`.
+    fn entries(dir: &Path) -> Vec<String> {
+        let mut names = fs::read_dir(dir)
+            .unwrap()
+            .map(|e| e.unwrap().file_name().to_string_lossy().into_owned())
+            .collect::<Vec<_>>();
+        names.sort();
+        names
+    }
+
+    // Both cases redirect the process-wide stdout, so they run in one test.
+    #[test]
+    fn commit_replaces_the_target_or_removes_the_temporary_file() {
+        let dir = env::temp_dir().join(format!("desktop-scout-output-{}", process::id()));
+        fs::create_dir_all(dir.join("blocked")).unwrap();
+
+        let out = OutputFile::create(&dir.join("report.json")).unwrap();
+        io::stdout().write_all(b"{}\n").unwrap();
+        out.commit().unwrap();
+        assert_eq!(fs::read_to_string(dir.join("report.json")).unwrap(), "{}\n");
+
+        // A file cannot be renamed over a directory, so the last step of the commit fails.
+        let out = OutputFile::create(&dir.join("blocked")).unwrap();
+        io::stdout().write_all(b"{}\n").unwrap();
+        let committed = out.commit();
+
+        let names = entries(&dir);
+        fs::remove_dir_all(&dir).unwrap();
+        assert!(committed.is_err());
+        assert_eq!(names, ["blocked", "report.json"]);
+    }
+
+    #[tokio::test]
+    async fn write_atomic_keeps_symlinks_and_permissions() {
+        use std::os::unix::fs::{PermissionsExt, symlink};
+
+        let dir = env::temp_dir().join(format!("desktop-scout-atomic-{}", process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("target"), "old").unwrap();
+        fs::set_permissions(dir.join("target"), fs::Permissions::from_mode(0o600)).unwrap();
+        symlink("target", dir.join("link")).unwrap();
+
+        write_atomic(&dir.join("link"), b"new").await.unwrap();
+
+        let mode = fs::metadata(dir.join("target"))
+            .unwrap()
+            .permissions()
+            .mode();
+        let link = fs::symlink_metadata(dir.join("link")).unwrap();
+        let content = fs::read_to_string(dir.join("target")).unwrap();
+        let names = entries(&dir);
+        fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(content, "new");
+        assert_eq!(mode & 0o777, 0o600);
+        assert!(link.file_type().is_symlink());
+        assert_eq!(names, ["link", "target"]);
+    }
+}
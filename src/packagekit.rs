@@ -0,0 +1,141 @@
+//! Package queries through PackageKit on the system D-Bus.
+//!
+//! PackageKit offers the same transaction API on every distribution it supports, so one code
+//! path answers package queries regardless of the native package manager. It is tried first;
+//! callers fall back to the package manager CLIs (`dpkg-query`, `rpm`, `pacman`) when the
+//! system bus or the PackageKit service is unavailable (containers, minimal installs) or the
+//! package is unknown to it.
+
+// -- std imports
+use std::{path::PathBuf, time::Duration};
+
+// -- crate imports
+use anyhow::{Context, Result, bail};
+use futures::StreamExt;
+use tokio::time;
+use zbus::{Connection, proxy, zvariant::OwnedObjectPath};
+
+/// `PK_FILTER_ENUM_INSTALLED` as a filter bitfield: only installed packages.
+const FILTER_INSTALLED: u64 = 1 << 2;
+
+/// Upper bound for a complete query (PackageKit may first have to load its backend).
+const QUERY_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// The PackageKit daemon.
+#[proxy(
+    interface = "org.freedesktop.PackageKit",
+    default_service = "org.freedesktop.PackageKit",
+    default_path = "/org/freedesktop/PackageKit"
+)]
+trait PackageKit {
+    /// Create a transaction object for a single query.
+    fn create_transaction(&self) -> zbus::Result<OwnedObjectPath>;
+}
+
+/// A PackageKit transaction; each one runs a single method and reports through signals.
+#[proxy(
+    interface = "org.freedesktop.PackageKit.Transaction",
+    default_service = "org.freedesktop.PackageKit"
+)]
+trait Transaction {
+    /// Look up packages by name.
+    fn resolve(&self, filter: u64, packages: &[&str]) -> zbus::Result<()>;
+
+    /// List the files of packages.
+    fn get_files(&self, package_ids: &[&str]) -> zbus::Result<()>;
+
+    /// A package matched the query.
+    #[zbus(signal)]
+    fn package(&self, info: u32, package_id: String, summary: String) -> zbus::Result<()>;
+
+    /// The files of a package.
+    #[zbus(signal)]
+    fn files(&self, package_id: String, file_list: Vec<String>) -> zbus::Result<()>;
+
+    /// The transaction failed.
+    #[zbus(signal)]
+    fn error_code(&self, code: u32, details: String) -> zbus::Result<()>;
+
+    /// The transaction is done; no further signals follow.
+    #[zbus(signal)]
+    fn finished(&self, exit: u32, runtime: u32) -> zbus::Result<()>;
+}
+
+/// Files owned by the installed package `package`, or `None` if PackageKit does not know it.
+///
+/// # Errors
+/// - [`anyhow::Error`] if the system bus or PackageKit is unavailable, a transaction fails or
+///   the query exceeds its time limit.
+pub async fn package_files(package: &str) -> Result<Option<Vec<PathBuf>>> {
+    time::timeout(QUERY_TIMEOUT, query_files(package))
+        .await
+        .unwrap_or_else(|_| {
+            bail!(
+                "PackageKit did not answer within {}s",
+                QUERY_TIMEOUT.as_secs()
+            )
+        })
+}
+
+/// [`package_files`] without the time limit.
+async fn query_files(package: &str) -> Result<Option<Vec<PathBuf>>> {
+    let conn = Connection::system()
+        .await
+        .context("Failed to connect to the system bus")?;
+
+    let Some(package_id) = resolve(&conn, package).await? else {
+        return Ok(None);
+    };
+
+    let tx = transaction(&conn).await?;
+    let mut files = tx.receive_files().await?;
+    let mut errors = tx.receive_error_code().await?;
+    let mut finished = tx.receive_finished().await?;
+    tx.get_files(&[&package_id]).await?;
+
+    let mut out = Vec::new();
+    loop {
+        // Signals arrive in order, so anything sent before `Finished` is already queued.
+        tokio::select! {
+            biased;
+            Some(signal) = files.next() => {
+                out.extend(signal.args()?.file_list.into_iter().map(PathBuf::from));
+            }
+            Some(signal) = errors.next() => bail!("PackageKit: {}", signal.args()?.details),
+            _ = finished.next() => break,
+        }
+    }
+    Ok(Some(out))
+}
+
+/// Package ID (`name;version;arch;repo`) of the installed package `package`, if any.
+async fn resolve(conn: &Connection, package: &str) -> Result<Option<String>> {
+    let tx = transaction(conn).await?;
+    let mut packages = tx.receive_package().await?;
+    let mut errors = tx.receive_error_code().await?;
+    let mut finished = tx.receive_finished().await?;
+    tx.resolve(FILTER_INSTALLED, &[package]).await?;
+
+    let mut package_id = None;
+    loop {
+        tokio::select! {
+            biased;
+            Some(signal) = packages.next() => {
+                package_id.get_or_insert(signal.args()?.package_id);
+            }
+            Some(signal) = errors.next() => bail!("PackageKit: {}", signal.args()?.details),
+            _ = finished.next() => break,
+        }
+    }
+    Ok(package_id)
+}
+
+/// Create a new transaction.
+async fn transaction(conn: &Connection) -> Result<TransactionProxy<'static>> {
+    let path = PackageKitProxy::new(conn)
+        .await?
+        .create_transaction()
+        .await
+        .context("Failed to create a PackageKit transaction")?;
+    Ok(TransactionProxy::builder(conn).path(path)?.build().await?)
+}
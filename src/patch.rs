@@ -0,0 +1,148 @@
+//! Unified diffs of proposed edits (`fix --emit-patch`).
+//!
+//! Edit-style fixes (hiding, repairs, `Exec=` cleanup, user overrides) can be written as a
+//! unified diff instead of being applied, for review or for attaching to distro bug reports.
+//! File names are relative to `/` so the patch applies with `cd / && patch -p0`; GNU patch
+//! refuses absolute file names.
+
+// -- std imports
+use std::path::Path;
+
+/// Lines of unchanged context around each hunk.
+const CONTEXT: usize = 3;
+
+/// A line of the edit script between two texts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Op<'a> {
+    /// Line present in both texts.
+    Keep(&'a str),
+    /// Line only in the old text.
+    Remove(&'a str),
+    /// Line only in the new text.
+    Add(&'a str),
+}
+
+/// Unified diff turning `old` into `new` for the file at `path` (`old` is `None` when the file
+/// is created). Returns an empty string if nothing changes.
+pub fn unified_diff(path: &Path, old: Option<&str>, new: &str) -> String {
+    let old_text = old.unwrap_or_default();
+    if old_text == new {
+        return String::new();
+    }
+
+    let name = path.strip_prefix("/").unwrap_or(path).display().to_string();
+    let old_name = if old.is_some() {
+        name.clone()
+    } else {
+        "/dev/null".to_string()
+    };
+    let mut out = format!("--- {old_name}\n+++ {name}\n");
+
+    let old_lines = old_text.lines().collect::<Vec<_>>();
+    let new_lines = new.lines().collect::<Vec<_>>();
+    let ops = edit_script(&old_lines, &new_lines);
+    for (start, end) in hunks(&ops) {
+        out.push_str(&hunk(&ops, start, end));
+    }
+    out
+}
+
+/// Line-level edit script from `old` to `new`, based on a longest common subsequence.
+///
+/// Desktop entries are small, so the quadratic table is cheaper than anything cleverer.
+fn edit_script<'a>(old: &[&'a str], new: &[&'a str]) -> Vec<Op<'a>> {
+    let (n, m) = (old.len(), new.len());
+    // lcs[i][j]: length of the LCS of old[i..] and new[j..].
+    let mut lcs = vec![vec![0usize; m + 1]; n + 1];
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            lcs[i][j] = if old[i] == new[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut ops = Vec::with_capacity(n.max(m));
+    let (mut i, mut j) = (0, 0);
+    while i < n || j < m {
+        if i < n && j < m && old[i] == new[j] {
+            ops.push(Op::Keep(old[i]));
+            i += 1;
+            j += 1;
+        } else if i < n && (j == m || lcs[i + 1][j] >= lcs[i][j + 1]) {
+            ops.push(Op::Remove(old[i]));
+            i += 1;
+        } else {
+            ops.push(Op::Add(new[j]));
+            j += 1;
+        }
+    }
+    ops
+}
+
+/// Ranges of `ops` forming hunks: changes plus [`CONTEXT`] lines around them, merged when their
+/// context overlaps.
+fn hunks(ops: &[Op<'_>]) -> Vec<(usize, usize)> {
+    let mut ranges: Vec<(usize, usize)> = Vec::new();
+    for (idx, op) in ops.iter().enumerate() {
+        if matches!(op, Op::Keep(_)) {
+            continue;
+        }
+        let start = idx.saturating_sub(CONTEXT);
+        let end = (idx + 1 + CONTEXT).min(ops.len());
+        match ranges.last_mut() {
+            Some(last) if start <= last.1 => last.1 = end,
+            _ => ranges.push((start, end)),
+        }
+    }
+    ranges
+}
+
+/// Render `ops[start..end]` as a hunk with its `@@` header.
+fn hunk(ops: &[Op<'_>], start: usize, end: usize) -> String {
+    // Lines of either text before the hunk, and lines of either text inside it.
+    let old_before = ops[..start]
+        .iter()
+        .filter(|op| !matches!(op, Op::Add(_)))
+        .count();
+    let new_before = ops[..start]
+        .iter()
+        .filter(|op| !matches!(op, Op::Remove(_)))
+        .count();
+    let old_len = ops[start..end]
+        .iter()
+        .filter(|op| !matches!(op, Op::Add(_)))
+        .count();
+    let new_len = ops[start..end]
+        .iter()
+        .filter(|op| !matches!(op, Op::Remove(_)))
+        .count();
+
+    let mut out = format!(
+        "@@ -{} +{} @@\n",
+        range(old_before, old_len),
+        range(new_before, new_len)
+    );
+    for op in &ops[start..end] {
+        let (prefix, line) = match op {
+            Op::Keep(l) => (' ', l),
+            Op::Remove(l) => ('-', l),
+            Op::Add(l) => ('+', l),
+        };
+        out.push(prefix);
+        out.push_str(line);
+        out.push('\n');
+    }
+    out
+}
+
+/// `start,len` range of a hunk header; empty ranges point at the line before them.
+fn range(before: usize, len: usize) -> String {
+    match len {
+        0 => format!("{before},0"),
+        1 => format!("{}", before + 1),
+        _ => format!("{},{len}", before + 1),
+    }
+}
@@ -0,0 +1,227 @@
+//! Screen-reader-friendly text output (`--plain`).
+//!
+//! Output is strictly linear: no tables, alignment, bullets, brackets or color. Every value is
+//! printed on its own line behind an explicit label, and each finding forms one paragraph that
+//! starts with its position ("Broken entry 2 of 5"), so it can be followed when read aloud.
+
+// -- module imports
+use crate::{
+    fleet::FleetReport,
+    i18n::tr,
+    report::{Finding, Report, Status, Warning},
+};
+
+/// Print a scan report; `suppressed` is the number of findings hidden by the baseline.
+pub fn print_report(report: &Report, suppressed: usize) {
+    for (i, w) in report.warnings.iter().enumerate() {
+        print_warning("plain-scan-warning", i + 1, w);
+    }
+    if !report.warnings.is_empty() {
+        println!();
+    }
+
+    let (broken, rest): (Vec<_>, Vec<_>) = report.findings.iter().partition(|f| f.is_broken());
+    let (warned, others): (Vec<_>, Vec<_>) = rest.into_iter().partition(|f| !f.warnings.is_empty());
+    println!(
+        "{}",
+        tr(
+            "plain-summary",
+            &[
+                ("broken", broken.len().into()),
+                ("warned", warned.len().into()),
+            ]
+        )
+    );
+    if suppressed > 0 {
+        println!(
+            "{}",
+            tr(
+                "report-baseline-suppressed",
+                &[("count", suppressed.into())]
+            )
+        );
+    }
+
+    for (i, f) in broken.iter().enumerate() {
+        println!();
+        print_finding("plain-broken-entry", i + 1, broken.len(), f);
+    }
+    for (i, f) in warned.iter().enumerate() {
+        println!();
+        print_finding("plain-warned-entry", i + 1, warned.len(), f);
+    }
+    for (i, f) in others.iter().enumerate() {
+        println!();
+        print_finding("plain-other-entry", i + 1, others.len(), f);
+    }
+}
+
+/// Print a fleet report, one paragraph per host followed by its broken entries.
+pub fn print_fleet(fleet: &FleetReport) {
+    println!(
+        "{}",
+        tr(
+            "plain-fleet-summary",
+            &[("hosts", fleet.hosts.len().into())]
+        )
+    );
+
+    for (i, h) in fleet.hosts.iter().enumerate() {
+        println!();
+        println!(
+            "{}",
+            tr(
+                "plain-host",
+                &[
+                    ("index", (i + 1).into()),
+                    ("total", fleet.hosts.len().into()),
+                ]
+            )
+        );
+        println!(
+            "{}",
+            tr("plain-host-name", &[("host", h.host.as_str().into())])
+        );
+        if let Some(e) = &h.error {
+            println!(
+                "{}",
+                tr("plain-host-error", &[("error", e.as_str().into())])
+            );
+            continue;
+        }
+        println!("{}", tr("plain-host-broken", &[("count", h.broken.into())]));
+
+        let broken = h
+            .report
+            .iter()
+            .flat_map(|r| &r.findings)
+            .filter(|f| f.is_broken())
+            .collect::<Vec<_>>();
+        for (j, f) in broken.iter().enumerate() {
+            println!();
+            print_finding("plain-broken-entry", j + 1, broken.len(), f);
+        }
+    }
+}
+
+/// Print one finding as a labeled paragraph headed by `heading_id` ("... `index` of `total`").
+fn print_finding(heading_id: &str, index: usize, total: usize, f: &Finding) {
+    println!(
+        "{}",
+        tr(
+            heading_id,
+            &[("index", index.into()), ("total", total.into())]
+        )
+    );
+    println!(
+        "{}",
+        tr(
+            "plain-file",
+            &[("path", f.desktop_file.display().to_string().into())]
+        )
+    );
+    println!("{}", tr("report-id", &[("id", f.id.as_str().into())]));
+    if let Some(user) = &f.user {
+        println!("{}", tr("report-user", &[("user", user.as_str().into())]));
+    }
+
+    let fields = [
+        ("report-name", "name", &f.name),
+        ("report-exec", "exec", &f.exec),
+        ("report-tryexec", "tryexec", &f.try_exec),
+        ("report-path", "path", &f.path_key),
+    ];
+    for (id, arg, value) in fields {
+        if let Some(v) = value {
+            println!("{}", tr(id, &[(arg, v.as_str().into())]));
+        }
+    }
+    println!(
+        "{}",
+        tr("plain-hidden", &[("value", f.hidden.to_string().into())])
+    );
+    println!(
+        "{}",
+        tr(
+            "plain-no-display",
+            &[("value", f.no_display.to_string().into())]
+        )
+    );
+
+    println!(
+        "{}",
+        tr("plain-status", &[("status", f.status.kind().into())])
+    );
+    if let Status::Broken { code, reason }
+    | Status::Skipped { code, reason }
+    | Status::Suppressed { code, reason, .. }
+    | Status::TimedOut { code, reason } = &f.status
+    {
+        println!(
+            "{}",
+            tr("plain-reason-code", &[("code", code.as_str().into())])
+        );
+        println!(
+            "{}",
+            tr("plain-reason", &[("reason", reason.as_str().into())])
+        );
+    }
+    if let Some(exec) = &f.suggested_exec {
+        println!(
+            "{}",
+            tr("report-suggested-exec", &[("exec", exec.as_str().into())])
+        );
+    }
+    if let Some(h) = &f.history {
+        println!(
+            "{}",
+            tr(
+                "plain-first-seen",
+                &[(
+                    "first_seen",
+                    h.first_seen.format("%Y-%m-%d %H:%M").to_string().into()
+                )]
+            )
+        );
+        println!(
+            "{}",
+            tr("plain-broken-scans", &[("scans", h.broken_scans.into())])
+        );
+    }
+    if let Some(fh) = &f.flathub {
+        println!("{}", fh.message());
+    }
+    for p in &f.same_file_as {
+        println!(
+            "{}",
+            tr(
+                "plain-same-file",
+                &[("path", p.display().to_string().into())]
+            )
+        );
+    }
+    for (i, w) in f.warnings.iter().enumerate() {
+        print_warning("plain-warning", i + 1, w);
+    }
+}
+
+/// Print a warning as two labeled lines (code, then message), numbered by `index`.
+fn print_warning(id: &str, index: usize, w: &Warning) {
+    println!(
+        "{}",
+        tr(
+            &format!("{id}-code"),
+            &[("index", index.into()), ("code", w.code.as_str().into())]
+        )
+    );
+    println!(
+        "{}",
+        tr(
+            id,
+            &[
+                ("index", index.into()),
+                ("message", w.message.as_str().into())
+            ]
+        )
+    );
+}
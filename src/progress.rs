@@ -0,0 +1,34 @@
+//! Progress bar for scans.
+//!
+//! Large scans give no feedback until the report is printed, so while files are inspected a bar
+//! with the file count, the last inspected file and an ETA is drawn on stderr. It is only shown
+//! for the text report on a terminal: machine-readable output and `--quiet` runs are piped or
+//! logged, and `--plain` output is read by screen readers.
+
+// -- std imports
+use std::io::{self, IsTerminal};
+
+// -- crate imports
+use indicatif::{ProgressBar, ProgressDrawTarget, ProgressStyle};
+
+// -- module imports
+use crate::args::{Args, OutputFormat};
+
+/// Layout of the bar.
+const TEMPLATE: &str = "{bar:30} {pos}/{len} files, ETA {eta} {wide_msg}";
+
+/// The progress bar of a scan configured by `args`; hidden unless stdout is a terminal and the
+/// text report is printed. Its length is set once the files to inspect are known.
+pub fn scan_bar(args: &Args) -> ProgressBar {
+    let shown = io::stdout().is_terminal()
+        && args.output_format() == OutputFormat::Text
+        && !args.quiet
+        && !args.plain;
+    if !shown {
+        return ProgressBar::hidden();
+    }
+    let style = ProgressStyle::with_template(TEMPLATE)
+        .expect("valid progress template")
+        .progress_chars("=> ");
+    ProgressBar::with_draw_target(Some(0), ProgressDrawTarget::stderr()).with_style(style)
+}
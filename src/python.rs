@@ -0,0 +1,186 @@
+//! Python bindings (`python` feature).
+//!
+//! Builds the `desktop_scout` extension module (e.g. with `maturin develop`), so Python tooling
+//! can run scans in-process instead of shelling out to the CLI and re-parsing its JSON:
+//!
+//! ```python
+//! import desktop_scout
+//!
+//! opts = desktop_scout.ScanOptions(dirs=["/opt/apps"], include_hidden=True)
+//! for f in desktop_scout.scan(opts):
+//!     if f.kind == "broken":
+//!         print(f.desktop_file, f.code, f.reason)
+//! ```
+
+// -- std imports
+use std::path::PathBuf;
+
+// -- crate imports
+use clap::Parser;
+use pyo3::{exceptions::PyRuntimeError, prelude::*};
+
+// -- module imports
+use crate::{
+    args::Args,
+    report::{self, Status},
+    scan,
+};
+
+/// Options for [`py_scan`], mirroring the corresponding CLI flags.
+#[pyclass(module = "desktop_scout", from_py_object)]
+#[derive(Debug, Clone, Default)]
+pub struct ScanOptions {
+    /// Extra directories to scan (`--dir`).
+    #[pyo3(get, set)]
+    pub dirs: Vec<PathBuf>,
+
+    /// Do not scan the default XDG directories (`--no-default`).
+    #[pyo3(get, set)]
+    pub no_default: bool,
+
+    /// Include `Hidden`/`NoDisplay` entries (`--include-hidden`).
+    #[pyo3(get, set)]
+    pub include_hidden: bool,
+
+    /// Enable the interpreter script heuristic (`--check-script-args`).
+    #[pyo3(get, set)]
+    pub check_script_args: bool,
+
+    /// Return healthy entries as well, not only broken ones and ones with warnings.
+    #[pyo3(get, set)]
+    pub all: bool,
+}
+
+#[pymethods]
+impl ScanOptions {
+    #[new]
+    #[pyo3(signature = (dirs = Vec::new(), no_default = false, include_hidden = false, check_script_args = false, all = false))]
+    fn new(
+        dirs: Vec<PathBuf>,
+        no_default: bool,
+        include_hidden: bool,
+        check_script_args: bool,
+        all: bool,
+    ) -> Self {
+        ScanOptions {
+            dirs,
+            no_default,
+            include_hidden,
+            check_script_args,
+            all,
+        }
+    }
+
+    fn __repr__(&self) -> String {
+        format!("{self:?}")
+    }
+}
+
+/// A single inspected `.desktop` file.
+#[pyclass(module = "desktop_scout", get_all, frozen)]
+#[derive(Debug)]
+pub struct Finding {
+    /// Stable finding ID.
+    pub id: String,
+    /// Path of the `.desktop` file.
+    pub desktop_file: PathBuf,
+    /// `Name=` value.
+    pub name: Option<String>,
+    /// `Exec=` value.
+    pub exec: Option<String>,
+    /// `TryExec=` value.
+    pub try_exec: Option<String>,
+    /// Status kind: `ok`, `broken`, `skipped`, `suppressed` or `timed_out`.
+    pub kind: String,
+    /// Reason code (e.g. `EXEC_NOT_FOUND`), `None` for `ok` entries.
+    pub code: Option<String>,
+    /// Human-readable reason, `None` for `ok` entries.
+    pub reason: Option<String>,
+    /// Resolved executable of `ok` entries.
+    pub resolved_executable: Option<PathBuf>,
+    /// Non-fatal warnings as `(code, message)` pairs.
+    pub warnings: Vec<(String, String)>,
+}
+
+#[pymethods]
+impl Finding {
+    fn __repr__(&self) -> String {
+        format!(
+            "Finding(desktop_file='{}', kind='{}', code={})",
+            self.desktop_file.display(),
+            self.kind,
+            self.code
+                .as_deref()
+                .map_or("None".to_string(), |c| format!("'{c}'"))
+        )
+    }
+}
+
+impl From<report::Finding> for Finding {
+    fn from(f: report::Finding) -> Self {
+        let kind = f.status.kind().to_string();
+        let (code, reason, resolved_executable) = match f.status {
+            Status::Ok {
+                resolved_executable,
+            } => (None, None, resolved_executable),
+            Status::Broken { code, reason }
+            | Status::Skipped { code, reason }
+            | Status::Suppressed { code, reason, .. }
+            | Status::TimedOut { code, reason } => (Some(code.to_string()), Some(reason), None),
+        };
+
+        Finding {
+            id: f.id,
+            desktop_file: f.desktop_file,
+            name: f.name,
+            exec: f.exec,
+            try_exec: f.try_exec,
+            kind,
+            code,
+            reason,
+            resolved_executable,
+            warnings: f
+                .warnings
+                .into_iter()
+                .map(|w| (w.code.to_string(), w.message))
+                .collect(),
+        }
+    }
+}
+
+/// Run a scan and return its findings.
+///
+/// Blocks until the scan is finished; the GIL is released meanwhile.
+#[pyfunction(name = "scan")]
+#[pyo3(signature = (options = None))]
+fn py_scan(py: Python<'_>, options: Option<ScanOptions>) -> PyResult<Vec<Finding>> {
+    let opts = options.unwrap_or_default();
+    let mut args = Args::try_parse_from(["desktop-scout"])
+        .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+    args.no_log = true;
+    args.no_cache = true;
+    args.extra_dirs = opts.dirs;
+    args.no_default = opts.no_default;
+    args.include_hidden = opts.include_hidden;
+    args.check_script_args = opts.check_script_args;
+
+    let report = py
+        .detach(|| scan::run_blocking(&args))
+        .map_err(|e| PyRuntimeError::new_err(format!("{e:#}")))?;
+
+    Ok(report
+        .findings
+        .into_iter()
+        .filter(|f| opts.all || f.is_reported())
+        .map(Finding::from)
+        .collect())
+}
+
+/// Python module definition.
+#[pymodule]
+fn desktop_scout(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<ScanOptions>()?;
+    m.add_class::<Finding>()?;
+    m.add_function(wrap_pyfunction!(py_scan, m)?)?;
+    Ok(())
+}
@@ -0,0 +1,277 @@
+//! Quarantine for broken entries (`fix --quarantine`, `restore`).
+//!
+//! Quarantined files are moved to `$XDG_DATA_HOME/desktop-scout/quarantine/<id>.desktop` (their
+//! companion artifacts to `<id>.d/`) and recorded in `manifest.json` next to them, so
+//! `restore <id>` can put them back where they came from. The quarantine ID is the finding ID of
+//! the broken entry.
+
+// -- std imports
+use std::{
+    io::ErrorKind,
+    path::{Path, PathBuf},
+};
+
+// -- crate imports
+use anyhow::{Context, Result, bail};
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+use tokio::fs;
+use tracing::info;
+use xdg::BaseDirectories;
+
+// -- module imports
+use crate::{
+    fix,
+    i18n::tr,
+    report::{Finding, ReasonCode, Status},
+};
+
+/// Name of the manifest file inside the quarantine directory.
+const MANIFEST_NAME: &str = "manifest.json";
+
+/// A quarantined `.desktop` file.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QuarantineEntry {
+    /// Quarantine ID (the finding ID, suffixed if it was already taken).
+    pub id: String,
+
+    /// Where the file was moved from (and is restored to).
+    pub original_path: PathBuf,
+
+    /// When the file was quarantined.
+    pub quarantined_at: DateTime<Local>,
+
+    /// Reason code of the finding that caused the quarantine.
+    pub code: Option<ReasonCode>,
+
+    /// Original paths of companion artifacts quarantined with the entry (icons, MIME packages).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub companions: Vec<PathBuf>,
+}
+
+/// Contents of `manifest.json`.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct Manifest {
+    entries: Vec<QuarantineEntry>,
+}
+
+/// The quarantine directory and its manifest.
+#[derive(Debug)]
+pub struct Quarantine {
+    dir: PathBuf,
+    manifest: Manifest,
+}
+
+impl Quarantine {
+    /// Open the user's quarantine, creating the directory if needed.
+    ///
+    /// # Errors
+    /// - [`anyhow::Error`] if the directory cannot be determined or created, or the manifest is
+    ///   unreadable.
+    pub async fn open() -> Result<Self> {
+        let dir = BaseDirectories::with_prefix("desktop-scout")
+            .get_data_home()
+            .context("Could not determine XDG data directory for the quarantine")?
+            .join("quarantine");
+        fs::create_dir_all(&dir)
+            .await
+            .with_context(|| format!("Failed to create {}", dir.display()))?;
+
+        let manifest_path = dir.join(MANIFEST_NAME);
+        let manifest = match fs::read(&manifest_path).await {
+            Ok(c) => serde_json::from_slice(&c)
+                .with_context(|| format!("{} is corrupt", manifest_path.display()))?,
+            Err(e) if e.kind() == ErrorKind::NotFound => Manifest::default(),
+            Err(e) => {
+                return Err(e)
+                    .with_context(|| format!("Failed to read {}", manifest_path.display()));
+            }
+        };
+
+        Ok(Quarantine { dir, manifest })
+    }
+
+    /// Quarantined entries, oldest first.
+    pub fn entries(&self) -> &[QuarantineEntry] {
+        &self.manifest.entries
+    }
+
+    /// Move the file of `finding` and its `companions` into the quarantine and return its
+    /// quarantine ID.
+    ///
+    /// # Errors
+    /// - [`anyhow::Error`] if a file cannot be moved or the manifest cannot be written.
+    pub async fn add(&mut self, finding: &Finding, companions: &[PathBuf]) -> Result<String> {
+        let mut id = finding.id.clone();
+        let mut n = 1;
+        while self.manifest.entries.iter().any(|e| e.id == id) {
+            n += 1;
+            id = format!("{}-{n}", finding.id);
+        }
+
+        move_file(&finding.desktop_file, &self.stored_path(&id)).await?;
+
+        // Record whatever was moved even if a companion fails, so it stays restorable.
+        let mut moved = Vec::new();
+        let mut companion_error = None;
+        for (idx, c) in companions.iter().enumerate() {
+            let result = match fs::create_dir_all(self.companion_dir(&id)).await {
+                Ok(()) => move_file(c, &self.companion_path(&id, idx, c)).await,
+                Err(e) => Err(e.into()),
+            };
+            match result {
+                Ok(()) => moved.push(c.clone()),
+                Err(e) => {
+                    companion_error = Some(e);
+                    break;
+                }
+            }
+        }
+
+        self.manifest.entries.push(QuarantineEntry {
+            id: id.clone(),
+            original_path: finding.desktop_file.clone(),
+            quarantined_at: Local::now(),
+            code: match &finding.status {
+                Status::Broken { code, .. }
+                | Status::Skipped { code, .. }
+                | Status::Suppressed { code, .. }
+                | Status::TimedOut { code, .. } => Some(*code),
+                Status::Ok { .. } => None,
+            },
+            companions: moved,
+        });
+        self.save().await?;
+        if let Some(e) = companion_error {
+            return Err(e.context(format!("Quarantined {id}, but not all companions")));
+        }
+
+        info!(id, file = %finding.desktop_file.display(), "Quarantined entry");
+        Ok(id)
+    }
+
+    /// Move the entry `id` (or a unique prefix of it) back to its original location.
+    ///
+    /// # Errors
+    /// - [`anyhow::Error`] if the ID is unknown or ambiguous, the original path is occupied, or
+    ///   moving fails.
+    pub async fn restore(&mut self, id: &str) -> Result<QuarantineEntry> {
+        let matches = self
+            .manifest
+            .entries
+            .iter()
+            .enumerate()
+            .filter(|(_, e)| e.id.starts_with(id))
+            .map(|(idx, _)| idx)
+            .collect::<Vec<_>>();
+        let idx = match matches.as_slice() {
+            [] => bail!("No quarantined entry with ID {id}"),
+            [idx] => *idx,
+            _ => bail!("ID {id} is ambiguous ({} matches)", matches.len()),
+        };
+
+        let entry = self.manifest.entries[idx].clone();
+        for target in std::iter::once(&entry.original_path).chain(&entry.companions) {
+            if fs::symlink_metadata(target).await.is_ok() {
+                bail!(
+                    "{} already exists; refusing to overwrite it",
+                    target.display()
+                );
+            }
+        }
+
+        restore_file(&self.stored_path(&entry.id), &entry.original_path).await?;
+        for (i, c) in entry.companions.iter().enumerate() {
+            restore_file(&self.companion_path(&entry.id, i, c), c).await?;
+        }
+        if !entry.companions.is_empty() {
+            fs::remove_dir(self.companion_dir(&entry.id)).await.ok();
+        }
+        self.manifest.entries.remove(idx);
+        self.save().await?;
+
+        info!(id = entry.id, file = %entry.original_path.display(), "Restored entry");
+        Ok(entry)
+    }
+
+    /// Location of the quarantined copy of `id`.
+    fn stored_path(&self, id: &str) -> PathBuf {
+        self.dir.join(format!("{id}.desktop"))
+    }
+
+    /// Directory holding the quarantined companions of `id`.
+    fn companion_dir(&self, id: &str) -> PathBuf {
+        self.dir.join(format!("{id}.d"))
+    }
+
+    /// Location of the `idx`-th quarantined companion of `id` (prefixed to avoid name clashes
+    /// between icon sizes).
+    fn companion_path(&self, id: &str, idx: usize, original: &Path) -> PathBuf {
+        let name = original
+            .file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        self.companion_dir(id).join(format!("{idx}-{name}"))
+    }
+
+    /// Write the manifest.
+    async fn save(&self) -> Result<()> {
+        let path = self.dir.join(MANIFEST_NAME);
+        let json = serde_json::to_string_pretty(&self.manifest)?;
+        fs::write(&path, json + "\n")
+            .await
+            .with_context(|| format!("Failed to write {}", path.display()))
+    }
+}
+
+/// Move a quarantined file back to `to`, recreating its parent directory.
+async fn restore_file(from: &Path, to: &Path) -> Result<()> {
+    if let Some(parent) = to.parent() {
+        fs::create_dir_all(parent)
+            .await
+            .with_context(|| format!("Failed to create {}", parent.display()))?;
+    }
+    move_file(from, to).await
+}
+
+/// Move `from` to `to`, falling back to copy + remove across filesystems (symlinks stay links).
+async fn move_file(from: &Path, to: &Path) -> Result<()> {
+    if fs::rename(from, to).await.is_ok() {
+        return Ok(());
+    }
+
+    fix::copy_file_or_link(from, to)
+        .await
+        .with_context(|| format!("Failed to copy {} to {}", from.display(), to.display()))?;
+    fs::remove_file(from)
+        .await
+        .with_context(|| format!("Failed to remove {}", from.display()))
+}
+
+/// Print quarantined entries, oldest first.
+pub fn print_entries(entries: &[QuarantineEntry]) {
+    if entries.is_empty() {
+        println!("{}", tr("quarantine-empty", &[]));
+        return;
+    }
+
+    for e in entries {
+        print!(
+            "{}  {}  {}",
+            e.id,
+            e.quarantined_at.format("%Y-%m-%d %H:%M"),
+            e.original_path.display()
+        );
+        if let Some(code) = e.code {
+            print!(" [{code}]");
+        }
+        if !e.companions.is_empty() {
+            let count = e.companions.len();
+            print!(
+                " {}",
+                tr("quarantine-companions", &[("count", count.into())])
+            );
+        }
+        println!();
+    }
+}
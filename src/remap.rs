@@ -0,0 +1,118 @@
+//! Path remapping rules for moved prefixes (`fix --mode repair --remap`).
+//!
+//! When an application directory is renamed (e.g. `/opt/oldapp` to `/opt/newapp`), every entry
+//! pointing into it breaks at once. A [`RemapRule`] rewrites such prefixes in `Exec=`,
+//! `TryExec=`, `Icon=` and `Path=` values. Rules are given as `OLD=NEW` on the command line or as
+//! `OLD -> NEW` lines in a rules file.
+
+// -- std imports
+use std::{path::Path, str::FromStr};
+
+// -- crate imports
+use anyhow::{Context, Result, bail};
+use tokio::fs;
+
+/// Desktop entry keys whose values are remapped.
+pub const REMAPPED_KEYS: &[&str] = &["Exec", "TryExec", "Icon", "Path"];
+
+/// Rewrite of a path prefix.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RemapRule {
+    /// Prefix to replace, without trailing slash.
+    pub from: String,
+
+    /// Replacement prefix, without trailing slash.
+    pub to: String,
+}
+
+impl FromStr for RemapRule {
+    type Err = String;
+
+    /// Parse `OLD -> NEW` or `OLD=NEW`; both sides must be absolute paths.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (from, to) = s
+            .split_once("->")
+            .or_else(|| s.split_once('='))
+            .ok_or_else(|| format!("expected `OLD=NEW` or `OLD -> NEW`, got `{s}`"))?;
+        let (from, to) = (normalize(from), normalize(to));
+
+        if !from.starts_with('/') || !to.starts_with('/') {
+            return Err(format!("both sides of `{s}` must be absolute paths"));
+        }
+        if from == "/" {
+            return Err("remapping `/` would rewrite every path".into());
+        }
+        Ok(RemapRule { from, to })
+    }
+}
+
+/// Trim whitespace and trailing slashes (keeping a lone `/`).
+fn normalize(path: &str) -> String {
+    let path = path.trim();
+    match path.trim_end_matches('/') {
+        "" if path.starts_with('/') => "/".into(),
+        p => p.to_string(),
+    }
+}
+
+/// Read rules from `path`: one `OLD -> NEW` (or `OLD=NEW`) per line, `#` starts a comment.
+///
+/// # Errors
+/// - [`anyhow::Error`] if the file cannot be read or a line is not a valid rule.
+pub async fn load_file(path: &Path) -> Result<Vec<RemapRule>> {
+    let content = fs::read_to_string(path)
+        .await
+        .with_context(|| format!("Failed to read remap rules from {}", path.display()))?;
+
+    let mut rules = Vec::new();
+    for (n, line) in content.lines().enumerate() {
+        let line = line.split('#').next().unwrap_or("").trim();
+        if line.is_empty() {
+            continue;
+        }
+        match line.parse() {
+            Ok(rule) => rules.push(rule),
+            Err(e) => bail!("{}:{}: {e}", path.display(), n + 1),
+        }
+    }
+    Ok(rules)
+}
+
+/// Apply the first matching rule to every path in `value` and return the rewritten value.
+///
+/// A prefix only matches whole path components: `/opt/app` rewrites `/opt/app/bin/x` and
+/// `"/opt/app"`, but not `/opt/application`. Returns `None` if nothing was rewritten.
+pub fn apply(value: &str, rules: &[RemapRule]) -> Option<String> {
+    let mut out = String::with_capacity(value.len());
+    let mut rest = value;
+    let mut changed = false;
+
+    'outer: while !rest.is_empty() {
+        // Paths start at the beginning of the value or after a separator.
+        let at_boundary = out
+            .chars()
+            .last()
+            .is_none_or(|c| c.is_whitespace() || matches!(c, '"' | '\'' | '=' | ':'));
+        if at_boundary {
+            for rule in rules {
+                if let Some(after) = rest.strip_prefix(rule.from.as_str())
+                    && after
+                        .chars()
+                        .next()
+                        .is_none_or(|c| c == '/' || c.is_whitespace() || matches!(c, '"' | '\''))
+                {
+                    out.push_str(&rule.to);
+                    rest = after;
+                    changed = true;
+                    continue 'outer;
+                }
+            }
+        }
+
+        let c = rest.chars().next().expect("rest is not empty");
+        out.push(c);
+        rest = &rest[c.len_utf8()..];
+    }
+
+    changed.then_some(out)
+}
@@ -0,0 +1,451 @@
+//! Machine-readable and tabular output (`--output`).
+//!
+//! Text output is printed by the individual modules; every other [`OutputFormat`] goes through
+//! here. Scan reports get dedicated CSV and table layouts (one row per finding). Other results
+//! are rendered generically from their JSON form: `jsonl` prints one line per element of a list
+//! result, and `csv`/`table` need a list of records. SARIF, JUnit, TAP, HTML and Markdown are only
+//! defined for scan reports (see [`crate::sarif`], [`crate::junit`], [`crate::tap`],
+//! [`crate::html`] and [`crate::markdown`]).
+
+// -- std imports
+use std::fmt::Write as _;
+
+// -- crate imports
+use anyhow::{Result, bail};
+use serde::Serialize;
+use serde_json::{Map, Value};
+use tracing::warn;
+
+// -- module imports
+use crate::{
+    args::OutputFormat,
+    html, junit, markdown,
+    report::{Finding, Report, Status},
+    sarif, tap,
+};
+
+/// Header of the CSV and table layouts of a scan report.
+const REPORT_COLUMNS: &[&str] = &["id", "file", "name", "status", "code", "reason", "warnings"];
+
+/// Print a scan report in `format` (anything but [`OutputFormat::Text`]).
+///
+/// # Errors
+/// - [`anyhow::Error`] if the report cannot be serialized.
+pub async fn print_report(report: &Report, format: OutputFormat) -> Result<()> {
+    match format {
+        OutputFormat::Sarif => {
+            println!(
+                "{}",
+                serde_json::to_string_pretty(&sarif::log(report).await)?
+            );
+        }
+        OutputFormat::Junit => print!("{}", junit::render(report)),
+        OutputFormat::Tap => print!("{}", tap::render(report)),
+        OutputFormat::Html => print!("{}", html::render(report).await),
+        OutputFormat::Markdown => print!("{}", markdown::render(report)),
+        OutputFormat::Jsonl => {
+            let mut out = String::new();
+            for w in &report.warnings {
+                out.push_str(&tagged_line("warning", w)?);
+            }
+            for f in &report.findings {
+                out.push_str(&tagged_line("finding", f)?);
+            }
+            for app in &report.applications {
+                out.push_str(&tagged_line("application", app)?);
+            }
+            if let Some(summary) = &report.summary {
+                out.push_str(&tagged_line("summary", summary)?);
+            }
+            print!("{out}");
+        }
+        OutputFormat::Csv => {
+            let rows = report.findings.iter().map(report_row).collect::<Vec<_>>();
+            print!("{}", csv(REPORT_COLUMNS, &rows));
+        }
+        OutputFormat::Table => {
+            let rows = report
+                .findings
+                .iter()
+                .map(|f| {
+                    let mut row = report_row(f);
+                    // The ID and the reason text are too wide for a terminal table.
+                    row.remove(5);
+                    row.remove(0);
+                    row
+                })
+                .collect::<Vec<_>>();
+            print!(
+                "{}",
+                table(&["file", "name", "status", "code", "warnings"], &rows)
+            );
+        }
+        _ => print(report, format)?,
+    }
+    Ok(())
+}
+
+/// Print any serializable result in `format` (anything but [`OutputFormat::Text`]).
+///
+/// # Errors
+/// - [`anyhow::Error`] if `value` cannot be serialized, `format` is `csv`/`table` and `value`
+///   is not a list, or `format` is only defined for scan reports (`sarif`, `junit`, `tap`, `html`,
+///   `markdown`, `template`).
+pub fn print<T: Serialize + ?Sized>(value: &T, format: OutputFormat) -> Result<()> {
+    let value = serde_json::to_value(value)?;
+    let out = match format {
+        OutputFormat::Text | OutputFormat::Json => serde_json::to_string_pretty(&value)? + "\n",
+        OutputFormat::Jsonl => match &value {
+            Value::Array(items) => items
+                .iter()
+                .map(|i| Ok(serde_json::to_string(i)? + "\n"))
+                .collect::<Result<String>>()?,
+            v => serde_json::to_string(v)? + "\n",
+        },
+        OutputFormat::Yaml => yaml(&value),
+        OutputFormat::Sarif
+        | OutputFormat::Junit
+        | OutputFormat::Tap
+        | OutputFormat::Html
+        | OutputFormat::Markdown
+        | OutputFormat::Template => {
+            bail!(
+                "--output {} is only available for scan reports; use json, jsonl or yaml here",
+                format.as_str()
+            )
+        }
+        OutputFormat::Csv | OutputFormat::Table => {
+            let Value::Array(items) = &value else {
+                bail!(
+                    "--output {} needs a list result; use json, jsonl or yaml here",
+                    format.as_str()
+                );
+            };
+            let (columns, rows) = records(items);
+            let columns = columns.iter().map(String::as_str).collect::<Vec<_>>();
+            if format == OutputFormat::Csv {
+                csv(&columns, &rows)
+            } else {
+                table(&columns, &rows)
+            }
+        }
+    };
+    print!("{out}");
+    Ok(())
+}
+
+/// Print the parts of a `--stream`ed `jsonl` report that are only known once the scan is done:
+/// the scan-level warnings and the summary (the findings were printed as they completed).
+///
+/// # Errors
+/// - [`anyhow::Error`] if the report cannot be serialized.
+pub fn print_stream_tail(report: &Report) -> Result<()> {
+    let mut out = String::new();
+    for w in &report.warnings {
+        out.push_str(&tagged_line("warning", w)?);
+    }
+    if let Some(summary) = &report.summary {
+        out.push_str(&tagged_line("summary", summary)?);
+    }
+    print!("{out}");
+    Ok(())
+}
+
+/// Print `value` as a `jsonl` line tagged with `"type": kind`, logging values that cannot be
+/// serialized instead.
+pub fn print_tagged_line<T: Serialize>(kind: &str, value: &T) {
+    match tagged_line(kind, value) {
+        Ok(line) => print!("{line}"),
+        Err(e) => warn!("Failed to serialize a {kind}: {e:#}"),
+    }
+}
+
+/// Compact JSON line of `value` with an added `"type": kind` field.
+fn tagged_line<T: Serialize>(kind: &str, value: &T) -> Result<String> {
+    let mut object = Map::new();
+    object.insert("type".into(), kind.into());
+    if let Value::Object(fields) = serde_json::to_value(value)? {
+        object.extend(fields);
+    }
+    Ok(serde_json::to_string(&object)? + "\n")
+}
+
+/// One CSV/table row of a finding, in [`REPORT_COLUMNS`] order.
+fn report_row(f: &Finding) -> Vec<String> {
+    let (code, reason) = match &f.status {
+        Status::Ok { .. } => (String::new(), String::new()),
+        Status::Broken { code, reason }
+        | Status::Skipped { code, reason }
+        | Status::Suppressed { code, reason, .. }
+        | Status::TimedOut { code, reason } => (code.to_string(), reason.clone()),
+    };
+    let warnings = f
+        .warnings
+        .iter()
+        .map(|w| w.code.as_str())
+        .collect::<Vec<_>>()
+        .join(";");
+    vec![
+        f.id.clone(),
+        f.desktop_file.display().to_string(),
+        f.name.clone().unwrap_or_default(),
+        f.status.kind().to_string(),
+        code,
+        reason,
+        warnings,
+    ]
+}
+
+/// Columns (keys of all objects, in first-seen order) and rows of a list of JSON records.
+///
+/// Nested values are rendered as compact JSON; non-object items become a single `value` column.
+fn records(items: &[Value]) -> (Vec<String>, Vec<Vec<String>>) {
+    let mut columns = Vec::<String>::new();
+    for item in items {
+        match item {
+            Value::Object(o) => {
+                for k in o.keys() {
+                    if !columns.contains(k) {
+                        columns.push(k.clone());
+                    }
+                }
+            }
+            _ if !columns.iter().any(|c| c == "value") => columns.push("value".into()),
+            _ => {}
+        }
+    }
+
+    let rows = items
+        .iter()
+        .map(|item| {
+            columns
+                .iter()
+                .map(|c| match item {
+                    Value::Object(o) => o.get(c).map(cell).unwrap_or_default(),
+                    v if c == "value" => cell(v),
+                    _ => String::new(),
+                })
+                .collect()
+        })
+        .collect();
+    (columns, rows)
+}
+
+/// Text of a single JSON value in a CSV/table cell.
+fn cell(v: &Value) -> String {
+    match v {
+        Value::Null => String::new(),
+        Value::String(s) => s.clone(),
+        v => v.to_string(),
+    }
+}
+
+/// RFC 4180 CSV with a header line.
+fn csv(columns: &[&str], rows: &[Vec<String>]) -> String {
+    let mut out = String::new();
+    let lines = std::iter::once(columns.iter().map(|c| c.to_string()).collect::<Vec<_>>())
+        .chain(rows.iter().cloned());
+    for line in lines {
+        let fields = line
+            .iter()
+            .map(|f| {
+                if f.contains([',', '"', '\n', '\r']) {
+                    format!("\"{}\"", f.replace('"', "\"\""))
+                } else {
+                    f.clone()
+                }
+            })
+            .collect::<Vec<_>>();
+        out.push_str(&fields.join(","));
+        out.push_str("\r\n");
+    }
+    out
+}
+
+/// Column-aligned plain-text table with an upper-case header.
+fn table(columns: &[&str], rows: &[Vec<String>]) -> String {
+    // Cells are kept on one line so rows stay aligned.
+    let rows = rows
+        .iter()
+        .map(|r| r.iter().map(|c| c.replace(['\n', '\r'], " ")).collect())
+        .collect::<Vec<Vec<String>>>();
+    let header = columns.iter().map(|c| c.to_uppercase()).collect::<Vec<_>>();
+    let widths = header
+        .iter()
+        .enumerate()
+        .map(|(i, h)| {
+            rows.iter()
+                .map(|r| r[i].chars().count())
+                .chain([h.chars().count()])
+                .max()
+                .unwrap_or(0)
+        })
+        .collect::<Vec<_>>();
+
+    let mut out = String::new();
+    for line in std::iter::once(&header).chain(&rows) {
+        let cells = line
+            .iter()
+            .zip(&widths)
+            .map(|(c, w)| format!("{c:<w$}"))
+            .collect::<Vec<_>>();
+        out.push_str(cells.join("  ").trim_end());
+        out.push('\n');
+    }
+    out
+}
+
+/// Block-style YAML document for a JSON value.
+fn yaml(value: &Value) -> String {
+    let mut out = String::from("---\n");
+    match value {
+        Value::Object(o) if !o.is_empty() => yaml_object(o, 0, &mut out),
+        Value::Array(a) if !a.is_empty() => yaml_array(a, 0, &mut out),
+        v => {
+            out.push_str(&yaml_inline(v));
+            out.push('\n');
+        }
+    }
+    out
+}
+
+/// Append the entries of `o` at `indent`.
+fn yaml_object(o: &Map<String, Value>, indent: usize, out: &mut String) {
+    for (k, v) in o {
+        let _ = write!(out, "{:indent$}{}:", "", yaml_string(k));
+        yaml_child(v, indent, out);
+    }
+}
+
+/// Append the items of `a` at `indent`.
+fn yaml_array(a: &[Value], indent: usize, out: &mut String) {
+    for v in a {
+        match v {
+            Value::Object(o) if !o.is_empty() => {
+                // Render as if nested one level deeper, then put the dash on its first line.
+                let mut item = String::new();
+                yaml_object(o, indent + 2, &mut item);
+                let _ = write!(out, "{:indent$}- {}", "", &item[indent + 2..]);
+            }
+            Value::Array(inner) if !inner.is_empty() => {
+                let _ = writeln!(out, "{:indent$}-", "");
+                yaml_array(inner, indent + 2, out);
+            }
+            v => {
+                let _ = writeln!(out, "{:indent$}- {}", "", yaml_inline(v));
+            }
+        }
+    }
+}
+
+/// Append the value of a mapping key (after `key:`) whose key sits at `indent`.
+fn yaml_child(v: &Value, indent: usize, out: &mut String) {
+    match v {
+        Value::Object(o) if !o.is_empty() => {
+            out.push('\n');
+            yaml_object(o, indent + 2, out);
+        }
+        Value::Array(a) if !a.is_empty() => {
+            out.push('\n');
+            yaml_array(a, indent + 2, out);
+        }
+        v => {
+            let _ = writeln!(out, " {}", yaml_inline(v));
+        }
+    }
+}
+
+/// Flow representation of a scalar or an empty collection.
+fn yaml_inline(v: &Value) -> String {
+    match v {
+        Value::Null => "null".into(),
+        Value::Bool(b) => b.to_string(),
+        Value::Number(n) => n.to_string(),
+        Value::String(s) => yaml_string(s),
+        Value::Array(_) => "[]".into(),
+        Value::Object(_) => "{}".into(),
+    }
+}
+
+/// `s` as a plain YAML scalar if that is unambiguous, otherwise double-quoted.
+fn yaml_string(s: &str) -> String {
+    let ambiguous = s.is_empty()
+        || s.trim() != s
+        || s.starts_with(|c: char| "-?:,[]{}#&*!|>'\"%@`~".contains(c))
+        || s.contains(": ")
+        || s.contains(" #")
+        || s.ends_with(':')
+        || s.chars().any(char::is_control)
+        || matches!(
+            s.to_ascii_lowercase().as_str(),
+            "true" | "false" | "yes" | "no" | "on" | "off" | "null" | "y" | "n"
+        )
+        || s.parse::<f64>().is_ok();
+    if ambiguous {
+        // JSON string syntax is valid YAML double-quoted syntax.
+        Value::String(s.to_string()).to_string()
+    } else {
+        s.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn csv_quotes_only_fields_that_need_it() {
+        let rows = vec![
+            vec!["plain".to_string(), "a,b".to_string()],
+            vec!["say \"hi\"".to_string(), "two\nlines".to_string()],
+        ];
+        assert_eq!(
+            csv(&["name", "reason"], &rows),
+            "name,reason\r\nplain,\"a,b\"\r\n\"say \"\"hi\"\"\",\"two\nlines\"\r\n"
+        );
+    }
+
+    #[test]
+    fn table_keeps_rows_on_one_line() {
+        let rows = vec![vec!["a\nb".to_string(), "x".to_string()]];
+        assert_eq!(table(&["name", "code"], &rows), "NAME  CODE\na b   x\n");
+    }
+
+    #[test]
+    fn yaml_quotes_ambiguous_scalars() {
+        for plain in ["foo", "/usr/bin/foo %U", "a-b", "EXEC_NOT_FOUND"] {
+            assert_eq!(yaml_string(plain), plain);
+        }
+        for ambiguous in [
+            "", " x", "-x", "#x", "*x", "a: b", "a #b", "a:", "yes", "Null", "1.5", "a\nb", "\"x",
+        ] {
+            let quoted = yaml_string(ambiguous);
+            assert_eq!(quoted, Value::String(ambiguous.into()).to_string());
+        }
+    }
+
+    #[test]
+    fn yaml_renders_nested_collections() {
+        let value = json!({
+            "findings": [{ "name": "a: b", "warnings": [] }, ["x"]],
+            "summary": { "ok": 1, "broken": null },
+            "empty": {},
+        });
+        assert_eq!(
+            yaml(&value),
+            "---\n\
+             empty: {}\n\
+             findings:\n  \
+               - name: \"a: b\"\n    \
+                 warnings: []\n  \
+               -\n    \
+                 - x\n\
+             summary:\n  \
+               broken: null\n  \
+               ok: 1\n"
+        );
+        assert_eq!(yaml(&json!("true")), "---\n\"true\"\n");
+    }
+}
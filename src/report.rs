@@ -1,17 +1,176 @@
 //! Data structures for reporting scan outcomes.
 //!
-//! These types are serializable to JSON for machine-readable output and are also used
-//! for human-readable printing in `main`.
+//! These types are serializable to JSON for machine-readable output (and deserializable, so
+//! reports produced elsewhere can be read back) and are also used for human-readable printing
+//! in `main`.
 
-use serde::Serialize;
-use std::path::PathBuf;
+use chrono::NaiveDate;
+use clap::ValueEnum;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::{
+    cmp::Ordering,
+    collections::{BTreeMap, HashMap},
+    fmt,
+    os::unix::ffi::OsStrExt,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+use crate::{
+    args::SortKey, desktop, flathub::FlathubStatus, history::FindingHistory, identity::Application,
+};
+
+/// The complete result of a scan, as printed by `--output json`.
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct Report {
+    /// Version of the report format ([`crate::schema::SCHEMA_VERSION`]; `0` in reports written
+    /// before it was added).
+    #[serde(default)]
+    pub schema_version: u32,
+
+    /// Scan-level warnings (environment problems, empty scans, ...).
+    pub warnings: Vec<Warning>,
+
+    /// Reported findings (broken entries and entries with warnings).
+    pub findings: Vec<Finding>,
+
+    /// Counts over every scanned file, including those filtered from `findings` (absent in
+    /// reports written before it was added).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub summary: Option<Summary>,
+
+    /// Findings grouped into applications (`--group-by app`); only applications with a
+    /// reported entry are listed, but with all of their entries.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub applications: Vec<Application>,
+
+    /// Findings grouped by directory or source layer (`--group-by dir|source`).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub groups: Vec<FindingGroup>,
+}
+
+/// Findings sharing a [`GroupKey`] (see [`FindingGroup::group`]).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct FindingGroup {
+    /// What the findings have in common.
+    pub key: GroupKey,
+
+    /// Paths of the findings, in report order.
+    pub desktop_files: Vec<PathBuf>,
+}
+
+/// What groups findings together; sources are ordered from the user's layer down.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, JsonSchema)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum GroupKey {
+    /// The directory containing the files (`--group-by dir`).
+    Directory { path: PathBuf },
+    /// `$XDG_DATA_HOME/applications`.
+    UserData { path: PathBuf },
+    /// The user's desktop folder (`XDG_DESKTOP_DIR`).
+    Desktop { path: PathBuf },
+    /// The `applications` directories of `$XDG_DATA_DIRS`.
+    SystemData,
+    /// Flatpak exports (user and system).
+    Flatpak,
+    /// Snap desktop exports.
+    Snap,
+    /// A directory passed via `--dir` or `--dirs-from`.
+    Extra { path: PathBuf },
+    /// Files outside every scan directory (`--files-from`).
+    Other,
+}
+
+impl FindingGroup {
+    /// Group `findings` by `key`, sorted by key; findings keep their order within a group.
+    pub fn group(findings: &[Finding], key: impl Fn(&Finding) -> GroupKey) -> Vec<FindingGroup> {
+        let mut groups = BTreeMap::<GroupKey, Vec<PathBuf>>::new();
+        for f in findings {
+            groups
+                .entry(key(f))
+                .or_default()
+                .push(f.desktop_file.clone());
+        }
+        groups
+            .into_iter()
+            .map(|(key, desktop_files)| FindingGroup { key, desktop_files })
+            .collect()
+    }
+}
+
+/// Summary statistics of a scan (see [`Summary::of`]).
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct Summary {
+    /// Number of files inspected.
+    pub files_scanned: usize,
+
+    /// Number of healthy entries.
+    pub ok: usize,
+
+    /// Number of broken entries.
+    pub broken: usize,
+
+    /// Number of skipped entries.
+    pub skipped: usize,
+
+    /// Number of broken entries accepted by a `--suppressions` entry.
+    #[serde(default)]
+    pub suppressed: usize,
+
+    /// Number of entries whose inspection did not finish in time (`--timeout`, `--max-runtime`).
+    #[serde(default)]
+    pub timed_out: usize,
+
+    /// Number of entries (of any status) with lint warnings.
+    pub with_warnings: usize,
+
+    /// The same counts per directory containing scanned files, sorted by path.
+    pub directories: Vec<DirectorySummary>,
+
+    /// Wall-clock duration of the scan in milliseconds (left out with `--frozen`, so reports of
+    /// the same files stay identical).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub duration_ms: Option<u64>,
+}
+
+/// Status counts of the scanned files in one directory.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct DirectorySummary {
+    /// Directory containing the files.
+    pub directory: PathBuf,
+
+    /// Number of files inspected in it.
+    pub files: usize,
+
+    /// Number of healthy entries.
+    pub ok: usize,
+
+    /// Number of broken entries.
+    pub broken: usize,
+
+    /// Number of skipped entries.
+    pub skipped: usize,
+
+    /// Number of broken entries accepted by a `--suppressions` entry.
+    #[serde(default)]
+    pub suppressed: usize,
+
+    /// Number of entries whose inspection did not finish in time.
+    #[serde(default)]
+    pub timed_out: usize,
+}
 
 /// A scan result for a single `.desktop` file.
 ///
-/// Contains basic metadata extracted from `[Desktop Entry]` and a `status` field
-/// describing whether it is OK, Broken, or Skipped.
-#[derive(Debug, Serialize)]
+/// Contains basic metadata extracted from `[Desktop Entry]`, a `status` field
+/// describing whether it is OK, Broken, or Skipped, and any non-fatal lint warnings.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct Finding {
+    /// Stable identifier of this finding (see [`finding_id`]).
+    pub id: String,
+
     /// Full path to the `.desktop` file inspected.
     pub desktop_file: PathBuf,
 
@@ -27,6 +186,10 @@ pub struct Finding {
     /// Value of the `Path=` key (if present).
     pub path_key: Option<String>,
 
+    /// Value of the `Type=` key (if present).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub entry_type: Option<String>,
+
     /// Whether `Hidden=true`.
     pub hidden: bool,
 
@@ -35,10 +198,57 @@ pub struct Finding {
 
     /// Inspection outcome.
     pub status: Status,
+
+    /// Spec violations that do not break the entry (see `lint`), down to `--min-severity`.
+    pub warnings: Vec<Warning>,
+
+    /// Login name of the user whose home directory contains the file (`--all-users`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub user: Option<String>,
+
+    /// Warnings accepted by a `--suppressions` entry (see `suppressions`).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub suppressed_warnings: Vec<Warning>,
+
+    /// Most severe problem of the entry (see [`Severity::of`]); absent for clean entries.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub severity: Option<Severity>,
+
+    /// Rewritten `Exec=` line pointing to a relocated binary, for entries whose absolute
+    /// executable is missing (applied by `fix --mode repair`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub suggested_exec: Option<String>,
+
+    /// When this file was first seen broken and for how many consecutive scans (see `history`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub history: Option<FindingHistory>,
+
+    /// Whether Flathub still publishes the app of a broken Flatpak entry (`--check-flathub`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub flathub: Option<FlathubStatus>,
+
+    /// Device and inode of the file (`None` if it could not be read).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub identity: Option<FileIdentity>,
+
+    /// Other scanned paths that are the same underlying file (bind mounts, overlayfs); see
+    /// [`link_same_files`].
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub same_file_as: Vec<PathBuf>,
+}
+
+/// Device and inode numbers identifying the underlying file of a path.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, JsonSchema)]
+pub struct FileIdentity {
+    /// Device number (`st_dev`).
+    pub device: u64,
+
+    /// Inode number (`st_ino`).
+    pub inode: u64,
 }
 
 /// Outcome of inspecting a `.desktop` file.
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(tag = "kind", rename_all = "snake_case")]
 pub enum Status {
     /// The entry appears healthy w.r.t. executable resolution.
@@ -51,13 +261,623 @@ pub enum Status {
 
     /// The entry appears broken/stale with a human-readable explanation.
     Broken {
+        /// Stable code classifying why the entry is broken.
+        code: ReasonCode,
+
         /// Reason describing why the entry is considered broken.
         reason: String,
     },
 
     /// The entry was intentionally not checked.
     Skipped {
+        /// Stable code classifying why the entry was skipped.
+        code: ReasonCode,
+
         /// Reason describing why the entry was skipped.
         reason: String,
     },
+
+    /// The entry is broken, but a `--suppressions` entry accepts it.
+    Suppressed {
+        /// Stable code classifying why the entry is broken.
+        code: ReasonCode,
+
+        /// Reason describing why the entry is considered broken.
+        reason: String,
+
+        /// Last day the suppression applies (`None` if it does not expire).
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        until: Option<NaiveDate>,
+
+        /// Why the breakage was accepted, as given in the suppressions file.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        comment: Option<String>,
+    },
+
+    /// The inspection did not finish in time (a hung filesystem), so whether the entry works
+    /// is unknown.
+    TimedOut {
+        /// Stable code classifying which time limit was hit.
+        code: ReasonCode,
+
+        /// Reason describing which time limit was hit.
+        reason: String,
+    },
+}
+
+/// A non-fatal problem found in a `.desktop` file or in the scan environment.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct Warning {
+    /// Stable machine-readable code.
+    pub code: ReasonCode,
+
+    /// Human-readable explanation.
+    pub message: String,
+
+    /// `[Desktop Entry]` key the warning is about, if it is about a single key (used to locate
+    /// it in the file, e.g. for `--output sarif`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub key: Option<String>,
+}
+
+/// How serious a problem is (`--min-severity`); severities are ordered from `info` to `error`.
+#[derive(
+    ValueEnum,
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Serialize,
+    Deserialize,
+    JsonSchema,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum Severity {
+    /// Hints that do not affect launching (missing icons, deprecated keys, skipped entries).
+    Info,
+    /// Spec violations and fragile setups that launchers may still tolerate.
+    Warning,
+    /// Broken entries and scans that found nothing.
+    Error,
+}
+
+impl Severity {
+    /// Most severe problem of an entry: `error` if it is broken, otherwise that of its most
+    /// severe warning (`None` for clean entries).
+    pub fn of(status: &Status, warnings: &[Warning]) -> Option<Self> {
+        match status {
+            Status::Broken { .. } => Some(Severity::Error),
+            Status::TimedOut { code, .. } => warnings
+                .iter()
+                .map(|w| w.code.severity())
+                .chain([code.severity()])
+                .max(),
+            _ => warnings.iter().map(|w| w.code.severity()).max(),
+        }
+    }
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Severity::Info => "info",
+            Severity::Warning => "warning",
+            Severity::Error => "error",
+        })
+    }
+}
+
+/// Stable machine-readable codes classifying reported problems.
+///
+/// Codes are serialized in `SCREAMING_SNAKE_CASE` and never change once released; wording of the
+/// accompanying messages may.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ReasonCode {
+    /// `Exec=` does not resolve to an executable.
+    ExecNotFound,
+    /// `Exec=` names a command that only exists as a shell alias or function.
+    ExecShellAlias,
+    /// `Exec=` names a shell builtin with no executable of that name on disk.
+    ExecShellBuiltin,
+    /// `TryExec=` does not resolve to an executable.
+    TryexecUnresolved,
+    /// `TryExec=` resolves, but `Exec=` does not.
+    ExecUnresolvedDespiteTryexec,
+    /// `Exec=` could not be shell-split or contains no executable token.
+    ExecParseError,
+    /// `Exec=` names an absolute path containing spaces without quoting it.
+    ExecUnquotedSpaces,
+    /// `Exec=` launches through `systemd-run`, which is not installed or has no user manager.
+    SystemdRunUnavailable,
+    /// `Exec=` runs an interpreter whose script argument is missing.
+    ScriptMissing,
+    /// Neither `Exec=` nor `DBusActivatable=true` is present.
+    ExecMissing,
+    /// The file could not be read or parsed.
+    UnreadableFile,
+    /// The file is a symlink whose target does not exist.
+    DanglingSymlink,
+    /// The file is a desktop entry but lacks the `.desktop` extension.
+    MissingDesktopExtension,
+    /// A check (or the whole inspection of a file) exceeded its time limit (`--timeout`).
+    CheckTimedOut,
+    /// The scan reached `--max-runtime` before the file was inspected.
+    MaxRuntimeExceeded,
+    /// Inspecting the file panicked; the scan continued with the other files.
+    InspectionPanicked,
+    /// The entry is `Hidden=true` or `NoDisplay=true` (skipped).
+    HiddenEntry,
+    /// The entry is not `Type=Application` (skipped).
+    UnsupportedType,
+    /// A boolean key has a value other than `true`/`false`.
+    InvalidBoolean,
+    /// A key is used with a `Type` the spec does not allow it for.
+    KeyNotAllowedForType,
+    /// `DBusActivatable=true` but the file name is not a D-Bus well-known name.
+    InvalidDbusName,
+    /// The entry still contains placeholders from a launcher template.
+    TemplatePlaceholder,
+    /// `Exec=` refers to a `$XDG_RUNTIME_DIR` path or session socket missing in this session.
+    SessionSocketMissing,
+    /// `Exec=` pins a GPU, driver or Vulkan/EGL manifest via the environment that is not present.
+    GpuEnvUnavailable,
+    /// `Icon=` is an absolute path into another package's private directory.
+    IconInForeignPackageDir,
+    /// A line is neither a group header, a comment nor a `key=value` pair.
+    MalformedLine,
+    /// The file does not start with a `[Desktop Entry]` group.
+    MissingDesktopEntryGroup,
+    /// The file is not valid UTF-8 (linted with `--inventory`, unreadable otherwise).
+    InvalidEncoding,
+    /// An application entry shown in menus has no `Icon=`.
+    MissingIcon,
+    /// A key the spec deprecated (e.g. `Encoding`, `MiniIcon`) is still set.
+    DeprecatedKey,
+    /// A `PATH` entry is empty, relative or contains `~` (scan-level).
+    UnsafePathEntry,
+    /// Discovery found no `.desktop` files at all (scan-level).
+    NoDesktopFiles,
+    /// A `--suppressions` entry is past its expiry date and no longer applies (scan-level).
+    SuppressionExpired,
+}
+
+impl ReasonCode {
+    /// All known codes, in declaration order.
+    pub const ALL: &'static [ReasonCode] = &[
+        ReasonCode::ExecNotFound,
+        ReasonCode::ExecShellAlias,
+        ReasonCode::ExecShellBuiltin,
+        ReasonCode::TryexecUnresolved,
+        ReasonCode::ExecUnresolvedDespiteTryexec,
+        ReasonCode::ExecParseError,
+        ReasonCode::ExecUnquotedSpaces,
+        ReasonCode::SystemdRunUnavailable,
+        ReasonCode::ScriptMissing,
+        ReasonCode::ExecMissing,
+        ReasonCode::UnreadableFile,
+        ReasonCode::DanglingSymlink,
+        ReasonCode::MissingDesktopExtension,
+        ReasonCode::CheckTimedOut,
+        ReasonCode::MaxRuntimeExceeded,
+        ReasonCode::InspectionPanicked,
+        ReasonCode::HiddenEntry,
+        ReasonCode::UnsupportedType,
+        ReasonCode::InvalidBoolean,
+        ReasonCode::KeyNotAllowedForType,
+        ReasonCode::InvalidDbusName,
+        ReasonCode::TemplatePlaceholder,
+        ReasonCode::SessionSocketMissing,
+        ReasonCode::GpuEnvUnavailable,
+        ReasonCode::IconInForeignPackageDir,
+        ReasonCode::MalformedLine,
+        ReasonCode::MissingDesktopEntryGroup,
+        ReasonCode::InvalidEncoding,
+        ReasonCode::MissingIcon,
+        ReasonCode::DeprecatedKey,
+        ReasonCode::UnsafePathEntry,
+        ReasonCode::NoDesktopFiles,
+        ReasonCode::SuppressionExpired,
+    ];
+
+    /// How serious a problem with this code is.
+    pub fn severity(&self) -> Severity {
+        match self {
+            ReasonCode::ExecNotFound
+            | ReasonCode::ExecShellAlias
+            | ReasonCode::ExecShellBuiltin
+            | ReasonCode::TryexecUnresolved
+            | ReasonCode::ExecUnresolvedDespiteTryexec
+            | ReasonCode::ExecParseError
+            | ReasonCode::ExecUnquotedSpaces
+            | ReasonCode::SystemdRunUnavailable
+            | ReasonCode::ScriptMissing
+            | ReasonCode::ExecMissing
+            | ReasonCode::UnreadableFile
+            | ReasonCode::DanglingSymlink
+            | ReasonCode::MissingDesktopExtension
+            | ReasonCode::InspectionPanicked
+            | ReasonCode::NoDesktopFiles => Severity::Error,
+            ReasonCode::InvalidBoolean
+            | ReasonCode::KeyNotAllowedForType
+            | ReasonCode::InvalidDbusName
+            | ReasonCode::TemplatePlaceholder
+            | ReasonCode::SessionSocketMissing
+            | ReasonCode::GpuEnvUnavailable
+            | ReasonCode::IconInForeignPackageDir
+            | ReasonCode::MalformedLine
+            | ReasonCode::MissingDesktopEntryGroup
+            | ReasonCode::InvalidEncoding
+            | ReasonCode::UnsafePathEntry
+            | ReasonCode::CheckTimedOut
+            | ReasonCode::MaxRuntimeExceeded
+            | ReasonCode::SuppressionExpired => Severity::Warning,
+            ReasonCode::HiddenEntry
+            | ReasonCode::UnsupportedType
+            | ReasonCode::MissingIcon
+            | ReasonCode::DeprecatedKey => Severity::Info,
+        }
+    }
+
+    /// Whether this code belongs to a rule that can be enabled or disabled (`--enable-rule`,
+    /// `--disable-rule`): a lint behind warnings or an opt-in heuristic. Codes deciding whether
+    /// an entry works at all are always checked.
+    pub fn is_rule(&self) -> bool {
+        match self {
+            ReasonCode::ExecShellAlias
+            | ReasonCode::ScriptMissing
+            | ReasonCode::InvalidBoolean
+            | ReasonCode::KeyNotAllowedForType
+            | ReasonCode::InvalidDbusName
+            | ReasonCode::TemplatePlaceholder
+            | ReasonCode::SessionSocketMissing
+            | ReasonCode::GpuEnvUnavailable
+            | ReasonCode::IconInForeignPackageDir
+            | ReasonCode::MalformedLine
+            | ReasonCode::MissingDesktopEntryGroup
+            | ReasonCode::InvalidEncoding
+            | ReasonCode::MissingIcon
+            | ReasonCode::DeprecatedKey
+            | ReasonCode::UnsafePathEntry => true,
+            ReasonCode::ExecNotFound
+            | ReasonCode::ExecShellBuiltin
+            | ReasonCode::TryexecUnresolved
+            | ReasonCode::ExecUnresolvedDespiteTryexec
+            | ReasonCode::ExecParseError
+            | ReasonCode::ExecUnquotedSpaces
+            | ReasonCode::SystemdRunUnavailable
+            | ReasonCode::ExecMissing
+            | ReasonCode::UnreadableFile
+            | ReasonCode::DanglingSymlink
+            | ReasonCode::MissingDesktopExtension
+            | ReasonCode::CheckTimedOut
+            | ReasonCode::MaxRuntimeExceeded
+            | ReasonCode::InspectionPanicked
+            | ReasonCode::HiddenEntry
+            | ReasonCode::UnsupportedType
+            | ReasonCode::NoDesktopFiles
+            | ReasonCode::SuppressionExpired => false,
+        }
+    }
+
+    /// The serialized form of this code (e.g. `TEMPLATE_PLACEHOLDER`).
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ReasonCode::ExecNotFound => "EXEC_NOT_FOUND",
+            ReasonCode::ExecShellAlias => "EXEC_SHELL_ALIAS",
+            ReasonCode::ExecShellBuiltin => "EXEC_SHELL_BUILTIN",
+            ReasonCode::TryexecUnresolved => "TRYEXEC_UNRESOLVED",
+            ReasonCode::ExecUnresolvedDespiteTryexec => "EXEC_UNRESOLVED_DESPITE_TRYEXEC",
+            ReasonCode::ExecParseError => "EXEC_PARSE_ERROR",
+            ReasonCode::ExecUnquotedSpaces => "EXEC_UNQUOTED_SPACES",
+            ReasonCode::SystemdRunUnavailable => "SYSTEMD_RUN_UNAVAILABLE",
+            ReasonCode::ScriptMissing => "SCRIPT_MISSING",
+            ReasonCode::ExecMissing => "EXEC_MISSING",
+            ReasonCode::UnreadableFile => "UNREADABLE_FILE",
+            ReasonCode::DanglingSymlink => "DANGLING_SYMLINK",
+            ReasonCode::MissingDesktopExtension => "MISSING_DESKTOP_EXTENSION",
+            ReasonCode::CheckTimedOut => "CHECK_TIMED_OUT",
+            ReasonCode::MaxRuntimeExceeded => "MAX_RUNTIME_EXCEEDED",
+            ReasonCode::InspectionPanicked => "INSPECTION_PANICKED",
+            ReasonCode::HiddenEntry => "HIDDEN_ENTRY",
+            ReasonCode::UnsupportedType => "UNSUPPORTED_TYPE",
+            ReasonCode::InvalidBoolean => "INVALID_BOOLEAN",
+            ReasonCode::KeyNotAllowedForType => "KEY_NOT_ALLOWED_FOR_TYPE",
+            ReasonCode::InvalidDbusName => "INVALID_DBUS_NAME",
+            ReasonCode::TemplatePlaceholder => "TEMPLATE_PLACEHOLDER",
+            ReasonCode::SessionSocketMissing => "SESSION_SOCKET_MISSING",
+            ReasonCode::GpuEnvUnavailable => "GPU_ENV_UNAVAILABLE",
+            ReasonCode::IconInForeignPackageDir => "ICON_IN_FOREIGN_PACKAGE_DIR",
+            ReasonCode::MalformedLine => "MALFORMED_LINE",
+            ReasonCode::MissingDesktopEntryGroup => "MISSING_DESKTOP_ENTRY_GROUP",
+            ReasonCode::InvalidEncoding => "INVALID_ENCODING",
+            ReasonCode::MissingIcon => "MISSING_ICON",
+            ReasonCode::DeprecatedKey => "DEPRECATED_KEY",
+            ReasonCode::UnsafePathEntry => "UNSAFE_PATH_ENTRY",
+            ReasonCode::NoDesktopFiles => "NO_DESKTOP_FILES",
+            ReasonCode::SuppressionExpired => "SUPPRESSION_EXPIRED",
+        }
+    }
+}
+
+impl FromStr for ReasonCode {
+    type Err = anyhow::Error;
+
+    /// Parse a code case-insensitively; `-` is accepted in place of `_`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let normalized = s.trim().to_ascii_uppercase().replace('-', "_");
+        ReasonCode::ALL
+            .iter()
+            .copied()
+            .find(|c| c.as_str() == normalized)
+            .ok_or_else(|| {
+                anyhow::anyhow!(
+                    "Unknown reason code: {s} (run `desktop-scout explain` to list all codes)"
+                )
+            })
+    }
+}
+
+impl fmt::Display for ReasonCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl Finding {
+    /// Whether the entry is broken.
+    pub fn is_broken(&self) -> bool {
+        matches!(self.status, Status::Broken { .. })
+    }
+
+    /// Whether the inspection of the entry did not finish in time.
+    pub fn is_timed_out(&self) -> bool {
+        matches!(self.status, Status::TimedOut { .. })
+    }
+
+    /// Whether the finding is included in the default report (broken, timed out or has
+    /// warnings).
+    pub fn is_reported(&self) -> bool {
+        self.is_broken() || self.is_timed_out() || !self.warnings.is_empty()
+    }
+}
+
+impl Report {
+    /// Sort the findings by `key`; ties are broken by path.
+    pub fn sort(&mut self, key: SortKey) {
+        fn rank(f: &Finding) -> u8 {
+            match f.status {
+                Status::Broken { .. } => 0,
+                Status::TimedOut { .. } => 1,
+                Status::Ok { .. } if !f.warnings.is_empty() => 2,
+                Status::Ok { .. } => 3,
+                Status::Suppressed { .. } => 4,
+                Status::Skipped { .. } => 5,
+            }
+        }
+        fn code(f: &Finding) -> Option<&'static str> {
+            match &f.status {
+                Status::Broken { code, .. }
+                | Status::Skipped { code, .. }
+                | Status::Suppressed { code, .. }
+                | Status::TimedOut { code, .. } => Some(code.as_str()),
+                Status::Ok { .. } => None,
+            }
+        }
+
+        self.findings.sort_by(|a, b| {
+            let by_key = match key {
+                SortKey::Path => Ordering::Equal,
+                // Comparing `is_none()` first puts entries without a value last.
+                SortKey::Name => {
+                    let name = |f: &Finding| f.name.as_ref().map(|n| n.to_lowercase());
+                    let (a, b) = (name(a), name(b));
+                    a.is_none().cmp(&b.is_none()).then(a.cmp(&b))
+                }
+                SortKey::Status => rank(a).cmp(&rank(b)),
+                SortKey::Code => {
+                    let (a, b) = (code(a), code(b));
+                    a.is_none().cmp(&b.is_none()).then(a.cmp(&b))
+                }
+                SortKey::Dir => a.desktop_file.parent().cmp(&b.desktop_file.parent()),
+            };
+            by_key.then_with(|| a.desktop_file.cmp(&b.desktop_file))
+        });
+    }
+}
+
+impl Summary {
+    /// Count the outcomes of `findings` (every inspected file of a scan).
+    pub fn of(findings: &[Finding], duration_ms: Option<u64>) -> Self {
+        let mut directories = BTreeMap::<PathBuf, DirectorySummary>::new();
+        let mut summary = Summary {
+            files_scanned: findings.len(),
+            duration_ms,
+            ..Summary::default()
+        };
+
+        for f in findings {
+            let directory = f
+                .desktop_file
+                .parent()
+                .map(PathBuf::from)
+                .unwrap_or_default();
+            let dir = directories
+                .entry(directory.clone())
+                .or_insert_with(|| DirectorySummary {
+                    directory,
+                    ..DirectorySummary::default()
+                });
+            dir.files += 1;
+
+            let (total, per_dir) = match f.status {
+                Status::Ok { .. } => (&mut summary.ok, &mut dir.ok),
+                Status::Broken { .. } => (&mut summary.broken, &mut dir.broken),
+                Status::Skipped { .. } => (&mut summary.skipped, &mut dir.skipped),
+                Status::Suppressed { .. } => (&mut summary.suppressed, &mut dir.suppressed),
+                Status::TimedOut { .. } => (&mut summary.timed_out, &mut dir.timed_out),
+            };
+            *total += 1;
+            *per_dir += 1;
+            if !f.warnings.is_empty() {
+                summary.with_warnings += 1;
+            }
+        }
+
+        summary.directories = directories.into_values().collect();
+        summary
+    }
+}
+
+impl Status {
+    /// Short machine-readable name of the status variant (matches the serialized `kind`).
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Status::Ok { .. } => "ok",
+            Status::Broken { .. } => "broken",
+            Status::Skipped { .. } => "skipped",
+            Status::Suppressed { .. } => "suppressed",
+            Status::TimedOut { .. } => "timed_out",
+        }
+    }
+
+    /// Key identifying the check outcome that produced this status.
+    ///
+    /// Used as the "rule" component of [`finding_id`]. Suppressed entries keep the key of the
+    /// broken entry, so suppressing a finding does not change its ID.
+    pub fn rule_key(&self) -> String {
+        match self {
+            Status::Ok { .. } => self.kind().to_string(),
+            Status::Broken { code, .. }
+            | Status::Skipped { code, .. }
+            | Status::TimedOut { code, .. } => format!("{}:{code}", self.kind()),
+            Status::Suppressed { code, .. } => format!("broken:{code}"),
+        }
+    }
+}
+
+/// Fill [`Finding::same_file_as`] for findings whose paths resolve to the same file.
+///
+/// Bind mounts and overlayfs can expose one `.desktop` file below several scanned directories;
+/// such paths are reported once each but must not be remediated twice.
+pub fn link_same_files(findings: &mut [Finding]) {
+    let mut by_identity = HashMap::<FileIdentity, Vec<PathBuf>>::new();
+    for f in findings.iter() {
+        if let Some(id) = f.identity {
+            by_identity
+                .entry(id)
+                .or_default()
+                .push(f.desktop_file.clone());
+        }
+    }
+
+    for f in findings.iter_mut() {
+        if let Some(paths) = f.identity.and_then(|id| by_identity.get(&id)) {
+            f.same_file_as = paths
+                .iter()
+                .filter(|p| **p != f.desktop_file)
+                .cloned()
+                .collect();
+        }
+    }
+}
+
+/// Compute a deterministic finding ID for the `.desktop` file at `path`.
+///
+/// The ID is a truncated SHA-256 over the directory of the file's `applications` tree (its own
+/// directory outside such a tree), its desktop ID, the rule key of `status` and a hash of the raw
+/// file content. It stays the same across runs as long as neither the file nor the outcome
+/// changes, so baselines and other tooling can reference findings unambiguously; byte-identical
+/// copies in two data directories (a user override of a system entry) still get different IDs.
+pub fn finding_id(path: &Path, status: &Status, content: &[u8]) -> String {
+    let content_hash = Sha256::digest(content);
+    let tree = desktop::applications_dir(path).or_else(|| path.parent());
+
+    let mut hasher = Sha256::new();
+    hasher.update(tree.unwrap_or(Path::new("")).as_os_str().as_bytes());
+    hasher.update([0]);
+    hasher.update(desktop::desktop_id(path).as_bytes());
+    hasher.update([0]);
+    hasher.update(status.rule_key().as_bytes());
+    hasher.update([0]);
+    hasher.update(content_hash);
+
+    hasher.finalize()[..8]
+        .iter()
+        .map(|b| format!("{b:02x}"))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn broken(code: ReasonCode) -> Status {
+        Status::Broken {
+            code,
+            reason: String::new(),
+        }
+    }
+
+    #[test]
+    fn finding_ids_are_stable_and_short() {
+        let path = Path::new("/usr/share/applications/foo.desktop");
+        let status = broken(ReasonCode::ExecNotFound);
+        let id = finding_id(path, &status, b"[Desktop Entry]\n");
+        assert_eq!(id, finding_id(path, &status, b"[Desktop Entry]\n"));
+        assert_eq!(id.len(), 16);
+        assert!(id.chars().all(|c| c.is_ascii_hexdigit()));
+    }
+
+    #[test]
+    fn finding_ids_depend_on_file_outcome_and_content() {
+        let content = b"[Desktop Entry]\nExec=foo\n";
+        let path = Path::new("/usr/share/applications/foo.desktop");
+        let id = finding_id(path, &broken(ReasonCode::ExecNotFound), content);
+
+        // A byte-identical copy in another data directory.
+        let user = Path::new("/home/user/.local/share/applications/foo.desktop");
+        assert_ne!(
+            id,
+            finding_id(user, &broken(ReasonCode::ExecNotFound), content)
+        );
+        // Another file of the same tree.
+        let other = Path::new("/usr/share/applications/bar.desktop");
+        assert_ne!(
+            id,
+            finding_id(other, &broken(ReasonCode::ExecNotFound), content)
+        );
+        // Another outcome.
+        assert_ne!(
+            id,
+            finding_id(path, &broken(ReasonCode::ExecMissing), content)
+        );
+        // Other content.
+        assert_ne!(
+            id,
+            finding_id(
+                path,
+                &broken(ReasonCode::ExecNotFound),
+                b"[Desktop Entry]\n"
+            )
+        );
+    }
+
+    #[test]
+    fn suppressed_findings_keep_their_id() {
+        let path = Path::new("/usr/share/applications/foo.desktop");
+        let suppressed = Status::Suppressed {
+            code: ReasonCode::ExecNotFound,
+            reason: String::new(),
+            until: None,
+            comment: None,
+        };
+        assert_eq!(
+            finding_id(path, &broken(ReasonCode::ExecNotFound), b""),
+            finding_id(path, &suppressed, b"")
+        );
+    }
 }
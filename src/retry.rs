@@ -0,0 +1,86 @@
+//! Bounded retries for transient filesystem errors.
+//!
+//! Network homes and automounted directories occasionally fail calls with errors that go away
+//! on their own (`EINTR`, `EAGAIN`, `EBUSY`, `ETIMEDOUT`, stale NFS handles while a mount is
+//! being re-established). Such errors are retried a few times with a short backoff before the
+//! result is used, so they do not turn into spurious `UNREADABLE_FILE` findings or missing
+//! executables. Permanent errors (`ENOENT`, `EACCES`, ...) are returned immediately.
+
+// -- std imports
+use std::{
+    fs::Metadata,
+    future::Future,
+    io::{self, ErrorKind},
+    path::Path,
+    time::Duration,
+};
+
+// -- crate imports
+use tokio::{fs, time};
+use tracing::debug;
+
+/// Total number of attempts for an operation failing with transient errors.
+const MAX_ATTEMPTS: u32 = 3;
+
+/// Delay before the first retry; doubled for every further retry.
+const INITIAL_BACKOFF: Duration = Duration::from_millis(25);
+
+/// Whether `e` is likely to go away when the operation is retried.
+pub fn is_transient(e: &io::Error) -> bool {
+    matches!(
+        e.kind(),
+        ErrorKind::Interrupted
+            | ErrorKind::WouldBlock
+            | ErrorKind::TimedOut
+            | ErrorKind::ResourceBusy
+            | ErrorKind::StaleNetworkFileHandle
+    )
+}
+
+/// Run `op` until it succeeds, fails permanently or [`MAX_ATTEMPTS`] are used up.
+///
+/// `what` describes the operation for debug logs.
+pub async fn with_retry<T, F, Fut>(what: &str, path: &Path, mut op: F) -> io::Result<T>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = io::Result<T>>,
+{
+    let mut backoff = INITIAL_BACKOFF;
+    let mut attempt = 1;
+    loop {
+        match op().await {
+            Err(e) if attempt < MAX_ATTEMPTS && is_transient(&e) => {
+                debug!(
+                    path = %path.display(),
+                    attempt,
+                    error = %e,
+                    "Transient error during {what}, retrying"
+                );
+                time::sleep(backoff).await;
+                backoff *= 2;
+                attempt += 1;
+            }
+            result => return result,
+        }
+    }
+}
+
+/// [`fs::read_to_string`] with retries.
+pub async fn read_to_string(path: &Path) -> io::Result<String> {
+    with_retry("read", path, || fs::read_to_string(path)).await
+}
+
+/// [`fs::read`] with retries.
+pub async fn read(path: &Path) -> io::Result<Vec<u8>> {
+    with_retry("read", path, || fs::read(path)).await
+}
+
+/// [`fs::metadata`] with retries.
+pub async fn metadata(path: &Path) -> io::Result<Metadata> {
+    with_retry("stat", path, || fs::metadata(path)).await
+}
+
+/// [`fs::read_dir`] with retries.
+pub async fn read_dir(path: &Path) -> io::Result<fs::ReadDir> {
+    with_retry("readdir", path, || fs::read_dir(path)).await
+}
@@ -0,0 +1,198 @@
+//! SARIF 2.1.0 logs of scan reports (`--output sarif`).
+//!
+//! SARIF is the interchange format of static-analysis tools; GitHub code scanning and most QA
+//! dashboards ingest it directly. Every [`ReasonCode`] is a rule, broken entries are `error`
+//! results and lint warnings are `warning` results. Results point at the line of the offending
+//! key in the `.desktop` file where it can be found. Scan-level warnings (unsafe `PATH`, empty
+//! scans) are not about a file, so they become tool execution notifications instead.
+
+// -- std imports
+use std::path::Path;
+
+// -- crate imports
+use serde_json::{Value, json};
+use tokio::fs;
+
+// -- module imports
+use crate::{
+    explain::explain,
+    i18n::tr,
+    report::{Finding, ReasonCode, Report, Severity, Status},
+};
+
+/// JSON schema of the emitted log.
+const SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+
+/// Build the SARIF log of `report`.
+///
+/// The `.desktop` files are read again to find the line numbers of the offending keys; results
+/// for files that can no longer be read point at the file only.
+pub async fn log(report: &Report) -> Value {
+    let rules = ReasonCode::ALL.iter().map(|c| rule(*c)).collect::<Vec<_>>();
+
+    let mut results = Vec::new();
+    for f in &report.findings {
+        let content = fs::read_to_string(&f.desktop_file).await.ok();
+        let content = content.as_deref();
+
+        match &f.status {
+            Status::Broken { code, reason } => results.push(result(
+                f,
+                *code,
+                "error",
+                reason,
+                status_key(*code),
+                content,
+            )),
+            Status::TimedOut { code, reason } => {
+                results.push(result(f, *code, "warning", reason, None, content))
+            }
+            _ => {}
+        }
+        for w in &f.warnings {
+            let level = match w.code.severity() {
+                Severity::Info => "note",
+                _ => "warning",
+            };
+            results.push(result(
+                f,
+                w.code,
+                level,
+                &w.message,
+                w.key.as_deref(),
+                content,
+            ));
+        }
+    }
+
+    let notifications = report
+        .warnings
+        .iter()
+        .map(|w| {
+            json!({
+                "level": "warning",
+                "message": { "text": w.message },
+                "descriptor": { "id": w.code.as_str() },
+            })
+        })
+        .collect::<Vec<_>>();
+
+    json!({
+        "$schema": SCHEMA,
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": env!("CARGO_PKG_NAME"),
+                    "version": env!("CARGO_PKG_VERSION"),
+                    "informationUri": env!("CARGO_PKG_HOMEPAGE"),
+                    "rules": rules,
+                },
+            },
+            "invocations": [{
+                "executionSuccessful": true,
+                "toolExecutionNotifications": notifications,
+            }],
+            "results": results,
+        }],
+    })
+}
+
+/// Rule descriptor of `code`, documented from `explain`.
+fn rule(code: ReasonCode) -> Value {
+    let e = explain(code);
+    let mut help = format!("{}\n", tr("explain-causes-header", &[]));
+    for cause in &e.causes {
+        help.push_str(&format!("- {cause}\n"));
+    }
+    help.push_str(&format!("\n{}\n", tr("explain-remediation-header", &[])));
+    for step in &e.remediation {
+        help.push_str(&format!("- {step}\n"));
+    }
+
+    json!({
+        "id": code.as_str(),
+        "shortDescription": { "text": e.summary },
+        "help": { "text": help, "markdown": help },
+    })
+}
+
+/// A single SARIF result for `code` on finding `f`, located at `key` if it is found in
+/// `content`.
+fn result(
+    f: &Finding,
+    code: ReasonCode,
+    level: &str,
+    message: &str,
+    key: Option<&str>,
+    content: Option<&str>,
+) -> Value {
+    let rule_index = ReasonCode::ALL.iter().position(|c| *c == code);
+
+    let mut location = json!({ "artifactLocation": { "uri": artifact_uri(&f.desktop_file) } });
+    if let Some(line) = key.zip(content).and_then(|(k, c)| key_line(c, k)) {
+        location["region"] = json!({ "startLine": line });
+    }
+
+    json!({
+        "ruleId": code.as_str(),
+        "ruleIndex": rule_index,
+        "level": level,
+        "message": { "text": message },
+        "locations": [{ "physicalLocation": location }],
+        "partialFingerprints": { "findingId/v1": f.id },
+    })
+}
+
+/// Key a broken status with `code` is about.
+fn status_key(code: ReasonCode) -> Option<&'static str> {
+    match code {
+        ReasonCode::ExecNotFound
+        | ReasonCode::ExecShellAlias
+        | ReasonCode::ExecShellBuiltin
+        | ReasonCode::ExecUnresolvedDespiteTryexec
+        | ReasonCode::ExecParseError
+        | ReasonCode::ExecUnquotedSpaces
+        | ReasonCode::SystemdRunUnavailable
+        | ReasonCode::ScriptMissing
+        | ReasonCode::SessionSocketMissing => Some("Exec"),
+        ReasonCode::TryexecUnresolved => Some("TryExec"),
+        _ => None,
+    }
+}
+
+/// 1-based line of `key` (not a localized variant) in the `[Desktop Entry]` group of `content`.
+fn key_line(content: &str, key: &str) -> Option<usize> {
+    let mut in_main_group = false;
+    for (idx, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if line.starts_with('[') && line.ends_with(']') {
+            in_main_group = line == "[Desktop Entry]";
+            continue;
+        }
+        if in_main_group
+            && !line.starts_with('#')
+            && line.split_once('=').is_some_and(|(k, _)| k.trim() == key)
+        {
+            return Some(idx + 1);
+        }
+    }
+    None
+}
+
+/// `file://` URI of an absolute path; relative paths stay relative (resolved against the
+/// analysis root, e.g. a repository checkout).
+fn artifact_uri(path: &Path) -> String {
+    let mut out = String::new();
+    if path.is_absolute() {
+        out.push_str("file://");
+    }
+    for b in path.to_string_lossy().bytes() {
+        if b.is_ascii_alphanumeric() || b"/-_.~".contains(&b) {
+            out.push(b as char);
+        } else {
+            out.push_str(&format!("%{b:02X}"));
+        }
+    }
+    out
+}
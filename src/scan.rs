@@ -6,57 +6,306 @@
 //! - Convert raw parsing/checking into `Finding` records.
 
 use crate::{
-    args::Args,
-    check, desktop,
-    report::{Finding, Status},
+    args::{Args, SymlinkPolicy},
+    cache::{self, ScanCache},
+    check, desktop, gpu,
+    i18n::tr,
+    ignore::{IGNORE_FILE_NAME, IgnoreFile, IgnoreRules, NameFilter},
+    lint, linux_fs,
+    report::{self, FileIdentity, Finding, ReasonCode, Report, Severity, Status, Summary, Warning},
+    retry, schema, session, shell,
+    suppressions::Suppressions,
+    sysroot,
 };
-use anyhow::Result;
+use anyhow::{Context, Result, bail};
+use chrono::{DateTime, Local, NaiveDate, NaiveDateTime, NaiveTime, TimeZone};
 use futures::stream::{self, StreamExt};
-use std::{env, path::PathBuf};
-use tokio::{fs, sync::Semaphore};
+use indicatif::ProgressBar;
+use std::{
+    collections::{HashMap, HashSet},
+    env,
+    os::unix::fs::MetadataExt,
+    path::{Path, PathBuf},
+    str::FromStr,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant, SystemTime},
+};
+use tokio::{
+    fs,
+    io::{self, AsyncReadExt},
+    sync::{OnceCell, Semaphore},
+    time,
+};
 use tracing::{debug, warn};
 
-/// Recursively collect `.desktop` files from a list of root directories.
+/// Upper bound for a single async check (`TryExec` or `Exec` resolution).
+const CHECK_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Result of a memoized `Exec=` validation (see [`ExecCache`]).
+type ExecResult = Result<Option<PathBuf>, Arc<anyhow::Error>>;
+
+/// Key of an [`ExecCache`] entry: the trimmed `Exec=` line and the `Path=` value.
+type ExecKey = (String, Option<String>);
+
+/// `Exec=` validation results of one scan.
+///
+/// Many entries share an `Exec=` line (e.g. one launcher per browser profile). Concurrent
+/// inspections of the same line wait for the first validation instead of repeating its stats.
+#[derive(Debug, Default)]
+struct ExecCache {
+    results: Mutex<HashMap<ExecKey, Arc<OnceCell<ExecResult>>>>,
+}
+
+impl ExecCache {
+    /// Run [`validate_exec_bounded`] once per distinct `exec_line` and `Path=`.
+    async fn validate(&self, exec_line: &str, ctx: &check::CheckContext<'_>) -> ExecResult {
+        let key = (
+            exec_line.trim().to_string(),
+            ctx.path_key.map(str::to_string),
+        );
+        let cell = self
+            .results
+            .lock()
+            .expect("exec cache lock poisoned")
+            .entry(key)
+            .or_default()
+            .clone();
+
+        cell.get_or_init(|| async {
+            validate_exec_bounded(exec_line, ctx)
+                .await
+                .map_err(Arc::new)
+        })
+        .await
+        .clone()
+    }
+}
+
+/// Run a complete scan as configured by `args`.
 ///
 /// This function:
-/// - walks directories using `tokio::fs::read_dir`
-/// - skips symlinks to avoid recursion loops
-/// - returns sorted, deduped paths
-pub async fn collect_desktop_files(dirs: &[PathBuf]) -> Result<Vec<PathBuf>> {
-    let mut out = Vec::new();
+/// - discovers `.desktop` files (or reads them from `--files-from`)
+/// - records scan-level warnings (unsafe `PATH` entries, empty scans, expired suppressions)
+/// - inspects every file concurrently
+/// - marks problems accepted by `--suppressions` as suppressed and, with `--all-users`,
+///   attributes findings to the user whose home directory contains them
+///
+/// The returned report contains all findings, including healthy and skipped entries, and their
+/// [`Summary`].
+pub async fn run(args: &Args) -> Result<Report> {
+    run_with(args, &ProgressBar::hidden(), &mut |_| {}).await
+}
 
-    for root in dirs {
-        let mut stack = vec![root.clone()];
+/// [`run`], advancing `progress` by one file and passing every finding to `on_finding` as soon
+/// as its inspection completes (for `--stream`). Findings passed on are not yet linked to their
+/// other paths ([`report::link_same_files`]).
+pub async fn run_with(
+    args: &Args,
+    progress: &ProgressBar,
+    on_finding: &mut (dyn FnMut(&Finding) + Send),
+) -> Result<Report> {
+    let started = Instant::now();
+    let suppressions = match &args.suppressions {
+        Some(path) => Suppressions::load(path, Local::now().date_naive()).await?,
+        None => Suppressions::default(),
+    };
+    let deadline = args
+        .max_runtime
+        .map(|secs| time::Instant::from_std(started) + Duration::from_secs(secs));
+    let (files, source_desc) = match deadline {
+        Some(deadline) => match time::timeout_at(deadline, discover(args)).await {
+            Ok(discovered) => discovered?,
+            Err(_) => bail!(
+                "Finding .desktop files took longer than --max-runtime ({}s)",
+                args.max_runtime.unwrap_or_default()
+            ),
+        },
+        None => discover(args).await?,
+    };
 
-        while let Some(dir) = stack.pop() {
-            let mut rd = match fs::read_dir(&dir).await {
-                Ok(rd) => rd,
-                Err(_) => continue, // skip missing/unreadable dirs
-            };
+    let mut warnings = if args.frozen || args.root.is_some() {
+        Vec::new()
+    } else {
+        check::diagnose_path_env(&env::var("PATH").unwrap_or_default())
+    };
+    if files.is_empty() {
+        warnings.push(Warning {
+            code: ReasonCode::NoDesktopFiles,
+            message: tr(
+                "warning-no-desktop-files",
+                &[("source", source_desc.into())],
+            ),
+            key: None,
+        });
+    }
+    warnings.extend(suppressions.expired_warnings());
+    warnings.retain(|w| args.keeps_warning(w.code));
+    for w in &warnings {
+        warn!(code = %w.code, "{}", w.message);
+    }
 
-            loop {
-                let ent = match rd.next_entry().await {
-                    Ok(Some(e)) => e,
-                    Ok(None) => break,
-                    Err(_) => break,
-                };
+    let users = match args.all_users {
+        true => linux_fs::user_homes(args.root.as_deref()),
+        false => Vec::new(),
+    };
+    let annotate = |f: &mut Finding| {
+        suppressions.apply(f);
+        f.user = linux_fs::owning_user(&f.desktop_file, &users).map(str::to_string);
+    };
+    // Simulated removals must neither reuse nor overwrite real verdicts.
+    let scan_cache = match args.no_cache || args.simulation.is_some() {
+        true => None,
+        false => match ScanCache::load(args, &path_env(args)).await {
+            Ok(c) => Some(c),
+            Err(e) => {
+                warn!("Scan cache unavailable: {e:#}");
+                None
+            }
+        },
+    };
+    progress.set_length(files.len() as u64);
+    let mut on_finding = |f: &Finding| {
+        progress.inc(1);
+        progress.set_message(f.desktop_file.display().to_string());
+        on_finding(f);
+    };
+    let mut findings = inspect_files_concurrently(
+        files,
+        args,
+        deadline,
+        scan_cache.as_ref(),
+        &annotate,
+        &mut on_finding,
+    )
+    .await;
+    progress.finish_and_clear();
+    if let Some(c) = scan_cache
+        && let Err(e) = c.save().await
+    {
+        warn!("Failed to update scan cache: {e:#}");
+    }
+    report::link_same_files(&mut findings);
 
-                let ft = match ent.file_type().await {
-                    Ok(ft) => ft,
-                    Err(_) => continue,
-                };
+    let duration_ms = (!args.frozen).then(|| started.elapsed().as_millis() as u64);
+    let summary = Summary::of(&findings, duration_ms);
+    Ok(Report {
+        schema_version: schema::SCHEMA_VERSION,
+        warnings,
+        findings,
+        summary: Some(summary),
+        applications: Vec::new(),
+        groups: Vec::new(),
+    })
+}
 
-                if ft.is_symlink() {
-                    continue; // avoid loops
-                }
-                let p = ent.path();
-                if ft.is_dir() {
-                    stack.push(p);
-                } else if ft.is_file() && p.extension().and_then(|e| e.to_str()) == Some("desktop")
-                {
-                    out.push(p);
+/// The `.desktop` files a scan configured by `args` covers (from `--files-from` or the scan
+/// directories), with a description of where they came from.
+///
+/// # Errors
+/// - [`anyhow::Error`] if the `--files-from` list cannot be read, the directory walk fails,
+///   `--all-users` is given without root privileges, or `--root` is not a directory.
+pub async fn discover(args: &Args) -> Result<(Vec<PathBuf>, String)> {
+    // SAFETY: geteuid has no preconditions and cannot fail.
+    if args.all_users && unsafe { libc::geteuid() } != 0 {
+        bail!("--all-users requires root to read the home directories of other users");
+    }
+    if let Some(root) = &args.root
+        && !root.is_dir()
+    {
+        bail!("--root {} is not a directory", root.display());
+    }
+    Ok(match &args.files_from {
+        Some(source) => (
+            read_path_list(source).await?,
+            tr(
+                "scan-source-file-list",
+                &[("path", source.display().to_string().into())],
+            ),
+        ),
+        None => {
+            let dirs = linux_fs::collect_application_dirs(args);
+            let desc = tr("scan-source-dirs", &[("count", dirs.len().into())]);
+            (collect_desktop_files(&dirs, args).await?, desc)
+        }
+    })
+}
+
+/// [`run`] on a private Tokio runtime, for callers outside async code (C and Python bindings).
+///
+/// # Errors
+/// - [`anyhow::Error`] if the runtime cannot be started or the scan fails.
+pub fn run_blocking(args: &Args) -> Result<Report> {
+    tokio::runtime::Builder::new_multi_thread()
+        .enable_all()
+        .build()
+        .context("Failed to start Tokio runtime")?
+        .block_on(run(args))
+}
+
+/// Recursively collect `.desktop` files from a list of root directories.
+///
+/// This function:
+/// - walks the roots and their subdirectories concurrently, one depth level at a time and at
+///   most `--jobs` directories at once (see [`walk_dir`])
+/// - follows symlinks below the roots according to `--follow-symlinks`, keeping dangling
+///   `.desktop` symlinks (see [`dangling_symlink`])
+/// - tracks visited directories by (device, inode) to avoid recursion loops; a directory
+///   reachable by several paths is walked by the first in walk order (shallowest, then in root
+///   and directory order), independent of timing
+/// - stays on the root's filesystem if `--one-file-system` is given
+/// - skips directories whose read does not finish within `--timeout` (a dead network mount)
+/// - descends at most `--max-depth` directory levels below each root
+/// - with `--detect-misnamed`, also returns files that contain a `[Desktop Entry]` group but
+///   lack the `.desktop` extension
+/// - leaves out files matched by `--ignore` and files and directories matched by
+///   `.desktopscoutignore` files, unless `--allow` matches them
+/// - leaves out files whose name does not pass `--include` and `--exclude`
+/// - returns sorted, deduped paths
+pub async fn collect_desktop_files(dirs: &[PathBuf], args: &Args) -> Result<Vec<PathBuf>> {
+    let rules = IgnoreRules::new(&args.ignore, &args.allow)?;
+    let names = NameFilter::new(&args.include, &args.exclude)?;
+    let jobs = args.jobs.unwrap_or_else(default_jobs);
+    let timeout = Duration::from_secs(args.timeout);
+    let (rules, names) = (&rules, &names);
+    let mut out = Vec::new();
+    let mut visited = HashSet::<(u64, u64)>::new();
+
+    // The directories of a level are read concurrently and merged in order, so the walk (and
+    // which path to a directory wins) does not depend on which reads finish first.
+    let mut level = dirs
+        .iter()
+        .map(|dir| WalkJob {
+            dir: dir.clone(),
+            ignore_files: Vec::new(),
+            depth: 0,
+            root_dev: None,
+        })
+        .collect::<Vec<_>>();
+
+    while !level.is_empty() {
+        let walked = stream::iter(level)
+            .map(|job| {
+                let dir = job.dir.clone();
+                async move {
+                    time::timeout(timeout, walk_dir(job, args, rules, names))
+                        .await
+                        .unwrap_or_else(|_| {
+                            warn!(dir = %dir.display(), "Reading directory timed out, skipping it");
+                            None
+                        })
                 }
+            })
+            .buffered(jobs)
+            .collect::<Vec<_>>()
+            .await;
+
+        level = Vec::new();
+        for walk in walked.into_iter().flatten() {
+            if !visited.insert(walk.identity) {
+                continue; // already walked (symlink loop or overlapping roots)
             }
+            out.extend(walk.files);
+            level.extend(walk.subdirs);
         }
     }
 
@@ -65,263 +314,912 @@ pub async fn collect_desktop_files(dirs: &[PathBuf]) -> Result<Vec<PathBuf>> {
     Ok(out)
 }
 
+/// A directory waiting to be walked by [`collect_desktop_files`].
+struct WalkJob {
+    /// The directory.
+    dir: PathBuf,
+
+    /// Ignore files of the directories above it, outermost first.
+    ignore_files: Vec<Arc<IgnoreFile>>,
+
+    /// Directory levels below its root.
+    depth: usize,
+
+    /// Device of its root (`None` for a root).
+    root_dev: Option<u64>,
+}
+
+/// What [`walk_dir`] found in a directory.
+struct DirWalk {
+    /// (device, inode) of the directory.
+    identity: (u64, u64),
+
+    /// Files to inspect.
+    files: Vec<PathBuf>,
+
+    /// Subdirectories to walk next.
+    subdirs: Vec<WalkJob>,
+}
+
+/// Read the directory of `job`: its files to inspect and its subdirectories to walk. Returns
+/// `None` for missing or unreadable directories and, with `--one-file-system`, for directories
+/// on another filesystem than their root.
+async fn walk_dir(
+    mut job: WalkJob,
+    args: &Args,
+    rules: &IgnoreRules,
+    names: &NameFilter,
+) -> Option<DirWalk> {
+    let md = retry::metadata(&job.dir).await.ok()?;
+    let root_dev = job.root_dev.unwrap_or(md.dev());
+    if args.one_file_system && md.dev() != root_dev {
+        debug!(dir = %job.dir.display(), "Skipping directory on another filesystem");
+        return None;
+    }
+
+    let mut rd = retry::read_dir(&job.dir).await.ok()?;
+    if let Some(file) = IgnoreFile::load(&job.dir).await {
+        debug!(dir = %job.dir.display(), "Applying {IGNORE_FILE_NAME}");
+        job.ignore_files.push(Arc::new(file));
+    }
+
+    let mut walk = DirWalk {
+        identity: (md.dev(), md.ino()),
+        files: Vec::new(),
+        subdirs: Vec::new(),
+    };
+    loop {
+        let ent = match rd.next_entry().await {
+            Ok(Some(e)) => e,
+            Ok(None) => break,
+            Err(_) => break,
+        };
+
+        let mut ft = match ent.file_type().await {
+            Ok(ft) => ft,
+            Err(_) => continue,
+        };
+        let p = ent.path();
+
+        // Dangling `.desktop` symlinks are collected and reported as broken.
+        let mut dangling = false;
+        if ft.is_symlink() {
+            if args.follow_symlinks == SymlinkPolicy::Never {
+                continue;
+            }
+            match retry::metadata(&p).await {
+                Ok(md) => ft = md.file_type(),
+                Err(_) => dangling = true,
+            }
+            if ft.is_dir() && args.follow_symlinks != SymlinkPolicy::All {
+                continue;
+            }
+        }
+
+        if rules.is_ignored(&p, ft.is_dir(), &job.ignore_files) {
+            debug!(path = %p.display(), "Ignoring path matched by an ignore pattern");
+        } else if !ft.is_dir() && !names.keeps(&p) {
+            debug!(path = %p.display(), "Skipping file filtered by --include/--exclude");
+        } else if ft.is_dir() {
+            if args.max_depth.is_some_and(|max| job.depth >= max) {
+                debug!(dir = %p.display(), "Skipping directory below --max-depth");
+            } else {
+                walk.subdirs.push(WalkJob {
+                    dir: p,
+                    ignore_files: job.ignore_files.clone(),
+                    depth: job.depth + 1,
+                    root_dev: Some(root_dev),
+                });
+            }
+        } else if let Some(newer_than) = &args.newer_than
+            && !newer_than.includes(&p).await
+        {
+            debug!(path = %p.display(), "Skipping file not modified since --newer-than");
+        } else if (ft.is_file() || dangling)
+            && p.extension().and_then(|e| e.to_str()) == Some("desktop")
+        {
+            walk.files.push(p);
+        } else if ft.is_file() && args.detect_misnamed && is_misnamed_desktop_file(&p).await {
+            debug!(file = %p.display(), "Found desktop entry without .desktop extension");
+            walk.files.push(p);
+        }
+    }
+    Some(walk)
+}
+
+/// The target of `path` if it is a symlink whose target does not exist.
+async fn dangling_symlink(path: &Path) -> Option<PathBuf> {
+    let target = fs::read_link(path).await.ok()?;
+    fs::metadata(path).await.is_err().then_some(target)
+}
+
+/// Largest file considered by [`is_misnamed_desktop_file`]; real entries are a few KiB.
+const MISNAMED_MAX_SIZE: u64 = 256 * 1024;
+
+/// Check whether `path` is a desktop entry that lacks the `.desktop` extension.
+///
+/// Dotfiles (e.g. KDE `.directory` files) and leftovers that already mention `.desktop` in
+/// their name (backups like `foo.desktop~` or `foo.desktop.dpkg-old`) are ignored, since
+/// renaming those is not the fix.
+async fn is_misnamed_desktop_file(path: &Path) -> bool {
+    let Some(name) = path.file_name().and_then(|n| n.to_str()) else {
+        return false;
+    };
+    if name.starts_with('.') || name.contains(".desktop") {
+        return false;
+    }
+
+    match fs::metadata(path).await {
+        Ok(md) if md.len() <= MISNAMED_MAX_SIZE => {}
+        _ => return false,
+    }
+
+    match fs::read_to_string(path).await {
+        Ok(content) => content.lines().any(|l| l.trim() == "[Desktop Entry]"),
+        Err(_) => false, // binary or unreadable
+    }
+}
+
+/// Read a newline-separated list of paths from `source` (`-` reads stdin).
+///
+/// Blank lines and surrounding whitespace are ignored. Paths are returned in input order.
+pub async fn read_path_list(source: &Path) -> Result<Vec<PathBuf>> {
+    let content = if source == Path::new("-") {
+        let mut buf = String::new();
+        io::stdin()
+            .read_to_string(&mut buf)
+            .await
+            .context("Failed to read path list from stdin")?;
+        buf
+    } else {
+        fs::read_to_string(source)
+            .await
+            .with_context(|| format!("Failed to read path list from {}", source.display()))?
+    };
+
+    let paths = content
+        .lines()
+        .map(str::trim)
+        .filter(|l| !l.is_empty())
+        .map(PathBuf::from)
+        .collect::<Vec<_>>();
+
+    debug!(count = paths.len(), source = %source.display(), "Read path list");
+    Ok(paths)
+}
+
 /// Inspect a list of `.desktop` files concurrently with bounded parallelism.
 ///
 /// - `args.jobs` controls max concurrency.
-/// - Each file is read and checked independently.
-/// - Any per-file errors are converted into a `Broken` finding.
-pub async fn inspect_files_concurrently(files: Vec<PathBuf>, args: &Args) -> Vec<Finding> {
-    let path_env = env::var("PATH").unwrap_or_default();
-    let jobs = args
-        .jobs
-        .unwrap_or_else(|| num_cpus::get().saturating_mul(4).max(8));
+/// - Files below a `--dir-jobs` directory form a lane of their own with its own, lower limit
+///   (see [`origin_lanes`]), so a slow origin such as a network mount cannot take up all jobs.
+/// - Each file is read and checked independently in its own Tokio task, so a panic while
+///   inspecting one file cannot abort the scan.
+/// - Each file is bounded by `--timeout`, so a hung filesystem cannot stall the scan, and no
+///   file is inspected past `deadline` (`--max-runtime`); such files are reported as
+///   `TimedOut`.
+/// - Identical `Exec=` lines are validated only once (see [`ExecCache`]).
+/// - Files `scan_cache` has a valid verdict for are not checked again; other verdicts are
+///   added to it unless they timed out.
+/// - Any per-file errors and panics are converted into a `Broken` finding.
+/// - Each finding is completed by `annotate` (suppressions, owning user).
+/// - Each finding is passed to `on_finding` as soon as it is complete.
+pub async fn inspect_files_concurrently(
+    files: Vec<PathBuf>,
+    args: &Args,
+    deadline: Option<time::Instant>,
+    scan_cache: Option<&ScanCache>,
+    annotate: &(dyn Fn(&mut Finding) + Sync),
+    on_finding: &mut (dyn FnMut(&Finding) + Send),
+) -> Vec<Finding> {
+    let path_env = path_env(args);
+    let jobs = args.jobs.unwrap_or_else(default_jobs);
+    let file_timeout = Duration::from_secs(args.timeout);
 
     let sem = Semaphore::new(jobs);
-    debug!(jobs, "Starting concurrent inspection");
-
-    stream::iter(files)
-        .map(|path| {
-            let sem = &sem;
-            let args = args.clone();
-            let path_env = path_env.clone();
-
-            async move {
-                let _permit = sem.acquire().await.expect("semaphore closed");
-                match inspect_one(&path, &path_env, &args).await {
-                    Ok(f) => f,
-                    Err(e) => {
-                        warn!(file = %path.display(), error = %e, "Failed to inspect file");
-                        Finding {
-                            desktop_file: path,
-                            name: None,
-                            exec: None,
-                            try_exec: None,
-                            path_key: None,
-                            hidden: false,
-                            no_display: false,
-                            status: Status::Broken {
-                                reason: format!("Failed to read/parse file: {e:#}"),
-                            },
+    let cache = Arc::new(ExecCache::default());
+    let lanes = origin_lanes(files, &args.dir_jobs, jobs);
+    debug!(jobs, lanes = lanes.len(), "Starting concurrent inspection");
+
+    let (sem, path_env, cache) = (&sem, &path_env, &cache);
+    let lanes = lanes.into_iter().map(move |(lane_jobs, files)| {
+        stream::iter(files)
+            .map(move |path| {
+                let path_env = path_env.clone();
+                let cache = Arc::clone(cache);
+
+                async move {
+                    let _permit = sem.acquire().await.expect("semaphore closed");
+
+                    let now = time::Instant::now();
+                    if deadline.is_some_and(|d| d <= now) {
+                        return max_runtime_finding(path, args);
+                    }
+                    // A file gets the full --timeout unless the scan's deadline comes first.
+                    let until = deadline.map_or(now + file_timeout, |d| d.min(now + file_timeout));
+
+                    let stamp = match scan_cache {
+                        Some(_) => time::timeout_at(until, cache::stamp(&path))
+                            .await
+                            .ok()
+                            .flatten(),
+                        None => None,
+                    };
+                    if let (Some(c), Some(stamp)) = (scan_cache, stamp)
+                        && let Ok(Some(f)) = time::timeout_at(until, c.get(&path, stamp)).await
+                    {
+                        debug!(file = %path.display(), "Reusing cached verdict");
+                        return f;
+                    }
+
+                    let task_path = path.clone();
+                    let task_args = args.clone();
+                    let mut task = tokio::spawn(async move {
+                        inspect_one(&task_path, &path_env, &task_args, &cache).await
+                    });
+
+                    match time::timeout_at(until, &mut task).await {
+                        Ok(Ok(Ok(f))) => {
+                            if let (Some(c), Some(stamp)) = (scan_cache, stamp)
+                                && !f.is_timed_out()
+                            {
+                                c.put(&path, stamp, &f).await;
+                            }
+                            f
+                        }
+                        Ok(Ok(Err(e))) => {
+                            warn!(file = %path.display(), error = %e, "Failed to inspect file");
+                            failed_finding(
+                                path,
+                                Status::Broken {
+                                    code: ReasonCode::UnreadableFile,
+                                    reason: tr(
+                                        "reason-unreadable-file",
+                                        &[("error", format!("{e:#}").into())],
+                                    ),
+                                },
+                            )
+                        }
+                        Ok(Err(e)) => {
+                            warn!(file = %path.display(), error = %e, "Inspection task failed");
+                            failed_finding(
+                                path,
+                                Status::Broken {
+                                    code: ReasonCode::InspectionPanicked,
+                                    reason: tr(
+                                        "reason-inspection-panicked",
+                                        &[("error", e.to_string().into())],
+                                    ),
+                                },
+                            )
+                        }
+                        Err(_) if deadline.is_some_and(|d| d <= until) => {
+                            task.abort();
+                            warn!(file = %path.display(), "Inspection stopped at --max-runtime");
+                            max_runtime_finding(path, args)
+                        }
+                        Err(_) => {
+                            task.abort();
+                            warn!(file = %path.display(), "Inspection timed out");
+                            failed_finding(
+                                path,
+                                Status::TimedOut {
+                                    code: ReasonCode::CheckTimedOut,
+                                    reason: tr(
+                                        "reason-inspection-timed-out",
+                                        &[("seconds", args.timeout.into())],
+                                    ),
+                                },
+                            )
                         }
                     }
                 }
-            }
+            })
+            .buffer_unordered(lane_jobs)
+            .boxed()
+    });
+    stream::select_all(lanes)
+        .map(|mut f| {
+            annotate(&mut f);
+            f
         })
-        .buffer_unordered(jobs)
+        .inspect(|f| on_finding(f))
         .collect()
         .await
 }
 
+/// `PATH` bare commands are resolved through: the environment's, or a standard one inside a
+/// `--root` image.
+fn path_env(args: &Args) -> String {
+    match args.root {
+        Some(_) => sysroot::IMAGE_PATH.to_string(),
+        None => env::var("PATH").unwrap_or_default(),
+    }
+}
+
+/// Inspection jobs without `--jobs`: four per CPU, at least 8 (inspections mostly wait on IO).
+pub fn default_jobs() -> usize {
+    num_cpus::get().saturating_mul(4).max(8)
+}
+
+/// Split `files` into inspection lanes with their concurrency: one per `--dir-jobs` limit
+/// (files below its directory; the most specific directory wins) and one with `jobs` for all
+/// other files. Empty lanes are dropped.
+fn origin_lanes(
+    files: Vec<PathBuf>,
+    limits: &[OriginLimit],
+    jobs: usize,
+) -> Vec<(usize, Vec<PathBuf>)> {
+    let mut lanes = limits
+        .iter()
+        .map(|l| (l.jobs.min(jobs), Vec::new()))
+        .collect::<Vec<_>>();
+    let mut rest = Vec::new();
+
+    for path in files {
+        let origin = limits
+            .iter()
+            .enumerate()
+            .filter(|(_, l)| path.starts_with(&l.dir))
+            .max_by_key(|(_, l)| l.dir.components().count())
+            .map(|(idx, _)| idx);
+        match origin {
+            Some(idx) => lanes[idx].1.push(path),
+            None => rest.push(path),
+        }
+    }
+
+    lanes.push((jobs, rest));
+    lanes.retain(|(_, files)| !files.is_empty());
+    lanes
+}
+
+/// Concurrency limit for the files below a directory (`--dir-jobs DIR=N`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OriginLimit {
+    /// Directory whose files are limited.
+    pub dir: PathBuf,
+
+    /// Maximum number of its files inspected at once.
+    pub jobs: usize,
+}
+
+impl FromStr for OriginLimit {
+    type Err = String;
+
+    /// Parse `DIR=N` with `N` at least 1 (the last `=` separates, so `DIR` may contain `=`).
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (dir, jobs) = s
+            .rsplit_once('=')
+            .ok_or_else(|| format!("expected `DIR=N`, got `{s}`"))?;
+        let jobs = jobs
+            .trim()
+            .parse::<usize>()
+            .ok()
+            .filter(|n| *n > 0)
+            .ok_or_else(|| format!("`{jobs}` in `{s}` is not a positive number of jobs"))?;
+        if dir.is_empty() {
+            return Err(format!("missing directory in `{s}`"));
+        }
+        Ok(OriginLimit {
+            dir: PathBuf::from(dir),
+            jobs,
+        })
+    }
+}
+
+/// Modification time filter (`--newer-than`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NewerThan {
+    /// Files last modified at or before this time are left out.
+    pub cutoff: SystemTime,
+}
+
+impl NewerThan {
+    /// Whether the file at `path` was modified after the cutoff. For symlinks, the link and its
+    /// target both count, so a freshly installed link to an old file is included.
+    async fn includes(&self, path: &Path) -> bool {
+        let link = fs::symlink_metadata(path)
+            .await
+            .and_then(|md| md.modified());
+        let target = retry::metadata(path).await.and_then(|md| md.modified());
+        [link, target]
+            .into_iter()
+            .flatten()
+            .any(|modified| modified > self.cutoff)
+    }
+}
+
+impl FromStr for NewerThan {
+    type Err = String;
+
+    /// Parse an age `<N><s|m|h|d|w>` (relative to now), a local `YYYY-MM-DD` (midnight),
+    /// `YYYY-MM-DDTHH:MM[:SS]` or an RFC 3339 timestamp.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let invalid =
+            || format!("expected an age like `7d` or a time like `2026-10-01T14:00`, got `{s}`");
+
+        if let Some(unit) = s.chars().last().filter(char::is_ascii_alphabetic)
+            && let Ok(n) = s[..s.len() - 1].parse::<u64>()
+        {
+            let secs = match unit {
+                's' => 1,
+                'm' => 60,
+                'h' => 60 * 60,
+                'd' => 24 * 60 * 60,
+                'w' => 7 * 24 * 60 * 60,
+                _ => return Err(invalid()),
+            };
+            let age = n
+                .checked_mul(secs)
+                .map(Duration::from_secs)
+                .ok_or_else(invalid)?;
+            let cutoff = SystemTime::now().checked_sub(age).ok_or_else(invalid)?;
+            return Ok(NewerThan { cutoff });
+        }
+
+        if let Ok(time) = DateTime::parse_from_rfc3339(s) {
+            return Ok(NewerThan {
+                cutoff: time.into(),
+            });
+        }
+        let naive = NaiveDate::parse_from_str(s, "%Y-%m-%d")
+            .map(|d| d.and_time(NaiveTime::MIN))
+            .or_else(|_| NaiveDateTime::parse_from_str(s, "%Y-%m-%dT%H:%M:%S"))
+            .or_else(|_| NaiveDateTime::parse_from_str(s, "%Y-%m-%dT%H:%M"))
+            .map_err(|_| invalid())?;
+        let time = Local
+            .from_local_datetime(&naive)
+            .earliest()
+            .ok_or_else(|| format!("`{s}` does not exist in the local time zone"))?;
+        Ok(NewerThan {
+            cutoff: time.into(),
+        })
+    }
+}
+
+/// A `TimedOut` finding for a file the scan did not get to before `--max-runtime`.
+fn max_runtime_finding(path: PathBuf, args: &Args) -> Finding {
+    failed_finding(
+        path,
+        Status::TimedOut {
+            code: ReasonCode::MaxRuntimeExceeded,
+            reason: tr(
+                "reason-max-runtime-exceeded",
+                &[("seconds", args.max_runtime.unwrap_or_default().into())],
+            ),
+        },
+    )
+}
+
+/// Build a finding with `status` (`Broken` or `TimedOut`) for a file that could not be inspected
+/// at all.
+fn failed_finding(path: PathBuf, status: Status) -> Finding {
+    Finding {
+        id: report::finding_id(&path, &status, &[]),
+        desktop_file: path,
+        name: None,
+        exec: None,
+        try_exec: None,
+        path_key: None,
+        entry_type: None,
+        hidden: false,
+        no_display: false,
+        severity: Severity::of(&status, &[]),
+        status,
+        warnings: Vec::new(),
+        user: None,
+        suppressed_warnings: Vec::new(),
+        suggested_exec: None,
+        history: None,
+        flathub: None,
+        identity: None,
+        same_file_as: Vec::new(),
+    }
+}
+
 /// Inspect a single `.desktop` file and return a `Finding`.
 ///
 /// This function:
-/// - reads the file asynchronously
+/// - reads the file asynchronously, retrying transient IO errors (see [`retry`]); with
+///   `--inventory`, files that are not valid UTF-8 are decoded lossily and linted
+/// - reports dangling symlinks as `DANGLING_SYMLINK`
 /// - parses `[Desktop Entry]`
-/// - applies skip rules (`Hidden`, `NoDisplay`, `Type!=Application`)
-/// - validates `TryExec` (preferred) and/or `Exec`
-/// - returns `Ok`, `Broken`, or `Skipped`
-async fn inspect_one(path: &PathBuf, path_env: &str, args: &Args) -> Result<Finding> {
-    let content = fs::read_to_string(path).await?;
+/// - evaluates the entry via [`entry_status`] (files without `.desktop` extension are reported
+///   as misnamed instead)
+/// - records the device and inode of the file (see [`report::link_same_files`])
+/// - runs file-intrinsic lints and checks session sockets and GPU settings referenced by `Exec=`
+///   (except with `--frozen`, which also leaves out the device and inode), keeping warnings down
+///   to `--min-severity`
+/// - suggests a relocated binary for a missing absolute `Exec=` executable
+/// - attaches a stable finding ID derived from the desktop ID, status and file content
+async fn inspect_one(
+    path: &Path,
+    path_env: &str,
+    args: &Args,
+    cache: &ExecCache,
+) -> Result<Finding> {
+    let bytes = match retry::read(path).await {
+        Ok(bytes) => bytes,
+        Err(e) => {
+            return match dangling_symlink(path).await {
+                Some(target) => Ok(failed_finding(
+                    path.to_path_buf(),
+                    Status::Broken {
+                        code: ReasonCode::DanglingSymlink,
+                        reason: tr(
+                            "reason-dangling-symlink",
+                            &[("target", target.display().to_string().into())],
+                        ),
+                    },
+                )),
+                None => Err(e.into()),
+            };
+        }
+    };
+    let (content, invalid_encoding) = match String::from_utf8(bytes) {
+        Ok(content) => (content, false),
+        Err(e) if args.inventory => (String::from_utf8_lossy(e.as_bytes()).into_owned(), true),
+        Err(e) => return Err(io::Error::new(io::ErrorKind::InvalidData, e.utf8_error()).into()),
+    };
+    let identity = match args.frozen {
+        true => None,
+        false => retry::metadata(path).await.ok().map(|md| FileIdentity {
+            device: md.dev(),
+            inode: md.ino(),
+        }),
+    };
     let kv = desktop::parse_desktop_entry_section(&content);
+    let status = if path.extension().and_then(|e| e.to_str()) == Some("desktop") {
+        entry_status(&kv, path_env, args, cache).await?
+    } else {
+        let name = path
+            .file_name()
+            .map(|n| n.to_string_lossy())
+            .unwrap_or_default();
+        Status::Broken {
+            code: ReasonCode::MissingDesktopExtension,
+            reason: tr(
+                "reason-missing-desktop-extension",
+                &[("name", name.into_owned().into())],
+            ),
+        }
+    };
+
+    let ctx = check_context(&kv, path_env, args);
+    let suggested_exec = match (&status, kv.get("Exec")) {
+        (
+            Status::Broken {
+                code: ReasonCode::ExecNotFound | ReasonCode::ExecUnresolvedDespiteTryexec,
+                ..
+            },
+            Some(exec_line),
+        ) => check::suggest_relocated_exec(exec_line, &ctx).await,
+        (
+            Status::Broken {
+                code: ReasonCode::ExecUnquotedSpaces,
+                ..
+            },
+            Some(exec_line),
+        ) => check::unquoted_spaced_exec(exec_line, &ctx)
+            .await
+            .map(|(_, fixed)| fixed),
+        _ => None,
+    };
+
+    let mut warnings = lint::lint_entry(path, &content, &kv, &status);
+    if invalid_encoding {
+        warnings.insert(0, lint::invalid_encoding());
+    }
+    if !args.frozen
+        && args.root.is_none()
+        && !matches!(status, Status::Skipped { .. })
+        && let Some(exec_line) = kv.get("Exec")
+    {
+        warnings.extend(session::check_exec(exec_line).await);
+        warnings.extend(gpu::check_exec(exec_line).await);
+    }
+    warnings.retain(|w| args.keeps_warning(w.code));
+
+    Ok(Finding {
+        id: report::finding_id(path, &status, content.as_bytes()),
+        desktop_file: path.to_path_buf(),
+        name: kv.get("Name").cloned(),
+        exec: kv.get("Exec").cloned(),
+        try_exec: kv.get("TryExec").cloned(),
+        path_key: kv.get("Path").cloned(),
+        entry_type: kv.get("Type").cloned(),
+        hidden: desktop::parse_bool(kv.get("Hidden")),
+        no_display: desktop::parse_bool(kv.get("NoDisplay")),
+        severity: Severity::of(&status, &warnings),
+        status,
+        warnings,
+        user: None,
+        suppressed_warnings: Vec::new(),
+        suggested_exec,
+        history: None,
+        flathub: None,
+        identity,
+        same_file_as: Vec::new(),
+    })
+}
+
+/// Build the [`check::CheckContext`] for a parsed `[Desktop Entry]` section.
+fn check_context<'a>(
+    kv: &'a HashMap<String, String>,
+    path_env: &'a str,
+    args: &'a Args,
+) -> check::CheckContext<'a> {
+    check::CheckContext {
+        path_env,
+        path_key: kv.get("Path").map(|s| s.as_str()),
+        check_script_args: args.runs_check(ReasonCode::ScriptMissing, args.check_script_args),
+        check_user_manager: args.check_user_manager,
+        simulation: args.simulation.as_deref(),
+        root: args.root.as_deref(),
+    }
+}
 
-    let name = kv.get("Name").cloned();
-    let exec = kv.get("Exec").cloned();
-    let try_exec = kv.get("TryExec").cloned();
+/// Decide the [`Status`] of a parsed `[Desktop Entry]` section.
+///
+/// This function:
+/// - applies skip rules (`Hidden`, `NoDisplay`, `Type!=Application`)
+/// - validates `TryExec` (preferred) and/or `Exec`, or with `--frozen` only the syntax of `Exec`
+///   (see [`frozen_status`])
+/// - returns `Ok`, `Broken`, or `Skipped`
+async fn entry_status(
+    kv: &HashMap<String, String>,
+    path_env: &str,
+    args: &Args,
+    cache: &ExecCache,
+) -> Result<Status> {
+    let exec = kv.get("Exec");
+    let try_exec = kv.get("TryExec");
     let hidden = desktop::parse_bool(kv.get("Hidden"));
     let no_display = desktop::parse_bool(kv.get("NoDisplay"));
-    let typ = kv.get("Type").map(|s| s.trim().to_string());
+    let typ = kv.get("Type").map(|s| s.trim());
     let dbus_activatable = desktop::parse_bool(kv.get("DBusActivatable"));
-    let path_key = kv.get("Path").cloned();
 
     if !args.include_hidden && (hidden || no_display) {
-        return Ok(Finding {
-            desktop_file: path.clone(),
-            name,
-            exec,
-            try_exec,
-            path_key,
-            hidden,
-            no_display,
-            status: Status::Skipped {
-                reason: "Hidden=true or NoDisplay=true (use --include-hidden to scan these)".into(),
-            },
+        return Ok(Status::Skipped {
+            code: ReasonCode::HiddenEntry,
+            reason: tr("reason-hidden-entry", &[]),
         });
     }
 
-    if let Some(t) = typ.as_deref() {
-        if t != "Application" {
-            return Ok(Finding {
-                desktop_file: path.clone(),
-                name,
-                exec,
-                try_exec,
-                path_key,
-                hidden,
-                no_display,
-                status: Status::Skipped {
-                    reason: format!("Type={t} (only Type=Application is checked)"),
-                },
-            });
-        }
+    if let Some(t) = typ
+        && t != "Application"
+    {
+        return Ok(Status::Skipped {
+            code: ReasonCode::UnsupportedType,
+            reason: tr("reason-unsupported-type", &[("type", t.into())]),
+        });
     }
 
     // DBus activatable entries may legitimately omit Exec.
     if dbus_activatable && exec.is_none() {
-        return Ok(Finding {
-            desktop_file: path.clone(),
-            name,
-            exec,
-            try_exec,
-            path_key,
-            hidden,
-            no_display,
-            status: Status::Ok {
-                resolved_executable: None,
-            },
+        return Ok(Status::Ok {
+            resolved_executable: None,
         });
     }
 
-    let ctx = check::CheckContext {
-        path_env,
-        path_key: path_key.as_deref(),
-        check_script_args: args.check_script_args,
-    };
+    if args.frozen {
+        return Ok(frozen_status(exec, try_exec));
+    }
+
+    let ctx = check_context(kv, path_env, args);
 
     // Prefer TryExec if present.
-    if let Some(tx) = try_exec.clone().as_deref() {
-        match check::validate_tryexec(tx, &ctx).await? {
-            Some(resolved_tx) => {
-                // Still validate Exec if present.
-                if let Some(exec_line) = exec.as_deref() {
-                    match check::validate_exec(exec_line, &ctx).await {
-                        Ok(Some(resolved_exec)) => {
-                            return Ok(Finding {
-                                desktop_file: path.clone(),
-                                name,
-                                exec,
-                                try_exec,
-                                path_key,
-                                hidden,
-                                no_display,
-                                status: Status::Ok {
-                                    resolved_executable: Some(resolved_exec),
-                                },
-                            });
-                        }
-                        Ok(None) => {
-                            return Ok(Finding {
-                                desktop_file: path.clone(),
-                                name,
-                                exec,
-                                try_exec,
-                                path_key,
-                                hidden,
-                                no_display,
-                                status: Status::Broken {
-                                    reason: "Exec does not resolve (even though TryExec does)"
-                                        .into(),
-                                },
-                            });
-                        }
-                        Err(e) => {
-                            return Ok(Finding {
-                                desktop_file: path.clone(),
-                                name,
-                                exec,
-                                try_exec,
-                                path_key,
-                                hidden,
-                                no_display,
-                                status: Status::Broken {
-                                    reason: format!("Exec check failed: {e:#}"),
-                                },
-                            });
-                        }
-                    }
-                }
+    if let Some(tx) = try_exec {
+        let Ok(tryexec_result) =
+            time::timeout(CHECK_TIMEOUT, check::validate_tryexec(tx, &ctx)).await
+        else {
+            return Ok(check_timed_out("TryExec"));
+        };
+        let Some(resolved_tx) = tryexec_result? else {
+            return Ok(Status::Broken {
+                code: ReasonCode::TryexecUnresolved,
+                reason: tr(
+                    "reason-tryexec-unresolved",
+                    &[("tryexec", tx.as_str().into())],
+                ),
+            });
+        };
 
-                return Ok(Finding {
-                    desktop_file: path.clone(),
-                    name,
-                    exec,
-                    try_exec,
-                    path_key,
-                    hidden,
-                    no_display,
-                    status: Status::Ok {
-                        resolved_executable: Some(resolved_tx),
-                    },
-                });
-            }
-            None => {
-                return Ok(Finding {
-                    desktop_file: path.clone(),
-                    name,
-                    exec,
-                    try_exec,
-                    path_key,
-                    hidden,
-                    no_display,
-                    status: Status::Broken {
-                        reason: format!("TryExec does not resolve: {tx}"),
-                    },
-                });
-            }
-        }
+        // Still validate Exec if present.
+        let Some(exec_line) = exec else {
+            return Ok(Status::Ok {
+                resolved_executable: Some(resolved_tx),
+            });
+        };
+
+        return Ok(match cache.validate(exec_line, &ctx).await {
+            Ok(Some(resolved_exec)) => Status::Ok {
+                resolved_executable: Some(resolved_exec),
+            },
+            Ok(None) => match unquoted_spaces(exec_line, &ctx).await {
+                Some(status) => status,
+                None => Status::Broken {
+                    code: ReasonCode::ExecUnresolvedDespiteTryexec,
+                    reason: tr("reason-exec-unresolved-despite-tryexec", &[]),
+                },
+            },
+            Err(e) => exec_check_failed(&e),
+        });
     }
 
     // Otherwise validate Exec.
-    if let Some(exec_line) = exec.as_deref() {
-        match check::validate_exec(exec_line, &ctx).await {
-            Ok(Some(resolved)) => Ok(Finding {
-                desktop_file: path.clone(),
-                name,
-                exec,
-                try_exec,
-                path_key,
-                hidden,
-                no_display,
-                status: Status::Ok {
-                    resolved_executable: Some(resolved),
-                },
-            }),
-            Ok(None) => Ok(Finding {
-                desktop_file: path.clone(),
-                name,
-                exec,
-                try_exec,
-                path_key,
-                hidden,
-                no_display,
-                status: Status::Broken {
-                    reason: "Exec does not resolve".into(),
-                },
-            }),
-            Err(e) => Ok(Finding {
-                desktop_file: path.clone(),
-                name,
-                exec,
-                try_exec,
-                path_key,
-                hidden,
-                no_display,
-                status: Status::Broken {
-                    reason: format!("Exec check failed: {e:#}"),
-                },
-            }),
-        }
-    } else {
-        Ok(Finding {
-            desktop_file: path.clone(),
-            name,
-            exec,
-            try_exec,
-            path_key,
-            hidden,
-            no_display,
-            status: Status::Broken {
-                reason: "No Exec key found (and not DBusActivatable)".into(),
+    let Some(exec_line) = exec else {
+        return Ok(Status::Broken {
+            code: ReasonCode::ExecMissing,
+            reason: tr("reason-exec-missing", &[]),
+        });
+    };
+
+    Ok(match cache.validate(exec_line, &ctx).await {
+        Ok(Some(resolved)) => Status::Ok {
+            resolved_executable: Some(resolved),
+        },
+        Ok(None) => exec_not_found(exec_line, &ctx, args).await,
+        Err(e) => exec_check_failed(&e),
+    })
+}
+
+/// Status of an application entry judged only by its own content (`--frozen`).
+///
+/// Nothing is resolved, so `TryExec=` only needs a value and `Exec=` only needs to parse and
+/// name an executable that is neither a field code nor a shell builtin.
+fn frozen_status(exec: Option<&String>, try_exec: Option<&String>) -> Status {
+    let Some(exec_line) = exec else {
+        return match try_exec {
+            Some(_) => Status::Ok {
+                resolved_executable: None,
+            },
+            None => Status::Broken {
+                code: ReasonCode::ExecMissing,
+                reason: tr("reason-exec-missing", &[]),
             },
+        };
+    };
+
+    match check::exec_syntax(exec_line) {
+        Ok(Some(cmd)) if shell::is_builtin(&cmd) => Status::Broken {
+            code: ReasonCode::ExecShellBuiltin,
+            reason: tr(
+                "reason-exec-shell-builtin",
+                &[("command", cmd.as_str().into())],
+            ),
+        },
+        Ok(Some(_)) => Status::Ok {
+            resolved_executable: None,
+        },
+        Ok(None) => Status::Broken {
+            code: ReasonCode::ExecNotFound,
+            reason: tr("reason-exec-not-found", &[]),
+        },
+        Err(e) => exec_check_failed(&e),
+    }
+}
+
+/// Status for an `Exec=` line whose executable does not resolve.
+///
+/// With `--check-shell-aliases` (or the EXEC_SHELL_ALIAS rule enabled), a bare command that is
+/// defined as a shell alias or function is reported as [`ReasonCode::ExecShellAlias`] instead of
+/// a generic [`ReasonCode::ExecNotFound`].
+/// A bare shell builtin name is always reported as [`ReasonCode::ExecShellBuiltin`], and an
+/// absolute path with unquoted spaces as [`ReasonCode::ExecUnquotedSpaces`].
+async fn exec_not_found(exec_line: &str, ctx: &check::CheckContext<'_>, args: &Args) -> Status {
+    if let Some(status) = unquoted_spaces(exec_line, ctx).await {
+        return status;
+    }
+
+    let Some(cmd) = check::exec_command_name(exec_line).filter(|c| !c.contains('/')) else {
+        return Status::Broken {
+            code: ReasonCode::ExecNotFound,
+            reason: tr("reason-exec-not-found", &[]),
+        };
+    };
+
+    if shell::is_builtin(&cmd) {
+        return Status::Broken {
+            code: ReasonCode::ExecShellBuiltin,
+            reason: tr(
+                "reason-exec-shell-builtin",
+                &[("command", cmd.as_str().into())],
+            ),
+        };
+    }
+
+    if args.runs_check(ReasonCode::ExecShellAlias, args.check_shell_aliases)
+        && let Some(def) = shell::find_definition(&cmd).await
+    {
+        return Status::Broken {
+            code: ReasonCode::ExecShellAlias,
+            reason: tr(
+                "reason-exec-shell-alias",
+                &[
+                    ("command", cmd.as_str().into()),
+                    ("kind", def.kind.to_string().into()),
+                    ("source", def.source.display().to_string().into()),
+                ],
+            ),
+        };
+    }
+
+    Status::Broken {
+        code: ReasonCode::ExecNotFound,
+        reason: tr("reason-exec-not-found", &[]),
+    }
+}
+
+/// [`ReasonCode::ExecUnquotedSpaces`] status if the unresolved `exec_line` names an existing
+/// path with spaces without quoting it (see [`check::unquoted_spaced_exec`]).
+async fn unquoted_spaces(exec_line: &str, ctx: &check::CheckContext<'_>) -> Option<Status> {
+    let (path, _) = check::unquoted_spaced_exec(exec_line, ctx).await?;
+    Some(Status::Broken {
+        code: ReasonCode::ExecUnquotedSpaces,
+        reason: tr(
+            "reason-exec-unquoted-spaces",
+            &[("path", path.display().to_string().into())],
+        ),
+    })
+}
+
+/// Run [`check::validate_exec`] bounded by [`CHECK_TIMEOUT`].
+///
+/// A timeout is reported as a [`check::CheckError`] with [`ReasonCode::CheckTimedOut`].
+async fn validate_exec_bounded(
+    exec_line: &str,
+    ctx: &check::CheckContext<'_>,
+) -> Result<Option<PathBuf>> {
+    time::timeout(CHECK_TIMEOUT, check::validate_exec(exec_line, ctx))
+        .await
+        .unwrap_or_else(|_| {
+            Err(check::CheckError::err(
+                ReasonCode::CheckTimedOut,
+                tr(
+                    "reason-check-timed-out",
+                    &[
+                        ("what", "Exec".into()),
+                        ("seconds", CHECK_TIMEOUT.as_secs().into()),
+                    ],
+                ),
+            ))
         })
+}
+
+/// Status for a check that exceeded [`CHECK_TIMEOUT`].
+fn check_timed_out(what: &str) -> Status {
+    Status::TimedOut {
+        code: ReasonCode::CheckTimedOut,
+        reason: tr(
+            "reason-check-timed-out",
+            &[
+                ("what", what.into()),
+                ("seconds", CHECK_TIMEOUT.as_secs().into()),
+            ],
+        ),
+    }
+}
+
+/// Convert an error from [`check::validate_exec`] into a `Broken` status, or a `TimedOut` one
+/// if the check exceeded [`CHECK_TIMEOUT`].
+///
+/// Errors carrying a [`check::CheckError`] keep their code; anything else is treated as a
+/// parse failure.
+fn exec_check_failed(e: &anyhow::Error) -> Status {
+    let code = e
+        .downcast_ref::<check::CheckError>()
+        .map(|c| c.code)
+        .unwrap_or(ReasonCode::ExecParseError);
+    let reason = tr(
+        "reason-exec-check-failed",
+        &[("error", format!("{e:#}").into())],
+    );
+
+    match code {
+        ReasonCode::CheckTimedOut => Status::TimedOut { code, reason },
+        _ => Status::Broken { code, reason },
     }
 }
@@ -0,0 +1,222 @@
+//! JSON Schema of the scan report (`schema` subcommand).
+//!
+//! Integrators validate `--output json` reports against it and pin the [`SCHEMA_VERSION`]
+//! recorded in every report. The schema is derived from the report types (`JsonSchema` next to
+//! their serde attributes), so it cannot drift from what is serialized; their doc comments become
+//! the descriptions. The version still has to be maintained by hand:
+//! - adding an optional field or a reason code keeps the version
+//! - removing or renaming a field, or changing its type or meaning, bumps it
+
+// -- crate imports
+use schemars::generate::SchemaSettings;
+use serde_json::{Value, json};
+
+// -- module imports
+use crate::report::Report;
+
+/// Version of the report format, recorded as `schema_version` in every report.
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// `$id` of the schema (one per [`SCHEMA_VERSION`]).
+const SCHEMA_ID: &str = concat!(env!("CARGO_PKG_HOMEPAGE"), "/schema/report.v1.json");
+
+/// The JSON Schema (draft 2020-12) of a scan report, as serialized by `--output json`.
+pub fn report_schema() -> Value {
+    let generator = SchemaSettings::draft2020_12()
+        .for_serialize()
+        .into_generator();
+    let mut schema = generator.into_root_schema_for::<Report>().to_value();
+    schema["$id"] = SCHEMA_ID.into();
+    schema["title"] = "desktop-scout scan report".into();
+    schema["properties"]["schema_version"] = json!({
+        "const": SCHEMA_VERSION,
+        "description": "Version of the report format.",
+    });
+    schema
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use chrono::{Local, NaiveDate};
+
+    use super::*;
+    use crate::{
+        flathub::FlathubStatus,
+        history::FindingHistory,
+        identity::{AppEntry, Application, Origin},
+        report::{
+            DirectorySummary, FileIdentity, Finding, FindingGroup, GroupKey, ReasonCode, Severity,
+            Status, Summary, Warning,
+        },
+    };
+
+    /// A finding with every optional field set.
+    fn finding(status: Status) -> Finding {
+        let warnings = ReasonCode::ALL
+            .iter()
+            .map(|&code| Warning {
+                code,
+                message: code.to_string(),
+                key: Some("Exec".into()),
+            })
+            .collect::<Vec<_>>();
+        Finding {
+            id: "0123456789abcdef".into(),
+            desktop_file: "/usr/share/applications/foo.desktop".into(),
+            name: Some("Foo".into()),
+            exec: Some("foo %U".into()),
+            try_exec: None,
+            path_key: None,
+            entry_type: Some("Application".into()),
+            hidden: false,
+            no_display: true,
+            status,
+            suppressed_warnings: warnings.clone(),
+            warnings,
+            user: Some("alice".into()),
+            severity: Some(Severity::Error),
+            suggested_exec: Some("/opt/foo/bin/foo %U".into()),
+            history: Some(FindingHistory {
+                first_seen: Local::now(),
+                last_seen: Local::now(),
+                broken_scans: 2,
+            }),
+            flathub: Some(FlathubStatus {
+                app_id: "org.example.Foo".into(),
+                on_flathub: false,
+            }),
+            identity: Some(FileIdentity {
+                device: 1,
+                inode: 2,
+            }),
+            same_file_as: vec!["/run/host/usr/share/applications/foo.desktop".into()],
+        }
+    }
+
+    /// One status of every kind, each with `code`.
+    fn statuses(code: ReasonCode) -> Vec<Status> {
+        vec![
+            Status::Ok {
+                resolved_executable: Some("/usr/bin/foo".into()),
+            },
+            Status::Ok {
+                resolved_executable: None,
+            },
+            Status::Broken {
+                code,
+                reason: "broken".into(),
+            },
+            Status::Skipped {
+                code,
+                reason: "skipped".into(),
+            },
+            Status::Suppressed {
+                code,
+                reason: "suppressed".into(),
+                until: NaiveDate::from_ymd_opt(2030, 1, 1),
+                comment: Some("accepted".into()),
+            },
+            Status::TimedOut {
+                code,
+                reason: "timed out".into(),
+            },
+        ]
+    }
+
+    #[test]
+    fn report_with_every_variant_validates() {
+        let path = PathBuf::from("/usr/share/applications");
+        let report = Report {
+            schema_version: SCHEMA_VERSION,
+            warnings: vec![Warning {
+                code: ReasonCode::ALL[0],
+                message: "scan warning".into(),
+                key: None,
+            }],
+            findings: ReasonCode::ALL
+                .iter()
+                .flat_map(|&code| statuses(code))
+                .map(finding)
+                .collect(),
+            summary: Some(Summary {
+                files_scanned: 3,
+                ok: 1,
+                broken: 1,
+                skipped: 1,
+                suppressed: 0,
+                timed_out: 0,
+                with_warnings: 1,
+                directories: vec![DirectorySummary {
+                    directory: path.clone(),
+                    files: 3,
+                    ok: 1,
+                    broken: 1,
+                    skipped: 1,
+                    suppressed: 0,
+                    timed_out: 0,
+                }],
+                duration_ms: Some(12),
+            }),
+            applications: vec![Application {
+                id: "org.example.Foo".into(),
+                name: None,
+                entries: [
+                    Origin::Native,
+                    Origin::Flatpak,
+                    Origin::Snap,
+                    Origin::Autostart,
+                ]
+                .into_iter()
+                .zip(statuses(ReasonCode::ALL[0]))
+                .map(|(origin, status)| AppEntry {
+                    desktop_file: path.join("foo.desktop"),
+                    origin,
+                    status,
+                    warnings: 0,
+                })
+                .collect(),
+            }],
+            groups: [
+                GroupKey::Directory { path: path.clone() },
+                GroupKey::UserData { path: path.clone() },
+                GroupKey::Desktop { path: path.clone() },
+                GroupKey::SystemData,
+                GroupKey::Flatpak,
+                GroupKey::Snap,
+                GroupKey::Extra { path: path.clone() },
+                GroupKey::Other,
+            ]
+            .into_iter()
+            .map(|key| FindingGroup {
+                key,
+                desktop_files: vec![path.join("foo.desktop")],
+            })
+            .collect(),
+        };
+
+        let validator = jsonschema::validator_for(&report_schema()).unwrap();
+        let instance = serde_json::to_value(&report).unwrap();
+        let errors = validator
+            .iter_errors(&instance)
+            .map(|e| format!("{} at {}", e, e.instance_path()))
+            .collect::<Vec<_>>();
+        assert!(errors.is_empty(), "{errors:#?}");
+    }
+
+    #[test]
+    fn schema_rejects_unknown_codes_and_versions() {
+        let validator = jsonschema::validator_for(&report_schema()).unwrap();
+        let report = |version: u32, code: &str| {
+            json!({
+                "schema_version": version,
+                "warnings": [{ "code": code, "message": "m" }],
+                "findings": [],
+            })
+        };
+        assert!(validator.is_valid(&report(SCHEMA_VERSION, "EXEC_NOT_FOUND")));
+        assert!(!validator.is_valid(&report(SCHEMA_VERSION, "NO_SUCH_CODE")));
+        assert!(!validator.is_valid(&report(SCHEMA_VERSION + 1, "EXEC_NOT_FOUND")));
+    }
+}
@@ -0,0 +1,139 @@
+//! Session-environment checks for `Exec=` lines.
+//!
+//! Some launchers hard-code paths below `$XDG_RUNTIME_DIR` (e.g. `/run/user/1000/...`) or name
+//! well-known session sockets (`WAYLAND_DISPLAY=wayland-1`, `pipewire-0`). Such entries fail
+//! when the socket does not exist in the current session, even though their binary is fine, so
+//! missing references are reported as `SESSION_SOCKET_MISSING` warnings rather than as a
+//! missing executable.
+
+// -- std imports
+use std::{
+    env,
+    path::{Path, PathBuf},
+};
+
+// -- crate imports
+use tokio::fs;
+
+// -- module imports
+use crate::{
+    i18n::tr,
+    report::{ReasonCode, Warning},
+};
+
+/// Environment variables whose value names a socket relative to `$XDG_RUNTIME_DIR`.
+const SOCKET_ENV_VARS: &[&str] = &["WAYLAND_DISPLAY", "PIPEWIRE_REMOTE"];
+
+/// Prefixes of well-known socket names below `$XDG_RUNTIME_DIR`, followed by a number.
+const SOCKET_NAME_PREFIXES: &[&str] = &["wayland-", "pipewire-"];
+
+/// Spellings of the runtime directory variable inside arguments.
+const RUNTIME_DIR_VARS: &[&str] = &["${XDG_RUNTIME_DIR}", "$XDG_RUNTIME_DIR"];
+
+/// Check the session paths referenced by `exec_line` against the current session.
+///
+/// Returns one warning per reference that does not exist (or cannot be located because
+/// `XDG_RUNTIME_DIR` is unset). Lines that cannot be shell-split yield no warnings; the
+/// executable checks report those.
+pub async fn check_exec(exec_line: &str) -> Vec<Warning> {
+    let Some(tokens) = shlex::split(exec_line) else {
+        return Vec::new();
+    };
+    let runtime_dir = env::var_os("XDG_RUNTIME_DIR")
+        .filter(|d| !d.is_empty())
+        .map(PathBuf::from);
+
+    let mut warnings = Vec::new();
+    for reference in tokens.iter().filter_map(|t| session_reference(t)) {
+        let message = match (&reference, &runtime_dir) {
+            (Reference::Absolute(path), _) => (!exists(path).await).then(|| {
+                tr(
+                    "session-path-missing",
+                    &[("path", path.display().to_string().into())],
+                )
+            }),
+            (Reference::InRuntimeDir(rel), Some(dir)) => {
+                let path = dir.join(rel);
+                (!exists(&path).await).then(|| {
+                    tr(
+                        "session-runtime-path-missing",
+                        &[
+                            ("relative", rel.as_str().into()),
+                            ("path", path.display().to_string().into()),
+                        ],
+                    )
+                })
+            }
+            (Reference::InRuntimeDir(rel), None) => Some(tr(
+                "session-runtime-dir-unset",
+                &[("relative", rel.as_str().into())],
+            )),
+        };
+
+        if let Some(message) = message {
+            warnings.push(Warning {
+                code: ReasonCode::SessionSocketMissing,
+                message,
+                key: Some("Exec".into()),
+            });
+        }
+    }
+    warnings
+}
+
+/// A session path referenced by an `Exec=` argument.
+#[derive(Debug)]
+enum Reference {
+    /// An absolute path (usually below `/run/user/<uid>/`).
+    Absolute(PathBuf),
+    /// A path relative to `$XDG_RUNTIME_DIR`.
+    InRuntimeDir(String),
+}
+
+/// Extract the session path referenced by a single `Exec=` argument, if any.
+///
+/// Recognizes `$XDG_RUNTIME_DIR/...` (also after `--opt=`), absolute `/run/user/<uid>/...`
+/// paths, `WAYLAND_DISPLAY=`/`PIPEWIRE_REMOTE=` assignments and bare well-known socket names.
+fn session_reference(token: &str) -> Option<Reference> {
+    for var in RUNTIME_DIR_VARS {
+        if let Some(idx) = token.find(var) {
+            let rel = token[idx + var.len()..].trim_start_matches('/');
+            return (!rel.is_empty()).then(|| Reference::InRuntimeDir(rel.to_string()));
+        }
+    }
+
+    if let Some(idx) = token.find("/run/user/") {
+        let path = &token[idx..];
+        let uid_and_rest = &path["/run/user/".len()..];
+        let uid_len = uid_and_rest
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(uid_and_rest.len());
+        return (uid_len > 0).then(|| Reference::Absolute(PathBuf::from(path)));
+    }
+
+    let value = match token.split_once('=') {
+        Some((name, value)) if SOCKET_ENV_VARS.contains(&name) => {
+            return match value {
+                "" => None,
+                v if v.starts_with('/') => Some(Reference::Absolute(PathBuf::from(v))),
+                v => Some(Reference::InRuntimeDir(v.to_string())),
+            };
+        }
+        Some((_, value)) => value,
+        None => token,
+    };
+    is_socket_name(value).then(|| Reference::InRuntimeDir(value.to_string()))
+}
+
+/// Whether `name` is a well-known socket name such as `wayland-0` or `pipewire-0`.
+fn is_socket_name(name: &str) -> bool {
+    SOCKET_NAME_PREFIXES.iter().any(|p| {
+        name.strip_prefix(p)
+            .is_some_and(|n| !n.is_empty() && n.chars().all(|c| c.is_ascii_digit()))
+    })
+}
+
+/// Whether a socket, file or directory exists at `path` (following symlinks).
+async fn exists(path: &Path) -> bool {
+    fs::metadata(path).await.is_ok()
+}
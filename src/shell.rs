@@ -0,0 +1,197 @@
+//! Shell builtin, alias and function lookup.
+//!
+//! Launchers execute `Exec=` directly without a shell, so a command that only exists as a builtin,
+//! or as an alias or function in a shell rc file (`--check-shell-aliases`), works in a terminal
+//! but never from the application menu. This module recognizes such names to turn a generic "not
+//! found" into an actionable reason.
+
+// -- std imports
+use std::{
+    collections::HashMap,
+    env, fmt,
+    path::{Path, PathBuf},
+};
+
+// -- crate imports
+use tokio::{fs, sync::OnceCell};
+use tracing::debug;
+
+/// Shell startup files checked for definitions, relative to `$HOME`.
+const USER_RC_FILES: &[&str] = &[
+    ".bashrc",
+    ".bash_aliases",
+    ".bash_profile",
+    ".profile",
+    ".zshrc",
+    ".zshenv",
+    ".zprofile",
+    ".config/fish/config.fish",
+];
+
+/// System-wide shell startup files checked for definitions.
+const SYSTEM_RC_FILES: &[&str] = &["/etc/bash.bashrc", "/etc/zsh/zshrc", "/etc/profile"];
+
+/// Directory of autoloaded fish functions (`<name>.fish`), relative to `$HOME`.
+const FISH_FUNCTIONS_DIR: &str = ".config/fish/functions";
+
+/// Builtins of POSIX sh, bash and zsh. Some (e.g. `test`, `[`, `echo`, `time`) usually also
+/// exist as binaries, but only the shell guarantees them.
+const BUILTINS: &[&str] = &[
+    ".", ":", "[", "[[", "alias", "bg", "bind", "break", "builtin", "cd", "command", "continue",
+    "declare", "dirs", "disown", "echo", "eval", "exec", "exit", "export", "false", "fc", "fg",
+    "getopts", "hash", "history", "jobs", "kill", "let", "local", "popd", "printf", "pushd", "pwd",
+    "read", "readonly", "return", "set", "shift", "shopt", "source", "test", "time", "times",
+    "trap", "true", "type", "typeset", "ulimit", "umask", "unalias", "unset", "wait",
+];
+
+/// Definitions parsed from the rc files, loaded once per process.
+static DEFINITIONS: OnceCell<HashMap<String, ShellDefinition>> = OnceCell::const_new();
+
+/// Kind of shell definition.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DefinitionKind {
+    /// `alias name=...`
+    Alias,
+    /// `name() { ... }`, `function name`, or a fish function file.
+    Function,
+}
+
+impl fmt::Display for DefinitionKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DefinitionKind::Alias => f.write_str("alias"),
+            DefinitionKind::Function => f.write_str("function"),
+        }
+    }
+}
+
+/// A shell alias or function and where it is defined.
+#[derive(Debug, Clone)]
+pub struct ShellDefinition {
+    /// Whether the name is an alias or a function.
+    pub kind: DefinitionKind,
+
+    /// File the definition was found in.
+    pub source: PathBuf,
+}
+
+/// Look up `name` among the aliases and functions of the common shell rc files.
+pub async fn find_definition(name: &str) -> Option<ShellDefinition> {
+    DEFINITIONS
+        .get_or_init(load_definitions)
+        .await
+        .get(name)
+        .cloned()
+}
+
+/// Whether `name` is a shell builtin (see [`BUILTINS`]).
+pub fn is_builtin(name: &str) -> bool {
+    BUILTINS.contains(&name)
+}
+
+/// The rc files definitions are read from, in lookup order.
+pub fn rc_files() -> Vec<PathBuf> {
+    let mut files = env::var_os("HOME")
+        .map(PathBuf::from)
+        .iter()
+        .flat_map(|h| USER_RC_FILES.iter().map(move |f| h.join(f)))
+        .collect::<Vec<_>>();
+    files.extend(SYSTEM_RC_FILES.iter().map(PathBuf::from));
+    files
+}
+
+/// The directory autoloaded fish functions are read from, if `$HOME` is set.
+pub fn fish_functions_dir() -> Option<PathBuf> {
+    env::var_os("HOME").map(|h| PathBuf::from(h).join(FISH_FUNCTIONS_DIR))
+}
+
+/// Parse all known rc files; the first definition of a name wins.
+async fn load_definitions() -> HashMap<String, ShellDefinition> {
+    let mut defs = HashMap::new();
+    for file in &rc_files() {
+        let Ok(content) = fs::read_to_string(file).await else {
+            continue;
+        };
+        for (name, kind) in parse_definitions(&content) {
+            defs.entry(name).or_insert_with(|| ShellDefinition {
+                kind,
+                source: file.clone(),
+            });
+        }
+    }
+
+    if let Some(dir) = fish_functions_dir() {
+        collect_fish_functions(&dir, &mut defs).await;
+    }
+
+    debug!(
+        count = defs.len(),
+        "Loaded shell alias/function definitions"
+    );
+    defs
+}
+
+/// Add autoloaded fish functions (one `<name>.fish` file per function).
+async fn collect_fish_functions(dir: &Path, defs: &mut HashMap<String, ShellDefinition>) {
+    let Ok(mut rd) = fs::read_dir(dir).await else {
+        return;
+    };
+    while let Ok(Some(entry)) = rd.next_entry().await {
+        let path = entry.path();
+        if path.extension().is_some_and(|e| e == "fish")
+            && let Some(name) = path.file_stem().and_then(|s| s.to_str())
+        {
+            defs.entry(name.to_string())
+                .or_insert_with(|| ShellDefinition {
+                    kind: DefinitionKind::Function,
+                    source: path.clone(),
+                });
+        }
+    }
+}
+
+/// Extract alias and function names from shell source.
+///
+/// Recognizes `alias name=...` (sh/bash/zsh, also fish), `alias name ...` (fish),
+/// `function name` (bash/zsh/fish) and `name() {` (POSIX). This is line-based and intentionally
+/// simple; it does not evaluate conditionals.
+fn parse_definitions(content: &str) -> Vec<(String, DefinitionKind)> {
+    let mut out = Vec::new();
+
+    for line in content.lines() {
+        let line = line.trim();
+        if line.starts_with('#') {
+            continue;
+        }
+
+        if let Some(rest) = line.strip_prefix("alias ") {
+            let words = rest.split_whitespace().skip_while(|w| w.starts_with('-'));
+            for (i, word) in words.enumerate() {
+                match word.split_once('=') {
+                    Some((name, _)) => out.push((name.to_string(), DefinitionKind::Alias)),
+                    // fish: `alias name 'command'`
+                    None if i == 0 => out.push((word.to_string(), DefinitionKind::Alias)),
+                    None => {}
+                }
+                if !word.contains('=') {
+                    break;
+                }
+            }
+        } else if let Some(rest) = line.strip_prefix("function ") {
+            if let Some(name) = rest
+                .split(|c: char| c.is_whitespace() || c == '(' || c == '{')
+                .next()
+                .filter(|n| !n.is_empty())
+            {
+                out.push((name.to_string(), DefinitionKind::Function));
+            }
+        } else if let Some((name, _)) = line.split_once("()")
+            && !name.trim().is_empty()
+            && !name.trim().contains(char::is_whitespace)
+        {
+            out.push((name.trim().to_string(), DefinitionKind::Function));
+        }
+    }
+
+    out
+}
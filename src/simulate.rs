@@ -0,0 +1,280 @@
+//! Dry-run simulation of PATH and package removals (`simulate` subcommand).
+//!
+//! A [`Simulation`] is threaded into the executable checks (see `check::CheckContext`) and makes
+//! the files it hides look missing. Scanning once without and once with it and comparing the
+//! results predicts which launchers an uninstall or PATH change would break. For directories
+//! scheduled for removal (`--without-dir`, e.g. a vendor tree in `/opt`), [`dependents`] also
+//! lists every launcher whose executable or script lives there, even if it would still resolve
+//! elsewhere afterwards.
+
+// -- std imports
+use std::{
+    collections::HashSet,
+    path::{Path, PathBuf},
+    process::Stdio,
+};
+
+// -- crate imports
+use anyhow::{Result, bail};
+use serde::Serialize;
+use tokio::{fs, process::Command};
+use tracing::debug;
+
+// -- module imports
+use crate::{
+    check, desktop, packagekit,
+    report::{Finding, Report, Status},
+};
+
+/// Package manager CLIs queried for file lists when PackageKit cannot answer, in order: program
+/// and arguments before the name.
+const PACKAGE_FILE_QUERIES: &[(&str, &[&str])] = &[
+    ("dpkg-query", &["-L"]),
+    ("rpm", &["-ql"]),
+    ("pacman", &["-Qlq"]),
+];
+
+/// Files and directories treated as removed.
+#[derive(Debug, Default)]
+pub struct Simulation {
+    /// Removed directory trees (`--remove-path`, `--without-dir`).
+    pub removed_dirs: Vec<PathBuf>,
+
+    /// Removed individual files (from `--without-package`).
+    pub removed_files: HashSet<PathBuf>,
+}
+
+impl Simulation {
+    /// Build a simulation from removed directories and package names.
+    ///
+    /// # Errors
+    /// - [`anyhow::Error`] if a package is not installed or no supported package manager knows it.
+    pub async fn new(
+        remove_path: &[PathBuf],
+        without_dir: &[PathBuf],
+        without_package: &[String],
+    ) -> Result<Self> {
+        let mut sim = Simulation {
+            removed_dirs: [remove_path, without_dir].concat(),
+            removed_files: HashSet::new(),
+        };
+
+        for package in without_package {
+            let files = package_files(package).await?;
+            debug!(package, files = files.len(), "Simulating package removal");
+            for f in files {
+                // Package lists include shared directories like `/usr/bin`; only files vanish.
+                if fs::metadata(&f).await.is_ok_and(|m| m.is_dir()) {
+                    continue;
+                }
+                if let Ok(c) = fs::canonicalize(&f).await {
+                    sim.removed_files.insert(c);
+                }
+                sim.removed_files.insert(f);
+            }
+        }
+
+        Ok(sim)
+    }
+
+    /// Whether `path` is removed under this simulation.
+    pub fn hides(&self, path: &Path) -> bool {
+        self.removed_files.contains(path) || self.removed_dirs.iter().any(|d| path.starts_with(d))
+    }
+}
+
+/// List the files owned by an installed package, asking PackageKit and then the first package
+/// manager CLI that knows it.
+async fn package_files(package: &str) -> Result<Vec<PathBuf>> {
+    match packagekit::package_files(package).await {
+        Ok(Some(files)) => return Ok(files),
+        Ok(None) => debug!(package, "PackageKit does not know the package"),
+        Err(e) => debug!(package, "PackageKit query failed: {e:#}"),
+    }
+
+    for (program, args) in PACKAGE_FILE_QUERIES {
+        let Ok(output) = Command::new(program)
+            .args(*args)
+            .arg(package)
+            .stdin(Stdio::null())
+            .stderr(Stdio::null())
+            .output()
+            .await
+        else {
+            continue;
+        };
+        if !output.status.success() {
+            continue;
+        }
+
+        return Ok(String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .map(str::trim)
+            .filter(|l| l.starts_with('/'))
+            .map(PathBuf::from)
+            .collect());
+    }
+
+    bail!("Package {package} is not installed (or no supported package manager was found)")
+}
+
+/// Launchers that would break under a simulation.
+#[derive(Debug, Serialize)]
+pub struct SimulationResult<'a> {
+    /// Entries that work today but are broken under the simulation, sorted by path.
+    pub would_break: Vec<&'a Finding>,
+
+    /// Entries whose executable or script lives below a `--without-dir` directory.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub dependents: Vec<Dependent<'a>>,
+}
+
+/// A launcher that needs a file below a directory scheduled for removal.
+#[derive(Debug, Serialize)]
+pub struct Dependent<'a> {
+    /// The `.desktop` file.
+    pub desktop_file: &'a Path,
+
+    /// Value of its `Name=` key (if present).
+    pub name: Option<&'a str>,
+
+    /// The file below the directory the launcher needs.
+    pub needs: PathBuf,
+
+    /// What `needs` is to the launcher.
+    pub role: DependencyRole,
+}
+
+/// How a launcher uses a file (see [`Dependent::role`]).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DependencyRole {
+    /// The resolved `Exec=`/`TryExec=` executable.
+    Executable,
+    /// The script passed to an interpreter (`python3 /opt/foo/app.py`).
+    Script,
+}
+
+/// Entries of `current` whose resolved executable or interpreter script lives below one of
+/// `dirs`, sorted by path.
+///
+/// Paths are compared both as written and with symlinks resolved, so `/usr/bin/foo ->
+/// /opt/vendor/bin/foo` counts as living below `/opt/vendor`.
+pub async fn dependents<'a>(current: &'a Report, dirs: &[PathBuf]) -> Vec<Dependent<'a>> {
+    if dirs.is_empty() {
+        return Vec::new();
+    }
+    let mut roots = dirs.to_vec();
+    for d in dirs {
+        if let Ok(c) = fs::canonicalize(d).await {
+            roots.push(c);
+        }
+    }
+
+    let mut out = Vec::new();
+    for f in &current.findings {
+        let Status::Ok {
+            resolved_executable: Some(exe),
+        } = &f.status
+        else {
+            continue;
+        };
+        let tokens = f.exec.as_deref().and_then(shlex::split).unwrap_or_default();
+        let (_, command) = desktop::unwrap_exec_prefixes(&tokens);
+        let script = check::script_argument(exe, command, f.path_key.as_deref());
+
+        let needs = [
+            (Some(exe.clone()), DependencyRole::Executable),
+            (script, DependencyRole::Script),
+        ];
+        for (path, role) in needs {
+            let Some(path) = path else {
+                continue;
+            };
+            if below_any(&path, &roots).await {
+                out.push(Dependent {
+                    desktop_file: &f.desktop_file,
+                    name: f.name.as_deref(),
+                    needs: path,
+                    role,
+                });
+            }
+        }
+    }
+    out.sort_by(|a, b| a.desktop_file.cmp(b.desktop_file));
+    out
+}
+
+/// Whether `path`, as written or with symlinks resolved, lies below one of `roots`.
+async fn below_any(path: &Path, roots: &[PathBuf]) -> bool {
+    if roots.iter().any(|r| path.starts_with(r)) {
+        return true;
+    }
+    fs::canonicalize(path)
+        .await
+        .is_ok_and(|c| roots.iter().any(|r| c.starts_with(r)))
+}
+
+/// Compare a regular scan with a simulated one.
+///
+/// Entries are matched by `.desktop` file path.
+pub fn compare<'a>(current: &Report, simulated: &'a Report) -> SimulationResult<'a> {
+    let broken_now = current
+        .findings
+        .iter()
+        .filter(|f| f.is_broken())
+        .map(|f| f.desktop_file.as_path())
+        .collect::<HashSet<_>>();
+
+    let mut would_break = simulated
+        .findings
+        .iter()
+        .filter(|f| f.is_broken() && !broken_now.contains(f.desktop_file.as_path()))
+        .collect::<Vec<_>>();
+    would_break.sort_by(|a, b| a.desktop_file.cmp(&b.desktop_file));
+
+    SimulationResult {
+        would_break,
+        dependents: Vec::new(),
+    }
+}
+
+/// Print a human-readable simulation result.
+pub fn print_text(result: &SimulationResult<'_>) {
+    if !result.dependents.is_empty() {
+        println!(
+            "Launchers depending on the removed directories ({}):\n",
+            result.dependents.len()
+        );
+        for d in &result.dependents {
+            println!("- {}", d.desktop_file.display());
+            if let Some(name) = d.name {
+                println!("  Name: {name}");
+            }
+            match d.role {
+                DependencyRole::Executable => println!("  Executable: {}", d.needs.display()),
+                DependencyRole::Script => println!("  Script: {}", d.needs.display()),
+            }
+        }
+        println!();
+    }
+
+    if result.would_break.is_empty() {
+        println!("No launchers would break.");
+        return;
+    }
+
+    println!(
+        "Launchers that would break ({}):\n",
+        result.would_break.len()
+    );
+    for f in &result.would_break {
+        println!("- {}", f.desktop_file.display());
+        if let Some(name) = &f.name {
+            println!("  Name: {name}");
+        }
+        if let Status::Broken { code, reason } = &f.status {
+            println!("  Reason [{code}]: {reason}");
+        }
+    }
+}
@@ -0,0 +1,202 @@
+//! Suppression files for accepted findings (`--suppressions`).
+//!
+//! Fleets often carry breakage that is known and harmless (a vendor launcher for a tool that is
+//! only installed on some machines). A suppressions file lists such entries:
+//!
+//! ```toml
+//! [[suppress]]
+//! path = "/usr/share/applications/vendor-tool.desktop"
+//! rule = "EXEC_NOT_FOUND"   # optional: every problem of the file if absent
+//! until = 2026-12-31        # optional: last day the suppression applies
+//! comment = "Only installed on build hosts"
+//! ```
+//!
+//! A broken entry matching a suppression is reported as `suppressed` instead of `broken`, and
+//! matching warnings move to [`Finding::suppressed_warnings`]. Expired suppressions no longer
+//! apply and are reported with a `SUPPRESSION_EXPIRED` warning, so stale entries get reviewed.
+
+// -- std imports
+use std::path::{Path, PathBuf};
+
+// -- crate imports
+use anyhow::{Context, Result, bail};
+use chrono::NaiveDate;
+use serde::Deserialize;
+use tokio::fs;
+
+// -- module imports
+use crate::{
+    i18n::tr,
+    report::{Finding, ReasonCode, Severity, Status, Warning},
+};
+
+/// On-disk suppressions format.
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct SuppressionFile {
+    /// The accepted findings.
+    #[serde(default)]
+    suppress: Vec<RawSuppression>,
+}
+
+/// A suppression as written in the file (`until` is a TOML date).
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RawSuppression {
+    /// Path of the `.desktop` file.
+    path: PathBuf,
+
+    /// Code of the accepted problem.
+    #[serde(default)]
+    rule: Option<ReasonCode>,
+
+    /// Last day the suppression applies.
+    #[serde(default)]
+    until: Option<toml::value::Datetime>,
+
+    /// Why the problem is accepted.
+    #[serde(default)]
+    comment: Option<String>,
+}
+
+/// A single accepted finding.
+#[derive(Debug, Clone)]
+pub struct Suppression {
+    /// Path of the `.desktop` file.
+    pub path: PathBuf,
+
+    /// Code of the accepted problem (every problem of the file if `None`).
+    pub rule: Option<ReasonCode>,
+
+    /// Last day the suppression applies (`None` if it does not expire).
+    pub until: Option<NaiveDate>,
+
+    /// Why the problem is accepted.
+    pub comment: Option<String>,
+}
+
+impl Suppression {
+    /// Whether this suppression covers a problem with `code` in the file at `path`.
+    fn covers(&self, path: &Path, code: ReasonCode) -> bool {
+        self.path == path && self.rule.is_none_or(|r| r == code)
+    }
+}
+
+/// The suppressions in effect for a scan.
+#[derive(Debug, Default)]
+pub struct Suppressions {
+    /// Suppressions that have not expired.
+    active: Vec<Suppression>,
+
+    /// Suppressions past their expiry date.
+    expired: Vec<Suppression>,
+}
+
+impl Suppressions {
+    /// Load the suppressions file at `path`, splitting its entries into active and expired ones
+    /// as of `today`.
+    ///
+    /// # Errors
+    /// - [`anyhow::Error`] if the file cannot be read, is not valid TOML, or contains unknown
+    ///   keys, unknown reason codes or an `until` value that is not a date.
+    pub async fn load(path: &Path, today: NaiveDate) -> Result<Self> {
+        let text = fs::read_to_string(path)
+            .await
+            .with_context(|| format!("Failed to read suppressions {}", path.display()))?;
+        let file: SuppressionFile = toml::from_str(&text)
+            .with_context(|| format!("{} is not a valid suppressions file", path.display()))?;
+
+        let mut suppressions = Suppressions::default();
+        for raw in file.suppress {
+            let until = raw
+                .until
+                .map(|d| to_date(&d))
+                .transpose()
+                .with_context(|| format!("Invalid suppression in {}", path.display()))?;
+            let suppression = Suppression {
+                path: raw.path,
+                rule: raw.rule,
+                until,
+                comment: raw.comment,
+            };
+            if until.is_some_and(|u| u < today) {
+                suppressions.expired.push(suppression);
+            } else {
+                suppressions.active.push(suppression);
+            }
+        }
+        Ok(suppressions)
+    }
+
+    /// A `SUPPRESSION_EXPIRED` scan-level warning per expired suppression.
+    pub fn expired_warnings(&self) -> Vec<Warning> {
+        self.expired
+            .iter()
+            .map(|s| {
+                let mut args = vec![
+                    ("path", s.path.display().to_string().into()),
+                    (
+                        "until",
+                        s.until.map(|u| u.to_string()).unwrap_or_default().into(),
+                    ),
+                ];
+                let id = match s.rule {
+                    Some(rule) => {
+                        args.push(("rule", rule.as_str().into()));
+                        "suppression-rule-expired"
+                    }
+                    None => "suppression-expired",
+                };
+                Warning {
+                    code: ReasonCode::SuppressionExpired,
+                    message: tr(id, &args),
+                    key: None,
+                }
+            })
+            .collect()
+    }
+
+    /// Mark the problems of `finding` that an active suppression covers: a broken status becomes
+    /// [`Status::Suppressed`], covered warnings move to [`Finding::suppressed_warnings`].
+    pub fn apply(&self, finding: &mut Finding) {
+        if self.active.is_empty() {
+            return;
+        }
+        if let Status::Broken { code, reason } = &finding.status
+            && let Some(s) = self
+                .active
+                .iter()
+                .find(|s| s.covers(&finding.desktop_file, *code))
+        {
+            finding.status = Status::Suppressed {
+                code: *code,
+                reason: reason.clone(),
+                until: s.until,
+                comment: s.comment.clone(),
+            };
+        }
+
+        let (suppressed, kept) = std::mem::take(&mut finding.warnings)
+            .into_iter()
+            .partition::<Vec<_>, _>(|w| {
+                self.active
+                    .iter()
+                    .any(|s| s.covers(&finding.desktop_file, w.code))
+            });
+        finding.warnings = kept;
+        finding.suppressed_warnings.extend(suppressed);
+        finding.severity = Severity::of(&finding.status, &finding.warnings);
+    }
+}
+
+/// The date of a TOML `until` value (a local date like `2026-12-31`).
+///
+/// # Errors
+/// - [`anyhow::Error`] if the value has a time or offset, or is not a valid date.
+fn to_date(value: &toml::value::Datetime) -> Result<NaiveDate> {
+    let (Some(date), None, None) = (value.date, value.time, value.offset) else {
+        bail!("`until = {value}` is not a date (write e.g. `until = 2026-12-31`)");
+    };
+    NaiveDate::from_ymd_opt(date.year.into(), date.month.into(), date.day.into())
+        .with_context(|| format!("`until = {value}` is not a valid date"))
+}
@@ -0,0 +1,76 @@
+//! Scanning an offline system image (`--root`).
+//!
+//! Image builders and chroot QA validate the `.desktop` files of a mounted root filesystem
+//! rather than of the running system. Paths inside the image (`Exec=/usr/bin/foo`, the default
+//! XDG directories, `PATH`) are resolved below the root instead, and symlinks in the image are
+//! followed inside it: an absolute link such as `/usr/bin/editor -> /etc/alternatives/editor`
+//! points into the image, not at the host.
+
+// -- std imports
+use std::{
+    ffi::OsString,
+    path::{Component, Path, PathBuf},
+};
+
+// -- crate imports
+use tokio::fs;
+
+/// `PATH` used to resolve bare commands inside an image (the host's `PATH` describes the host).
+pub const IMAGE_PATH: &str = "/usr/local/sbin:/usr/local/bin:/usr/sbin:/usr/bin:/sbin:/bin";
+
+/// `XDG_DATA_DIRS` of an image (the spec's default; the host's value describes the host).
+pub const IMAGE_DATA_DIRS: &[&str] = &["/usr/local/share", "/usr/share"];
+
+/// Symlinks followed while resolving a single path before giving up (as `MAXSYMLINKS`).
+const MAX_SYMLINKS: usize = 40;
+
+/// The host path of the absolute image path `path`, without resolving symlinks.
+pub fn rebase(root: &Path, path: &Path) -> PathBuf {
+    root.join(path.strip_prefix("/").unwrap_or(path))
+}
+
+/// The host path of the absolute image path `path`, resolving symlinks inside the image.
+///
+/// `..` never leaves the root, and absolute link targets start over at the root. Components
+/// that do not exist are appended unresolved, so checking the result still fails for them.
+pub async fn resolve(root: &Path, path: &Path) -> PathBuf {
+    let mut resolved = root.to_path_buf();
+    let mut pending = components(path);
+    let mut links = 0;
+
+    while let Some(name) = pending.pop() {
+        if name == ".." {
+            if resolved != root {
+                resolved.pop();
+            }
+            continue;
+        }
+        let candidate = resolved.join(&name);
+        let target = match fs::read_link(&candidate).await {
+            Ok(target) if links < MAX_SYMLINKS => target,
+            _ => {
+                resolved = candidate;
+                continue;
+            }
+        };
+
+        links += 1;
+        if target.is_absolute() {
+            resolved = root.to_path_buf();
+        }
+        pending.extend(components(&target));
+    }
+    resolved
+}
+
+/// The normal and `..` components of `path`, last first (a stack of names to resolve).
+fn components(path: &Path) -> Vec<OsString> {
+    path.components()
+        .rev()
+        .filter_map(|c| match c {
+            Component::Normal(name) => Some(name.to_os_string()),
+            Component::ParentDir => Some("..".into()),
+            Component::RootDir | Component::CurDir | Component::Prefix(_) => None,
+        })
+        .collect()
+}
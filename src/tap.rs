@@ -0,0 +1,74 @@
+//! Test Anything Protocol output of scans (`--output tap`).
+//!
+//! Every scanned `.desktop` file is a test point, in report order (`--sort`), followed by a YAML
+//! diagnostic block with its finding ID: broken entries are `not ok` with their code and reason in
+//! that block, skipped entries carry a `# SKIP` directive, and lint warnings and scan-level
+//! warnings are `#` comments. The stream follows TAP version 13, as understood by `prove` and
+//! automake's TAP driver.
+
+// -- std imports
+use std::fmt::Write as _;
+
+// -- crate imports
+use serde_json::Value;
+
+// -- module imports
+use crate::report::{Finding, Report, Status};
+
+/// Render `report` as a TAP stream.
+pub fn render(report: &Report) -> String {
+    let mut out = format!("TAP version 13\n1..{}\n", report.findings.len());
+    for w in &report.warnings {
+        let _ = writeln!(out, "# {}: {}", w.code, comment(&w.message));
+    }
+    for (idx, f) in report.findings.iter().enumerate() {
+        test_point(idx + 1, f, &mut out);
+    }
+    out
+}
+
+/// Append test point `number` for `f`, with a YAML diagnostic block carrying its finding ID.
+fn test_point(number: usize, f: &Finding, out: &mut String) {
+    let description = comment(&f.desktop_file.display().to_string());
+    let mut diagnostic = vec![("id", f.id.clone())];
+    match &f.status {
+        Status::Ok { .. } => {
+            let _ = writeln!(out, "ok {number} - {description}");
+        }
+        Status::Skipped { code, reason } => {
+            let _ = writeln!(
+                out,
+                "ok {number} - {description} # SKIP {code}: {}",
+                comment(reason)
+            );
+        }
+        Status::Broken { code, reason } | Status::TimedOut { code, reason } => {
+            let _ = writeln!(out, "not ok {number} - {description}");
+            diagnostic.push(("code", code.to_string()));
+            diagnostic.push(("message", reason.clone()));
+            if let Some(exec) = &f.exec {
+                diagnostic.push(("exec", exec.clone()));
+            }
+        }
+        Status::Suppressed { code, reason, .. } => {
+            let _ = writeln!(
+                out,
+                "not ok {number} - {description} # TODO suppressed {code}: {}",
+                comment(reason)
+            );
+        }
+    }
+    out.push_str("  ---\n");
+    for (key, value) in diagnostic {
+        let _ = writeln!(out, "  {key}: {}", Value::String(value));
+    }
+    out.push_str("  ...\n");
+    for w in &f.warnings {
+        let _ = writeln!(out, "# {}: {}", w.code, comment(&w.message));
+    }
+}
+
+/// `s` on a single line without `#`, so it cannot end a description early or break the stream.
+fn comment(s: &str) -> String {
+    s.replace(['\n', '\r'], " ").replace('#', "\\#")
+}
@@ -0,0 +1,213 @@
+//! User-provided report templates (`--template FILE`).
+//!
+//! Templates are [Tera](https://keats.github.io/tera/docs/) templates rendered over the JSON form
+//! of the scan report (the same data as `--output json`), so bespoke formats (wiki markup, ticket
+//! bodies) need no new built-in output. The report's top-level fields are the template variables
+//! (`findings`, `warnings`, `summary`, ...); `summary`, `applications` and `groups` are only
+//! defined when the report has them.
+//!
+//! Other files next to the template with the same extension are loaded as well, so a template
+//! can `{% include %}`, `{% import %}` or `{% extends %}` them by file name. As in Tera, templates
+//! whose name ends in `.html`, `.htm` or `.xml` are autoescaped.
+
+// -- std imports
+use std::path::Path;
+
+// -- crate imports
+use anyhow::{Context, Result};
+use tera::Tera;
+use tokio::fs;
+
+// -- module imports
+use crate::report::Report;
+
+/// Render `report` with the template file at `path`.
+///
+/// # Errors
+/// - [`anyhow::Error`] if the template or one of its siblings cannot be read or parsed, or
+///   rendering fails (e.g. an undefined variable is printed).
+pub async fn render_file(path: &Path, report: &Report) -> Result<String> {
+    let name = path
+        .file_name()
+        .with_context(|| format!("{} is not a file path", path.display()))?
+        .to_string_lossy()
+        .into_owned();
+    let source = fs::read_to_string(path)
+        .await
+        .with_context(|| format!("Failed to read template {}", path.display()))?;
+
+    let mut templates = sibling_templates(path).await?;
+    templates.push((name.clone(), source));
+    render(templates, &name, report).with_context(|| format!("Template {}", path.display()))
+}
+
+/// Render the template `name` of `templates` (name and source pairs) with `report`.
+///
+/// # Errors
+/// - [`anyhow::Error`] if a template is invalid or rendering fails.
+pub fn render(templates: Vec<(String, String)>, name: &str, report: &Report) -> Result<String> {
+    let mut tera = Tera::default();
+    tera.add_raw_templates(templates)
+        .context("Invalid template")?;
+    let context = tera::Context::from_serialize(report)?;
+    tera.render(name, &context)
+        .context("Failed to render template")
+}
+
+/// Files next to `path` with its extension, as name and source pairs.
+async fn sibling_templates(path: &Path) -> Result<Vec<(String, String)>> {
+    let (Some(dir), Some(ext)) = (path.parent(), path.extension()) else {
+        return Ok(Vec::new());
+    };
+    let dir = if dir.as_os_str().is_empty() {
+        Path::new(".")
+    } else {
+        dir
+    };
+
+    let mut templates = Vec::new();
+    let mut entries = fs::read_dir(dir)
+        .await
+        .with_context(|| format!("Failed to read {}", dir.display()))?;
+    while let Some(entry) = entries.next_entry().await? {
+        let sibling = entry.path();
+        if sibling.extension() != Some(ext)
+            || sibling.file_name() == path.file_name()
+            || !entry.file_type().await?.is_file()
+        {
+            continue;
+        }
+        let source = fs::read_to_string(&sibling)
+            .await
+            .with_context(|| format!("Failed to read template {}", sibling.display()))?;
+        templates.push((entry.file_name().to_string_lossy().into_owned(), source));
+    }
+    Ok(templates)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, fs, process};
+
+    use super::*;
+    use crate::report::{Finding, ReasonCode, Status, Warning};
+
+    fn finding(name: &str, status: Status) -> Finding {
+        Finding {
+            id: format!("id-{name}"),
+            desktop_file: format!("/usr/share/applications/{name}.desktop").into(),
+            name: Some(name.into()),
+            exec: Some(name.into()),
+            try_exec: None,
+            path_key: None,
+            entry_type: None,
+            hidden: false,
+            no_display: false,
+            status,
+            warnings: Vec::new(),
+            user: None,
+            suppressed_warnings: Vec::new(),
+            severity: None,
+            suggested_exec: None,
+            history: None,
+            flathub: None,
+            identity: None,
+            same_file_as: Vec::new(),
+        }
+    }
+
+    fn report() -> Report {
+        Report {
+            schema_version: 1,
+            warnings: vec![Warning {
+                code: ReasonCode::ALL[0],
+                message: "<warning> & more".into(),
+                key: None,
+            }],
+            findings: vec![
+                finding(
+                    "foo",
+                    Status::Broken {
+                        code: ReasonCode::ExecNotFound,
+                        reason: "Exec does not resolve".into(),
+                    },
+                ),
+                finding(
+                    "bar",
+                    Status::Ok {
+                        resolved_executable: None,
+                    },
+                ),
+            ],
+            summary: None,
+            applications: Vec::new(),
+            groups: Vec::new(),
+        }
+    }
+
+    fn render_one(source: &str) -> Result<String> {
+        render(vec![("t".into(), source.into())], "t", &report())
+    }
+
+    #[test]
+    fn renders_loops_conditions_and_filters() {
+        let source = "{% for f in findings -%}\n\
+                      {{ loop.index }}. {{ f.name | upper }}\
+                      {% if f.status.kind == \"broken\" %} [{{ f.status.code }}]{% endif %}\n\
+                      {% endfor -%}\n\
+                      {{ findings | length }} {{ findings | map(attribute=\"id\") | join(sep=\",\") }}";
+        assert_eq!(
+            render_one(source).unwrap(),
+            "1. FOO [EXEC_NOT_FOUND]\n2. BAR\n2 id-foo,id-bar"
+        );
+    }
+
+    #[test]
+    fn supports_set_macros_and_tests() {
+        let source = "{% macro tag(f) %}<{{ f.id }}>{% endmacro tag %}\
+                      {% set broken = findings | filter(attribute=\"status.kind\", value=\"broken\") %}\
+                      {% for f in broken %}{{ self::tag(f=f) }}{% endfor %} \
+                      {{ summary is defined }} {{ findings.0.try_exec | default(value=\"-\") }}";
+        assert_eq!(render_one(source).unwrap(), "<id-foo> false -");
+    }
+
+    #[test]
+    fn escapes_only_html_templates() {
+        let source = "{{ warnings.0.message }}";
+        assert_eq!(render_one(source).unwrap(), "<warning> & more");
+        assert_eq!(
+            render(vec![("t.html".into(), source.into())], "t.html", &report()).unwrap(),
+            "&lt;warning&gt; &amp; more"
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_templates() {
+        assert!(render_one("{% for f in findings %}").is_err());
+        assert!(render_one("{{ findings | no_such_filter }}").is_err());
+        assert!(render_one("{{ no_such_variable }}").is_err());
+    }
+
+    #[tokio::test]
+    async fn render_file_loads_sibling_templates() {
+        let dir = env::temp_dir().join(format!("desktop-scout-template-{}", process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(
+            dir.join("macros.tera"),
+            "{% macro line(f) %}- {{ f.name }}{% endmacro line %}",
+        )
+        .unwrap();
+        fs::write(dir.join("header.tera"), "Findings:").unwrap();
+        fs::write(dir.join("ignored.txt"), "{% invalid").unwrap();
+        fs::write(
+            dir.join("report.tera"),
+            "{% import \"macros.tera\" as m %}{% include \"header.tera\" %}\n\
+             {% for f in findings %}{{ m::line(f=f) }}\n{% endfor %}",
+        )
+        .unwrap();
+
+        let rendered = render_file(&dir.join("report.tera"), &report()).await;
+        fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(rendered.unwrap(), "Findings:\n- foo\n- bar\n");
+    }
+}
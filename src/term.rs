@@ -0,0 +1,678 @@
+//! Terminal text output of scan reports (the default `--output text`).
+//!
+//! Reported entries are rendered as one aligned table with a row per problem: the status of a
+//! broken entry and each of its warnings. Healthy and skipped entries selected with `--status` or
+//! `--all` get a row with their status as well. Details that do not fit a column (suggested `Exec=`
+//! line, history, Flathub status, hard links) follow their row as indented notes. When stdout is
+//! a terminal, the file and reason columns are truncated to its width and statuses are colored
+//! (see `--color`).
+
+// -- std imports
+use std::{
+    env,
+    io::{self, IsTerminal},
+    path::Path,
+};
+
+// -- module imports
+use crate::{
+    args::{ColorChoice, FailOn},
+    i18n::tr,
+    identity::Application,
+    report::{Finding, GroupKey, ReasonCode, Report, Severity, Status, Summary, Warning},
+};
+
+/// Space between table columns.
+const COLUMN_GAP: &str = "  ";
+
+/// Narrowest width a truncated column is shrunk to.
+const MIN_TRUNCATED_WIDTH: usize = 16;
+
+/// Marker for truncated text.
+const ELLIPSIS: char = '…';
+
+/// Text colors (ANSI SGR sequences).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Color {
+    /// No styling.
+    None,
+    /// Bold red, for broken entries.
+    Red,
+    /// Yellow, for warnings.
+    Yellow,
+    /// Green, for healthy entries.
+    Green,
+    /// Dimmed, for notes.
+    Dim,
+    /// Bold, for headers.
+    Bold,
+}
+
+impl Color {
+    /// SGR parameters of the color.
+    fn sgr(self) -> Option<&'static str> {
+        match self {
+            Color::None => None,
+            Color::Red => Some("1;31"),
+            Color::Yellow => Some("33"),
+            Color::Green => Some("32"),
+            Color::Dim => Some("2"),
+            Color::Bold => Some("1"),
+        }
+    }
+}
+
+/// Capabilities of the output terminal.
+#[derive(Debug, Clone, Copy)]
+pub struct Terminal {
+    /// Whether to emit color escape sequences.
+    pub color: bool,
+
+    /// Width to truncate lines to; `None` leaves them at full length (e.g. when piped).
+    pub width: Option<usize>,
+}
+
+impl Terminal {
+    /// Detect the capabilities of stdout.
+    ///
+    /// `auto` colors only a terminal and honors `NO_COLOR`. The width is taken from `COLUMNS`,
+    /// or queried from the terminal.
+    pub fn detect(choice: ColorChoice) -> Self {
+        let is_terminal = io::stdout().is_terminal();
+        let color = match choice {
+            ColorChoice::Always => true,
+            ColorChoice::Never => false,
+            ColorChoice::Auto => {
+                is_terminal && env::var_os("NO_COLOR").is_none_or(|v| v.is_empty())
+            }
+        };
+        let width = env::var("COLUMNS")
+            .ok()
+            .and_then(|c| c.parse().ok())
+            .filter(|w| *w > 0)
+            .or_else(|| is_terminal.then(stdout_width).flatten());
+        Terminal { color, width }
+    }
+
+    /// `text` in `color`, if colors are enabled.
+    pub fn paint(&self, text: &str, color: Color) -> String {
+        match color.sgr() {
+            Some(sgr) if self.color && !text.is_empty() => format!("\x1b[{sgr}m{text}\x1b[0m"),
+            _ => text.to_string(),
+        }
+    }
+}
+
+/// Where a column cuts off text that does not fit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Truncate {
+    /// Never truncated.
+    Never,
+    /// Cut off the end (messages).
+    End,
+    /// Cut off the start, keeping the file name of paths.
+    Start,
+}
+
+/// A column of a [`Table`].
+#[derive(Debug, Clone)]
+pub struct Column {
+    /// Header text.
+    pub header: String,
+
+    /// How the column is truncated when the table is wider than the terminal.
+    pub truncate: Truncate,
+}
+
+/// A line of a [`Table`].
+#[derive(Debug, Clone)]
+enum Line {
+    /// Cells with their colors, one per column.
+    Row(Vec<(String, Color)>),
+    /// Indented free text below the previous row.
+    Note(String),
+}
+
+/// An aligned text table with colored cells and notes between rows.
+#[derive(Debug, Clone)]
+pub struct Table {
+    columns: Vec<Column>,
+    lines: Vec<Line>,
+}
+
+impl Table {
+    /// Create an empty table.
+    pub fn new(columns: Vec<Column>) -> Self {
+        Table {
+            columns,
+            lines: Vec::new(),
+        }
+    }
+
+    /// Append a row; `cells` holds one cell and its color per column.
+    pub fn row(&mut self, cells: Vec<(String, Color)>) {
+        debug_assert_eq!(cells.len(), self.columns.len());
+        let cells = cells
+            .into_iter()
+            .map(|(text, color)| (text.replace(['\n', '\r', '\t'], " "), color))
+            .collect();
+        self.lines.push(Line::Row(cells));
+    }
+
+    /// Append a note below the last row.
+    pub fn note(&mut self, text: String) {
+        self.lines.push(Line::Note(text.replace(['\n', '\r'], " ")));
+    }
+
+    /// Render the table for `term`.
+    pub fn render(&self, term: &Terminal) -> String {
+        let widths = self.widths(term.width);
+        let mut out = String::new();
+
+        let header = self
+            .columns
+            .iter()
+            .map(|c| (c.header.to_uppercase(), Color::Bold))
+            .collect::<Vec<_>>();
+        for line in std::iter::once(Line::Row(header)).chain(self.lines.iter().cloned()) {
+            match line {
+                Line::Row(cells) => {
+                    let last = cells.len() - 1;
+                    for (i, ((text, color), column)) in cells.iter().zip(&self.columns).enumerate()
+                    {
+                        let text = truncate(text, widths[i], column.truncate);
+                        let padding = if i == last {
+                            0
+                        } else {
+                            widths[i] - text.chars().count()
+                        };
+                        out.push_str(&term.paint(&text, *color));
+                        out.push_str(&" ".repeat(padding));
+                        if i != last {
+                            out.push_str(COLUMN_GAP);
+                        }
+                    }
+                }
+                Line::Note(text) => {
+                    let width = term.width.map_or(usize::MAX, |w| w.saturating_sub(4));
+                    let text = format!("  ↳ {}", truncate(&text, width, Truncate::End));
+                    out.push_str(&term.paint(&text, Color::Dim));
+                }
+            }
+            let trimmed = out.trim_end_matches(' ').len();
+            out.truncate(trimmed);
+            out.push('\n');
+        }
+        out
+    }
+
+    /// Width of each column: the widest cell, with truncatable columns shrunk (widest first)
+    /// until the table fits into `max_width`.
+    fn widths(&self, max_width: Option<usize>) -> Vec<usize> {
+        let mut widths = self
+            .columns
+            .iter()
+            .map(|c| c.header.chars().count())
+            .collect::<Vec<_>>();
+        for line in &self.lines {
+            if let Line::Row(cells) = line {
+                for (w, (text, _)) in widths.iter_mut().zip(cells) {
+                    *w = (*w).max(text.chars().count());
+                }
+            }
+        }
+
+        let Some(max_width) = max_width else {
+            return widths;
+        };
+        let gaps = COLUMN_GAP.len() * widths.len().saturating_sub(1);
+        while widths.iter().sum::<usize>() + gaps > max_width {
+            let excess = widths.iter().sum::<usize>() + gaps - max_width;
+            let Some((i, w)) = widths
+                .iter()
+                .enumerate()
+                .filter(|(i, w)| {
+                    self.columns[*i].truncate != Truncate::Never && **w > MIN_TRUNCATED_WIDTH
+                })
+                .max_by_key(|(_, w)| **w)
+                .map(|(i, w)| (i, *w))
+            else {
+                break;
+            };
+            widths[i] = w.saturating_sub(excess).max(MIN_TRUNCATED_WIDTH);
+        }
+        widths
+    }
+}
+
+/// Print a scan report as a table; `suppressed` is the number of findings hidden by the
+/// baseline.
+pub fn print_report(report: &Report, suppressed: usize, term: &Terminal) {
+    print_warnings(report, term);
+
+    let (broken, rest): (Vec<_>, Vec<_>) = report.findings.iter().partition(|f| f.is_broken());
+    let (warned, others): (Vec<_>, Vec<_>) = rest.into_iter().partition(|f| !f.warnings.is_empty());
+    if suppressed > 0 {
+        println!(
+            "{}\n",
+            tr(
+                "report-baseline-suppressed",
+                &[("count", suppressed.into())]
+            )
+        );
+    }
+    if broken.is_empty() {
+        println!("{}", tr("report-no-broken", &[]));
+    } else {
+        println!(
+            "{}",
+            tr("report-broken-header", &[("count", broken.len().into())])
+        );
+    }
+    if !warned.is_empty() {
+        println!(
+            "{}",
+            tr("report-warned-header", &[("count", warned.len().into())])
+        );
+    }
+    if !report.applications.is_empty() {
+        print_applications(&report.applications, term);
+    } else if !report.groups.is_empty() {
+        print_groups(report, term);
+    } else if !report.findings.is_empty() {
+        print!("\n{}", findings_table(broken, warned, others).render(term));
+    }
+    if let Some(summary) = &report.summary {
+        print_summary(summary, term);
+    }
+}
+
+/// The findings table: broken entries first, then entries with warnings, then the rest.
+fn findings_table(broken: Vec<&Finding>, warned: Vec<&Finding>, others: Vec<&Finding>) -> Table {
+    let column = |id: &str, truncate| Column {
+        header: tr(id, &[]),
+        truncate,
+    };
+    let mut table = Table::new(vec![
+        column("table-status", Truncate::Never),
+        column("table-code", Truncate::Never),
+        column("table-id", Truncate::Never),
+        column("table-name", Truncate::End),
+        column("table-file", Truncate::Start),
+        column("table-reason", Truncate::End),
+    ]);
+    for f in broken.into_iter().chain(warned).chain(others) {
+        add_finding(&mut table, f);
+    }
+    table
+}
+
+/// Print a findings table per group (`--group-by dir|source`) under a bold header.
+fn print_groups(report: &Report, term: &Terminal) {
+    for group in &report.groups {
+        let findings = report
+            .findings
+            .iter()
+            .filter(|f| group.desktop_files.contains(&f.desktop_file));
+        let (broken, rest): (Vec<_>, Vec<_>) = findings.partition(|f| f.is_broken());
+        let (warned, others): (Vec<_>, Vec<_>) =
+            rest.into_iter().partition(|f| !f.warnings.is_empty());
+
+        let title = match &group.key {
+            GroupKey::Directory { path } => display_path(path),
+            GroupKey::UserData { path } => {
+                tr("group-user-data", &[("path", display_path(path).into())])
+            }
+            GroupKey::Desktop { path } => {
+                tr("group-desktop", &[("path", display_path(path).into())])
+            }
+            GroupKey::SystemData => tr("group-system-data", &[]),
+            GroupKey::Flatpak => tr("group-flatpak", &[]),
+            GroupKey::Snap => tr("group-snap", &[]),
+            GroupKey::Extra { path } => tr("group-extra", &[("path", display_path(path).into())]),
+            GroupKey::Other => tr("group-other", &[]),
+        };
+        println!("\n{}", term.paint(&title, Color::Bold));
+        print!("{}", findings_table(broken, warned, others).render(term));
+    }
+}
+
+/// Print `apps` (`--group-by app`): one row per entry, with the application named on its first.
+fn print_applications(apps: &[Application], term: &Terminal) {
+    let column = |id: &str, truncate| Column {
+        header: tr(id, &[]),
+        truncate,
+    };
+    let mut table = Table::new(vec![
+        column("table-application", Truncate::End),
+        column("table-status", Truncate::Never),
+        column("table-code", Truncate::Never),
+        column("table-origin", Truncate::Never),
+        column("table-file", Truncate::Start),
+    ]);
+    for app in apps {
+        let title = match &app.name {
+            Some(name) => format!("{name} ({})", app.id),
+            None => app.id.clone(),
+        };
+        for (i, entry) in app.entries.iter().enumerate() {
+            let (status, code) = match &entry.status {
+                Status::Broken { code, .. } => {
+                    ((tr("table-broken", &[]), Color::Red), code.to_string())
+                }
+                Status::Skipped { code, .. } => {
+                    ((tr("table-skipped", &[]), Color::Dim), code.to_string())
+                }
+                Status::Suppressed { code, .. } => {
+                    ((tr("table-suppressed", &[]), Color::Dim), code.to_string())
+                }
+                Status::TimedOut { code, .. } => (
+                    (tr("table-timed-out", &[]), Color::Yellow),
+                    code.to_string(),
+                ),
+                Status::Ok { .. } if entry.warnings > 0 => {
+                    ((tr("table-warning", &[]), Color::Yellow), String::new())
+                }
+                Status::Ok { .. } => ((tr("table-ok", &[]), Color::Green), String::new()),
+            };
+            let title = if i == 0 { title.clone() } else { String::new() };
+            table.row(vec![
+                (title, Color::Bold),
+                status,
+                (code, Color::None),
+                (entry.origin.to_string(), Color::None),
+                (display_path(&entry.desktop_file), Color::None),
+            ]);
+        }
+    }
+    print!("\n{}", table.render(term));
+}
+
+/// Print the totals of `summary` and its per-directory breakdown.
+fn print_summary(summary: &Summary, term: &Terminal) {
+    let args = [
+        ("files", summary.files_scanned.into()),
+        ("ok", summary.ok.into()),
+        ("broken", summary.broken.into()),
+        ("skipped", summary.skipped.into()),
+    ];
+    let line = match summary.duration_ms {
+        Some(ms) => {
+            let mut args = args.to_vec();
+            args.push(("seconds", format!("{:.2}", ms as f64 / 1000.0).into()));
+            tr("summary-scanned-in", &args)
+        }
+        None => tr("summary-scanned", &args),
+    };
+    println!("\n{}", term.paint(&line, Color::Bold));
+    if summary.suppressed > 0 {
+        println!(
+            "{}",
+            tr(
+                "summary-suppressed",
+                &[("count", summary.suppressed.into())]
+            )
+        );
+    }
+    if summary.timed_out > 0 {
+        println!(
+            "{}",
+            tr("summary-timed-out", &[("count", summary.timed_out.into())])
+        );
+    }
+    if summary.directories.is_empty() {
+        return;
+    }
+
+    let column = |id: &str, truncate| Column {
+        header: tr(id, &[]),
+        truncate,
+    };
+    let mut table = Table::new(vec![
+        column("table-directory", Truncate::Start),
+        column("table-files", Truncate::Never),
+        column("table-count-ok", Truncate::Never),
+        column("table-count-broken", Truncate::Never),
+        column("table-count-skipped", Truncate::Never),
+    ]);
+    for dir in &summary.directories {
+        let broken_color = if dir.broken > 0 {
+            Color::Red
+        } else {
+            Color::None
+        };
+        table.row(vec![
+            (display_path(&dir.directory), Color::None),
+            (dir.files.to_string(), Color::None),
+            (dir.ok.to_string(), Color::None),
+            (dir.broken.to_string(), broken_color),
+            (dir.skipped.to_string(), Color::None),
+        ]);
+    }
+    print!("\n{}", table.render(term));
+}
+
+/// Print the scan-level warnings of `report`, followed by a blank line.
+pub fn print_warnings(report: &Report, term: &Terminal) {
+    for w in &report.warnings {
+        println!("{}", term.paint(&warning_line(w), Color::Yellow));
+    }
+    if !report.warnings.is_empty() {
+        println!();
+    }
+}
+
+/// Print the scan report of `--quiet`: nothing for a clean scan, otherwise one uncolored line
+/// per broken entry, and with `--fail-on warning` per lint and scan-level warning (not `info`
+/// hints). An empty scan
+/// is always mentioned, since it has an exit status of its own.
+pub fn print_quiet(report: &Report, fail_on: FailOn) {
+    let warnings = fail_on == FailOn::Warning;
+    for w in &report.warnings {
+        if (warnings && w.code.severity() >= Severity::Warning)
+            || w.code == ReasonCode::NoDesktopFiles
+        {
+            println!("{}", warning_line(w));
+        }
+    }
+
+    for f in &report.findings {
+        let file = f.desktop_file.display().to_string();
+        if let Status::Broken { code, reason } = &f.status {
+            println!(
+                "{}",
+                tr(
+                    "quiet-broken",
+                    &[
+                        ("file", file.as_str().into()),
+                        ("code", code.as_str().into()),
+                        ("reason", reason.as_str().into()),
+                    ],
+                )
+            );
+        }
+        if !warnings {
+            continue;
+        }
+        for w in f
+            .warnings
+            .iter()
+            .filter(|w| w.code.severity() >= Severity::Warning)
+        {
+            println!(
+                "{}",
+                tr(
+                    "quiet-warning",
+                    &[
+                        ("file", file.as_str().into()),
+                        ("code", w.code.as_str().into()),
+                        ("message", w.message.as_str().into()),
+                    ],
+                )
+            );
+        }
+    }
+}
+
+/// Append the rows and notes of `f`.
+fn add_finding(table: &mut Table, f: &Finding) {
+    let id = f.id.clone();
+    let name = f.name.clone().unwrap_or_default();
+    let file = display_path(&f.desktop_file);
+
+    match &f.status {
+        // Healthy entries with warnings are represented by their warning rows.
+        Status::Ok {
+            resolved_executable,
+        } if f.warnings.is_empty() => table.row(vec![
+            (tr("table-ok", &[]), Color::Green),
+            (String::new(), Color::None),
+            (id.clone(), Color::Dim),
+            (name.clone(), Color::None),
+            (file.clone(), Color::None),
+            (
+                resolved_executable
+                    .as_deref()
+                    .map(display_path)
+                    .unwrap_or_default(),
+                Color::None,
+            ),
+        ]),
+        Status::Ok { .. } => {}
+        Status::Skipped { code, reason } => table.row(vec![
+            (tr("table-skipped", &[]), Color::Dim),
+            (code.to_string(), Color::None),
+            (id.clone(), Color::Dim),
+            (name.clone(), Color::None),
+            (file.clone(), Color::None),
+            (reason.clone(), Color::None),
+        ]),
+        Status::Broken { code, reason } => table.row(vec![
+            (tr("table-broken", &[]), Color::Red),
+            (code.to_string(), Color::None),
+            (id.clone(), Color::Dim),
+            (name.clone(), Color::None),
+            (file.clone(), Color::None),
+            (reason.clone(), Color::None),
+        ]),
+        Status::Suppressed { code, reason, .. } => table.row(vec![
+            (tr("table-suppressed", &[]), Color::Dim),
+            (code.to_string(), Color::None),
+            (id.clone(), Color::Dim),
+            (name.clone(), Color::None),
+            (file.clone(), Color::None),
+            (reason.clone(), Color::None),
+        ]),
+        Status::TimedOut { code, reason } => table.row(vec![
+            (tr("table-timed-out", &[]), Color::Yellow),
+            (code.to_string(), Color::None),
+            (id.clone(), Color::Dim),
+            (name.clone(), Color::None),
+            (file.clone(), Color::None),
+            (reason.clone(), Color::None),
+        ]),
+    }
+
+    if f.is_broken() {
+        if let Some(user) = &f.user {
+            table.note(tr("report-user", &[("user", user.as_str().into())]));
+        }
+        if let Some(exec) = &f.suggested_exec {
+            table.note(tr(
+                "report-suggested-exec",
+                &[("exec", exec.as_str().into())],
+            ));
+        }
+        if let Some(h) = &f.history {
+            table.note(tr(
+                "report-history",
+                &[
+                    (
+                        "first_seen",
+                        h.first_seen.format("%Y-%m-%d %H:%M").to_string().into(),
+                    ),
+                    ("scans", h.broken_scans.into()),
+                ],
+            ));
+        }
+        if let Some(fh) = &f.flathub {
+            table.note(fh.message());
+        }
+        if !f.same_file_as.is_empty() {
+            let paths = f
+                .same_file_as
+                .iter()
+                .map(|p| display_path(p))
+                .collect::<Vec<_>>()
+                .join(", ");
+            table.note(tr("report-same-file", &[("paths", paths.into())]));
+        }
+    }
+
+    for w in &f.warnings {
+        let status = match w.code.severity() {
+            Severity::Info => (tr("table-info", &[]), Color::Dim),
+            _ => (tr("table-warning", &[]), Color::Yellow),
+        };
+        table.row(vec![
+            status,
+            (w.code.to_string(), Color::None),
+            (id.clone(), Color::Dim),
+            (name.clone(), Color::None),
+            (file.clone(), Color::None),
+            (w.message.clone(), Color::None),
+        ]);
+    }
+}
+
+/// `path` with the home directory abbreviated to `~`.
+fn display_path(path: &Path) -> String {
+    if let Some(home) = env::var_os("HOME").filter(|h| !h.is_empty())
+        && let Ok(rest) = path.strip_prefix(&home)
+    {
+        return Path::new("~").join(rest).display().to_string();
+    }
+    path.display().to_string()
+}
+
+/// Localized `Warning [CODE]: message` line.
+fn warning_line(w: &Warning) -> String {
+    tr(
+        "report-warning",
+        &[
+            ("code", w.code.as_str().into()),
+            ("message", w.message.as_str().into()),
+        ],
+    )
+}
+
+/// `text` cut to `width` characters at the side given by `truncate`, marked with [`ELLIPSIS`].
+fn truncate(text: &str, width: usize, truncate: Truncate) -> String {
+    let len = text.chars().count();
+    if len <= width || truncate == Truncate::Never || width == 0 {
+        return text.to_string();
+    }
+    match truncate {
+        Truncate::Start => std::iter::once(ELLIPSIS)
+            .chain(text.chars().skip(len - width + 1))
+            .collect(),
+        _ => text
+            .chars()
+            .take(width - 1)
+            .chain(std::iter::once(ELLIPSIS))
+            .collect(),
+    }
+}
+
+/// Column count of the terminal on stdout, if it can be queried.
+fn stdout_width() -> Option<usize> {
+    let mut size = libc::winsize {
+        ws_row: 0,
+        ws_col: 0,
+        ws_xpixel: 0,
+        ws_ypixel: 0,
+    };
+    // SAFETY: TIOCGWINSZ only writes a `winsize` to the valid pointer passed.
+    let rc = unsafe { libc::ioctl(libc::STDOUT_FILENO, libc::TIOCGWINSZ, &mut size) };
+    (rc == 0 && size.ws_col > 0).then_some(usize::from(size.ws_col))
+}
@@ -0,0 +1,152 @@
+//! systemd user timer installation (`install-timer` subcommand).
+//!
+//! Writes a `desktop-scout.service` + `desktop-scout.timer` pair into the user's systemd unit
+//! directory (`$XDG_CONFIG_HOME/systemd/user`). The service runs a JSON scan and writes the
+//! report to a file with `--output-file`, so a failed or killed run leaves the previous report in
+//! place; the timer triggers it periodically.
+
+// -- std imports
+use std::{env, fs, path::Path};
+
+// -- crate imports
+use anyhow::{Context, Result, bail};
+use tracing::info;
+use xdg::BaseDirectories;
+
+// -- module imports
+use crate::i18n::tr;
+
+/// Base name of the generated unit files.
+const UNIT_NAME: &str = "desktop-scout";
+
+/// Options for [`install`].
+#[derive(Debug)]
+pub struct TimerOptions<'a> {
+    /// systemd `OnCalendar=` expression controlling the cadence (e.g. `daily`, `hourly`).
+    pub on_calendar: &'a str,
+
+    /// File the JSON report is written to (defaults to the XDG state dir).
+    pub output: Option<&'a Path>,
+
+    /// Additional arguments passed to the scan, split shell-style.
+    pub scan_args: Option<&'a str>,
+
+    /// Overwrite existing unit files.
+    pub force: bool,
+}
+
+/// Write the service and timer units and print how to enable them.
+///
+/// # Errors
+/// - [`anyhow::Error`] if the unit directory or default report path cannot be determined,
+///   either unit file already exists (without `force`; nothing is written then), `scan_args`
+///   cannot be split, or writing fails (a service written before the timer failed is removed
+///   again).
+pub fn install(opts: &TimerOptions<'_>) -> Result<()> {
+    let xdg = BaseDirectories::new();
+    let unit_dir = xdg
+        .get_config_home()
+        .context("Could not determine XDG config directory")?
+        .join("systemd/user");
+
+    let output = match opts.output {
+        Some(p) => p.to_path_buf(),
+        None => BaseDirectories::with_prefix(UNIT_NAME)
+            .place_state_file("report.json")
+            .context("Could not determine default report path")?,
+    };
+    if let Some(parent) = output.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create {}", parent.display()))?;
+    }
+
+    let exe = env::current_exe().context("Could not determine desktop-scout executable path")?;
+    let mut exec_start = vec![
+        systemd_quote(&exe.display().to_string()),
+        "--no-log".into(),
+        "--output".into(),
+        "json".into(),
+        "--output-file".into(),
+        systemd_quote(&output.display().to_string()),
+    ];
+    if let Some(extra) = opts.scan_args {
+        let tokens = shlex::split(extra).context("Failed to shell-split --scan-args")?;
+        exec_start.extend(tokens.iter().map(|t| systemd_quote(t)));
+    }
+
+    let service = format!(
+        "[Unit]\n\
+         Description=Scan for broken .desktop entries\n\
+         \n\
+         [Service]\n\
+         Type=oneshot\n\
+         ExecStart={}\n\
+         SuccessExitStatus=4\n",
+        exec_start.join(" "),
+    );
+    let timer = format!(
+        "[Unit]\n\
+         Description=Periodic desktop-scout scan\n\
+         \n\
+         [Timer]\n\
+         OnCalendar={}\n\
+         Persistent=true\n\
+         \n\
+         [Install]\n\
+         WantedBy=timers.target\n",
+        opts.on_calendar,
+    );
+
+    fs::create_dir_all(&unit_dir)
+        .with_context(|| format!("Failed to create {}", unit_dir.display()))?;
+    let service_path = unit_dir.join(format!("{UNIT_NAME}.service"));
+    let timer_path = unit_dir.join(format!("{UNIT_NAME}.timer"));
+    // Check both units before writing either, so an existing timer does not leave a new
+    // service behind.
+    for path in [&service_path, &timer_path] {
+        if path.exists() && !opts.force {
+            bail!(
+                "{} already exists (use --force to overwrite)",
+                path.display()
+            );
+        }
+    }
+    let service_existed = service_path.exists();
+    write_unit(&service_path, &service)?;
+    if let Err(e) = write_unit(&timer_path, &timer) {
+        if !service_existed {
+            let _ = fs::remove_file(&service_path);
+        }
+        return Err(e);
+    }
+    info!(service = %service_path.display(), timer = %timer_path.display(), "Installed units");
+
+    for path in [&service_path, &timer_path] {
+        let path = path.display().to_string();
+        println!("{}", tr("timer-unit-written", &[("path", path.into())]));
+    }
+    let output = output.display().to_string();
+    println!("{}", tr("timer-report-path", &[("path", output.into())]));
+    println!("\n{}\n", tr("timer-enable", &[]));
+    println!("  systemctl --user daemon-reload");
+    println!("  systemctl --user enable --now {UNIT_NAME}.timer");
+    Ok(())
+}
+
+/// Write a unit file.
+fn write_unit(path: &Path, content: &str) -> Result<()> {
+    fs::write(path, content).with_context(|| format!("Failed to write {}", path.display()))
+}
+
+/// Quote a single `ExecStart=` word for systemd.
+///
+/// Words containing whitespace or quotes are wrapped in double quotes with `"` and `\` escaped;
+/// `%` is always doubled because systemd treats it as a specifier prefix.
+fn systemd_quote(word: &str) -> String {
+    let escaped = word.replace('%', "%%");
+    if escaped.contains(|c: char| c.is_whitespace() || c == '"' || c == '\'' || c == '\\') {
+        format!("\"{}\"", escaped.replace('\\', "\\\\").replace('"', "\\\""))
+    } else {
+        escaped
+    }
+}
@@ -0,0 +1,193 @@
+//! Moving files to the freedesktop.org Trash (`fix --trash`).
+//!
+//! Follows the Trash specification: a trashed file is moved to `files/` of a trash directory and
+//! described by a `.trashinfo` file of the same name in `info/` (original path and deletion
+//! date), so it shows up in the desktop's wastebasket and can be restored from there. Files on
+//! the same device as the home trash (`$XDG_DATA_HOME/Trash`) go there; files on other devices
+//! go to `$topdir/.Trash-$uid` of their mount point.
+
+// -- std imports
+use std::{
+    io::ErrorKind,
+    os::unix::fs::MetadataExt,
+    path::{Path, PathBuf},
+};
+
+// -- crate imports
+use anyhow::{Context, Result, bail};
+use chrono::Local;
+use tokio::{fs, io::AsyncWriteExt};
+use xdg::BaseDirectories;
+
+/// Maximum number of name suffixes tried when the trash already contains a file of that name.
+const MAX_NAME_ATTEMPTS: u32 = 1000;
+
+/// Move `path` to the trash and return its location in the trash's `files/` directory.
+///
+/// # Errors
+/// - [`anyhow::Error`] if no trash directory can be used or the file cannot be moved.
+pub async fn trash(path: &Path) -> Result<PathBuf> {
+    let dev = fs::symlink_metadata(path)
+        .await
+        .with_context(|| format!("Failed to stat {}", path.display()))?
+        .dev();
+    let (trash_dir, topdir) = trash_dir_for(path, dev).await?;
+
+    let files = trash_dir.join("files");
+    let info = trash_dir.join("info");
+    for dir in [&files, &info] {
+        fs::create_dir_all(dir)
+            .await
+            .with_context(|| format!("Failed to create {}", dir.display()))?;
+    }
+
+    // Home trash records absolute paths, top-directory trashes paths relative to the mount.
+    let recorded = match &topdir {
+        Some(top) => path.strip_prefix(top).unwrap_or(path),
+        None => path,
+    };
+    let name = path
+        .file_name()
+        .with_context(|| format!("{} has no file name", path.display()))?
+        .to_string_lossy()
+        .into_owned();
+
+    let (trashed, info_file) = reserve_name(&files, &info, &name, recorded).await?;
+    if let Err(e) = fs::rename(path, &trashed).await {
+        let _ = fs::remove_file(&info_file).await;
+        return Err(e).with_context(|| format!("Failed to move {} to the trash", path.display()));
+    }
+    Ok(trashed)
+}
+
+/// Move the trashed file `trashed` back to `original` and remove its `.trashinfo`.
+///
+/// # Errors
+/// - [`anyhow::Error`] if `original` already exists or moving fails.
+pub async fn restore(trashed: &Path, original: &Path) -> Result<()> {
+    if fs::symlink_metadata(original).await.is_ok() {
+        bail!(
+            "{} already exists; refusing to overwrite it",
+            original.display()
+        );
+    }
+    if let Some(parent) = original.parent() {
+        fs::create_dir_all(parent)
+            .await
+            .with_context(|| format!("Failed to create {}", parent.display()))?;
+    }
+    fs::rename(trashed, original)
+        .await
+        .with_context(|| format!("Failed to move {} back from the trash", original.display()))?;
+
+    if let Some(info_file) = info_file_of(trashed) {
+        let _ = fs::remove_file(info_file).await;
+    }
+    Ok(())
+}
+
+/// Trash directory for a file on device `dev`, and the mount point it belongs to for
+/// top-directory trashes (`None` for the home trash).
+async fn trash_dir_for(path: &Path, dev: u64) -> Result<(PathBuf, Option<PathBuf>)> {
+    let home_trash = BaseDirectories::new()
+        .get_data_home()
+        .context("Could not determine XDG data directory for the trash")?
+        .join("Trash");
+
+    // The home trash may not exist yet; compare against its closest existing ancestor.
+    for dir in home_trash.ancestors() {
+        if let Ok(md) = fs::metadata(dir).await {
+            if md.dev() == dev {
+                return Ok((home_trash, None));
+            }
+            break;
+        }
+    }
+
+    let topdir = mount_point(path, dev).await;
+    let uid = fs::metadata("/proc/self")
+        .await
+        .context("Failed to determine the current user ID")?
+        .uid();
+    Ok((topdir.join(format!(".Trash-{uid}")), Some(topdir)))
+}
+
+/// Topmost ancestor of `path` that is still on device `dev`.
+async fn mount_point(path: &Path, dev: u64) -> PathBuf {
+    let mut top = path.parent().unwrap_or(path).to_path_buf();
+    for dir in path.ancestors().skip(1) {
+        match fs::metadata(dir).await {
+            Ok(md) if md.dev() == dev => top = dir.to_path_buf(),
+            _ => break,
+        }
+    }
+    top
+}
+
+/// Atomically create the `.trashinfo` for a free name based on `name` and return the paths
+/// of the reserved `files/` entry and its info file.
+async fn reserve_name(
+    files: &Path,
+    info: &Path,
+    name: &str,
+    recorded: &Path,
+) -> Result<(PathBuf, PathBuf)> {
+    let content = format!(
+        "[Trash Info]\nPath={}\nDeletionDate={}\n",
+        percent_encode(&recorded.to_string_lossy()),
+        Local::now().format("%Y-%m-%dT%H:%M:%S")
+    );
+
+    for n in 1..=MAX_NAME_ATTEMPTS {
+        let candidate = if n == 1 {
+            name.to_string()
+        } else {
+            format!("{name}.{n}")
+        };
+        let trashed = files.join(&candidate);
+        let info_file = info.join(format!("{candidate}.trashinfo"));
+        if fs::symlink_metadata(&trashed).await.is_ok() {
+            continue;
+        }
+
+        // Per the spec, creating the info file with O_EXCL is what claims the name.
+        match fs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&info_file)
+            .await
+        {
+            Ok(mut f) => {
+                f.write_all(content.as_bytes())
+                    .await
+                    .with_context(|| format!("Failed to write {}", info_file.display()))?;
+                return Ok((trashed, info_file));
+            }
+            Err(e) if e.kind() == ErrorKind::AlreadyExists => continue,
+            Err(e) => {
+                return Err(e).with_context(|| format!("Failed to create {}", info_file.display()));
+            }
+        }
+    }
+    bail!("No free name for {name} in {}", files.display())
+}
+
+/// `.trashinfo` file describing the trashed file `trashed` (`<trash>/files/<name>`).
+fn info_file_of(trashed: &Path) -> Option<PathBuf> {
+    let name = trashed.file_name()?.to_string_lossy();
+    let trash_dir = trashed.parent()?.parent()?;
+    Some(trash_dir.join("info").join(format!("{name}.trashinfo")))
+}
+
+/// Percent-encode a path for the `Path=` key (RFC 2396 escaping; `/` is kept).
+fn percent_encode(path: &str) -> String {
+    let mut out = String::with_capacity(path.len());
+    for b in path.bytes() {
+        if b.is_ascii_alphanumeric() || b"/-_.!~*'()".contains(&b) {
+            out.push(b as char);
+        } else {
+            out.push_str(&format!("%{b:02X}"));
+        }
+    }
+    out
+}
@@ -0,0 +1,235 @@
+//! Reverting fixes recorded in the journal (`undo` subcommand).
+//!
+//! Every `fix` outcome in the journal (see [`crate::journal`]) carries what is needed to revert it:
+//! deleted files and their companions are copied back from the backup directory, edited files are
+//! overwritten with their backup, user overrides are removed, and quarantined or trashed entries
+//! are restored. Each successful revert is journaled as well, so nothing is undone twice.
+
+// -- std imports
+use std::{
+    collections::HashSet,
+    path::{Path, PathBuf},
+};
+
+// -- crate imports
+use anyhow::{Context, Result, bail};
+use serde::Serialize;
+use tokio::fs;
+use tracing::{info, warn};
+
+// -- module imports
+use crate::{
+    fix::{self, FixOutcome, FixResult},
+    i18n::tr,
+    journal::{self, Journal, JournalAction},
+    quarantine::Quarantine,
+    trash,
+};
+
+/// Outcome of an `undo` run.
+#[derive(Debug, Serialize)]
+pub struct UndoReport {
+    /// One outcome per reverted fix, newest first.
+    pub outcomes: Vec<UndoOutcome>,
+}
+
+impl UndoReport {
+    /// Whether any revert failed.
+    pub fn has_failures(&self) -> bool {
+        self.outcomes.iter().any(|o| o.error.is_some())
+    }
+}
+
+/// Outcome of reverting a single fix.
+#[derive(Debug, Serialize)]
+pub struct UndoOutcome {
+    /// The `.desktop` file the fix changed.
+    pub desktop_file: PathBuf,
+
+    /// Run ID of the fix (see [`journal::JournalEntry::run`]).
+    pub run: String,
+
+    /// Error message if the fix could not be reverted.
+    pub error: Option<String>,
+}
+
+/// Revert the fixes of the most recent fix run that was not undone yet, or of all runs with
+/// `all`.
+///
+/// # Errors
+/// - [`anyhow::Error`] if the journal cannot be read. Per-file failures are recorded in the
+///   returned [`UndoReport`] instead.
+pub async fn run(all: bool) -> Result<UndoReport> {
+    let entries = journal::read().await?;
+    let undone = entries
+        .iter()
+        .filter_map(|e| match &e.action {
+            JournalAction::Undo {
+                fix_run,
+                desktop_file,
+            } => Some((fix_run.clone(), desktop_file.clone())),
+            _ => None,
+        })
+        .collect::<HashSet<_>>();
+
+    let pending = entries
+        .iter()
+        .filter_map(|e| match &e.action {
+            JournalAction::Fix { outcome }
+                if !matches!(outcome.result, FixResult::Failed { .. })
+                    && !undone.contains(&(e.run.clone(), outcome.desktop_file.clone())) =>
+            {
+                Some((e.run.as_str(), outcome))
+            }
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    let last_run = pending.last().map(|(run, _)| *run);
+
+    let journal = Journal::open().await?;
+    let mut quarantine = None;
+    let mut outcomes = Vec::new();
+    for (run, outcome) in pending.into_iter().rev() {
+        if !all && Some(run) != last_run {
+            continue;
+        }
+
+        let path = &outcome.desktop_file;
+        let error = match revert(outcome, &mut quarantine).await {
+            Ok(()) => {
+                info!(file = %path.display(), run, "Reverted fix");
+                if let Err(e) = journal
+                    .append(JournalAction::Undo {
+                        fix_run: run.to_string(),
+                        desktop_file: path.clone(),
+                    })
+                    .await
+                {
+                    warn!("Failed to write the journal: {e:#}");
+                }
+                None
+            }
+            Err(e) => {
+                warn!(file = %path.display(), run, error = %e, "Failed to revert fix");
+                Some(format!("{e:#}"))
+            }
+        };
+        outcomes.push(UndoOutcome {
+            desktop_file: path.clone(),
+            run: run.to_string(),
+            error,
+        });
+    }
+
+    Ok(UndoReport { outcomes })
+}
+
+/// Revert a single fix; `quarantine` is opened on first use.
+async fn revert(outcome: &FixOutcome, quarantine: &mut Option<Quarantine>) -> Result<()> {
+    let path = &outcome.desktop_file;
+    match &outcome.result {
+        FixResult::Deleted { backup } => {
+            let companions = match outcome.companions_removed {
+                true => outcome.companions.as_slice(),
+                false => &[],
+            };
+            restore_deleted(backup, path, companions).await
+        }
+        FixResult::PartiallyDeleted {
+            backup,
+            removed_companions,
+            ..
+        } => restore_deleted(backup, path, removed_companions).await,
+        FixResult::Hidden { backup } | FixResult::Repaired { backup, .. } => {
+            fs::copy(backup, path)
+                .await
+                .with_context(|| format!("Failed to copy back {}", backup.display()))?;
+            Ok(())
+        }
+        FixResult::Overridden { override_file } => fs::remove_file(override_file)
+            .await
+            .with_context(|| format!("Failed to remove {}", override_file.display())),
+        FixResult::Quarantined { id } => {
+            let q = match quarantine {
+                Some(q) => q,
+                None => quarantine.insert(Quarantine::open().await?),
+            };
+            q.restore(id).await.map(|_| ())
+        }
+        FixResult::Trashed {
+            trash_file,
+            companion_trash_files,
+        } => {
+            trash::restore(trash_file, path).await?;
+            for (trashed, c) in companion_trash_files.iter().zip(&outcome.companions) {
+                trash::restore(trashed, c).await?;
+            }
+            Ok(())
+        }
+        FixResult::Skipped { .. } | FixResult::Failed { .. } => Ok(()),
+    }
+}
+
+/// Copy the deleted `path` back from its `backup`, and its removed `companions` from the same
+/// backup directory.
+async fn restore_deleted(backup: &Path, path: &Path, companions: &[PathBuf]) -> Result<()> {
+    restore_copy(backup, path).await?;
+    if !companions.is_empty() {
+        let root = backup_root(backup, path)
+            .context("Backup location does not match the journaled file")?;
+        for c in companions {
+            restore_copy(&root.join(c.strip_prefix("/").unwrap_or(c)), c).await?;
+        }
+    }
+    Ok(())
+}
+
+/// Copy `backup` back to `original` (recreating a backed-up symlink as a symlink), refusing to
+/// overwrite an existing file.
+async fn restore_copy(backup: &Path, original: &Path) -> Result<()> {
+    if fs::symlink_metadata(original).await.is_ok() {
+        bail!(
+            "{} already exists; refusing to overwrite it",
+            original.display()
+        );
+    }
+    if let Some(parent) = original.parent() {
+        fs::create_dir_all(parent)
+            .await
+            .with_context(|| format!("Failed to create {}", parent.display()))?;
+    }
+    fix::copy_file_or_link(backup, original)
+        .await
+        .with_context(|| format!("Failed to copy back {}", backup.display()))
+}
+
+/// Backup directory of a run, given the backup of `original` (which mirrors its absolute path
+/// below that directory).
+fn backup_root<'a>(backup: &'a Path, original: &Path) -> Option<&'a Path> {
+    let depth = original.components().count().checked_sub(1)?;
+    let root = backup.ancestors().nth(depth)?;
+    (root.join(original.strip_prefix("/").ok()?) == backup).then_some(root)
+}
+
+/// Print a human-readable summary of an [`UndoReport`].
+pub fn print_text(undo: &UndoReport) {
+    if undo.outcomes.is_empty() {
+        println!("{}", tr("undo-nothing", &[]));
+        return;
+    }
+
+    for o in &undo.outcomes {
+        let path = o.desktop_file.display().to_string();
+        let line = match &o.error {
+            None => tr(
+                "undo-reverted",
+                &[("path", path.into()), ("run", o.run.clone().into())],
+            ),
+            Some(e) => tr(
+                "undo-failed",
+                &[("path", path.into()), ("error", e.clone().into())],
+            ),
+        };
+        println!("{line}");
+    }
+}